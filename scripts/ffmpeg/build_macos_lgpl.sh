@@ -33,6 +33,7 @@ echo "[ffmpeg] Configuring (LGPL-only, shared libs, no GPL/non-free)"
   --disable-gpl \
   --disable-nonfree \
   --disable-autodetect \
+  --enable-avfoundation \
   --enable-shared \
   --disable-static \
   --disable-debug \
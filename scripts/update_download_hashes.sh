@@ -0,0 +1,85 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Pin the SHA-256 of the built-in whisper models, the whisper.cpp release zips and the ffmpeg
+# release zips into ui/src-tauri/download-hashes.json. Run before tagging a release; the app
+# checks downloads against these before trusting a hash reported by a host or mirror.
+
+ROOT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
+OUTPUT="${OUTPUT:-${ROOT_DIR}/ui/src-tauri/download-hashes.json}"
+MODELS="${MODELS:-tiny base small medium large-v3}"
+FFMPEG_RELEASE="${FFMPEG_RELEASE:-https://api.github.com/repos/ravaru/voicenoteapp/releases/latest}"
+# The defaults of whisper_binary_url: bizenlabs on macOS, whisper.cpp itself elsewhere.
+WHISPER_RELEASES="${WHISPER_RELEASES:-https://api.github.com/repos/ggml-org/whisper.cpp/releases/latest https://api.github.com/repos/bizenlabs/whisper-cpp-macos-bin/releases/latest}"
+
+entries=()
+
+add_entry() {
+  echo "[hashes] $1 $2"
+  entries+=("  \"$1\": \"$2\"")
+}
+
+# Prints "<tag>\t<name>\t<digest or empty>\t<url>" for the zip assets of a GitHub release.
+release_zips() {
+  curl -sf "$1" | python3 -c 'import json, sys
+release = json.load(sys.stdin)
+for asset in release.get("assets", []):
+    if asset["name"].endswith(".zip"):
+        digest = asset.get("digest") or ""
+        digest = digest[len("sha256:"):] if digest.startswith("sha256:") else ""
+        print("\t".join([release["tag_name"], asset["name"], digest, asset["browser_download_url"]]))'
+}
+
+# Falls back to hashing the asset itself when GitHub lists no digest for it (older uploads).
+asset_hash() {
+  if [ -n "$1" ]; then
+    echo "$1"
+  else
+    curl -sfL "$2" | shasum -a 256 | awk '{ print $1 }'
+  fi
+}
+
+for model in ${MODELS}; do
+  name="ggml-${model}.bin"
+  # The Hub answers the redirect with the LFS object id, which is the file's SHA-256.
+  hash="$(curl -sI "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/${name}" \
+    | tr -d '\r' | awk -F': ' 'tolower($1) == "x-linked-etag" { gsub(/"/, "", $2); print $2 }')"
+  if [ -z "${hash}" ]; then
+    echo "[hashes] No hash for ${name}" >&2
+    exit 1
+  fi
+  add_entry "${name}" "${hash}"
+done
+
+for release in ${WHISPER_RELEASES}; do
+  found=0
+  # whisper.cpp names its zips the same in every release, so they are keyed by tag.
+  while IFS=$'\t' read -r tag name digest url; do
+    add_entry "${tag}/${name}" "$(asset_hash "${digest}" "${url}")"
+    found=1
+  done < <(release_zips "${release}")
+  if [ "${found}" -eq 0 ]; then
+    echo "[hashes] No zip assets in ${release}" >&2
+    exit 1
+  fi
+done
+
+while IFS=$'\t' read -r _tag name digest url; do
+  case "${name}" in
+    ffmpeg-*) add_entry "${name}" "$(asset_hash "${digest}" "${url}")" ;;
+  esac
+done < <(release_zips "${FFMPEG_RELEASE}")
+
+{
+  echo "{"
+  for i in "${!entries[@]}"; do
+    if [ "$i" -lt $((${#entries[@]} - 1)) ]; then
+      echo "${entries[$i]},"
+    else
+      echo "${entries[$i]}"
+    fi
+  done
+  echo "}"
+} > "${OUTPUT}"
+
+echo "[hashes] Wrote ${OUTPUT}"
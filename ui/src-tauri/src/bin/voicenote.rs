@@ -0,0 +1,11 @@
+//! `voicenote transcribe …` and `voicenote mcp`: the core without a window, for scripts, servers
+//! and MCP clients.
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let code = voicenote_core::run_cli(&args).unwrap_or_else(|| {
+        eprintln!("{}", voicenote_core::commands::CLI_USAGE);
+        2
+    });
+    std::process::exit(code);
+}
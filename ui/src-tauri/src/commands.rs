@@ -1,1392 +1,925 @@
 use serde::{Deserialize, Serialize};
+use chrono::{Offset, TimeZone};
+use sha2::{Digest, Sha256};
+use chacha20poly1305::{
+    aead::{
+        rand_core::RngCore,
+        stream::{DecryptorBE32, EncryptorBE32},
+        Aead, AeadCore, KeyInit, OsRng,
+    },
+    Key, XChaCha20Poly1305, XNonce,
+};
 use std::{
     fs::{self, File},
     io::{Read, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::mpsc,
     time::{SystemTime, UNIX_EPOCH},
     sync::Mutex,
     thread,
     process::Command,
     io::{BufRead, BufReader},
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     sync::Arc,
-    os::unix::fs::PermissionsExt,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
 };
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
 use tauri::{AppHandle, State, Emitter, Manager};
+use tauri::menu::{IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::TrayIconBuilder;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons};
+use tauri_plugin_updater::UpdaterExt;
+
+mod calendar;
+mod cli;
+mod export;
+mod model;
+mod pipeline;
+mod share;
+mod store;
+mod summarize;
+mod support;
+use calendar::*;
+pub use cli::*;
+pub use export::*;
+pub use model::*;
+pub use pipeline::*;
+use share::*;
+pub use store::*;
+pub use summarize::*;
+pub use support::*;
+
+// What the pipeline needs from the app: managed state, events, paths and the version. The
+// window hands its AppHandle over; the CLI (src/bin/voicenote.rs) runs the same pipeline
+// without starting Tauri, so it keeps the state itself and has nobody to send events to.
+#[derive(Clone)]
+pub enum Core {
+    App(AppHandle),
+    Headless(Arc<HeadlessCore>),
+}
+
+pub struct HeadlessCore {
+    identifier: String,
+    package_info: tauri::PackageInfo,
+    // Managed state lives as long as the process, as it does in Tauri.
+    states: Mutex<HashMap<std::any::TypeId, &'static (dyn std::any::Any + Send + Sync)>>,
+}
+
+impl Core {
+    pub fn headless<R: tauri::Runtime>(context: &tauri::Context<R>) -> Self {
+        Core::Headless(Arc::new(HeadlessCore {
+            identifier: context.config().identifier.clone(),
+            package_info: context.package_info().clone(),
+            states: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    // The AppHandle, when running in the window.
+    pub fn app(&self) -> Option<&AppHandle> {
+        match self {
+            Core::App(app) => Some(app),
+            Core::Headless(_) => None,
+        }
+    }
+
+    pub fn state<T: Send + Sync + 'static>(&self) -> &T {
+        self.try_state()
+            .unwrap_or_else(|| panic!("{} is not managed", std::any::type_name::<T>()))
+    }
+
+    pub fn try_state<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        match self {
+            Core::App(app) => app.try_state::<T>().map(|state| state.inner()),
+            Core::Headless(core) => {
+                let state = core
+                    .states
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner())
+                    .get(&std::any::TypeId::of::<T>())
+                    .copied()?;
+                state.downcast_ref::<T>()
+            }
+        }
+    }
 
-// These are minimal Rust-side mirrors of the existing UI types.
-// They intentionally mirror the TS shapes so we can return stub data now,
-// and later evolve them into the real Job/Config models.
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct AppConfig {
-    pub initialized: bool,
-    pub vault_path: String,
-    pub output_subfolder: String,
-    pub model_size: String,
-    pub preload_model: bool,
-    pub language: Option<String>,
-    pub enable_summarization: bool,
-    pub auto_summarize_after_transcription: bool,
-    pub ollama_base_url: String,
-    pub ollama_model: String,
-    pub summary_prompt: String,
-    pub include_timestamps: bool,
-    pub watch_inbox_enabled: bool,
-    pub inbox_poll_seconds: u32,
-    pub whisper_binary_url: Option<String>,
-    pub ffmpeg_binary_url: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Job {
-    pub id: String,
-    pub filename: String,
-    pub status: String,
-    pub progress: f32,
-    pub stage: String,
-    pub logs: Vec<String>,
-    pub created_at: String,
-    pub audio_path: String,
-    pub transcript_txt_path: String,
-    pub transcript_json_path: String,
-    pub transcript_srt_path: String,
-    pub md_preview: Option<String>,
-    pub summary_status: Option<String>,
-    pub summary_model: Option<String>,
-    pub summary_error: Option<String>,
-    pub summary_md: Option<String>,
-    pub exported_to_obsidian: bool,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Segment {
-    pub start: f32,
-    pub end: f32,
-    pub text: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SummaryResponse {
-    pub summary_status: String,
-    pub summary_model: String,
-    pub summary_error: Option<String>,
-    pub summary_md: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ModelDownloadStatus {
-    pub state: String,
-    pub model_size: String,
-    pub repo_id: String,
-    pub total_bytes: u64,
-    pub downloaded_bytes: u64,
-    pub message: Option<String>,
-    pub started_at: Option<u64>,
-    pub finished_at: Option<u64>,
-}
+    // Like Manager::manage: the first value of a type wins.
+    pub fn manage<T: Send + Sync + 'static>(&self, state: T) -> bool {
+        match self {
+            Core::App(app) => app.manage(state),
+            Core::Headless(core) => {
+                let mut states = core.states.lock().unwrap_or_else(|err| err.into_inner());
+                let key = std::any::TypeId::of::<T>();
+                if states.contains_key(&key) {
+                    return false;
+                }
+                states.insert(key, Box::leak(Box::new(state)));
+                true
+            }
+        }
+    }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct JobLogEvent {
-    id: String,
-    line: String,
-}
-
-pub struct ModelDownloadState {
-    models_dir: PathBuf,
-    whisper_dir: PathBuf,
-    ffmpeg_dir: PathBuf,
-    statuses: Arc<Mutex<HashMap<String, ModelDownloadStatus>>>,
-}
-
-impl ModelDownloadState {
-    pub fn load(app: &AppHandle) -> Result<Self, String> {
-        let base_dir = app
-            .path()
-            .app_data_dir()
-            .map_err(|err| format!("app_data_dir unavailable: {err}"))?;
-        let app_dir = base_dir.join("voicenote");
-        fs::create_dir_all(&app_dir)
-            .map_err(|err| format!("failed to create app data dir: {err}"))?;
-        let models_dir = app_dir.join("models");
-        fs::create_dir_all(&models_dir)
-            .map_err(|err| format!("failed to create models dir: {err}"))?;
-        let whisper_dir = app_dir.join("whisper");
-        fs::create_dir_all(&whisper_dir)
-            .map_err(|err| format!("failed to create whisper dir: {err}"))?;
-        let ffmpeg_dir = app_dir.join("ffmpeg");
-        fs::create_dir_all(&ffmpeg_dir)
-            .map_err(|err| format!("failed to create ffmpeg dir: {err}"))?;
-        Ok(Self {
-            models_dir,
-            whisper_dir,
-            ffmpeg_dir,
-            statuses: Arc::new(Mutex::new(HashMap::new())),
-        })
+    pub fn emit<S: Serialize + Clone>(&self, event: &str, payload: S) -> Result<(), String> {
+        match self {
+            Core::App(app) => app.emit(event, payload).map_err(|err| err.to_string()),
+            Core::Headless(_) => Ok(()),
+        }
     }
-}
 
-fn emit_job_updated(app: &AppHandle, job: &Job) {
-    // Fire-and-forget so UI can update without polling in Tauri mode.
-    let _ = app.emit("job:updated", job);
-}
+    pub fn package_info(&self) -> &tauri::PackageInfo {
+        match self {
+            Core::App(app) => app.package_info(),
+            Core::Headless(core) => &core.package_info,
+        }
+    }
 
-fn emit_job_log(app: &AppHandle, job_id: &str, line: &str) {
-    // Small payload so UI can append to its log buffer.
-    let payload = JobLogEvent {
-        id: job_id.to_string(),
-        line: line.to_string(),
-    };
-    let _ = app.emit("job:log", payload);
-}
+    // Tauri's app data folder; see app_data_root for where the library actually lives.
+    fn app_data_dir(&self) -> Result<PathBuf, String> {
+        match self {
+            Core::App(app) => app
+                .path()
+                .app_data_dir()
+                .map_err(|err| format!("app_data_dir unavailable: {err}")),
+            Core::Headless(core) => dirs::data_dir()
+                .map(|dir| dir.join(&core.identifier))
+                .ok_or_else(|| "app_data_dir unavailable: no data folder on this system".to_string()),
+        }
+    }
+
+    fn resource_dir(&self) -> Option<PathBuf> {
+        match self {
+            Core::App(app) => app.path().resource_dir().ok(),
+            Core::Headless(core) => {
+                tauri::utils::platform::resource_dir(&core.package_info, &tauri::Env::default()).ok()
+            }
+        }
+    }
 
-fn push_log(job: &mut Job, line: &str) {
-    // Keep a bounded in-memory log buffer to avoid unbounded growth.
-    job.logs.push(line.to_string());
-    if job.logs.len() > 2000 {
-        let excess = job.logs.len() - 2000;
-        job.logs.drain(0..excess);
+    pub fn is_headless(&self) -> bool {
+        matches!(self, Core::Headless(_))
     }
-}
 
-impl Default for AppConfig {
-    fn default() -> Self {
-        AppConfig {
-            initialized: false,
-            vault_path: String::new(),
-            output_subfolder: "VoiceNote".to_string(),
-            model_size: "small".to_string(),
-            preload_model: false,
-            language: Some("en".to_string()),
-            enable_summarization: true,
-            auto_summarize_after_transcription: true,
-            ollama_base_url: "http://127.0.0.1:11434".to_string(),
-            ollama_model: "qwen2.5:7b-instruct".to_string(),
-            summary_prompt: "Summarize the transcript.".to_string(),
-            include_timestamps: true,
-            watch_inbox_enabled: false,
-            inbox_poll_seconds: 10,
-            whisper_binary_url: Some(
-                "https://github.com/bizenlabs/whisper-cpp-macos-bin/releases/latest"
-                    .to_string(),
-            ),
-            ffmpeg_binary_url: Some(
-                "https://github.com/ravaru/voicenoteapp/releases/latest/download/ffmpeg-macos-arm64-lgpl.zip".to_string(),
-            ),
+    // A desktop notification that opens the job when clicked; the CLI shows none.
+    fn notify_job(&self, job_id: &str, title: &str, body: &str) -> Result<(), String> {
+        match self {
+            Core::App(app) => show_job_notification(app, job_id, title, body),
+            Core::Headless(_) => Ok(()),
         }
     }
-}
 
-pub struct ConfigState {
-    path: PathBuf,
-    config: Mutex<AppConfig>,
-}
-
-impl ConfigState {
-    pub fn load(app: &AppHandle) -> Result<Self, String> {
-        let base_dir = app
-            .path()
-            .app_data_dir()
-            .map_err(|err| format!("app_data_dir unavailable: {err}"))?;
-        let app_dir = base_dir.join("voicenote");
-        fs::create_dir_all(&app_dir)
-            .map_err(|err| format!("failed to create app data dir: {err}"))?;
-        let path = app_dir.join("config.json");
-        let config = load_config_from_disk(&path)?;
-        Ok(Self {
-            path,
-            config: Mutex::new(config),
-        })
+    // Restarts the window so every state struct is loaded again; the CLI has nothing to restart.
+    fn restart(&self) {
+        if let Core::App(app) = self {
+            app.restart();
+        }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct JobIndex {
-    jobs: Vec<Job>,
-}
-
-pub struct JobIndexState {
-    path: PathBuf,
-    jobs_dir: PathBuf,
-    index: Mutex<JobIndex>,
-}
-
-impl JobIndexState {
-    pub fn load(app: &AppHandle) -> Result<Self, String> {
-        let base_dir = app
-            .path()
-            .app_data_dir()
-            .map_err(|err| format!("app_data_dir unavailable: {err}"))?;
-        let app_dir = base_dir.join("voicenote");
-        fs::create_dir_all(&app_dir)
-            .map_err(|err| format!("failed to create app data dir: {err}"))?;
-        let path = app_dir.join("index.json");
-        let jobs_dir = app_dir.join("jobs");
-        fs::create_dir_all(&jobs_dir)
-            .map_err(|err| format!("failed to create jobs dir: {err}"))?;
-        let index = load_index_from_disk(&path)?;
-        Ok(Self {
-            path,
-            jobs_dir,
-            index: Mutex::new(index),
-        })
+impl From<AppHandle> for Core {
+    fn from(app: AppHandle) -> Self {
+        Core::App(app)
     }
 }
 
-pub struct JobQueueState {
-    sender: mpsc::Sender<String>,
+impl From<&AppHandle> for Core {
+    fn from(app: &AppHandle) -> Self {
+        Core::App(app.clone())
+    }
 }
 
-impl JobQueueState {
-    pub fn new(sender: mpsc::Sender<String>) -> Self {
-        Self { sender }
+// Lets commands take `app: Core` where they took the AppHandle.
+impl<'de> tauri::ipc::CommandArg<'de, tauri::Wry> for Core {
+    fn from_command(command: tauri::ipc::CommandItem<'de, tauri::Wry>) -> Result<Self, tauri::ipc::InvokeError> {
+        AppHandle::from_command(command).map(Core::App)
     }
+}
 
-    pub fn enqueue(&self, job_id: String) -> Result<(), String> {
-        self.sender
-            .send(job_id)
-            .map_err(|err| format!("failed to enqueue job: {err}"))
-    }
+#[tauri::command]
+pub fn set_llm_api_key(app: Core, key: String) -> Result<bool, String> {
+    store_llm_api_key(&app, &key)?;
+    Ok(!key.trim().is_empty())
 }
 
-fn load_config_from_disk(path: &PathBuf) -> Result<AppConfig, String> {
-    if !path.exists() {
-        return Ok(AppConfig::default());
-    }
-    let mut file =
-        File::open(path).map_err(|err| format!("failed to open config.json: {err}"))?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)
-        .map_err(|err| format!("failed to read config.json: {err}"))?;
-    if contents.trim().is_empty() {
-        return Ok(AppConfig::default());
-    }
-    serde_json::from_str(&contents).map_err(|err| format!("invalid config.json: {err}"))
+#[tauri::command]
+pub fn has_llm_api_key(app: Core) -> bool {
+    load_llm_api_key(&app).is_some()
 }
 
-fn save_config_to_disk(path: &PathBuf, config: &AppConfig) -> Result<(), String> {
-    let json = serde_json::to_string_pretty(config)
-        .map_err(|err| format!("failed to serialize config.json: {err}"))?;
-    let mut file =
-        File::create(path).map_err(|err| format!("failed to write config.json: {err}"))?;
-    file.write_all(json.as_bytes())
-        .map_err(|err| format!("failed to save config.json: {err}"))
+// Stores a named secret in the keychain; returns false when the value was empty (cleared).
+#[tauri::command]
+pub fn set_secret(app: Core, name: String, value: String) -> Result<bool, String> {
+    let name = name.trim();
+    validate_secret_name(name)?;
+    store_secret(&app, SECRETS_KEYCHAIN_SERVICE, name, &named_secret_file(name), &value)?;
+    Ok(!value.trim().is_empty())
 }
 
-fn load_index_from_disk(path: &PathBuf) -> Result<JobIndex, String> {
-    if !path.exists() {
-        return Ok(JobIndex { jobs: Vec::new() });
-    }
-    let mut file =
-        File::open(path).map_err(|err| format!("failed to open index.json: {err}"))?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)
-        .map_err(|err| format!("failed to read index.json: {err}"))?;
-    if contents.trim().is_empty() {
-        return Ok(JobIndex { jobs: Vec::new() });
-    }
-    serde_json::from_str(&contents).map_err(|err| format!("invalid index.json: {err}"))
-}
-
-fn save_index_to_disk(path: &PathBuf, index: &JobIndex) -> Result<(), String> {
-    let json = serde_json::to_string_pretty(index)
-        .map_err(|err| format!("failed to serialize index.json: {err}"))?;
-    let mut file =
-        File::create(path).map_err(|err| format!("failed to write index.json: {err}"))?;
-    file.write_all(json.as_bytes())
-        .map_err(|err| format!("failed to save index.json: {err}"))
-}
-
-fn generate_job_id() -> String {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_micros())
-        .unwrap_or(0);
-    let pid = std::process::id();
-    format!("job_{now}_{pid}")
-}
-
-fn unix_timestamp_string() -> String {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_secs())
-        .unwrap_or(0);
-    now.to_string()
-}
-
-fn build_job_audio_path(jobs_dir: &PathBuf, job_id: &str, source_path: &str) -> Result<PathBuf, String> {
-    let job_dir = jobs_dir.join(job_id);
-    fs::create_dir_all(&job_dir)
-        .map_err(|err| format!("failed to create job dir: {err}"))?;
-    let ext = std::path::Path::new(source_path)
-        .extension()
-        .and_then(|value| value.to_str())
-        .unwrap_or("");
-    let filename = if ext.is_empty() {
-        "audio.original".to_string()
-    } else {
-        format!("audio.original.{ext}")
-    };
-    Ok(job_dir.join(filename))
-}
-
-fn job_dir_from_audio_path(audio_path: &str) -> Option<PathBuf> {
-    std::path::Path::new(audio_path).parent().map(|p| p.to_path_buf())
-}
-
-fn write_stub_artifacts(job_dir: &PathBuf) -> Result<(String, String, String), String> {
-    fs::create_dir_all(job_dir)
-        .map_err(|err| format!("failed to create job dir: {err}"))?;
-    let transcript_path = job_dir.join("transcript.txt");
-    let segments_path = job_dir.join("segments.json");
-    let srt_path = job_dir.join("transcript.srt");
-    let transcript = "Stub transcript from Rust core.\n";
-    let segments = r#"[{"start":0.0,"end":1.5,"text":"Stub segment one."},{"start":1.6,"end":3.2,"text":"Stub segment two."}]"#;
-    fs::write(&transcript_path, transcript)
-        .map_err(|err| format!("failed to write transcript.txt: {err}"))?;
-    fs::write(&segments_path, segments)
-        .map_err(|err| format!("failed to write segments.json: {err}"))?;
-    fs::write(&srt_path, "")
-        .map_err(|err| format!("failed to write transcript.srt: {err}"))?;
-    Ok((
-        transcript_path.to_string_lossy().to_string(),
-        segments_path.to_string_lossy().to_string(),
-        srt_path.to_string_lossy().to_string(),
-    ))
-}
-
-fn read_transcript_text(path: &str) -> Result<String, String> {
-    let content =
-        fs::read_to_string(path).map_err(|err| format!("failed to read transcript: {err}"))?;
-    Ok(content)
-}
-
-fn build_summary_prompt(template: &str, transcript: &str) -> String {
-    if template.contains("{text}") {
-        template.replace("{text}", transcript)
-    } else {
-        format!("{template}\n\n{text}\n", template = template, text = transcript)
-    }
+#[tauri::command]
+pub fn delete_secret(app: Core, name: String) -> Result<(), String> {
+    let name = name.trim();
+    validate_secret_name(name)?;
+    store_secret(&app, SECRETS_KEYCHAIN_SERVICE, name, &named_secret_file(name), "")
 }
 
-fn sanitize_filename(input: &str) -> String {
-    input
-        .chars()
-        .map(|ch| match ch {
-            '/' | '\\' | ':' | '\0' => '-',
-            _ => ch,
-        })
-        .collect::<String>()
-        .trim()
-        .to_string()
+// Whether a secret is stored; the value itself never goes back to the UI.
+#[tauri::command]
+pub fn has_secret(app: Core, name: String) -> bool {
+    load_named_secret(&app, name.trim()).is_some()
 }
 
-fn write_summary_file(job_dir: &PathBuf, content: &str) -> Result<String, String> {
-    let summary_path = job_dir.join("summary.md");
-    fs::write(&summary_path, content)
-        .map_err(|err| format!("failed to write summary.md: {err}"))?;
-    Ok(summary_path.to_string_lossy().to_string())
+// Brings every job folder, row and log in line with the encryption settings after they change.
+// Jobs that are still being transcribed are left for process_job to seal when it finishes. The
+// index lock is only held to save each job, not while its files are rewritten.
+#[tauri::command]
+pub async fn apply_job_encryption(app: Core) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || apply_job_encryption_blocking(&app))
+        .await
+        .map_err(|err| format!("encryption task failed: {err}"))?
 }
 
-fn summarize_with_ollama(
-    base_url: &str,
-    model: &str,
-    prompt: &str,
-) -> Result<String, String> {
-    let url = format!("{}/api/generate", base_url.trim_end_matches('/'));
-    let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(120))
-        .build()
-        .map_err(|err| format!("Failed to build HTTP client: {err}"))?;
-    let payload = serde_json::json!({
-        "model": model,
-        "prompt": prompt,
-        "stream": false
-    });
-    let resp = client
-        .post(&url)
-        .json(&payload)
-        .send()
-        .map_err(|err| {
-            if err.is_timeout() {
-                format!("Ollama timeout after 120s at {url}")
-            } else if err.is_connect() {
-                format!("Ollama not reachable at {url}. Is Ollama running?")
-            } else {
-                format!("Ollama request failed: {err}")
-            }
-        })?;
-    if !resp.status().is_success() {
-        let status = resp.status();
-        let body = resp.text().unwrap_or_default();
-        return Err(format!("Ollama error: {status} {body}"));
-    }
-    let json: serde_json::Value = resp
-        .json()
-        .map_err(|err| format!("Invalid Ollama response: {err}"))?;
-    let response = json
-        .get("response")
-        .and_then(|v| v.as_str())
-        .unwrap_or("")
-        .to_string();
-    if response.trim().is_empty() {
-        return Err("Ollama returned empty response.".to_string());
-    }
-    Ok(response)
+// Stops the job's summary, template, title and action item runs: running ones stop waiting for
+// the LLM and write nothing, queued ones are skipped. True when a run was in progress.
+#[tauri::command]
+pub fn cancel_summary(
+    app: Core,
+    cancel_state: State<SummaryCancelState>,
+    id: String,
+) -> Result<bool, String> {
+    cancel_summary_inner(&app, &cancel_state, id)
 }
 
-fn parse_progress_from_line(line: &str) -> Option<f32> {
-    for token in line.split_whitespace() {
-        if let Some(stripped) = token.strip_suffix('%') {
-            if let Ok(value) = stripped.parse::<f32>() {
-                return Some(value.max(0.0).min(100.0));
-            }
-        }
-    }
-    None
+#[tauri::command]
+pub fn list_ollama_models(
+    config_state: State<ConfigState>,
+    base_url: Option<String>,
+) -> Result<Vec<OllamaModel>, String> {
+    list_ollama_models_inner(&config_state, base_url)
 }
 
-fn update_job_and_emit<F>(app: &AppHandle, job_id: &str, mutator: F) -> Result<(), String>
-where
-    F: FnOnce(&mut Job),
-{
-    let index_state = app.state::<JobIndexState>();
-    let mut guard = index_state
-        .index
+#[tauri::command]
+pub fn check_llm_connection(
+    app: Core,
+    config_state: State<ConfigState>,
+) -> Result<LlmConnectionStatus, String> {
+    let config = config_state
+        .config
         .lock()
-        .map_err(|_| "job index mutex poisoned".to_string())?;
-    let mut snapshot: Option<Job> = None;
-    if let Some(job) = guard.jobs.iter_mut().find(|job| job.id == job_id) {
-        mutator(job);
-        snapshot = Some(job.clone());
-        save_index_to_disk(&index_state.path, &guard)?;
-    }
-    if let Some(job) = snapshot {
-        emit_job_updated(app, &job);
-    }
-    Ok(())
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    Ok(llm_connection_status(&LlmSettings::resolve(&app, &config)))
 }
 
-fn append_job_log(app: &AppHandle, job_id: &str, line: &str) -> Result<(), String> {
-    update_job_and_emit(app, job_id, |job| {
-        push_log(job, line);
-    })?;
-    emit_job_log(app, job_id, line);
+#[tauri::command]
+pub async fn ask_transcript(app: Core, id: String, question: String) -> Result<TranscriptAnswer, String> {
+    tauri::async_runtime::spawn_blocking(move || ask_transcript_blocking(&app, &id, &question))
+        .await
+        .map_err(|err| format!("question task failed: {err}"))?
+}
+
+#[tauri::command]
+pub fn index_embeddings(
+    app: Core,
+    state: State<JobIndexState>,
+    config_state: State<ConfigState>,
+    ids: Option<Vec<String>>,
+) -> Result<usize, String> {
+    index_embeddings_inner(&app, &state, &config_state, ids)
+}
+
+#[tauri::command]
+pub async fn semantic_search(app: Core, query: String, limit: Option<usize>) -> Result<Vec<SemanticMatch>, String> {
+    tauri::async_runtime::spawn_blocking(move || semantic_search_blocking(&app, &query, limit))
+        .await
+        .map_err(|err| format!("search task failed: {err}"))?
+}
+
+// What ffmpeg, ffprobe and whisper.cpp on PATH (and in Homebrew's folders) would give the
+// pipeline, whether or not use_system_binaries is on.
+#[tauri::command]
+pub fn detect_system_binaries(config_state: State<ConfigState>) -> Result<SystemBinaries, String> {
+    detect_system_binaries_inner(&config_state)
+}
+
+#[tauri::command]
+pub fn list_custom_models(state: State<ModelDownloadState>) -> Vec<CustomModel> {
+    load_custom_models(state.metadata_dir())
+}
+
+// Copies a local ggml/gguf model into the models folder and adds it to the catalog under
+// `name` (default: the file name). Progress goes out as "model:import" events.
+#[tauri::command]
+pub async fn import_model_from_path(app: Core, path: String, name: Option<String>) -> Result<CustomModel, String> {
+    tauri::async_runtime::spawn_blocking(move || import_model_file(&app, &path, name))
+        .await
+        .map_err(|err| format!("model import task failed: {err}"))?
+}
+
+#[tauri::command]
+pub fn get_run_manifest(
+    state: State<JobIndexState>,
+    id: String,
+) -> Result<Option<RunManifest>, String> {
+    get_run_manifest_inner(&state, id)
+}
+
+#[tauri::command]
+pub fn get_do_not_disturb_status() -> bool {
+    is_do_not_disturb_active()
+}
+
+#[tauri::command]
+pub fn get_system_status(app: Core) -> Result<SystemStatus, String> {
+    get_system_status_inner(&app)
+}
+
+// The notification plugin shows desktop notifications and lets go of them, so a click never
+// reaches the app. notify-rust, which the plugin uses underneath, hands the notification back
+// instead: waiting on it tells a click on the body from a dismissal, and a click opens the job.
+fn show_job_notification(app: &AppHandle, job_id: &str, title: &str, body: &str) -> Result<(), String> {
+    let mut notification = notify_rust::Notification::new();
+    notification.summary(title).body(body).auto_icon();
+    // Same as the plugin: a dev build has no bundle of its own to post from.
+    #[cfg(target_os = "macos")]
+    let _ = notify_rust::set_application(if tauri::is_dev() {
+        "com.apple.Terminal"
+    } else {
+        &app.config().identifier
+    });
+    #[cfg(windows)]
+    if !tauri::is_dev() {
+        notification.app_id(&app.config().identifier);
+    }
+    // Notification servers only report a click on the body for a "default" action.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    notification.action("default", "Open");
+    let shown = notification.show().map_err(|err| err.to_string())?;
+    let (app, job_id) = (app.clone(), job_id.to_string());
+    // Blocks until the notification is clicked or goes away.
+    thread::spawn(move || {
+        let _ = shown.wait_for_response(|response: &notify_rust::NotificationResponse| {
+            if *response == notify_rust::NotificationResponse::Default {
+                let _ = open_job_in_window(&app, &job_id);
+            }
+        });
+    });
     Ok(())
 }
 
-fn is_macho_binary(path: &PathBuf) -> bool {
-    let mut file = match File::open(path) {
-        Ok(file) => file,
-        Err(_) => return false,
-    };
-    let mut buf = [0u8; 4];
-    if file.read_exact(&mut buf).is_err() {
-        return false;
-    }
-    let be = u32::from_be_bytes(buf);
-    let le = u32::from_le_bytes(buf);
-    matches!(
-        be,
-        0xFEEDFACE | 0xFEEDFACF | 0xCAFEBABE | 0xBEBAFECA | 0xCEFAEDFE | 0xCFFAEDFE
-    ) || matches!(
-        le,
-        0xFEEDFACE | 0xFEEDFACF | 0xCAFEBABE | 0xBEBAFECA | 0xCEFAEDFE | 0xCFFAEDFE
-    )
+#[tauri::command]
+pub fn get_queue_status(app: Core) -> Result<QueueStatus, String> {
+    queue_status(&app)
 }
 
-fn resolve_whisper_paths(app: &AppHandle, model_size: &str) -> Result<(PathBuf, PathBuf), String> {
-    if let (Ok(bin), Ok(model)) = (
-        std::env::var("VOICENOTE_WHISPER_PATH"),
-        std::env::var("VOICENOTE_WHISPER_MODEL"),
-    ) {
-        let bin_path = PathBuf::from(bin);
-        let model_path = PathBuf::from(model);
-        if bin_path.exists() && model_path.exists() {
-            return Ok((bin_path, model_path));
-        }
-    }
+#[tauri::command]
+pub fn set_queue_paused(app: Core, paused: bool) -> Result<QueueStatus, String> {
+    app.state::<JobQueueState>().paused.store(paused, Ordering::SeqCst);
+    let status = queue_status(&app)?;
+    let _ = app.emit("queue:updated", &status);
+    Ok(status)
+}
 
-    let mut bin_candidates: Vec<PathBuf> = Vec::new();
-    let mut model_candidates: Vec<PathBuf> = Vec::new();
-    let model_name = format!("ggml-{model_size}.bin");
+pub const TRAY_ID: &str = "main";
 
-    bin_candidates.push(PathBuf::from("third_party/whisper/bin/whisper"));
-    bin_candidates.push(PathBuf::from("third_party/whisper/bin/main"));
+const TRAY_RECENT_JOBS: usize = 5;
 
-    model_candidates.push(PathBuf::from(format!(
-        "third_party/whisper/models/{model_name}"
-    )));
+const TRAY_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
 
-    if let Ok(cwd) = std::env::current_dir() {
-        bin_candidates.push(cwd.join("third_party/whisper/bin/whisper"));
-        bin_candidates.push(cwd.join("third_party/whisper/bin/main"));
-        model_candidates.push(cwd.join(format!(
-            "third_party/whisper/models/{model_name}"
-        )));
-    }
+// What the tray menu shows. The menu is rebuilt only when this changes, not on every
+// progress update.
+#[derive(Debug, Clone, PartialEq)]
+struct TrayMenuModel {
+    status: String,
+    paused: bool,
+    recording: bool,
+    // (job id, label), newest first.
+    recent: Vec<(String, String)>,
+    last_note: Option<String>,
+}
 
-    if let Ok(resource_dir) = app.path().resource_dir() {
-        bin_candidates.push(resource_dir.join("whisper/bin/whisper"));
-        bin_candidates.push(resource_dir.join("whisper/bin/main"));
-        model_candidates.push(resource_dir.join(format!("whisper/models/{model_name}")));
+fn tray_menu_model(jobs: &[Job], paused: bool, recording: bool) -> TrayMenuModel {
+    let visible: Vec<&Job> = jobs.iter().filter(|job| job.trashed_at.is_none()).collect();
+    let running = visible.iter().filter(|job| job.status == JobStatus::Running).count();
+    let queued = visible.iter().filter(|job| job.status == JobStatus::Queued).count();
+    let mut parts = Vec::new();
+    if running > 0 {
+        parts.push(format!("{running} transcribing"));
     }
-
-    if let Ok(app_data_dir) = app.path().app_data_dir() {
-        model_candidates.push(app_data_dir.join(format!("voicenote/models/{model_name}")));
-        bin_candidates.push(app_data_dir.join("voicenote/whisper/bin/whisper"));
-        bin_candidates.push(app_data_dir.join("voicenote/whisper/bin/main"));
+    if queued > 0 {
+        parts.push(format!("{queued} queued"));
     }
-
-    if let Ok(exe) = std::env::current_exe() {
-        if let Some(dir) = exe.parent() {
-            bin_candidates.push(dir.join("whisper"));
-            bin_candidates.push(dir.join("main"));
-            model_candidates.push(dir.join(format!(
-                "../Resources/whisper/models/{model_name}"
-            )));
-        }
+    let status = match (paused, parts.is_empty()) {
+        (false, true) => "Idle".to_string(),
+        (false, false) => parts.join(", "),
+        (true, true) => "Queue paused".to_string(),
+        (true, false) => format!("Queue paused ({})", parts.join(", ")),
+    };
+    let recent = visible
+        .iter()
+        .take(TRAY_RECENT_JOBS)
+        .map(|job| {
+            let title = job_title(job);
+            let label = if job.status == JobStatus::Done { title } else { format!("{title} ({})", job.status) };
+            (job.id.clone(), label)
+        })
+        .collect();
+    let last_note = visible.iter().find(|job| job.status == JobStatus::Done).map(|job| job.id.clone());
+    TrayMenuModel {
+        status,
+        paused,
+        recording,
+        recent,
+        last_note,
     }
+}
 
-    let bin = bin_candidates
-        .into_iter()
-        .find(|p| p.exists() && is_macho_binary(p));
-    let model = model_candidates.into_iter().find(|p| p.exists());
+fn current_tray_model(app: &Core) -> TrayMenuModel {
+    let paused = app.state::<JobQueueState>().is_paused();
+    let recording = is_recording(app);
+    app.state::<JobIndexState>()
+        .index
+        .lock()
+        .map(|guard| tray_menu_model(&guard.jobs, paused, recording))
+        .unwrap_or_else(|_| tray_menu_model(&[], paused, recording))
+}
 
-    if let (Some(bin), Some(model)) = (bin, model) {
-        return Ok((bin, model));
-    }
+fn is_recording(app: &Core) -> bool {
+    app.state::<RecordingState>()
+        .active
+        .lock()
+        .map(|active| active.is_some())
+        .unwrap_or(false)
+}
 
-    Err(
-        "Whisper binary/model not found. Provide whisper.cpp at third_party/whisper/bin/whisper \
-and model at third_party/whisper/models/ggml-<size>.bin, or set VOICENOTE_WHISPER_PATH \
-and VOICENOTE_WHISPER_MODEL."
-            .to_string(),
+fn build_tray_menu(app: &AppHandle, model: &TrayMenuModel) -> tauri::Result<Menu<tauri::Wry>> {
+    let status = MenuItem::with_id(app, "tray:status", &model.status, false, None::<&str>)?;
+    let recent_items = model
+        .recent
+        .iter()
+        .map(|(id, label)| MenuItem::with_id(app, format!("tray:job:{id}"), label, true, None::<&str>))
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let recent_refs: Vec<&dyn IsMenuItem<tauri::Wry>> =
+        recent_items.iter().map(|item| item as &dyn IsMenuItem<tauri::Wry>).collect();
+    let recent = Submenu::with_items(app, "Recent jobs", !recent_refs.is_empty(), &recent_refs)?;
+    let pause_label = if model.paused { "Resume queue" } else { "Pause queue" };
+    let pause = MenuItem::with_id(app, "tray:pause", pause_label, true, None::<&str>)?;
+    let record_label = if model.recording { "Stop recording" } else { "Start recording" };
+    let record = MenuItem::with_id(app, "tray:record", record_label, true, None::<&str>)?;
+    let last = MenuItem::with_id(app, "tray:last", "Open last note", model.last_note.is_some(), None::<&str>)?;
+    let show = MenuItem::with_id(app, "tray:show", "Show window", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "tray:quit", "Quit VoiceNote", true, None::<&str>)?;
+    Menu::with_items(
+        app,
+        &[
+            &status,
+            &recent,
+            &PredefinedMenuItem::separator(app)?,
+            &pause,
+            &record,
+            &last,
+            &PredefinedMenuItem::separator(app)?,
+            &show,
+            &quit,
+        ],
     )
 }
 
-fn model_filename(model_size: &str) -> Result<String, String> {
-    let filename = match model_size {
-        "tiny" => "ggml-tiny.bin",
-        "base" => "ggml-base.bin",
-        "small" => "ggml-small.bin",
-        "medium" => "ggml-medium.bin",
-        "large-v3" => "ggml-large-v3.bin",
-        other => {
-            return Err(format!(
-                "Unknown model size: {other}. Expected tiny/base/small/medium/large-v3."
-            ));
-        }
+fn handle_tray_menu(app: &AppHandle, id: &str) {
+    let core = Core::from(app);
+    let result = match id {
+        "tray:pause" => {
+            let paused = !app.state::<JobQueueState>().is_paused();
+            set_queue_paused(core, paused).map(|_| ())
+        }
+        "tray:record" if is_recording(&core) => {
+            stop_recording(core, app.state(), app.state(), app.state()).map(|_| ())
+        }
+        "tray:record" => start_recording(core, app.state(), app.state()).map(|_| ()),
+        "tray:last" => match current_tray_model(&core).last_note {
+            Some(job_id) => open_job_in_window(app, &job_id),
+            None => Ok(()),
+        },
+        "tray:show" => {
+            show_main_window(app);
+            Ok(())
+        }
+        "tray:quit" => {
+            app.exit(0);
+            Ok(())
+        }
+        other => match other.strip_prefix("tray:job:") {
+            Some(job_id) => open_job_in_window(app, job_id),
+            None => Ok(()),
+        },
     };
-    Ok(filename.to_string())
+    if let Err(err) = result {
+        tracing::warn!("tray action {id} failed: {err}");
+    }
+}
+
+// Menu bar item with the queue status, recent jobs and quick actions. A thread polls the
+// job list and swaps in a new menu when what it shows has changed.
+pub fn spawn_tray(app: &AppHandle) -> Result<(), String> {
+    let core = Core::from(app);
+    let model = current_tray_model(&core);
+    let menu = build_tray_menu(app, &model).map_err(|err| format!("failed to build tray menu: {err}"))?;
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .tooltip(format!("VoiceNote — {}", model.status))
+        .show_menu_on_left_click(true)
+        .on_menu_event(|app, event| handle_tray_menu(app, event.id().as_ref()));
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder
+        .build(app)
+        .map_err(|err| format!("failed to create tray icon: {err}"))?;
+    let handle = app.clone();
+    thread::spawn(move || {
+        let mut shown = model;
+        loop {
+            thread::sleep(TRAY_REFRESH_INTERVAL);
+            let model = current_tray_model(&core);
+            if model == shown {
+                continue;
+            }
+            if let (Some(tray), Ok(menu)) = (handle.tray_by_id(TRAY_ID), build_tray_menu(&handle, &model)) {
+                let _ = tray.set_menu(Some(menu));
+                let _ = tray.set_tooltip(Some(format!("VoiceNote — {}", model.status)));
+            }
+            shown = model;
+        }
+    });
+    Ok(())
 }
 
-fn model_url(model_size: &str) -> Result<String, String> {
-    let filename = model_filename(model_size)?;
-    Ok(format!(
-        "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/{filename}?download=true"
-    ))
+// With close_to_tray the main window only hides, so running and queued jobs carry on; the
+// tray's Quit (or Cmd+Q) ends the app. Without a tray icon (spawn_tray failed, e.g. Linux without
+// appindicator) a hidden window couldn't be brought back, so closing it quits as usual.
+pub fn close_to_tray(app: &AppHandle) -> bool {
+    let enabled = app
+        .try_state::<ConfigState>()
+        .and_then(|state| state.config.lock().ok().map(|config| config.close_to_tray))
+        .unwrap_or(false);
+    enabled && app.tray_by_id(TRAY_ID).is_some()
 }
 
-fn now_ts() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_secs())
-        .unwrap_or(0)
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
 }
 
-fn whisper_binary_status_key() -> String {
-    "whisper-binary".to_string()
+fn open_job_in_window(app: &AppHandle, id: &str) -> Result<(), String> {
+    find_job(&app.state::<JobIndexState>(), id)?;
+    show_main_window(app);
+    app.emit("deeplink:job", DeepLinkJobEvent { id: id.to_string() })
+        .map_err(|err| format!("failed to open job: {err}"))
 }
 
-fn ffmpeg_status_key() -> String {
-    "ffmpeg".to_string()
+#[tauri::command]
+pub fn get_health() -> bool {
+    // Tauri is running; no external backend is required in this mode.
+    true
 }
 
-fn github_repo_from_api(url: &str) -> Option<String> {
-    let marker = "api.github.com/repos/";
-    let idx = url.find(marker)?;
-    let rest = &url[idx + marker.len()..];
-    let mut parts = rest.split('/');
-    let owner = parts.next()?;
-    let repo = parts.next()?;
-    Some(format!("https://github.com/{owner}/{repo}"))
+#[tauri::command]
+pub fn get_config(state: State<ConfigState>) -> Result<Revisioned<AppConfig>, String> {
+    let guard = state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?;
+    Ok(Revisioned {
+        value: guard.clone(),
+        revision: state.revision.load(Ordering::SeqCst),
+    })
 }
 
-fn is_macos_arm_asset(name: &str) -> bool {
-    let name_lc = name.to_lowercase();
-    let is_arm = name_lc.contains("arm64") || name_lc.contains("aarch64");
-    let is_macos = name_lc.contains("macos")
-        || name_lc.contains("osx")
-        || name_lc.contains("darwin")
-        || name_lc.contains("apple");
-    let is_zip = name_lc.ends_with(".zip");
-    is_arm && is_macos && is_zip
+// Lets a second window (e.g. the mini recorder) catch up from the revision of the last event or
+// response it saw, instead of reloading every job.
+#[tauri::command]
+pub fn get_changes_since(
+    config_state: State<ConfigState>,
+    index_state: State<JobIndexState>,
+    revision: u64,
+) -> Result<StateChanges, String> {
+    get_changes_since_inner(&config_state, &index_state, revision)
 }
 
-fn extract_latest_tag(html: &str) -> Option<String> {
-    let needle = "/releases/tag/";
-    let mut idx = 0usize;
-    while let Some(pos) = html[idx..].find(needle) {
-        let start = idx + pos + needle.len();
-        let rest = &html[start..];
-        let end = rest
-            .find(['"', '\'', '?', '#', '<', ' '])
-            .unwrap_or(rest.len());
-        if end > 0 {
-            return Some(rest[..end].to_string());
-        }
-        idx = start + end;
-    }
-    None
-}
-
-fn probe_download_url(client: &reqwest::blocking::Client, url: &str) -> bool {
-    let resp = client
-        .get(url)
-        .header(reqwest::header::USER_AGENT, "voicenote")
-        .header(reqwest::header::RANGE, "bytes=0-0")
-        .send();
-    if let Ok(resp) = resp {
-        let status = resp.status();
-        return status.is_success() || status.is_redirection();
-    }
-    false
+#[tauri::command]
+pub fn validate_config(cfg: AppConfig) -> Vec<ConfigFieldError> {
+    config_field_errors(&cfg)
 }
 
-fn resolve_whisper_download_url(url: &str) -> Result<String, String> {
-    let trimmed = url.trim();
-    if trimmed.is_empty() {
-        return Err("Whisper download URL is empty.".to_string());
-    }
-    let url = trimmed.replace("http://", "https://");
-    let normalized = if url.contains("github.com/") && url.contains("/releases") {
-        let parts: Vec<&str> = url.split("github.com/").collect();
-        if parts.len() == 2 {
-            format!("https://api.github.com/repos/{}", parts[1])
-                .replace("/releases/latest", "/releases/latest")
-        } else {
-            url.clone()
-        }
-    } else {
-        url.clone()
-    };
-    let is_github_api = normalized.contains("api.github.com/repos/") && normalized.contains("/releases");
-    if !is_github_api {
-        return Ok(normalized);
-    }
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .get(&normalized)
-        .header(reqwest::header::USER_AGENT, "voicenote")
-        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
-        .header("X-GitHub-Api-Version", "2022-11-28")
-        .send();
-    if let Ok(resp) = resp {
-        if resp.status().is_success() {
-            let json: serde_json::Value = resp
-                .json()
-                .map_err(|err| format!("Invalid GitHub response: {err}"))?;
-            let assets = json
-                .get("assets")
-                .and_then(|v| v.as_array())
-                .ok_or_else(|| "No assets in release.".to_string())?;
-            for asset in assets {
-                let name = asset.get("name").and_then(|v| v.as_str()).unwrap_or("");
-                let url = asset
-                    .get("browser_download_url")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-                if is_macos_arm_asset(name) {
-                    return Ok(url.to_string());
-                }
-            }
-        } else {
-            let status = resp.status();
-            let body = resp.text().unwrap_or_default();
-            if !normalized.ends_with("/releases/latest") {
-                return Err(format!("GitHub API error: {status} {body}"));
-            }
-        }
-    }
-
-    let repo_url = if let Some(repo_url) = github_repo_from_api(&normalized) {
-        repo_url
-    } else {
-        return Err("No macOS arm64 zip asset found in GitHub release.".to_string());
-    };
+#[tauri::command]
+pub fn get_watcher_status(state: State<InboxWatcherState>) -> Result<WatcherStatus, String> {
+    state
+        .status
+        .lock()
+        .map(|status| status.clone())
+        .map_err(|_| "watcher status mutex poisoned".to_string())
+}
 
-    if !repo_url.is_empty() {
-        let latest_url = format!("{}/releases/latest", repo_url);
-        let resp = client
-            .get(&latest_url)
-            .header(reqwest::header::USER_AGENT, "voicenote")
-            .send()
-            .map_err(|err| format!("GitHub HTML request failed: {err}"))?;
-        if resp.status().is_success() {
-            let html = resp.text().unwrap_or_default();
-            let tag = extract_latest_tag(&html);
-            let mut best: Option<String> = None;
-            let needle = "/releases/download/";
-            let mut index = 0;
-            while let Some(pos) = html[index..].find(needle) {
-                let start = index + pos;
-                let rest = &html[start..];
-                if let Some(end) = rest.find(".zip") {
-                    let end_pos = start + end + 4;
-                    let url_path = &html[start..end_pos];
-                    let url = format!("https://github.com{}", url_path);
-                    if is_macos_arm_asset(&url) {
-                        best = Some(url);
-                        break;
-                    }
-                    if best.is_none() {
-                        best = Some(url);
-                    }
-                    index = end_pos;
-                } else {
-                    break;
-                }
-            }
-            if let Some(url) = best {
-                return Ok(url);
-            }
+// Files and voicenote:// links the OS hands to the app (Dock drops, Open With, launch arguments)
+// that arrived before setup created the job queue; open_launch_files takes them from there.
+#[derive(Default)]
+pub struct OpenedFilesState {
+    pending: Mutex<Vec<PathBuf>>,
+    links: Mutex<Vec<tauri::Url>>,
+}
 
-            if let Some(tag) = tag {
-                let version = tag.trim_start_matches('v');
-                let candidates = [
-                    format!("whisper-cpp-{version}-macos-arm64-metal.zip"),
-                    format!("whisper-cpp-v{version}-macos-arm64-metal.zip"),
-                    format!("whisper-cpp-{version}-macos-arm64-accelerate.zip"),
-                    format!("whisper-cpp-v{version}-macos-arm64-accelerate.zip"),
-                    format!("whisper-cpp-{version}-macos-arm64.zip"),
-                    format!("whisper-cpp-v{version}-macos-arm64.zip"),
-                    "whisper-cpp-macos-arm64-metal.zip".to_string(),
-                    "whisper-cpp-macos-arm64.zip".to_string(),
-                ];
-                for name in candidates {
-                    let candidate = format!(
-                        "{}/releases/download/{}/{}",
-                        repo_url, tag, name
-                    );
-                    if probe_download_url(&client, &candidate) {
-                        return Ok(candidate);
-                    }
-                }
-            }
+impl OpenedFilesState {
+    pub fn new() -> Self {
+        Self {
+            pending: Mutex::new(Vec::new()),
+            links: Mutex::new(Vec::new()),
         }
     }
-
-    Err(
-        "No macOS arm64 zip asset found in GitHub release. Paste a direct .zip asset URL from the release."
-            .to_string(),
-    )
 }
 
-fn download_to_file(url: &str, dest: &PathBuf, status: &mut ModelDownloadStatus, status_map: &Arc<Mutex<HashMap<String, ModelDownloadStatus>>>) -> Result<(), String> {
-    let client = reqwest::blocking::Client::new();
-    let mut resp = client
-        .get(url)
-        .header(reqwest::header::USER_AGENT, "voicenote")
-        .send()
-        .map_err(|err| format!("Request failed: {err}"))?;
-    if !resp.status().is_success() {
-        let status_code = resp.status();
-        let body = resp.text().unwrap_or_default();
-        return Err(format!("Download failed ({status_code}): {body}"));
+// Creates and enqueues a job per audio or video file, or holds the paths until setup is done.
+pub fn open_files(app: &AppHandle, paths: Vec<PathBuf>) {
+    let paths: Vec<PathBuf> = paths.into_iter().filter(|path| path.is_file() && is_inbox_media(path)).collect();
+    if paths.is_empty() {
+        return;
     }
-    if let Some(len) = resp.headers().get(reqwest::header::CONTENT_LENGTH) {
-        if let Ok(len) = len.to_str() {
-            if let Ok(bytes) = len.parse::<u64>() {
-                status.total_bytes = bytes;
-                let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-                guard.insert(status.model_size.clone(), status.clone());
+    let (Some(index_state), Some(queue)) = (app.try_state::<JobIndexState>(), app.try_state::<JobQueueState>()) else {
+        if let Some(opened) = app.try_state::<OpenedFilesState>() {
+            if let Ok(mut pending) = opened.pending.lock() {
+                pending.extend(paths);
             }
         }
+        return;
+    };
+    for path in paths {
+        let result = create_job_from_path_inner(&Core::from(app), &index_state, path.to_string_lossy().to_string(), JobOptions::default())
+            .and_then(|job| queue.enqueue(job.id));
+        if let Err(err) = result {
+            tracing::warn!("failed to open {}: {err}", path.display());
+        }
     }
-    let mut file = File::create(dest)
-        .map_err(|err| format!("Failed to create file: {err}"))?;
-    let mut downloaded = 0u64;
-    let mut buffer = [0u8; 1024 * 64];
-    loop {
-        let read = match resp.read(&mut buffer) {
-            Ok(0) => break,
-            Ok(n) => n,
-            Err(err) => return Err(format!("Download error: {err}")),
-        };
-        file.write_all(&buffer[..read])
-            .map_err(|err| format!("Write error: {err}"))?;
-        downloaded += read as u64;
-        status.downloaded_bytes = downloaded;
-        let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-        guard.insert(status.model_size.clone(), status.clone());
-    }
-    Ok(())
+    show_main_window(app);
 }
 
-fn extract_whisper_zip(zip_path: &PathBuf, dest_path: &PathBuf) -> Result<(), String> {
-    let file = File::open(zip_path)
-        .map_err(|err| format!("Failed to open zip: {err}"))?;
-    let mut archive = zip::ZipArchive::new(file)
-        .map_err(|err| format!("Invalid zip: {err}"))?;
-    for i in 0..archive.len() {
-        let mut entry = archive
-            .by_index(i)
-            .map_err(|err| format!("Zip entry error: {err}"))?;
-        let name = entry.name().to_string();
-        if name.ends_with("/whisper")
-            || name.ends_with("/main")
-            || name.ends_with("/whisper-cli")
-            || name == "whisper"
-            || name == "main"
-            || name == "whisper-cli"
-        {
-            let mut out = File::create(dest_path)
-                .map_err(|err| format!("Failed to create binary: {err}"))?;
-            std::io::copy(&mut entry, &mut out)
-                .map_err(|err| format!("Failed to extract binary: {err}"))?;
-            return Ok(());
+// Imports the files the app was launched with (and runs voicenote:// URLs among them): command
+// line arguments on Windows and Linux, and on macOS whatever Finder sent before setup finished.
+pub fn open_launch_files(app: &AppHandle) {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for arg in std::env::args_os().skip(1) {
+        let text = arg.to_string_lossy();
+        match tauri::Url::parse(&text) {
+            Ok(url) if url.scheme() == DEEP_LINK_SCHEME => handle_deep_link(app, &url),
+            _ => paths.push(PathBuf::from(arg)),
         }
     }
-    Err("Whisper binary not found in zip.".to_string())
-}
-
-fn extract_ffmpeg_zip(zip_path: &PathBuf, dest_dir: &PathBuf) -> Result<(), String> {
-    let file = File::open(zip_path)
-        .map_err(|err| format!("Failed to open zip: {err}"))?;
-    let mut archive = zip::ZipArchive::new(file)
-        .map_err(|err| format!("Invalid zip: {err}"))?;
-    let mut found_ffmpeg = false;
-    let mut found_ffprobe = false;
-    let mut found_dylib = false;
-    for i in 0..archive.len() {
-        let mut entry = archive
-            .by_index(i)
-            .map_err(|err| format!("Zip entry error: {err}"))?;
-        let name = entry.name().to_string();
-        if name.ends_with(".dylib") {
-            let file_name = PathBuf::from(&name)
-                .file_name()
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|| name.clone());
-            let out_path = dest_dir.join("lib").join(file_name);
-            fs::create_dir_all(out_path.parent().unwrap())
-                .map_err(|err| format!("Failed to create ffmpeg lib dir: {err}"))?;
-            let mut out = File::create(&out_path)
-                .map_err(|err| format!("Failed to create dylib: {err}"))?;
-            std::io::copy(&mut entry, &mut out)
-                .map_err(|err| format!("Failed to extract dylib: {err}"))?;
-            found_dylib = true;
-            continue;
-        }
-        if name.ends_with("/ffmpeg") || name == "ffmpeg" {
-            let out_path = dest_dir.join("bin/ffmpeg");
-            fs::create_dir_all(out_path.parent().unwrap())
-                .map_err(|err| format!("Failed to create ffmpeg dir: {err}"))?;
-            let mut out = File::create(&out_path)
-                .map_err(|err| format!("Failed to create ffmpeg binary: {err}"))?;
-            std::io::copy(&mut entry, &mut out)
-                .map_err(|err| format!("Failed to extract ffmpeg: {err}"))?;
-            found_ffmpeg = true;
+    if let Some(opened) = app.try_state::<OpenedFilesState>() {
+        if let Ok(mut pending) = opened.pending.lock() {
+            paths.append(&mut pending);
         }
-        if name.ends_with("/ffprobe") || name == "ffprobe" {
-            let out_path = dest_dir.join("bin/ffprobe");
-            fs::create_dir_all(out_path.parent().unwrap())
-                .map_err(|err| format!("Failed to create ffprobe dir: {err}"))?;
-            let mut out = File::create(&out_path)
-                .map_err(|err| format!("Failed to create ffprobe binary: {err}"))?;
-            std::io::copy(&mut entry, &mut out)
-                .map_err(|err| format!("Failed to extract ffprobe: {err}"))?;
-            found_ffprobe = true;
-        }
-    }
-    if !found_ffmpeg {
-        return Err("ffmpeg binary not found in zip.".to_string());
-    }
-    if !found_ffprobe {
-        return Err("ffprobe binary not found in zip.".to_string());
-    }
-    if !found_dylib {
-        return Err("FFmpeg dylibs not found in zip.".to_string());
-    }
-    Ok(())
-}
-
-fn apply_ffmpeg_env(cmd: &mut Command, ffmpeg_path: &PathBuf) {
-    if let Some(root) = ffmpeg_path.parent().and_then(|p| p.parent()) {
-        let lib_dir = root.join("lib");
-        if lib_dir.exists() {
-            cmd.env("DYLD_LIBRARY_PATH", &lib_dir);
-            cmd.env("DYLD_FALLBACK_LIBRARY_PATH", &lib_dir);
+        let links = opened.links.lock().map(|mut links| std::mem::take(&mut *links)).unwrap_or_default();
+        for url in &links {
+            handle_deep_link(app, url);
         }
     }
+    open_files(app, paths);
 }
 
-fn convert_to_wav(ffmpeg_path: &PathBuf, input: &str, output: &PathBuf) -> Result<(), String> {
-    let mut cmd = Command::new(ffmpeg_path);
-    apply_ffmpeg_env(&mut cmd, ffmpeg_path);
-    let status = cmd
-        .args([
-            "-y",
-            "-i",
-            input,
-            "-vn",
-            "-acodec",
-            "pcm_s16le",
-            "-ar",
-            "16000",
-            "-ac",
-            "1",
-            output.to_str().unwrap_or_default(),
-        ])
-        .status()
-        .map_err(|err| format!("failed to run ffmpeg: {err}"))?;
-    if !status.success() {
-        return Err("ffmpeg convert failed".to_string());
-    }
-    Ok(())
-}
-
-fn run_whisper_cpp(
-    app: &AppHandle,
-    job_id: &str,
-    bin: &PathBuf,
-    model: &PathBuf,
-    audio_path: &PathBuf,
-    output_base: &PathBuf,
-    language: Option<&str>,
-) -> Result<(), String> {
-    let mut args = vec![
-        "-m".to_string(),
-        model.to_str().unwrap_or_default().to_string(),
-        "-f".to_string(),
-        audio_path.to_str().unwrap_or_default().to_string(),
-        "-oj".to_string(),
-        "-osrt".to_string(),
-        "-otxt".to_string(),
-        "-of".to_string(),
-        output_base.to_str().unwrap_or_default().to_string(),
-    ];
-    if let Some(lang) = language {
-        let trimmed = lang.trim();
-        if !trimmed.is_empty() {
-            args.push("-l".to_string());
-            args.push(trimmed.to_string());
-        }
-    }
-    let mut child = Command::new(bin)
-        .args(args)
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .spawn()
-        .map_err(|err| format!("failed to run whisper: {err}"))?;
-
-    if let Some(stderr) = child.stderr.take() {
-        let app_handle = app.clone();
-        let job_id = job_id.to_string();
-        thread::spawn(move || {
-            let reader = BufReader::new(stderr);
-            for line in reader.lines().flatten() {
-                let _ = append_job_log(&app_handle, &job_id, &line);
-            }
-        });
-    }
-
-    if let Some(stdout) = child.stdout.take() {
-        let reader = BufReader::new(stdout);
-        for line in reader.lines().flatten() {
-            if let Some(progress) = parse_progress_from_line(&line) {
-                let mapped = 0.3 + (progress / 100.0) * 0.6;
-                let _ = update_job_and_emit(app, job_id, |job| {
-                    job.stage = "transcribe".to_string();
-                    job.progress = mapped;
-                });
+// A second launch of the app hands its arguments here instead of opening the job store again:
+// a file opened with the app, or a voicenote:// link on Windows and Linux, where the OS starts a
+// new process for each. Relative paths are taken from `cwd`, where that process ran.
+pub fn open_second_instance(app: &AppHandle, args: Vec<String>, cwd: &str) {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    let mut links = false;
+    for arg in args.into_iter().skip(1) {
+        match tauri::Url::parse(&arg) {
+            Ok(url) if url.scheme() == DEEP_LINK_SCHEME => {
+                links = true;
+                handle_deep_link(app, &url);
             }
-            let _ = append_job_log(app, job_id, &line);
+            _ => paths.push(Path::new(cwd).join(arg)),
         }
     }
-
-    let status = child
-        .wait()
-        .map_err(|err| format!("failed to wait for whisper: {err}"))?;
-    if !status.success() {
-        return Err("whisper failed".to_string());
+    if !links && paths.is_empty() {
+        show_main_window(app);
     }
-    Ok(())
+    open_files(app, paths);
 }
 
-fn ensure_clip(
-    ffmpeg_path: &PathBuf,
-    audio_path: &str,
-    job_dir: &PathBuf,
-    start: f64,
-    end: f64,
-) -> Result<String, String> {
-    // We try to create a real clipped file using ffmpeg if available.
-    // If ffmpeg is not present, we fall back to the full audio file.
-    let clips_dir = job_dir.join("clips");
-    fs::create_dir_all(&clips_dir)
-        .map_err(|err| format!("failed to create clips dir: {err}"))?;
-    let start_ms = (start * 1000.0).max(0.0).round() as u64;
-    let end_ms = (end * 1000.0).max(0.0).round() as u64;
-    let clip_name = format!("clip_{start_ms}_{end_ms}.wav");
-    let clip_path = clips_dir.join(clip_name);
-    if clip_path.exists() {
-        return Ok(clip_path.to_string_lossy().to_string());
-    }
+const DEEP_LINK_SCHEME: &str = "voicenote";
 
-    let mut cmd = Command::new(ffmpeg_path);
-    apply_ffmpeg_env(&mut cmd, ffmpeg_path);
-    let status = cmd
-        .args([
-            "-y",
-            "-i",
-            audio_path,
-            "-ss",
-            &start.to_string(),
-            "-to",
-            &end.to_string(),
-            "-vn",
-            "-acodec",
-            "pcm_s16le",
-            "-ar",
-            "16000",
-            "-ac",
-            "1",
-            clip_path.to_str().unwrap_or_default(),
-        ])
-        .status()
-        .map_err(|err| format!("failed to run ffmpeg: {err}"))?;
-
-    if !status.success() {
-        return Ok(audio_path.to_string());
-    }
+// What a voicenote:// URL asks for; see parse_deep_link.
+#[derive(Debug, Clone, PartialEq)]
+enum DeepLinkAction {
+    Import(Vec<PathBuf>),
+    StartRecording,
+    StopRecording,
+    OpenJob(String),
+}
 
-    Ok(clip_path.to_string_lossy().to_string())
+#[derive(Debug, Clone, Serialize)]
+struct DeepLinkJobEvent {
+    id: String,
 }
 
-fn resolve_ffmpeg_path(app: &AppHandle) -> Result<PathBuf, String> {
-    if let Ok(explicit) = std::env::var("VOICENOTE_FFMPEG_PATH") {
-        let path = PathBuf::from(explicit);
-        if path.exists() {
-            return ensure_lgpl_ffmpeg(path);
+// voicenote://import?path=<file>[&path=<file>…], voicenote://record/start, voicenote://record/stop
+// and voicenote://job/<id>.
+fn parse_deep_link(url: &tauri::Url) -> Result<DeepLinkAction, String> {
+    if url.scheme() != DEEP_LINK_SCHEME {
+        return Err(format!("Not a {DEEP_LINK_SCHEME}:// URL: {url}"));
+    }
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|segment| !segment.is_empty()).collect())
+        .unwrap_or_default();
+    match (url.host_str().unwrap_or(""), segments.as_slice()) {
+        ("import", []) => {
+            let paths: Vec<PathBuf> = url
+                .query_pairs()
+                .filter(|(key, value)| key == "path" && !value.trim().is_empty())
+                .map(|(_, value)| PathBuf::from(value.trim()))
+                .collect();
+            if paths.is_empty() {
+                return Err("voicenote://import needs a path parameter.".to_string());
+            }
+            Ok(DeepLinkAction::Import(paths))
         }
+        ("record", ["start"]) => Ok(DeepLinkAction::StartRecording),
+        ("record", ["stop"]) => Ok(DeepLinkAction::StopRecording),
+        ("job", [id]) => Ok(DeepLinkAction::OpenJob(id.to_string())),
+        _ => Err(format!("Unknown {DEEP_LINK_SCHEME}:// action: {url}")),
     }
+}
 
-    let mut candidates: Vec<PathBuf> = Vec::new();
-    if let Ok(cwd) = std::env::current_dir() {
-        candidates.push(cwd.join("third_party/ffmpeg/bin/ffmpeg"));
-        let mut cursor = Some(cwd.as_path());
-        for _ in 0..4 {
-            if let Some(dir) = cursor {
-                candidates.push(dir.join("third_party/ffmpeg/bin/ffmpeg"));
-                cursor = dir.parent();
+// Runs a voicenote:// URL from Shortcuts, Raycast, Alfred and the like. Failures only reach the
+// log: the caller is another app that has no way to show them.
+pub fn handle_deep_link(app: &AppHandle, url: &tauri::Url) {
+    if app.try_state::<JobQueueState>().is_none() {
+        if let Some(opened) = app.try_state::<OpenedFilesState>() {
+            if let Ok(mut links) = opened.links.lock() {
+                links.push(url.clone());
             }
         }
-    }
-    if let Ok(resource_dir) = app.path().resource_dir() {
-        candidates.push(resource_dir.join("ffmpeg/bin/ffmpeg"));
-        candidates.push(resource_dir.join("resources/ffmpeg/bin/ffmpeg"));
-        candidates.push(resource_dir.join("third_party/ffmpeg/bin/ffmpeg"));
-    }
-    if let Ok(app_data_dir) = app.path().app_data_dir() {
-        candidates.push(app_data_dir.join("voicenote/ffmpeg/bin/ffmpeg"));
-    }
-    if let Ok(exe) = std::env::current_exe() {
-        if let Some(dir) = exe.parent() {
-            candidates.push(dir.join("../Resources/ffmpeg/bin/ffmpeg"));
-            candidates.push(dir.join("../Resources/resources/ffmpeg/bin/ffmpeg"));
-            candidates.push(dir.join("../Resources/third_party/ffmpeg/bin/ffmpeg"));
+        return;
+    }
+    let result = parse_deep_link(url).and_then(|action| match action {
+        DeepLinkAction::Import(paths) => {
+            let missing: Vec<String> = paths
+                .iter()
+                .filter(|path| !path.is_file())
+                .map(|path| path.display().to_string())
+                .collect();
+            open_files(app, paths);
+            if missing.is_empty() {
+                Ok(())
+            } else {
+                Err(format!("Not a file: {}", missing.join(", ")))
+            }
         }
-    }
-
-    for candidate in candidates {
-        if candidate.exists() {
-            return ensure_lgpl_ffmpeg(candidate);
+        DeepLinkAction::StartRecording => {
+            confirm_deep_link_recording(app);
+            Ok(())
         }
+        DeepLinkAction::StopRecording => {
+            stop_recording(Core::from(app), app.state(), app.state(), app.state()).map(|_| ())
+        }
+        DeepLinkAction::OpenJob(id) => open_job_in_window(app, &id),
+    });
+    if let Err(err) = result {
+        tracing::warn!("deep link failed: {err}");
     }
-
-    Err(
-        "FFmpeg not found. Provide an LGPL build at ./third_party/ffmpeg/bin/ffmpeg \
-(see scripts/ffmpeg/build_macos_lgpl.sh) or set VOICENOTE_FFMPEG_PATH."
-            .to_string(),
-    )
 }
 
-fn ensure_lgpl_ffmpeg(path: PathBuf) -> Result<PathBuf, String> {
-    let mut cmd = Command::new(&path);
-    apply_ffmpeg_env(&mut cmd, &path);
-    let output = cmd
-        .arg("-version")
-        .output()
-        .map_err(|err| format!("Failed to run ffmpeg: {err}"))?;
-    let text = String::from_utf8_lossy(&output.stdout);
-    if text.contains("--enable-gpl") || text.contains("--enable-nonfree") {
-        return Err("FFmpeg build contains GPL/nonfree flags; please use LGPL build.".to_string());
-    }
-    Ok(path)
+// Any web page or script can open a voicenote:// URL, so the microphone only starts once the
+// window is up and the user has agreed to it.
+fn confirm_deep_link_recording(app: &AppHandle) {
+    show_main_window(app);
+    let handle = app.clone();
+    app.dialog()
+        .message("A link asked VoiceNote to start recording from the microphone.")
+        .title("Start recording?")
+        .buttons(MessageDialogButtons::OkCancelCustom("Start recording".to_string(), "Cancel".to_string()))
+        .show(move |confirmed| {
+            if !confirmed {
+                return;
+            }
+            // start_recording waits for the first audio; the dialog callback may be on the main thread.
+            thread::spawn(move || {
+                if let Err(err) = start_recording(Core::from(&handle), handle.state(), handle.state()) {
+                    tracing::warn!("deep link failed: {err}");
+                }
+            });
+        });
 }
 
-fn process_job(app: &AppHandle, job_id: &str) -> Result<(), String> {
-    let index_state = app.state::<JobIndexState>();
-    let config_state = app.state::<ConfigState>();
-    let (model_size, language, enable_summarization, auto_summarize, ollama_base, ollama_model, summary_prompt) = {
-        let guard = config_state
-            .config
-            .lock()
-            .map_err(|_| "config mutex poisoned".to_string())?;
-        (
-            guard.model_size.clone(),
-            guard.language.clone(),
-            guard.enable_summarization,
-            guard.auto_summarize_after_transcription,
-            guard.ollama_base_url.clone(),
-            guard.ollama_model.clone(),
-            guard.summary_prompt.clone(),
-        )
-    };
-    let mut job_snapshot: Option<Job> = None;
-    let mut job_dir: Option<PathBuf> = None;
-    let mut audio_path: Option<String> = None;
-    {
-        let mut guard = index_state
-            .index
-            .lock()
-            .map_err(|_| "job index mutex poisoned".to_string())?;
-        if let Some(job) = guard.jobs.iter_mut().find(|job| job.id == job_id) {
-            job.status = "running".to_string();
-            job.stage = "convert".to_string();
-            job.progress = 0.1;
-            push_log(job, "Worker started.");
-            job_snapshot = Some(job.clone());
-            job_dir = job_dir_from_audio_path(&job.audio_path);
-            audio_path = Some(job.audio_path.clone());
-        }
-        if job_snapshot.is_some() {
-            save_index_to_disk(&index_state.path, &guard)?;
-        }
-    }
-    if let Some(job) = job_snapshot.as_ref() {
-        emit_job_updated(app, job);
-        emit_job_log(app, &job.id, "Worker started.");
+// Runs the command on a headless Core, without Tauri or a display, and returns the exit code:
+// 1 if any file failed. The window may be open in another process, so the inbox, interrupted
+// imports and jobs, and the trash are left to it.
+pub fn run_cli<R: tauri::Runtime>(context: &tauri::Context<R>, command: CliCommand) -> i32 {
+    let app = Core::headless(context);
+    if let Err(err) = init_logging(&app) {
+        eprintln!("{err}");
     }
-
-    let mark_error = |message: &str| -> Result<(), String> {
-        update_job_and_emit(app, job_id, |job| {
-            job.status = "error".to_string();
-            job.stage = "error".to_string();
-            push_log(job, message);
-        })?;
-        emit_job_log(app, job_id, message);
-        Ok(())
-    };
-
-    let job_dir = job_dir.ok_or_else(|| "missing job directory".to_string())?;
-    let audio_path = audio_path.ok_or_else(|| "missing audio path".to_string())?;
-
-    let ffmpeg_path = match resolve_ffmpeg_path(app) {
-        Ok(path) => path,
+    let result = start_cli_core(&app, &command).and_then(|_| match &command {
+        CliCommand::Transcribe(cli) => run_cli_transcribe(&app, cli),
+        CliCommand::Mcp => run_mcp_server(&app).map(|_| 0),
+    });
+    match result {
+        Ok(0) => 0,
+        Ok(_) => 1,
         Err(err) => {
-            mark_error(&err)?;
-            return Ok(());
-        }
-    };
-    let wav_path = job_dir.join("audio.wav");
-    if !wav_path.exists() {
-        emit_job_log(app, job_id, "Converting audio to 16k mono WAV...");
-        if let Err(err) = convert_to_wav(&ffmpeg_path, &audio_path, &wav_path) {
-            mark_error(&err)?;
-            return Ok(());
-        }
-    }
-
-    let _ = update_job_and_emit(app, job_id, |job| {
-        job.stage = "transcribe".to_string();
-        job.progress = 0.3;
-    });
-
-    emit_job_log(app, job_id, "Running whisper.cpp...");
-    let (whisper_bin, whisper_model) = match resolve_whisper_paths(app, &model_size) {
-        Ok(paths) => paths,
-        Err(err) => {
-            mark_error(&err)?;
-            return Ok(());
-        }
-    };
-    let output_base = job_dir.join("whisper");
-    if let Err(err) = run_whisper_cpp(
-        app,
-        job_id,
-        &whisper_bin,
-        &whisper_model,
-        &wav_path,
-        &output_base,
-        language.as_deref(),
-    ) {
-        mark_error(&err)?;
-        return Ok(());
-    }
-
-    let transcript_txt_path = output_base
-        .with_extension("txt")
-        .to_string_lossy()
-        .to_string();
-    let transcript_json_path = output_base
-        .with_extension("json")
-        .to_string_lossy()
-        .to_string();
-    let transcript_srt_path = output_base
-        .with_extension("srt")
-        .to_string_lossy()
-        .to_string();
-
-    if !std::path::Path::new(&transcript_txt_path).exists()
-        || !std::path::Path::new(&transcript_json_path).exists()
-    {
-        emit_job_log(app, job_id, "Whisper output missing; falling back to stub.");
-        let (txt, json, srt) = write_stub_artifacts(&job_dir)?;
-        let mut completed_snapshot: Option<Job> = None;
-        {
-            let mut guard = index_state
-                .index
-                .lock()
-                .map_err(|_| "job index mutex poisoned".to_string())?;
-            if let Some(job) = guard.jobs.iter_mut().find(|job| job.id == job_id) {
-                job.progress = 1.0;
-                job.status = "done".to_string();
-                job.stage = "done".to_string();
-                job.transcript_txt_path = txt;
-                job.transcript_json_path = json;
-                job.transcript_srt_path = srt;
-                job.md_preview = Some("Stub transcript from Rust core.".to_string());
-                job.summary_status = Some("skipped".to_string());
-                push_log(job, "Worker finished (stub).");
-                completed_snapshot = Some(job.clone());
-            }
-            if completed_snapshot.is_some() {
-                save_index_to_disk(&index_state.path, &guard)?;
-            }
-        }
-        if let Some(job) = completed_snapshot {
-            emit_job_updated(app, &job);
-            emit_job_log(app, &job.id, "Worker finished (stub).");
-        }
-        return Ok(());
-    }
-
-    let mut completed_snapshot: Option<Job> = None;
-    {
-        let mut guard = index_state
-            .index
-            .lock()
-            .map_err(|_| "job index mutex poisoned".to_string())?;
-        if let Some(job) = guard.jobs.iter_mut().find(|job| job.id == job_id) {
-            job.progress = 1.0;
-            job.status = "done".to_string();
-            job.stage = "done".to_string();
-            job.transcript_txt_path = transcript_txt_path;
-            job.transcript_json_path = transcript_json_path;
-            job.transcript_srt_path = transcript_srt_path;
-            job.md_preview = Some("Transcript ready.".to_string());
-            job.summary_status = Some(
-                if enable_summarization {
-                    "not_started"
-                } else {
-                    "skipped"
-                }
-                .to_string(),
-            );
-            push_log(job, "Whisper finished.");
-            completed_snapshot = Some(job.clone());
-        }
-        if completed_snapshot.is_some() {
-            save_index_to_disk(&index_state.path, &guard)?;
+            eprintln!("{err}");
+            1
         }
     }
+}
 
-    if let Some(job) = completed_snapshot {
-        emit_job_updated(app, &job);
-        emit_job_log(app, &job.id, "Whisper finished.");
-    }
+// Imports Voice Memos recordings now instead of waiting for the next scan.
+#[tauri::command]
+pub fn import_voice_memos(app: Core, include_existing: Option<bool>) -> Result<Vec<Job>, String> {
+    import_voice_memos_inner(&app, include_existing.unwrap_or(false))
+}
 
-    if enable_summarization && auto_summarize {
-        emit_job_log(app, job_id, "Summarization queued.");
-        let app_handle = app.clone();
-        let job_id = job_id.to_string();
-        let base_url = ollama_base.clone();
-        let model = ollama_model.clone();
-        let prompt = summary_prompt.clone();
-        thread::spawn(move || {
-            let _ = summarize_job_internal(
-                &app_handle,
-                &job_id,
-                &base_url,
-                &model,
-                &prompt,
-                false,
-            );
-        });
-    } else {
-        emit_job_log(app, job_id, "Summarization skipped.");
-    }
+#[tauri::command]
+pub fn update_config(app: Core, cfg: AppConfig) -> Result<AppConfig, String> {
+    check_config(&cfg)?;
+    store_config(&app, cfg)
+}
 
-    Ok(())
+#[tauri::command]
+pub fn export_settings(state: State<ConfigState>, path: String) -> Result<String, String> {
+    let config = state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    let json = serde_json::to_string_pretty(&exportable_config(&config))
+        .map_err(|err| format!("failed to serialize settings: {err}"))?;
+    fs::write(&path, json).map_err(|err| format!("Failed to write {path}: {err}"))?;
+    Ok(path)
 }
 
-pub fn spawn_worker(app: &AppHandle) -> JobQueueState {
-    let (sender, receiver) = mpsc::channel::<String>();
-    let handle = app.clone();
-    thread::spawn(move || {
-        for job_id in receiver {
-            if let Err(err) = process_job(&handle, &job_id) {
-                let _ = handle.emit("job:log", JobLogEvent {
-                    id: job_id.clone(),
-                    line: format!("Worker error: {err}"),
-                });
-            }
-        }
-    });
-    JobQueueState::new(sender)
+#[tauri::command]
+pub fn import_settings(
+    app: Core,
+    state: State<ConfigState>,
+    path: String,
+) -> Result<SettingsImport, String> {
+    import_settings_inner(&app, &state, path)
 }
 
 #[tauri::command]
-pub fn get_health() -> bool {
-    // Tauri is running; no external backend is required in this mode.
-    true
+pub fn reset_to_defaults(
+    app: Core,
+    state: State<ConfigState>,
+    scope: String,
+) -> Result<AppConfig, String> {
+    reset_to_defaults_inner(&app, &state, scope)
 }
 
+// All profiles, the active one first with its current settings.
 #[tauri::command]
-pub fn get_config(state: State<ConfigState>) -> Result<AppConfig, String> {
+pub fn list_profiles(state: State<ConfigState>) -> Result<Vec<ConfigProfile>, String> {
     let guard = state
         .config
         .lock()
         .map_err(|_| "config mutex poisoned".to_string())?;
-    Ok(guard.clone())
+    Ok(std::iter::once(ConfigProfile::capture(&guard))
+        .chain(guard.profiles.iter().cloned())
+        .collect())
 }
 
 #[tauri::command]
-pub fn update_config(state: State<ConfigState>, cfg: AppConfig) -> Result<AppConfig, String> {
-    let mut guard = state
+pub fn switch_profile(app: Core, state: State<ConfigState>, name: String) -> Result<AppConfig, String> {
+    let mut next = state
         .config
         .lock()
-        .map_err(|_| "config mutex poisoned".to_string())?;
-    *guard = cfg;
-    save_config_to_disk(&state.path, &guard)?;
-    Ok(guard.clone())
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    switch_config_profile(&mut next, &name)?;
+    check_config(&next)?;
+    store_config(&app, next)
 }
 
 #[tauri::command]
-pub fn initialize_config(state: State<ConfigState>, mut cfg: AppConfig) -> Result<AppConfig, String> {
-    cfg.initialized = true;
-    let mut guard = state
+pub fn delete_profile(app: Core, state: State<ConfigState>, name: String) -> Result<AppConfig, String> {
+    let mut next = state
         .config
         .lock()
-        .map_err(|_| "config mutex poisoned".to_string())?;
-    *guard = cfg;
-    save_config_to_disk(&state.path, &guard)?;
-    Ok(guard.clone())
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    if next.active_profile == name.trim() {
+        return Err("Switch to another profile before deleting this one.".to_string());
+    }
+    next.profiles.retain(|profile| profile.name != name.trim());
+    store_config(&app, next)
+}
+
+#[tauri::command]
+pub fn initialize_config(app: Core, mut cfg: AppConfig) -> Result<AppConfig, String> {
+    check_config(&cfg)?;
+    cfg.initialized = true;
+    store_config(&app, cfg)
 }
 
 #[tauri::command]
@@ -1400,11 +933,77 @@ pub fn get_config_initialized(state: State<ConfigState>) -> Result<bool, String>
 
 #[tauri::command]
 pub fn list_jobs(state: State<JobIndexState>) -> Result<Vec<Job>, String> {
+    list_jobs_inner(&state)
+}
+
+// Filtered, sorted page of the job list without logs; see get_job_logs for those.
+#[tauri::command]
+pub fn query_jobs(state: State<JobIndexState>, filter: Option<JobQuery>) -> Result<JobPage, String> {
+    let guard = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?;
+    filter.unwrap_or_default().run(&guard.jobs)
+}
+
+// A window of a job's log, oldest line first; a zero `limit` returns the rest of it.
+#[tauri::command]
+pub fn get_job_logs(
+    state: State<JobIndexState>,
+    id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<JobLogPage, String> {
+    get_job_logs_inner(&state, id, offset, limit)
+}
+
+// Replaces the job's tags; they are normalized like generated ones (lowercase, no '#').
+#[tauri::command]
+pub fn set_job_tags(app: Core, id: String, tags: Vec<String>) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.iter().filter_map(|tag| normalize_tag(tag)) {
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    find_job(app.state::<JobIndexState>(), &id)?;
+    update_job_and_emit(&app, &id, |job| job.tags = normalized.clone())?;
+    Ok(normalized)
+}
+
+// Sets the title shown in the list and used for exports; an empty title goes back to the
+// automatic one. Returns the title now in effect.
+#[tauri::command]
+pub fn rename_job(app: Core, id: String, title: String) -> Result<String, String> {
+    let title = title.trim().to_string();
+    find_job(app.state::<JobIndexState>(), &id)?;
+    update_job_and_emit(&app, &id, |job| {
+        job.display_title = (!title.is_empty()).then(|| title.clone());
+    })?;
+    Ok(job_title(&find_job(app.state::<JobIndexState>(), &id)?))
+}
+
+#[tauri::command]
+pub fn set_job_notes(app: Core, id: String, text: String) -> Result<(), String> {
+    find_job(app.state::<JobIndexState>(), &id)?;
+    update_job_and_emit(&app, &id, |job| job.notes = text.clone())
+}
+
+#[tauri::command]
+pub fn toggle_favorite(app: Core, id: String) -> Result<bool, String> {
+    let favorite = !find_job(app.state::<JobIndexState>(), &id)?.favorite;
+    update_job_and_emit(&app, &id, |job| job.favorite = favorite)?;
+    Ok(favorite)
+}
+
+// Every tag in use with the number of jobs carrying it, most used first.
+#[tauri::command]
+pub fn list_tags(state: State<JobIndexState>) -> Result<Vec<TagCount>, String> {
     let guard = state
         .index
         .lock()
         .map_err(|_| "job index mutex poisoned".to_string())?;
-    Ok(guard.jobs.clone())
+    Ok(tag_counts(&guard.jobs))
 }
 
 #[tauri::command]
@@ -1422,656 +1021,477 @@ pub fn get_job(state: State<JobIndexState>, id: String) -> Result<Job, String> {
 }
 
 #[tauri::command]
-fn create_job_from_path_inner(
-    app: &AppHandle,
-    state: &JobIndexState,
-    path: String,
+pub fn find_duplicates(
+    app: Core,
+    state: State<JobIndexState>,
+    id: String,
+) -> Result<Vec<DuplicateMatch>, String> {
+    find_duplicates_inner(&app, &state, id)
+}
+
+#[tauri::command]
+pub fn dismiss_duplicate(
+    app: Core,
+    queue: State<JobQueueState>,
+    id: String,
+) -> Result<bool, String> {
+    dismiss_duplicate_inner(&app, &queue, id)
+}
+
+#[tauri::command]
+pub fn start_recording(
+    app: Core,
+    state: State<JobIndexState>,
+    recording: State<RecordingState>,
+) -> Result<RecordingStatus, String> {
+    start_recording_inner(&app, &state, &recording)
+}
+
+#[tauri::command]
+pub fn stop_recording(
+    app: Core,
+    state: State<JobIndexState>,
+    queue: State<JobQueueState>,
+    recording: State<RecordingState>,
 ) -> Result<Job, String> {
-    let filename = std::path::Path::new(&path)
-        .file_name()
-        .and_then(|name| name.to_str())
-        .unwrap_or("unknown-audio");
-    let job_id = generate_job_id();
-    let dest_path = build_job_audio_path(&state.jobs_dir, &job_id, &path)?;
-    fs::copy(&path, &dest_path)
-        .map_err(|err| format!("failed to copy audio into job folder: {err}"))?;
-    let mut job = Job {
-        id: job_id,
-        filename: filename.to_string(),
-        status: "queued".to_string(),
-        progress: 0.0,
-        stage: "import".to_string(),
-        logs: Vec::new(),
-        created_at: unix_timestamp_string(),
-        audio_path: dest_path.to_string_lossy().to_string(),
-        transcript_txt_path: String::new(),
-        transcript_json_path: String::new(),
-        transcript_srt_path: String::new(),
-        md_preview: None,
-        summary_status: Some("not_started".to_string()),
-        summary_model: None,
-        summary_error: None,
-        summary_md: None,
-        exported_to_obsidian: false,
-    };
-    push_log(&mut job, "Queued for processing.");
-    let mut guard = state
-        .index
-        .lock()
-        .map_err(|_| "job index mutex poisoned".to_string())?;
-    guard.jobs.insert(0, job.clone());
-    save_index_to_disk(&state.path, &guard)?;
-    emit_job_updated(app, &job);
-    emit_job_log(app, &job.id, "Queued for processing.");
-    Ok(job)
+    stop_recording_inner(&app, &state, &queue, &recording)
+}
+
+#[tauri::command]
+pub fn get_recording_status(recording: State<RecordingState>) -> Result<RecordingStatus, String> {
+    get_recording_status_inner(&recording)
 }
 
 #[tauri::command]
 pub fn create_job_from_path(
-    app: AppHandle,
+    app: Core,
     state: State<JobIndexState>,
     queue: State<JobQueueState>,
     path: String,
+    options: Option<JobOptions>,
 ) -> Result<Job, String> {
-    let job = create_job_from_path_inner(&app, state.inner(), path)?;
+    let job = create_job_from_path_inner(&app, state.inner(), path, options.unwrap_or_default())?;
     queue.enqueue(job.id.clone())?;
     Ok(job)
 }
 
 #[tauri::command]
 pub fn add_files(
-    app: AppHandle,
+    app: Core,
     state: State<JobIndexState>,
     queue: State<JobQueueState>,
     paths: Vec<String>,
+    options: Option<JobOptions>,
 ) -> Result<Vec<Job>, String> {
-    let mut created = Vec::new();
-    for path in paths {
-        let job = create_job_from_path_inner(&app, state.inner(), path)?;
-        queue.enqueue(job.id.clone())?;
-        created.push(job);
-    }
-    Ok(created)
+    add_files_inner(&app, &state, &queue, paths, options)
 }
 
 #[tauri::command]
-pub fn cancel_job(
-    app: AppHandle,
+pub fn trim_job_audio(
+    app: Core,
     state: State<JobIndexState>,
+    queue: State<JobQueueState>,
     id: String,
-) -> Result<bool, String> {
-    let mut guard = state
-        .index
-        .lock()
-        .map_err(|_| "job index mutex poisoned".to_string())?;
-    let mut updated_job: Option<Job> = None;
-    if let Some(job) = guard.jobs.iter_mut().find(|job| job.id == id) {
-        job.status = "cancelled".to_string();
-        job.stage = "cancelled".to_string();
-        push_log(job, "Job cancelled.");
-        updated_job = Some(job.clone());
-    }
-    if updated_job.is_none() {
-        return Ok(false);
-    }
-    save_index_to_disk(&state.path, &guard)?;
-    if let Some(job) = updated_job {
-        emit_job_updated(&app, &job);
-        emit_job_log(&app, &job.id, "Job cancelled.");
-    }
-    Ok(true)
+    start: f64,
+    end: f64,
+) -> Result<Job, String> {
+    trim_job_audio_inner(&app, &state, &queue, id, start, end)
 }
 
 #[tauri::command]
-pub fn delete_job(state: State<JobIndexState>, id: String) -> Result<bool, String> {
-    let mut guard = state
-        .index
-        .lock()
-        .map_err(|_| "job index mutex poisoned".to_string())?;
-    let before = guard.jobs.len();
-    guard.jobs.retain(|job| job.id != id);
-    if guard.jobs.len() != before {
-        save_index_to_disk(&state.path, &guard)?;
-        return Ok(true);
-    }
-    Ok(false)
+pub fn split_job(
+    app: Core,
+    state: State<JobIndexState>,
+    queue: State<JobQueueState>,
+    id: String,
+    boundaries: Vec<f64>,
+) -> Result<Vec<Job>, String> {
+    split_job_inner(&app, &state, &queue, id, boundaries)
 }
 
 #[tauri::command]
-pub fn export_to_obsidian(
-    app: AppHandle,
+pub fn cancel_job(
+    app: Core,
     state: State<JobIndexState>,
-    config_state: State<ConfigState>,
     id: String,
 ) -> Result<bool, String> {
-    let (vault_path, output_subfolder, enable_summarization, summary_prompt) = {
-        let guard = config_state
-            .config
-            .lock()
-            .map_err(|_| "config mutex poisoned".to_string())?;
-        (
-            guard.vault_path.clone(),
-            guard.output_subfolder.clone(),
-            guard.enable_summarization,
-            guard.summary_prompt.clone(),
-        )
-    };
-    if vault_path.trim().is_empty() {
-        return Err("Obsidian vault path is not configured.".to_string());
-    }
-
-    let mut job_snapshot: Option<Job> = None;
-    {
-        let mut guard = state
-            .index
-            .lock()
-            .map_err(|_| "job index mutex poisoned".to_string())?;
-        if let Some(job) = guard.jobs.iter_mut().find(|job| job.id == id) {
-            job_snapshot = Some(job.clone());
-        }
-    }
-    let job = job_snapshot.ok_or_else(|| "job not found".to_string())?;
-    let job_dir = job_dir_from_audio_path(&job.audio_path)
-        .ok_or_else(|| "job directory not found".to_string())?;
-
-    let transcript_path = if !job.transcript_txt_path.is_empty() {
-        job.transcript_txt_path.clone()
-    } else {
-        job_dir
-            .join("transcript.txt")
-            .to_string_lossy()
-            .to_string()
-    };
-    let transcript = if std::path::Path::new(&transcript_path).exists() {
-        read_transcript_text(&transcript_path)?
-    } else {
-        String::new()
-    };
+    cancel_job_inner(&app, &state, id)
+}
 
-    let summary_from_job = job.summary_md.clone().unwrap_or_default();
-    let summary_path = job_dir.join("summary.md");
-    let summary_from_disk = if summary_path.exists() {
-        fs::read_to_string(&summary_path)
-            .map_err(|err| format!("failed to read summary.md: {err}"))?
-    } else {
-        String::new()
-    };
+// Moves the job folder to trash/ and hides the job until restore_job. Deleting a job that is
+// already in the trash removes it for good.
+#[tauri::command]
+pub fn delete_job(app: Core, id: String) -> Result<bool, String> {
+    delete_job_inner(&app, id)
+}
 
-    let summary_status = job
-        .summary_status
-        .clone()
-        .unwrap_or_else(|| "not_started".to_string());
-    let manual_prompt = if summary_from_job.trim().is_empty() {
-        if summary_from_disk.trim().is_empty()
-            && (summary_status == "skipped" || !enable_summarization)
-        {
-            build_summary_prompt(&summary_prompt, &transcript)
-        } else {
-            summary_from_disk.clone()
-        }
-    } else {
-        summary_from_job.clone()
-    };
+#[tauri::command]
+pub fn restore_job(app: Core, id: String) -> Result<Job, String> {
+    restore_job_inner(&app, id)
+}
 
-    let mut note = String::new();
-    let title = sanitize_filename(
-        std::path::Path::new(&job.filename)
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or(&job.id),
-    );
-    if !title.is_empty() {
-        note.push_str("# ");
-        note.push_str(&title);
-        note.push_str("\n\n");
-    }
+// Permanently deletes trashed jobs, all of them or those trashed more than
+// `older_than_days` ago; returns how many went.
+#[tauri::command]
+pub fn empty_trash(state: State<JobIndexState>, older_than_days: Option<u64>) -> Result<usize, String> {
+    purge_trash(&state, older_than_days)
+}
 
-    if summary_status == "skipped" || !enable_summarization {
-        if !manual_prompt.trim().is_empty() {
-            note.push_str("## Summary prompt\n\n");
-            note.push_str(&manual_prompt);
-            note.push_str("\n\n");
-        }
-    } else if !manual_prompt.trim().is_empty() {
-        note.push_str(&manual_prompt);
-        if !manual_prompt.ends_with('\n') {
-            note.push('\n');
-        }
-        note.push('\n');
-    }
+// Compares jobs/ with the job list. "report" (default) only lists orphaned folders and jobs
+// with missing files; "adopt" re-indexes orphaned folders that still hold audio; "delete"
+// removes orphaned folders and drops jobs whose folder is gone.
+#[tauri::command]
+pub fn reconcile_storage(app: Core, action: Option<String>) -> Result<StorageReconciliation, String> {
+    reconcile_storage_inner(&app, action)
+}
 
-    if !(summary_status == "skipped" || !enable_summarization) && !transcript.trim().is_empty() {
-        note.push_str("## Transcript\n\n");
-        note.push_str(&transcript);
-        if !note.ends_with('\n') {
-            note.push('\n');
-        }
-    }
+#[tauri::command]
+pub fn export_to_obsidian(
+    app: Core,
+    config_state: State<ConfigState>,
+    id: String,
+) -> Result<bool, String> {
+    export_to_obsidian_inner(&app, &config_state, id)
+}
 
-    let target_dir = if output_subfolder.trim().is_empty() {
-        PathBuf::from(vault_path)
-    } else {
-        PathBuf::from(vault_path).join(output_subfolder)
-    };
-    fs::create_dir_all(&target_dir)
-        .map_err(|err| format!("failed to create export dir: {err}"))?;
-    let target_path = target_dir.join(format!("{title}.md"));
-    fs::write(&target_path, note)
-        .map_err(|err| format!("failed to write note: {err}"))?;
+// Shows where export_to_obsidian would write the job with the current config, without
+// touching the vault. Errors when the path template is invalid.
+#[tauri::command]
+pub fn preview_export_path(
+    state: State<JobIndexState>,
+    config_state: State<ConfigState>,
+    id: String,
+) -> Result<ExportPathPreview, String> {
+    preview_export_path_inner(&state, &config_state, id)
+}
 
-    let mut guard = state
-        .index
+// Refreshes a previously exported note in place. A note the user edited keeps everything
+// outside the managed markers; only its summary and transcript are refreshed. Jobs that were never exported (or
+// whose note is gone) get a regular export. Returns "exported", "updated" or "unchanged".
+#[tauri::command]
+pub fn update_obsidian_export(
+    app: Core,
+    config_state: State<ConfigState>,
+    id: String,
+) -> Result<String, String> {
+    let config = config_state
+        .config
         .lock()
-        .map_err(|_| "job index mutex poisoned".to_string())?;
-    if let Some(job) = guard.jobs.iter_mut().find(|job| job.id == id) {
-        job.exported_to_obsidian = true;
-        push_log(job, "Exported to Obsidian.");
-        save_index_to_disk(&state.path, &guard)?;
-        emit_job_updated(&app, job);
-        emit_job_log(&app, &job.id, "Exported to Obsidian.");
-    }
-
-    Ok(true)
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    sync_obsidian_note(&app, &config, &id)
 }
 
 #[tauri::command]
 pub fn get_segments(state: State<JobIndexState>, id: String) -> Result<Vec<Segment>, String> {
-    let guard = state
-        .index
-        .lock()
-        .map_err(|_| "job index mutex poisoned".to_string())?;
-    let job = guard
-        .jobs
-        .iter()
-        .find(|job| job.id == id)
-        .cloned()
-        .ok_or_else(|| "job not found".to_string())?;
-    if job.transcript_json_path.is_empty() {
-        return Ok(Vec::new());
-    }
-    let contents = fs::read_to_string(&job.transcript_json_path)
-        .map_err(|err| format!("failed to read transcript json: {err}"))?;
-
-    if let Ok(segments) = serde_json::from_str::<Vec<Segment>>(&contents) {
-        return Ok(segments);
-    }
-
-    let value: serde_json::Value = serde_json::from_str(&contents)
-        .map_err(|err| format!("invalid transcript json: {err}"))?;
-    if let Some(segments_val) = value.get("segments").and_then(|v| v.as_array()) {
-        let mut segments = Vec::new();
-        for seg in segments_val {
-            let text = seg
-                .get("text")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .trim()
-                .to_string();
-            let start = seg
-                .get("start")
-                .and_then(|v| v.as_f64())
-                .or_else(|| seg.get("t0").and_then(|v| v.as_f64()).map(|v| v / 100.0))
-                .unwrap_or(0.0);
-            let end = seg
-                .get("end")
-                .and_then(|v| v.as_f64())
-                .or_else(|| seg.get("t1").and_then(|v| v.as_f64()).map(|v| v / 100.0))
-                .unwrap_or(start);
-            if !text.is_empty() {
-                segments.push(Segment {
-                    start: start as f32,
-                    end: end as f32,
-                    text,
-                });
-            }
-        }
-        return Ok(segments);
-    }
-
-    if let Some(transcription_val) = value.get("transcription").and_then(|v| v.as_array()) {
-        let mut segments = Vec::new();
-        for seg in transcription_val {
-            let text = seg
-                .get("text")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .trim()
-                .to_string();
-            let offsets = seg.get("offsets").and_then(|v| v.as_object());
-            let start_ms = offsets
-                .and_then(|o| o.get("from"))
-                .and_then(|v| v.as_f64())
-                .unwrap_or(0.0);
-            let end_ms = offsets
-                .and_then(|o| o.get("to"))
-                .and_then(|v| v.as_f64())
-                .unwrap_or(start_ms);
-            if !text.is_empty() {
-                segments.push(Segment {
-                    start: (start_ms / 1000.0) as f32,
-                    end: (end_ms / 1000.0) as f32,
-                    text,
-                });
-            }
-        }
-        return Ok(segments);
-    }
-
-    Err("segments not found in transcript json".to_string())
+    get_segments_inner(&state, id)
 }
 
+// Writes "<title>.srt" or "<title>.vtt" into options.dest, re-cut to the caption layout
+// instead of whisper's raw segmentation, and returns the written path.
 #[tauri::command]
-pub fn get_clip_path(
-    app: AppHandle,
+pub fn export_subtitles(
     state: State<JobIndexState>,
+    config_state: State<ConfigState>,
     id: String,
-    start: f64,
-    end: f64,
+    format: String,
+    options: SubtitleExportOptions,
 ) -> Result<String, String> {
-    let guard = state
-        .index
-        .lock()
-        .map_err(|_| "job index mutex poisoned".to_string())?;
-    let job = guard
-        .jobs
-        .iter()
-        .find(|job| job.id == id)
-        .cloned()
-        .ok_or_else(|| "job not found".to_string())?;
-    let job_dir = job_dir_from_audio_path(&job.audio_path)
-        .ok_or_else(|| "missing job directory".to_string())?;
-    let ffmpeg_path = match resolve_ffmpeg_path(&app) {
-        Ok(path) => path,
-        Err(message) => {
-            emit_job_log(&app, &id, &message);
-            return Ok(job.audio_path);
-        }
-    };
-    ensure_clip(&ffmpeg_path, &job.audio_path, &job_dir, start, end)
+    export_subtitles_inner(&state, &config_state, id, format, options)
 }
 
+// Shares a job outside Obsidian: writes "<title>.<format>" (html, docx or pdf) into `dest`
+// and returns the written path.
 #[tauri::command]
-pub fn get_summary(
+pub fn export_job(
     state: State<JobIndexState>,
     config_state: State<ConfigState>,
     id: String,
-) -> Result<SummaryResponse, String> {
-    let guard = state
-        .index
-        .lock()
-        .map_err(|_| "job index mutex poisoned".to_string())?;
-    let job = guard
-        .jobs
-        .iter()
-        .find(|job| job.id == id)
-        .cloned()
-        .ok_or_else(|| "job not found".to_string())?;
-    let summary_status = job
-        .summary_status
-        .clone()
-        .unwrap_or_else(|| "not_started".to_string());
-    let default_model = {
-        let guard = config_state
-            .config
-            .lock()
-            .map_err(|_| "config mutex poisoned".to_string())?;
-        guard.ollama_model.clone()
-    };
+    format: String,
+    dest: String,
+) -> Result<String, String> {
+    export_job_inner(&state, &config_state, id, format, dest)
+}
 
-    if let Some(summary) = job.summary_md.clone() {
-        if !summary.trim().is_empty() {
-            return Ok(SummaryResponse {
-                summary_status: summary_status.clone(),
-                summary_model: job.summary_model.unwrap_or_else(|| default_model.clone()),
-                summary_error: job.summary_error,
-                summary_md: summary,
-            });
-        }
-    }
+#[tauri::command]
+pub fn export_to_target(
+    app: Core,
+    state: State<JobIndexState>,
+    config_state: State<ConfigState>,
+    id: String,
+    target_id: String,
+) -> Result<String, String> {
+    export_to_target_inner(&app, &state, &config_state, id, target_id)
+}
 
-    let job_dir = job_dir_from_audio_path(&job.audio_path)
-        .ok_or_else(|| "missing job directory".to_string())?;
-    let summary_path = job_dir.join("summary.md");
-    if summary_path.exists() {
-        let content = fs::read_to_string(&summary_path)
-            .map_err(|err| format!("failed to read summary.md: {err}"))?;
-        return Ok(SummaryResponse {
-            summary_status: summary_status.clone(),
-            summary_model: job.summary_model.unwrap_or_else(|| default_model.clone()),
-            summary_error: job.summary_error,
-            summary_md: content,
-        });
-    }
-
-    if summary_status == "skipped" {
-        let prompt_template = {
-            let guard = config_state
-                .config
-                .lock()
-                .map_err(|_| "config mutex poisoned".to_string())?;
-            guard.summary_prompt.clone()
-        };
-        let transcript = if !job.transcript_txt_path.is_empty()
-            && std::path::Path::new(&job.transcript_txt_path).exists()
-        {
-            read_transcript_text(&job.transcript_txt_path)?
-        } else {
-            String::new()
-        };
-        let prompt = if transcript.is_empty() {
-            prompt_template
-        } else {
-            build_summary_prompt(&prompt_template, &transcript)
-        };
-        return Ok(SummaryResponse {
-            summary_status: summary_status.clone(),
-            summary_model: job.summary_model.unwrap_or_else(|| default_model.clone()),
-            summary_error: job.summary_error,
-            summary_md: prompt,
-        });
-    }
+// Stores the Joplin Web Clipper token for a target in the keychain; empty clears it.
+#[tauri::command]
+pub fn set_export_target_token(app: Core, target_id: String, token: String) -> Result<bool, String> {
+    store_secret(&app, EXPORT_KEYCHAIN_SERVICE, &target_id, &export_target_token_file(&target_id), &token)?;
+    Ok(!token.trim().is_empty())
+}
 
-    Ok(SummaryResponse {
-        summary_status: summary_status.clone(),
-        summary_model: job.summary_model.unwrap_or_else(|| default_model.clone()),
-        summary_error: job.summary_error,
-        summary_md: "".to_string(),
-    })
+// Stores the CalDAV password in the keychain; empty clears it.
+#[tauri::command]
+pub fn set_calendar_password(app: Core, password: String) -> Result<bool, String> {
+    store_secret(&app, CALENDAR_KEYCHAIN_SERVICE, CALENDAR_KEYCHAIN_ACCOUNT, "calendar_password", &password)?;
+    Ok(!password.trim().is_empty())
 }
 
+// Looks up the event overlapping the recording now, e.g. after the calendar was configured.
 #[tauri::command]
-pub fn summarize_job(app: AppHandle, id: String) -> Result<SummaryResponse, String> {
-    let config_state = app.state::<ConfigState>();
-    let (enable, base_url, model, prompt) = {
-        let guard = config_state
+pub async fn match_calendar_event(app: Core, id: String) -> Result<Option<CalendarEvent>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let config = app
+            .state::<ConfigState>()
             .config
             .lock()
-            .map_err(|_| "config mutex poisoned".to_string())?;
-        (
-            guard.enable_summarization,
-            guard.ollama_base_url.clone(),
-            guard.ollama_model.clone(),
-            guard.summary_prompt.clone(),
-        )
-    };
-    if !enable {
-        return Ok(SummaryResponse {
-            summary_status: "skipped".to_string(),
-            summary_model: model,
-            summary_error: None,
-            summary_md: "".to_string(),
-        });
-    }
-    let index_state = app.state::<JobIndexState>();
-    if let Ok(guard) = index_state.index.lock() {
-        if let Some(job) = guard.jobs.iter().find(|job| job.id == id) {
-            let status = job.summary_status.clone().unwrap_or_else(|| "not_started".to_string());
-            if status == "running" {
-                return Ok(SummaryResponse {
-                    summary_status: status,
-                    summary_model: job.summary_model.clone().unwrap_or_else(|| model.clone()),
-                    summary_error: job.summary_error.clone(),
-                    summary_md: job.summary_md.clone().unwrap_or_default(),
-                });
-            }
-        }
+            .map_err(|_| "config mutex poisoned".to_string())?
+            .clone();
+        attach_calendar_event(&app, &config, &id)
+    })
+    .await
+    .map_err(|err| format!("calendar lookup task failed: {err}"))?
+}
+
+// Compresses a finished job's audio and raw artifacts, keeping transcript and summaries
+// readable, and hides it from the default job list. Returns the bytes freed.
+#[tauri::command]
+pub async fn archive_job(app: Core, id: String, drop_wav: Option<bool>) -> Result<u64, String> {
+    tauri::async_runtime::spawn_blocking(move || archive_job_blocking(&app, &id, drop_wav))
+        .await
+        .map_err(|err| format!("archive task failed: {err}"))?
+}
+
+#[tauri::command]
+pub async fn unarchive_job(app: Core, id: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || unarchive_job_blocking(&app, &id))
+        .await
+        .map_err(|err| format!("unarchive task failed: {err}"))?
+}
+
+// Packs the job folder (minus the converted WAV) and a manifest.json into
+// "<title>.voicenote.zip" inside `dest`; returns the bundle path.
+#[tauri::command]
+pub async fn export_job_bundle(app: Core, id: String, dest: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || export_job_bundle_blocking(&app, &id, dest))
+        .await
+        .map_err(|err| format!("bundle export task failed: {err}"))?
+}
+
+#[tauri::command]
+pub async fn import_job_bundle(app: Core, path: String) -> Result<Job, String> {
+    tauri::async_runtime::spawn_blocking(move || import_job_bundle_blocking(&app, &path))
+        .await
+        .map_err(|err| format!("bundle import task failed: {err}"))?
+}
+
+fn write_clipboard(app: &AppHandle, text: &str) -> Result<(), String> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+    app.clipboard()
+        .write_text(text)
+        .map_err(|err| format!("failed to write clipboard: {err}"))
+}
+
+// Copies the transcript for pasting into chat or email; `with_timestamps` prefixes each
+// segment with [mm:ss] (and the speaker, when known).
+#[tauri::command]
+pub fn copy_transcript_to_clipboard(
+    app: AppHandle,
+    state: State<JobIndexState>,
+    id: String,
+    with_timestamps: bool,
+) -> Result<(), String> {
+    let job = find_job(&state, &id)?;
+    write_clipboard(&app, &clipboard_transcript(&job, with_timestamps)?)
+}
+
+#[tauri::command]
+pub fn copy_summary_to_clipboard(app: AppHandle, state: State<JobIndexState>, id: String) -> Result<(), String> {
+    let job = find_job(&state, &id)?;
+    let summary = stored_summary(&job);
+    if summary.trim().is_empty() {
+        return Err("No summary to copy yet.".to_string());
     }
+    write_clipboard(&app, summary.trim())
+}
 
-    update_job_and_emit(&app, &id, |job| {
-        job.summary_status = Some("running".to_string());
-        job.summary_model = Some(model.clone());
-        job.summary_error = None;
-    })?;
-    emit_job_log(&app, &id, "Summarization started.");
+#[tauri::command]
+pub fn export_bilingual(
+    app: Core,
+    state: State<JobIndexState>,
+    config_state: State<ConfigState>,
+    id: String,
+    dest: String,
+    target_language: Option<String>,
+    include_clips: bool,
+) -> Result<String, String> {
+    export_bilingual_inner(&app, &state, &config_state, id, dest, target_language, include_clips)
+}
 
-    let app_handle = app.clone();
-    let id_clone = id.clone();
-    let base_url_clone = base_url.clone();
-    let model_clone = model.clone();
-    let prompt_clone = prompt.clone();
-    thread::spawn(move || {
-        let _ = summarize_job_internal(
-            &app_handle,
-            &id_clone,
-            &base_url_clone,
-            &model_clone,
-            &prompt_clone,
-            true,
-        );
-    });
+#[tauri::command]
+pub fn reveal_job_in_finder(
+    state: State<JobIndexState>,
+    id: String,
+    artifact: Option<String>,
+) -> Result<bool, String> {
+    reveal_job_in_finder_inner(&state, id, artifact)
+}
 
-    Ok(SummaryResponse {
-        summary_status: "running".to_string(),
-        summary_model: model,
-        summary_error: None,
-        summary_md: "".to_string(),
-    })
+#[tauri::command]
+pub fn get_clip_path(
+    app: Core,
+    state: State<JobIndexState>,
+    config_state: State<ConfigState>,
+    id: String,
+    start: f64,
+    end: f64,
+) -> Result<String, String> {
+    get_clip_path_inner(&app, &state, &config_state, id, start, end)
+}
+
+#[tauri::command]
+pub fn get_storage_usage(
+    state: State<JobIndexState>,
+    config_state: State<ConfigState>,
+) -> Result<StorageUsage, String> {
+    get_storage_usage_inner(&state, &config_state)
+}
+
+// Totals and a monthly series for a stats dashboard; `year` limits it to recordings
+// from that calendar year.
+#[tauri::command]
+pub async fn get_stats(app: Core, year: Option<i64>) -> Result<VoiceNoteStats, String> {
+    tauri::async_runtime::spawn_blocking(move || stats_report(&app, year))
+        .await
+        .map_err(|err| format!("stats task failed: {err}"))?
+}
+
+#[tauri::command]
+pub fn clear_clip_cache(state: State<JobIndexState>, id: Option<String>) -> Result<u64, String> {
+    clear_clip_cache_inner(&state, id)
 }
 
-fn summarize_job_internal(
-    app: &AppHandle,
-    job_id: &str,
-    base_url: &str,
-    model: &str,
-    prompt_template: &str,
-    force: bool,
+#[tauri::command]
+pub fn export_clip(
+    app: Core,
+    state: State<JobIndexState>,
+    id: String,
+    start: f64,
+    end: f64,
+    format: String,
+    dest: String,
+) -> Result<String, String> {
+    export_clip_inner(&app, &state, id, start, end, format, dest)
+}
+
+#[tauri::command]
+pub fn get_waveform(
+    state: State<JobIndexState>,
+    id: String,
+    samples_per_second: u32,
+) -> Result<Waveform, String> {
+    get_waveform_inner(&state, id, samples_per_second)
+}
+
+#[tauri::command]
+pub fn get_chapters(state: State<JobIndexState>, id: String) -> Result<Vec<Chapter>, String> {
+    get_chapters_inner(&state, id)
+}
+
+#[tauri::command]
+pub fn get_summary(
+    state: State<JobIndexState>,
+    config_state: State<ConfigState>,
+    id: String,
+    template: Option<String>,
 ) -> Result<SummaryResponse, String> {
-    let index_state = app.state::<JobIndexState>();
-    let mut transcript_path: Option<String> = None;
-    let mut job_dir: Option<PathBuf> = None;
+    get_summary_inner(&state, &config_state, id, template)
+}
 
-    if !force {
-        let guard = index_state
-            .index
-            .lock()
-            .map_err(|_| "job index mutex poisoned".to_string())?;
-        if let Some(job) = guard.jobs.iter().find(|job| job.id == job_id) {
-            let status = job.summary_status.clone().unwrap_or_else(|| "not_started".to_string());
-            if status == "running" {
-                return Ok(SummaryResponse {
-                    summary_status: status,
-                    summary_model: job.summary_model.clone().unwrap_or_else(|| model.to_string()),
-                    summary_error: job.summary_error.clone(),
-                    summary_md: job.summary_md.clone().unwrap_or_default(),
-                });
-            }
-            if status == "done" {
-                if let Some(summary) = job.summary_md.clone() {
-                    if !summary.trim().is_empty() {
-                        return Ok(SummaryResponse {
-                            summary_status: status,
-                            summary_model: job.summary_model.clone().unwrap_or_else(|| model.to_string()),
-                            summary_error: job.summary_error.clone(),
-                            summary_md: summary,
-                        });
-                    }
-                }
-            }
-        }
-    }
+#[tauri::command]
+pub fn get_action_items(state: State<JobIndexState>, id: String) -> Result<Vec<ActionItem>, String> {
+    get_action_items_inner(&state, id)
+}
 
-    update_job_and_emit(app, job_id, |job| {
-        job.summary_status = Some("running".to_string());
-        job.summary_model = Some(model.to_string());
-    })?;
-    emit_job_log(app, job_id, "Summarization started.");
+#[tauri::command]
+pub fn extract_action_items(app: Core, id: String) -> Result<bool, String> {
+    extract_action_items_inner(&app, id)
+}
 
-    {
-        let guard = index_state
-            .index
-            .lock()
-            .map_err(|_| "job index mutex poisoned".to_string())?;
-        if let Some(job) = guard.jobs.iter().find(|job| job.id == job_id) {
-            transcript_path = Some(job.transcript_txt_path.clone());
-            job_dir = job_dir_from_audio_path(&job.audio_path);
-        }
-    }
+#[tauri::command]
+pub fn get_summaries(
+    state: State<JobIndexState>,
+    config_state: State<ConfigState>,
+    id: String,
+) -> Result<BTreeMap<String, SummaryResponse>, String> {
+    get_summaries_inner(&state, &config_state, id)
+}
 
-    let transcript_path = transcript_path.ok_or_else(|| "Transcript not found.".to_string())?;
-    if transcript_path.is_empty() {
-        return Err("Transcript path missing.".to_string());
-    }
-    let job_dir = job_dir.ok_or_else(|| "Job directory missing.".to_string())?;
-    let result = (|| -> Result<String, String> {
-        let transcript = read_transcript_text(&transcript_path)?;
-        let prompt = build_summary_prompt(prompt_template, &transcript);
-        let summary = summarize_with_ollama(base_url, model, &prompt)?;
-        let _summary_path = write_summary_file(&job_dir, &summary)?;
-        Ok(summary)
-    })();
+#[tauri::command]
+pub fn summarize_job(
+    app: Core,
+    id: String,
+    template: Option<String>,
+) -> Result<SummaryResponse, String> {
+    summarize_job_inner(&app, id, template)
+}
 
-    match result {
-        Ok(summary) => {
-            update_job_and_emit(app, job_id, |job| {
-                job.summary_status = Some("done".to_string());
-                job.summary_md = Some(summary.clone());
-                job.summary_error = None;
-                job.summary_model = Some(model.to_string());
-                job.md_preview = Some(summary.clone());
-            })?;
-            emit_job_log(app, job_id, "Summarization finished.");
-            Ok(SummaryResponse {
-                summary_status: "done".to_string(),
-                summary_model: model.to_string(),
-                summary_error: None,
-                summary_md: summary,
-            })
-        }
-        Err(err) => {
-            update_job_and_emit(app, job_id, |job| {
-                job.summary_status = Some("error".to_string());
-                job.summary_error = Some(err.clone());
-                job.summary_model = Some(model.to_string());
-            })?;
-            emit_job_log(app, job_id, &format!("Summarization failed: {err}"));
-            Err(err)
-        }
-    }
+#[tauri::command]
+pub fn regenerate_summary(
+    app: Core,
+    state: State<JobIndexState>,
+    id: String,
+    extra_instructions: String,
+) -> Result<SummaryResponse, String> {
+    regenerate_summary_inner(&app, &state, id, extra_instructions)
 }
 
 #[tauri::command]
-pub fn get_model_size(model_size: String) -> u64 {
-    let url = match model_url(&model_size) {
-        Ok(url) => url,
-        Err(_) => return 0,
-    };
-    let client = match reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(5))
-        .build()
-    {
-        Ok(client) => client,
-        Err(_) => return 0,
-    };
-    if let Ok(resp) = client.head(url).send() {
-        if let Some(len) = resp.headers().get(reqwest::header::CONTENT_LENGTH) {
-            if let Ok(len) = len.to_str() {
-                if let Ok(bytes) = len.parse::<u64>() {
-                    return bytes;
-                }
-            }
-        }
+pub fn list_summary_versions(
+    state: State<JobIndexState>,
+    id: String,
+) -> Result<Vec<SummaryVersion>, String> {
+    list_summary_versions_inner(&state, id)
+}
+
+#[tauri::command]
+pub fn resummarize_jobs(
+    app: Core,
+    state: State<JobIndexState>,
+    config_state: State<ConfigState>,
+    batch: State<ResummarizeState>,
+    filter: JobFilter,
+) -> Result<Vec<String>, String> {
+    resummarize_jobs_inner(&app, &state, &config_state, &batch, filter)
+}
+
+// Exports every finished job matching `filter` that has no note in the current vault yet,
+// emitting "export:batch" progress along the way.
+#[tauri::command]
+pub async fn export_all(app: Core, filter: JobFilter) -> Result<ExportAllSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || export_all_blocking(&app, &filter))
+        .await
+        .map_err(|err| format!("export task failed: {err}"))?
+}
+
+#[tauri::command]
+pub fn cancel_resummarize(batch: State<ResummarizeState>) -> bool {
+    use std::sync::atomic::Ordering;
+    batch.cancel.store(true, Ordering::SeqCst);
+    batch.running.load(Ordering::SeqCst)
+}
+
+#[tauri::command]
+pub fn get_model_size(
+    state: State<ModelDownloadState>,
+    config_state: State<ConfigState>,
+    model_size: String,
+) -> u64 {
+    match model_source_url(state.metadata_dir(), &model_size) {
+        Ok(url) => remote_content_length(&download_network(&config_state), &url),
+        Err(_) => 0,
     }
-    0
 }
 
 #[tauri::command]
@@ -2079,497 +1499,511 @@ pub fn get_model_download_status(
     state: State<ModelDownloadState>,
     model_size: String,
 ) -> ModelDownloadStatus {
-    let guard = state.statuses.lock().ok();
-    if let Some(guard) = guard {
-        if let Some(status) = guard.get(&model_size) {
-            return status.clone();
-        }
-    }
-    ModelDownloadStatus {
-        state: "idle".to_string(),
-        model_size,
-        repo_id: "whisper.cpp".to_string(),
-        total_bytes: 0,
-        downloaded_bytes: 0,
-        message: None,
-        started_at: None,
-        finished_at: None,
-    }
+    get_model_download_status_inner(&state, model_size)
 }
 
 #[tauri::command]
 pub fn get_model_installed(state: State<ModelDownloadState>, model_size: String) -> bool {
-    if let Ok(filename) = model_filename(&model_size) {
-        let in_app_data = state.models_dir.join(&filename);
-        if in_app_data.exists() {
-            return true;
-        }
-        let in_third_party = PathBuf::from("third_party/whisper/models").join(&filename);
-        if in_third_party.exists() {
-            return true;
-        }
-        if let Ok(cwd) = std::env::current_dir() {
-            if cwd.join("third_party/whisper/models").join(&filename).exists() {
-                return true;
-            }
-        }
-        if let Some(resource_dir) = state
-            .models_dir
-            .parent()
-            .and_then(|p| p.parent())
-            .map(|p| p.join("Resources/whisper/models").join(&filename))
-        {
-            if resource_dir.exists() {
-                return true;
-            }
-        }
-    }
-    false
+    get_model_installed_inner(&state, model_size)
+}
+
+#[tauri::command]
+pub fn verify_model(
+    app: Core,
+    state: State<ModelDownloadState>,
+    model_size: String,
+) -> Result<ModelVerification, String> {
+    verify_model_inner(&app, &state, model_size)
 }
 
 #[tauri::command]
 pub fn start_model_download(
     state: State<ModelDownloadState>,
+    config_state: State<ConfigState>,
     model_size: String,
+    url: Option<String>,
+    name: Option<String>,
 ) -> Result<ModelDownloadStatus, String> {
-    let filename = model_filename(&model_size)?;
-    let url = model_url(&model_size)?;
-    let dest_path = state.models_dir.join(&filename);
-    let tmp_path = state.models_dir.join(format!("{filename}.part"));
-
-    let mut guard = state
-        .statuses
-        .lock()
-        .map_err(|_| "model download mutex poisoned".to_string())?;
-    if let Some(existing) = guard.get(&model_size) {
-        if existing.state == "downloading" {
-            return Ok(existing.clone());
-        }
-    }
-    let status = ModelDownloadStatus {
-        state: "downloading".to_string(),
-        model_size: model_size.clone(),
-        repo_id: "whisper.cpp".to_string(),
-        total_bytes: 0,
-        downloaded_bytes: 0,
-        message: Some(format!("Downloading {filename}")),
-        started_at: Some(now_ts()),
-        finished_at: None,
-    };
-    guard.insert(model_size.clone(), status.clone());
-    drop(guard);
-
-    let status_map = Arc::clone(&state.inner().statuses);
-    let status_for_thread = status.clone();
-    thread::spawn(move || {
-        let mut result_status = status_for_thread.clone();
-        let download_result =
-            download_to_file(&url, &tmp_path, &mut result_status, &status_map);
-        if let Err(err) = download_result {
-            result_status.state = "error".to_string();
-            result_status.message = Some(err);
-            let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-            guard.insert(model_size.clone(), result_status);
-            return;
-        }
-
-        if let Err(err) = fs::rename(&tmp_path, &dest_path) {
-            result_status.state = "error".to_string();
-            result_status.message = Some(format!("Finalize error: {err}"));
-            let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-            guard.insert(model_size.clone(), result_status);
-            return;
-        }
-
-        result_status.state = "done".to_string();
-        result_status.finished_at = Some(now_ts());
-        result_status.message = Some("Download complete".to_string());
-        let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-        guard.insert(model_size.clone(), result_status);
-    });
-
-    Ok(status)
+    start_model_download_inner(&state, &config_state, model_size, url, name)
 }
 
 #[tauri::command]
 pub fn get_whisper_download_status(state: State<ModelDownloadState>) -> ModelDownloadStatus {
-    let key = whisper_binary_status_key();
-    let guard = state.statuses.lock().ok();
-    if let Some(guard) = guard {
-        if let Some(status) = guard.get(&key) {
-            return status.clone();
-        }
-    }
-    ModelDownloadStatus {
-        state: "idle".to_string(),
-        model_size: key,
-        repo_id: "whisper.cpp".to_string(),
-        total_bytes: 0,
-        downloaded_bytes: 0,
-        message: None,
-        started_at: None,
-        finished_at: None,
-    }
+    get_whisper_download_status_inner(&state)
 }
 
 #[tauri::command]
-pub fn get_whisper_installed(state: State<ModelDownloadState>) -> bool {
-    let bin = state.whisper_dir.join("bin/whisper");
-    let alt = state.whisper_dir.join("bin/main");
-    if bin.exists() || alt.exists() {
-        return true;
-    }
-    let third_party = PathBuf::from("third_party/whisper/bin/whisper");
-    let third_party_alt = PathBuf::from("third_party/whisper/bin/main");
-    if third_party.exists() || third_party_alt.exists() {
-        return true;
-    }
+pub fn get_whisper_installed(app: Core, state: State<ModelDownloadState>) -> bool {
+    let mut dirs = vec![state.whisper_dir.clone(), PathBuf::from("third_party/whisper")];
     if let Ok(cwd) = std::env::current_dir() {
-        if cwd.join("third_party/whisper/bin/whisper").exists()
-            || cwd.join("third_party/whisper/bin/main").exists()
-        {
-            return true;
-        }
+        dirs.push(cwd.join("third_party/whisper"));
     }
-    false
+    dirs.iter()
+        .any(|dir| WHISPER_BINARY_NAMES.iter().any(|name| dir.join(bin_path(name)).exists()))
+        || (use_system_binaries(&app) && find_system_binary(SYSTEM_WHISPER_NAMES).is_some())
 }
 
 #[tauri::command]
 pub fn get_ffmpeg_download_status(state: State<ModelDownloadState>) -> ModelDownloadStatus {
-    let key = ffmpeg_status_key();
-    let guard = state.statuses.lock().ok();
-    if let Some(guard) = guard {
-        if let Some(status) = guard.get(&key) {
-            return status.clone();
-        }
-    }
-    ModelDownloadStatus {
-        state: "idle".to_string(),
-        model_size: key,
-        repo_id: "ffmpeg".to_string(),
-        total_bytes: 0,
-        downloaded_bytes: 0,
-        message: None,
-        started_at: None,
-        finished_at: None,
-    }
+    get_ffmpeg_download_status_inner(&state)
 }
 
 #[tauri::command]
-pub fn get_ffmpeg_installed(state: State<ModelDownloadState>) -> bool {
-    let bin = state.ffmpeg_dir.join("bin/ffmpeg");
-    let probe = state.ffmpeg_dir.join("bin/ffprobe");
-    if bin.exists() && probe.exists() {
-        return true;
-    }
-    let third_party = PathBuf::from("third_party/ffmpeg/bin/ffmpeg");
-    if third_party.exists() {
-        return true;
-    }
-    if let Ok(cwd) = std::env::current_dir() {
-        if cwd.join("third_party/ffmpeg/bin/ffmpeg").exists() {
-            return true;
-        }
-    }
-    false
+pub fn get_ffmpeg_installed(app: Core, state: State<ModelDownloadState>) -> bool {
+    get_ffmpeg_installed_inner(&app, &state)
 }
 
 #[tauri::command]
 pub fn start_ffmpeg_download(
-    app: AppHandle,
+    app: Core,
     state: State<ModelDownloadState>,
+    config_state: State<ConfigState>,
     url: String,
 ) -> Result<ModelDownloadStatus, String> {
-    if url.trim().is_empty() {
-        return Err("FFmpeg download URL is empty.".to_string());
-    }
-    let url = url.replace("http://", "https://");
-    let key = ffmpeg_status_key();
-    let bin_dir = state.ffmpeg_dir.join("bin");
-    fs::create_dir_all(&bin_dir)
-        .map_err(|err| format!("failed to create ffmpeg bin dir: {err}"))?;
-    let tmp_path = state.ffmpeg_dir.join("ffmpeg.part");
-    let _ = fs::remove_file(&tmp_path);
-
-    let mut guard = state
-        .statuses
-        .lock()
-        .map_err(|_| "model download mutex poisoned".to_string())?;
-    if let Some(existing) = guard.get(&key) {
-        if existing.state == "downloading" {
-            return Ok(existing.clone());
-        }
-    }
-    let status = ModelDownloadStatus {
-        state: "downloading".to_string(),
-        model_size: key.clone(),
-        repo_id: "ffmpeg".to_string(),
-        total_bytes: 0,
-        downloaded_bytes: 0,
-        message: Some("Downloading FFmpeg".to_string()),
-        started_at: Some(now_ts()),
-        finished_at: None,
-    };
-    guard.insert(key.clone(), status.clone());
-    drop(guard);
-
-    let status_map = Arc::clone(&state.inner().statuses);
-    let app_handle = app.clone();
-    let status_for_thread = status.clone();
-    let ffmpeg_dir = state.ffmpeg_dir.clone();
-    let key_for_thread = key.clone();
-    thread::spawn(move || {
-        let mut result_status = status_for_thread.clone();
-        let download_result = download_to_file(&url, &tmp_path, &mut result_status, &status_map);
-        if let Err(err) = download_result {
-            result_status.state = "error".to_string();
-            result_status.message = Some(err);
-            let _ = fs::remove_file(&tmp_path);
-            let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-            guard.insert(key_for_thread.clone(), result_status);
-            let _ = app_handle.emit("job:log", JobLogEvent {
-                id: "ffmpeg-download".to_string(),
-                line: "FFmpeg download failed.".to_string(),
-            });
-            return;
-        }
+    start_ffmpeg_download_inner(&app, &state, &config_state, url)
+}
 
-        let is_zip = url.to_lowercase().ends_with(".zip");
-        if is_zip {
-            if let Err(err) = extract_ffmpeg_zip(&tmp_path, &ffmpeg_dir) {
-                result_status.state = "error".to_string();
-                result_status.message = Some(err);
-                let _ = fs::remove_file(&tmp_path);
-                let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-                guard.insert(key_for_thread.clone(), result_status);
-                return;
-            }
-            let _ = fs::remove_file(&tmp_path);
-        } else {
-            let dest_path = ffmpeg_dir.join("bin/ffmpeg");
-            if let Err(err) = fs::rename(&tmp_path, &dest_path) {
-                result_status.state = "error".to_string();
-                result_status.message = Some(format!("Finalize error: {err}"));
-                let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-                guard.insert(key_for_thread.clone(), result_status);
-                return;
-            }
-        }
+#[tauri::command]
+pub fn start_whisper_download(
+    app: Core,
+    state: State<ModelDownloadState>,
+    config_state: State<ConfigState>,
+    url: String,
+) -> Result<ModelDownloadStatus, String> {
+    start_whisper_download_inner(&app, &state, &config_state, url)
+}
 
-        let ffmpeg_path = ffmpeg_dir.join("bin/ffmpeg");
-        let ffprobe_path = ffmpeg_dir.join("bin/ffprobe");
-        if let Ok(mut perms) = fs::metadata(&ffmpeg_path).map(|meta| meta.permissions()) {
-            perms.set_mode(0o755);
-            let _ = fs::set_permissions(&ffmpeg_path, perms);
-        }
-        if ffprobe_path.exists() {
-            if let Ok(mut perms) = fs::metadata(&ffprobe_path).map(|meta| meta.permissions()) {
-                perms.set_mode(0o755);
-                let _ = fs::set_permissions(&ffprobe_path, perms);
-            }
-        }
+// Models downloaded into the app data folder, with size and last use, for the Settings list.
+#[tauri::command]
+pub fn list_installed_models(state: State<ModelDownloadState>) -> Vec<InstalledModel> {
+    scan_installed_models(&state.models_dir(), state.metadata_dir())
+}
 
-        if let Err(err) = ensure_lgpl_ffmpeg(ffmpeg_path.clone()) {
-            result_status.state = "error".to_string();
-            result_status.message = Some(err);
-            let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-            guard.insert(key_for_thread.clone(), result_status);
-            return;
-        }
+// Removes a downloaded model (and any partial download of it). Returns the bytes freed. Files
+// in a shared models folder that the app didn't download are left to their owner.
+#[tauri::command]
+pub fn delete_model(state: State<ModelDownloadState>, model_size: String) -> Result<u64, String> {
+    delete_model_inner(&state, model_size)
+}
 
-        result_status.state = "done".to_string();
-        result_status.finished_at = Some(now_ts());
-        result_status.message = Some("Download complete".to_string());
-        let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-        guard.insert(key_for_thread.clone(), result_status);
-    });
+// Deletes .part files left by interrupted model downloads. Returns the bytes freed.
+#[tauri::command]
+pub fn remove_partial_model_downloads(state: State<ModelDownloadState>) -> Result<u64, String> {
+    let active = active_model_downloads(&state)?;
+    Ok(remove_stale_parts(&state.models_dir(), &active))
+}
 
-    Ok(status)
+#[tauri::command]
+pub fn cancel_model_download(state: State<ModelDownloadState>, key: String) -> Result<bool, String> {
+    cancel_download(&state, &key)
 }
 
 #[tauri::command]
-pub fn start_whisper_download(
-    app: AppHandle,
-    state: State<ModelDownloadState>,
-    url: String,
-) -> Result<ModelDownloadStatus, String> {
-    let url = resolve_whisper_download_url(&url)?;
-    let key = whisper_binary_status_key();
-    let bin_dir = state.whisper_dir.join("bin");
-    fs::create_dir_all(&bin_dir)
-        .map_err(|err| format!("failed to create whisper bin dir: {err}"))?;
-    let dest_path = bin_dir.join("whisper");
-    let tmp_path = bin_dir.join("whisper.part");
-    let _ = fs::remove_file(&dest_path);
-    let _ = fs::remove_file(&tmp_path);
-
-    let mut guard = state
-        .statuses
+pub fn cancel_whisper_download(state: State<ModelDownloadState>) -> Result<bool, String> {
+    cancel_download(&state, &whisper_binary_status_key())
+}
+
+#[tauri::command]
+pub fn cancel_ffmpeg_download(state: State<ModelDownloadState>) -> Result<bool, String> {
+    cancel_download(&state, &ffmpeg_status_key())
+}
+
+// Moves jobs, models, binaries and config to another folder (e.g. an external SSD), rewrites
+// the paths stored in jobs.db and config.json, and restarts the app on the new location.
+#[tauri::command]
+pub async fn move_app_data(app: Core, new_location: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || move_app_data_blocking(&app, &new_location))
+        .await
+        .map_err(|err| format!("move app data task failed: {err}"))?
+}
+
+// Checks every external dependency in one pass so onboarding can show what is missing and
+// offer a fix for each. The LLM probe can wait on the network, so it all runs off the main thread.
+#[tauri::command]
+pub async fn run_diagnostics(app: Core) -> Result<DiagnosticsReport, String> {
+    tauri::async_runtime::spawn_blocking(move || diagnostics_report(&app))
+        .await
+        .map_err(|err| format!("diagnostics task failed: {err}"))?
+}
+
+#[tauri::command]
+pub fn get_app_logs(app: Core, level: Option<String>, limit: Option<usize>) -> Result<Vec<AppLogEntry>, String> {
+    let limit = limit.unwrap_or(APP_LOGS_DEFAULT_LIMIT).clamp(1, APP_LOGS_MAX_LIMIT);
+    let level = level.unwrap_or_else(|| "info".to_string());
+    Ok(read_app_logs(&log_files(&log_dir(&app)?), &level, limit))
+}
+
+// Zips the log files, the config with secrets redacted, a job manifest and basic system
+// information for a bug report. Returns the path of the zip, written to `dest_dir` or
+// <app data>/support.
+#[tauri::command]
+pub async fn create_support_bundle(app: Core, dest_dir: Option<String>) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || create_support_bundle_blocking(&app, dest_dir))
+        .await
+        .map_err(|err| format!("support bundle task failed: {err}"))?
+}
+
+// Version that last ran from this app data folder; a different one means the bundle was replaced.
+const APP_VERSION_FILE: &str = "app_version";
+
+const UPDATE_INSTALL_POLL: std::time::Duration = std::time::Duration::from_secs(2);
+
+// A summary stuck on an LLM that never answers would hold the install off forever; after this
+// long it installs anyway and the next launch requeues whatever was cut short.
+const UPDATE_INSTALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+// Download progress is emitted in steps of this many bytes, not per chunk.
+const UPDATE_PROGRESS_STEP: u64 = 512 * 1024;
+
+// Emitted as "update:status" whenever it changes.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AppUpdateStatus {
+    // "idle", "available", "downloading", "ready", "waiting_for_jobs", "installing" or "error".
+    pub state: String,
+    // False for builds without the release signing key; the settings hide the updater then.
+    pub configured: bool,
+    pub current_version: String,
+    pub version: Option<String>,
+    pub notes: Option<String>,
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,
+    // Running or queued jobs a waiting install is holding off for.
+    pub blocking_jobs: usize,
+    pub error: Option<String>,
+    // Bundled ffmpeg and whisper checks from the first launch after an update.
+    pub verification: Vec<DiagnosticCheck>,
+}
+
+// The updater checks and downloads here in the core; installing restarts the app, so it waits
+// until no job is in the middle of the pipeline. Until releases are signed only the status (the
+// version and the checks after an update) is served; lib.rs leaves the updater unregistered.
+pub struct AppUpdateState {
+    status: Mutex<AppUpdateStatus>,
+    update: Mutex<Option<tauri_plugin_updater::Update>>,
+    bytes: Mutex<Option<Vec<u8>>>,
+    // Set by install_app_update_now: stop waiting for the jobs.
+    install_now: Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl AppUpdateState {
+    pub fn new(app: &AppHandle) -> Self {
+        Self {
+            status: Mutex::new(AppUpdateStatus {
+                state: "idle".to_string(),
+                configured: updater_configured(app),
+                current_version: app.package_info().version.to_string(),
+                ..AppUpdateStatus::default()
+            }),
+            update: Mutex::new(None),
+            bytes: Mutex::new(None),
+            install_now: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        }
+    }
+}
+
+fn set_update_status(app: &AppHandle, change: impl FnOnce(&mut AppUpdateStatus)) -> Result<AppUpdateStatus, String> {
+    let state = app.state::<AppUpdateState>();
+    let mut status = state
+        .status
         .lock()
-        .map_err(|_| "model download mutex poisoned".to_string())?;
-    if let Some(existing) = guard.get(&key) {
-        if existing.state == "downloading" {
-            return Ok(existing.clone());
-        }
+        .map_err(|_| "update status mutex poisoned".to_string())?;
+    change(&mut status);
+    let _ = app.emit("update:status", &*status);
+    Ok(status.clone())
+}
+
+// Without the release signing key in tauri.conf.json every download would fail verification.
+fn updater_configured(app: &AppHandle) -> bool {
+    app.config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|updater| updater.get("pubkey"))
+        .and_then(|pubkey| pubkey.as_str())
+        .is_some_and(|pubkey| !pubkey.trim().is_empty())
+}
+
+fn ensure_updater_configured(app: &AppHandle) -> Result<(), String> {
+    if !updater_configured(app) {
+        return Err("Updates are not configured for this build (plugins.updater.pubkey is empty).".to_string());
     }
-    let status = ModelDownloadStatus {
-        state: "downloading".to_string(),
-        model_size: key.clone(),
-        repo_id: "whisper.cpp".to_string(),
-        total_bytes: 0,
-        downloaded_bytes: 0,
-        message: Some("Downloading whisper.cpp binary".to_string()),
-        started_at: Some(now_ts()),
-        finished_at: None,
-    };
-    guard.insert(key.clone(), status.clone());
-    drop(guard);
+    Ok(())
+}
 
-    let status_map = Arc::clone(&state.inner().statuses);
-    let app_handle = app.clone();
-    let status_for_thread = status.clone();
-    thread::spawn(move || {
-        let mut result_status = status_for_thread.clone();
-        let download_result = download_to_file(&url, &tmp_path, &mut result_status, &status_map);
-        if let Err(err) = download_result {
-            result_status.state = "error".to_string();
-            result_status.message = Some(err);
-            let _ = fs::remove_file(&tmp_path);
-            let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-            guard.insert(key.clone(), result_status);
-            let _ = app_handle.emit("job:log", JobLogEvent {
-                id: "whisper-download".to_string(),
-                line: "Whisper download failed.".to_string(),
-            });
-            return;
-        }
+#[tauri::command]
+pub fn get_app_update_status(state: State<AppUpdateState>) -> Result<AppUpdateStatus, String> {
+    state
+        .status
+        .lock()
+        .map(|status| status.clone())
+        .map_err(|_| "update status mutex poisoned".to_string())
+}
 
-        let is_zip = url.to_lowercase().ends_with(".zip");
-        if is_zip {
-            if let Err(err) = extract_whisper_zip(&tmp_path, &dest_path) {
-                result_status.state = "error".to_string();
-                result_status.message = Some(err);
-                let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-                guard.insert(key.clone(), result_status);
-                return;
-            }
-            let _ = fs::remove_file(&tmp_path);
-        } else if let Err(err) = fs::rename(&tmp_path, &dest_path) {
-            result_status.state = "error".to_string();
-            result_status.message = Some(format!("Finalize error: {err}"));
-            let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-            guard.insert(key.clone(), result_status);
-            return;
-        }
+#[tauri::command]
+pub async fn check_for_app_update(app: AppHandle) -> Result<AppUpdateStatus, String> {
+    ensure_updater_configured(&app)?;
+    let update = app
+        .updater()
+        .map_err(|err| format!("updater unavailable: {err}"))?
+        .check()
+        .await
+        .map_err(|err| format!("update check failed: {err}"))?;
+    let status = set_update_status(&app, |status| {
+        status.state = if update.is_some() { "available" } else { "idle" }.to_string();
+        status.version = update.as_ref().map(|update| update.version.clone());
+        status.notes = update.as_ref().and_then(|update| update.body.clone());
+        status.downloaded_bytes = 0;
+        status.total_bytes = None;
+        status.error = None;
+    })?;
+    let state = app.state::<AppUpdateState>();
+    *state.update.lock().map_err(|_| "update mutex poisoned".to_string())? = update;
+    *state.bytes.lock().map_err(|_| "update mutex poisoned".to_string())? = None;
+    Ok(status)
+}
 
-        if let Ok(mut perms) = fs::metadata(&dest_path).map(|meta| meta.permissions()) {
-            perms.set_mode(0o755);
-            let _ = fs::set_permissions(&dest_path, perms);
+#[tauri::command]
+pub async fn download_app_update(app: AppHandle) -> Result<AppUpdateStatus, String> {
+    let update = app
+        .state::<AppUpdateState>()
+        .update
+        .lock()
+        .map_err(|_| "update mutex poisoned".to_string())?
+        .clone()
+        .ok_or_else(|| "No update to download; check for updates first.".to_string())?;
+    set_update_status(&app, |status| {
+        status.state = "downloading".to_string();
+        status.downloaded_bytes = 0;
+        status.total_bytes = None;
+        status.error = None;
+    })?;
+    let progress_app = app.clone();
+    let (mut downloaded, mut reported) = (0u64, 0u64);
+    let result = update
+        .download(
+            move |chunk, total| {
+                downloaded += chunk as u64;
+                if downloaded - reported >= UPDATE_PROGRESS_STEP || total == Some(downloaded) {
+                    reported = downloaded;
+                    let _ = set_update_status(&progress_app, |status| {
+                        status.downloaded_bytes = downloaded;
+                        status.total_bytes = total;
+                    });
+                }
+            },
+            || {},
+        )
+        .await;
+    match result {
+        Ok(bytes) => {
+            *app.state::<AppUpdateState>()
+                .bytes
+                .lock()
+                .map_err(|_| "update mutex poisoned".to_string())? = Some(bytes);
+            set_update_status(&app, |status| status.state = "ready".to_string())
+        }
+        Err(err) => {
+            let message = format!("update download failed: {err}");
+            set_update_status(&app, |status| {
+                status.state = "error".to_string();
+                status.error = Some(message.clone());
+            })?;
+            Err(message)
         }
+    }
+}
 
-        result_status.state = "done".to_string();
-        result_status.finished_at = Some(now_ts());
-        result_status.message = Some("Download complete".to_string());
-        let mut guard = status_map.lock().unwrap_or_else(|e| e.into_inner());
-        guard.insert(key.clone(), result_status);
-    });
+// Jobs an install has to wait for: transcriptions running (or queued, unless the queue is
+// paused or power saving defers them) and summaries in progress.
+fn jobs_blocking_install(jobs: &[Job], queue_paused: bool, deferred: &HashSet<String>) -> usize {
+    jobs.iter()
+        .filter(|job| job.trashed_at.is_none())
+        .filter(|job| {
+            job.status == JobStatus::Running
+                || (job.status == JobStatus::Queued && !queue_paused && !deferred.contains(&job.id))
+                || matches!(job.summary_status.as_deref(), Some("queued" | "running"))
+        })
+        .count()
+}
 
-    Ok(status)
+fn jobs_blocking_update(app: &Core) -> Result<usize, String> {
+    let queue = app.state::<JobQueueState>();
+    let paused = queue.is_paused();
+    let deferred = queue
+        .deferred
+        .lock()
+        .map_err(|_| "job queue mutex poisoned".to_string())?
+        .clone();
+    let state = app.state::<JobIndexState>();
+    let guard = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?;
+    Ok(jobs_blocking_install(&guard.jobs, paused, &deferred))
 }
 
+// None on a first launch (or one from before the version file existed), which is not an update.
+fn updated_from(previous: Option<&str>, current: &str) -> Option<String> {
+    previous
+        .map(str::trim)
+        .filter(|previous| !previous.is_empty() && *previous != current)
+        .map(str::to_string)
+}
+
+// Installs the downloaded update and restarts once no job is mid-pipeline. With pause_queue the
+// queue is paused first, so only the jobs already running have to finish; the rest start again
+// after the restart (see check_after_update). Otherwise the whole queue drains first. Either way
+// it gives up waiting after UPDATE_INSTALL_TIMEOUT or when install_app_update_now is called.
 #[tauri::command]
-pub fn get_latest_whisper_release_url() -> Result<String, String> {
-    let bizenlabs_latest =
-        "https://github.com/bizenlabs/whisper-cpp-macos-bin/releases/latest";
-    let ggml_latest = "https://api.github.com/repos/ggml-org/whisper.cpp/releases/latest";
-    let ggml_backup = "https://api.github.com/repos/ggml-org/whisper.cpp/releases";
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .get(bizenlabs_latest)
-        .header(reqwest::header::USER_AGENT, "voicenote")
-        .send();
-
-    let assets = if let Ok(resp) = resp {
-        if resp.status().is_success() {
-            let json: serde_json::Value = resp
-                .json()
-                .map_err(|err| format!("Invalid GitHub response: {err}"))?;
-            json.get("assets")
-                .and_then(|v| v.as_array())
-                .ok_or_else(|| "No assets in release.".to_string())?
-                .to_vec()
-        } else {
-            Vec::new()
-        }
-    } else {
-        Vec::new()
-    };
+pub fn install_app_update(app: AppHandle, pause_queue: bool) -> Result<AppUpdateStatus, String> {
+    let ready = app
+        .state::<AppUpdateState>()
+        .bytes
+        .lock()
+        .map_err(|_| "update mutex poisoned".to_string())?
+        .is_some();
+    if !ready {
+        return Err("Download the update first.".to_string());
+    }
+    if pause_queue {
+        set_queue_paused(Core::from(&app), true)?;
+    }
+    let blocking = jobs_blocking_update(&Core::from(&app))?;
+    let status = set_update_status(&app, |status| {
+        status.state = "waiting_for_jobs".to_string();
+        status.blocking_jobs = blocking;
+    })?;
+    app.state::<AppUpdateState>().install_now.store(false, Ordering::SeqCst);
+    let handle = app.clone();
+    thread::spawn(move || install_when_idle(&handle));
+    Ok(status)
+}
 
-    let assets = if assets.is_empty() {
-        let resp = client
-            .get(ggml_latest)
-            .header(reqwest::header::USER_AGENT, "voicenote")
-            .send();
-        if let Ok(resp) = resp {
-            if resp.status().is_success() {
-                let json: serde_json::Value = resp
-                    .json()
-                    .map_err(|err| format!("Invalid GitHub response: {err}"))?;
-                json.get("assets")
-                    .and_then(|v| v.as_array())
-                    .ok_or_else(|| "No assets in release.".to_string())?
-                    .to_vec()
-            } else {
-                Vec::new()
-            }
-        } else {
-            Vec::new()
-        }
-    } else {
-        assets
-    };
+// The jobs cut short are requeued (and their summaries marked failed) on the next launch.
+#[tauri::command]
+pub fn install_app_update_now(state: State<AppUpdateState>) -> Result<(), String> {
+    state.install_now.store(true, Ordering::SeqCst);
+    Ok(())
+}
 
-    let assets = if assets.is_empty() {
-        let resp = client
-            .get(ggml_backup)
-            .header(reqwest::header::USER_AGENT, "voicenote")
-            .send()
-            .map_err(|err| format!("Failed to fetch releases: {err}"))?;
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().unwrap_or_default();
-            return Err(format!("GitHub API error: {status} {body}"));
-        }
-        let json: serde_json::Value = resp
-            .json()
-            .map_err(|err| format!("Invalid GitHub response: {err}"))?;
-        json.as_array()
-            .and_then(|arr| arr.first())
-            .and_then(|v| v.get("assets"))
-            .and_then(|v| v.as_array())
-            .ok_or_else(|| "No assets in release list.".to_string())?
-            .to_vec()
-    } else {
-        assets
+fn install_when_idle(app: &AppHandle) {
+    let started = std::time::Instant::now();
+    let install_now = app.state::<AppUpdateState>().install_now.clone();
+    loop {
+        let blocking = jobs_blocking_update(&Core::from(app)).unwrap_or(0);
+        if blocking == 0 {
+            break;
+        }
+        if install_now.load(Ordering::SeqCst) || started.elapsed() >= UPDATE_INSTALL_TIMEOUT {
+            tracing::warn!("installing the update with {blocking} jobs still in the pipeline");
+            break;
+        }
+        let _ = set_update_status(app, |status| status.blocking_jobs = blocking);
+        thread::sleep(UPDATE_INSTALL_POLL);
+    }
+    // Nothing may start between the check above and the restart.
+    let queue = app.state::<JobQueueState>();
+    let was_paused = queue.is_paused();
+    queue.paused.store(true, Ordering::SeqCst);
+    let _ = set_update_status(app, |status| {
+        status.state = "installing".to_string();
+        status.blocking_jobs = 0;
+    });
+    let state = app.state::<AppUpdateState>();
+    let update = state.update.lock().ok().and_then(|update| update.clone());
+    let bytes = state.bytes.lock().ok().and_then(|mut bytes| bytes.take());
+    let result = match (update, bytes) {
+        (Some(update), Some(bytes)) => update
+            .install(bytes)
+            .map_err(|err| format!("update install failed: {err}")),
+        _ => Err("The downloaded update is gone; download it again.".to_string()),
     };
-    for asset in assets {
-        let name = asset.get("name").and_then(|v| v.as_str()).unwrap_or("");
-        let url = asset
-            .get("browser_download_url")
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
-        let name_lc = name.to_lowercase();
-        let is_arm = name_lc.contains("arm64") || name_lc.contains("aarch64");
-        let is_macos = name_lc.contains("macos")
-            || name_lc.contains("osx")
-            || name_lc.contains("darwin")
-            || name_lc.contains("apple");
-        let is_zip = name_lc.ends_with(".zip");
-        if is_arm && is_zip && (is_macos || name_lc.contains("whisper")) {
-            return Ok(url.to_string());
+    match result {
+        Ok(()) => app.restart(),
+        Err(err) => {
+            queue.paused.store(was_paused, Ordering::SeqCst);
+            let _ = set_update_status(app, |status| {
+                status.state = "error".to_string();
+                status.error = Some(err);
+            });
         }
     }
-    Err("No macOS arm64 zip asset found in latest release.".to_string())
+}
+
+// First launch of a new version: the bundle was replaced, so check that the ffmpeg copy build.rs
+// put into resources/ and the whisper binary still resolve and run. The jobs left queued when
+// install_app_update restarted the app are started by resume_interrupted_jobs.
+pub fn check_after_update(app: &AppHandle) {
+    let core = Core::from(app);
+    let current = app.package_info().version.to_string();
+    let Ok(dir) = app_data_root(&core) else {
+        return;
+    };
+    let path = dir.join(APP_VERSION_FILE);
+    let previous = fs::read_to_string(&path).ok();
+    let _ = fs::write(&path, &current);
+    let Some(previous) = updated_from(previous.as_deref(), &current) else {
+        return;
+    };
+    let handle = app.clone();
+    thread::spawn(move || {
+        let model_size = handle
+            .state::<ConfigState>()
+            .config
+            .lock()
+            .map(|config| config.model_size.clone())
+            .unwrap_or_default();
+        let (ffmpeg, ffprobe) = check_ffmpeg(&core);
+        let checks = vec![ffmpeg, ffprobe, check_whisper_binary(&core, &model_size)];
+        for check in checks.iter().filter(|check| check.status == "error") {
+            tracing::error!("after update from {previous} to {current}: {}", check.message);
+        }
+        let _ = set_update_status(&handle, |status| status.verification = checks);
+    });
+}
+
+// Installs whatever run_diagnostics reports missing (whisper binary, FFmpeg, the configured
+// model) one after another with one progress stream. If a step fails, files installed by the
+// earlier steps are removed so onboarding can simply start over. Returns the planned steps.
+#[tauri::command]
+pub fn bootstrap_dependencies(
+    app: Core,
+    state: State<ModelDownloadState>,
+    config_state: State<ConfigState>,
+) -> Result<Vec<String>, String> {
+    bootstrap_dependencies_inner(&app, &state, &config_state)
+}
+
+#[tauri::command]
+pub fn get_whisper_version(app: Core, state: State<ModelDownloadState>) -> WhisperVersion {
+    installed_whisper_version(&app, &state)
+}
+
+#[tauri::command]
+pub fn check_for_whisper_update(
+    app: Core,
+    state: State<ModelDownloadState>,
+    config_state: State<ConfigState>,
+) -> Result<WhisperUpdateCheck, String> {
+    check_for_whisper_update_inner(&app, &state, &config_state)
+}
+
+// Downloads a newer whisper.cpp next to the current one and swaps it in only after it passes
+// verify_whisper_binary; until then the installed binary keeps working. Progress uses the
+// whisper download status key. `url` defaults to the latest release.
+#[tauri::command]
+pub fn upgrade_whisper_binary(
+    app: Core,
+    state: State<ModelDownloadState>,
+    config_state: State<ConfigState>,
+    url: Option<String>,
+) -> Result<ModelDownloadStatus, String> {
+    upgrade_whisper_binary_inner(&app, &state, &config_state, url)
+}
+
+#[tauri::command]
+pub fn get_latest_whisper_release_url(config_state: State<ConfigState>) -> Result<String, String> {
+    latest_whisper_release_url(&download_network(&config_state))
 }
 
 #[cfg(test)]
@@ -0,0 +1,663 @@
+// Matching a recording to the calendar event it was made in: ICS and CalDAV loading and the
+// ICS parsing behind it (time zones, recurrence rules).
+
+use super::*;
+
+pub(super) const CALENDAR_KEYCHAIN_SERVICE: &str = "voicenote.calendar";
+
+pub(super) const CALENDAR_KEYCHAIN_ACCOUNT: &str = "caldav";
+
+pub(super) fn attach_calendar_event(app: &Core, config: &AppConfig, job_id: &str) -> Result<Option<CalendarEvent>, String> {
+    let job = find_job(app.state::<JobIndexState>(), job_id)?;
+    let (start, end) = recording_interval(&job, &WallClock::Local);
+    // A day either side is plenty for the server-side range and keeps the REPORT small.
+    let (from, to) = (start.saturating_sub(86_400), end + 86_400);
+    let ics = load_calendar(app, config, from, to)?;
+    let events = parse_ics_events(&ics, &WallClock::Local, from, to);
+    let Some(event) = best_calendar_match(&events, start, end).cloned() else {
+        emit_job_log(app, job_id, "No calendar event overlaps the recording.");
+        return Ok(None);
+    };
+    update_job_and_emit(app, job_id, |job| {
+        // A name the user gave the recording wins over the meeting's.
+        let named = job.display_title.is_some() || job.source_metadata.as_ref().is_some_and(|meta| meta.title.is_some());
+        if !event.title.is_empty() && !named {
+            job.title = Some(event.title.clone());
+        }
+        job.calendar_event = Some(event.clone());
+        push_log(job, &format!("Matched calendar event \"{}\".", event.title));
+    })?;
+    Ok(Some(event))
+}
+
+// Recording span in unix seconds: recorded_at (else the import time) plus the duration.
+pub(super) fn recording_interval(job: &Job, clock: &WallClock) -> (u64, u64) {
+    let start = job
+        .source_metadata
+        .as_ref()
+        .and_then(|meta| meta.recorded_at.clone())
+        .or_else(|| job.media_info.as_ref().and_then(|info| info.recorded_at.clone()))
+        .and_then(|value| parse_iso_timestamp(&value, clock))
+        .unwrap_or_else(|| job.created_at.trim().parse::<u64>().unwrap_or_else(|_| now_ts()));
+    let duration = job
+        .media_info
+        .as_ref()
+        .and_then(|info| info.duration_seconds)
+        .unwrap_or(0.0)
+        .max(1.0);
+    (start, start + duration as u64)
+}
+
+// The event sharing the most time with the recording; events merely near it don't count.
+pub(super) fn best_calendar_match(events: &[(u64, u64, CalendarEvent)], start: u64, end: u64) -> Option<&CalendarEvent> {
+    events
+        .iter()
+        .map(|(event_start, event_end, event)| ((*event_end).min(end).saturating_sub((*event_start).max(start)), event))
+        .filter(|(overlap, _)| *overlap > 0)
+        .max_by_key(|(overlap, _)| *overlap)
+        .map(|(_, event)| event)
+}
+
+// Returns iCalendar text: a local file, an .ics/webcal feed, or a CalDAV calendar-query
+// REPORT for [start, end) with recurring events expanded by the server.
+pub(super) fn load_calendar(app: &Core, config: &AppConfig, start: u64, end: u64) -> Result<String, String> {
+    let source = config.calendar_source.trim();
+    if source.is_empty() {
+        return Err("No calendar source is configured.".to_string());
+    }
+    if !source.contains("://") {
+        return fs::read_to_string(source).map_err(|err| format!("failed to read calendar file: {err}"));
+    }
+    let url = match source.strip_prefix("webcal://") {
+        Some(rest) => format!("https://{rest}"),
+        None => source.to_string(),
+    };
+    let is_feed = source.starts_with("webcal://")
+        || url.split('?').next().unwrap_or_default().to_lowercase().ends_with(".ics");
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(20))
+        .build()
+        .map_err(|err| format!("Failed to build HTTP client: {err}"))?;
+    let mut request = if is_feed {
+        client.get(&url)
+    } else {
+        let method = reqwest::Method::from_bytes(b"REPORT").map_err(|err| err.to_string())?;
+        client
+            .request(method, &url)
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(caldav_calendar_query(start, end))
+    };
+    let username = config.calendar_username.trim();
+    if !username.is_empty() {
+        let password = load_secret(app, CALENDAR_KEYCHAIN_SERVICE, CALENDAR_KEYCHAIN_ACCOUNT, "calendar_password");
+        request = request.basic_auth(username, password);
+    }
+    let resp = request.send().map_err(|err| format!("Calendar request failed: {err}"))?;
+    if !resp.status().is_success() {
+        return Err(format!("Calendar server returned {}", resp.status()));
+    }
+    let body = resp.text().map_err(|err| format!("Failed to read calendar: {err}"))?;
+    // The multistatus XML carries each event as escaped text in <calendar-data>; once
+    // unescaped, the ICS parser skips the surrounding XML lines.
+    Ok(if is_feed { body } else { unescape_xml(&body) })
+}
+
+pub(super) fn caldav_calendar_query(start: u64, end: u64) -> String {
+    let stamp = |secs: u64| iso_from_unix(secs).replace(['-', ':'], "");
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop>
+    <c:calendar-data>
+      <c:expand start="{start}" end="{end}"/>
+    </c:calendar-data>
+  </d:prop>
+  <c:filter>
+    <c:comp-filter name="VCALENDAR">
+      <c:comp-filter name="VEVENT">
+        <c:time-range start="{start}" end="{end}"/>
+      </c:comp-filter>
+    </c:comp-filter>
+  </c:filter>
+</c:calendar-query>
+"#,
+        start = stamp(start),
+        end = stamp(end)
+    )
+}
+
+pub(super) fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#13;", "\r")
+        .replace("&#xD;", "\r")
+        .replace("&#10;", "\n")
+        .replace("&amp;", "&")
+}
+
+// Timed VEVENTs as (start, end, event); RRULEs are expanded in the event's own zone up to
+// `to`, keeping occurrences that end after `from`. All-day events are skipped. Times with a
+// TZID are read in that zone, "Z" times as UTC and floating ones on `floating`.
+pub(super) fn parse_ics_events(text: &str, floating: &WallClock, from: u64, to: u64) -> Vec<(u64, u64, CalendarEvent)> {
+    let lines = unfold_ics_lines(text);
+    let zones = parse_ics_timezones(&lines);
+    let clock_for = |params: &[(String, String)]| {
+        params
+            .iter()
+            .find(|(key, _)| key == "TZID")
+            .and_then(|(_, tzid)| WallClock::from_tzid(tzid, &zones))
+            .unwrap_or_else(|| floating.clone())
+    };
+    let mut parsed: Vec<IcsEvent> = Vec::new();
+    let mut current: Option<IcsEvent> = None;
+    // VALARMs inside an event carry their own SUMMARY and DURATION.
+    let mut nested = 0usize;
+    for line in &lines {
+        let (name, params, value) = ics_property(line);
+        match (name.as_str(), current.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => current = Some(IcsEvent::default()),
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => parsed.extend(current.take()),
+            (_, Some(_)) if nested > 0 => {}
+            ("UID", Some(event)) => event.uid = value.trim().to_string(),
+            ("SUMMARY", Some(event)) => event.info.title = unescape_ics_text(&value),
+            ("DTSTART", Some(event)) => event.start = IcsTime::parse(&value, clock_for(&params)),
+            ("DTEND", Some(event)) => event.end = IcsTime::parse(&value, clock_for(&params)),
+            ("DURATION", Some(event)) => event.duration = parse_ics_duration(&value),
+            ("RRULE", Some(event)) => event.rrule = Some(value.clone()),
+            ("EXDATE", Some(event)) => {
+                let clock = clock_for(&params);
+                let dates = value.split(',').filter_map(|date| IcsTime::parse(date, clock.clone())?.unix());
+                event.exdates.extend(dates);
+            }
+            ("RECURRENCE-ID", Some(event)) => {
+                event.recurrence_id = IcsTime::parse(&value, clock_for(&params)).and_then(|time| time.unix());
+            }
+            ("ATTENDEE" | "ORGANIZER", Some(event)) => {
+                let name = params
+                    .iter()
+                    .find(|(key, _)| key == "CN")
+                    .map(|(_, cn)| cn.clone())
+                    .unwrap_or_else(|| value.trim_start_matches("mailto:").trim_start_matches("MAILTO:").to_string());
+                if !name.is_empty() && !event.info.attendees.contains(&name) {
+                    event.info.attendees.push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // A moved or edited occurrence comes as its own VEVENT whose RECURRENCE-ID names the
+    // occurrence it replaces.
+    let moved: HashSet<(&str, i64)> = parsed
+        .iter()
+        .filter_map(|event| Some((event.uid.as_str(), event.recurrence_id?)))
+        .collect();
+    let (from, to) = (i64::try_from(from).unwrap_or(i64::MAX), i64::try_from(to).unwrap_or(i64::MAX));
+    let mut events = Vec::new();
+    for event in &parsed {
+        let Some(start) = event.start.as_ref().filter(|start| !start.date_only) else {
+            continue;
+        };
+        let Some(first) = start.unix() else {
+            continue;
+        };
+        let length = match (&event.end, event.duration) {
+            (Some(end), _) => end.unix().map_or(0, |end| end - first),
+            (None, Some(duration)) => duration,
+            (None, None) => 0,
+        };
+        if length <= 0 {
+            continue;
+        }
+        let starts = match event.rrule.as_deref().filter(|_| event.recurrence_id.is_none()) {
+            Some(rule) => expand_rrule(&RecurrenceRule::parse(rule, &start.clock), start, length, from, to),
+            None => vec![first],
+        };
+        for occurrence in starts {
+            if event.exdates.contains(&occurrence)
+                || (event.recurrence_id.is_none() && moved.contains(&(event.uid.as_str(), occurrence)))
+            {
+                continue;
+            }
+            let (Ok(start), Ok(end)) = (u64::try_from(occurrence), u64::try_from(occurrence + length)) else {
+                continue;
+            };
+            let mut info = event.info.clone();
+            info.start = iso_from_unix(start);
+            info.end = iso_from_unix(end);
+            events.push((start, end, info));
+        }
+    }
+    events
+}
+
+#[derive(Debug, Default)]
+pub(super) struct IcsEvent {
+    pub(super) uid: String,
+    pub(super) info: CalendarEvent,
+    pub(super) start: Option<IcsTime>,
+    pub(super) end: Option<IcsTime>,
+    pub(super) duration: Option<i64>,
+    pub(super) rrule: Option<String>,
+    pub(super) exdates: Vec<i64>,
+    pub(super) recurrence_id: Option<i64>,
+}
+
+// A DATE or DATE-TIME value as seconds of wall-clock time since 1970-01-01T00:00 on `clock`.
+#[derive(Debug, Clone)]
+pub(super) struct IcsTime {
+    pub(super) wall: i64,
+    pub(super) date_only: bool,
+    pub(super) clock: WallClock,
+}
+
+impl IcsTime {
+    // "20240501T100000Z" (UTC), "20240501T100000" (floating or TZID, read on `clock`) or the
+    // date-only "20240501" of all-day events.
+    pub(super) fn parse(value: &str, clock: WallClock) -> Option<Self> {
+        let value = value.trim();
+        let field = |range: std::ops::Range<usize>| -> Option<i64> { value.get(range)?.parse().ok() };
+        let wall = days_from_civil(field(0..4)?, field(4..6)?, field(6..8)?) * 86_400;
+        if value.len() == 8 {
+            return Some(IcsTime { wall, date_only: true, clock });
+        }
+        if value.get(8..9) != Some("T") {
+            return None;
+        }
+        let wall = wall + field(9..11)? * 3600 + field(11..13)? * 60 + field(13..15)?;
+        let clock = match value.get(15..)? {
+            "" => clock,
+            "Z" | "z" => WallClock::Fixed(0),
+            _ => return None,
+        };
+        Some(IcsTime { wall, date_only: false, clock })
+    }
+
+    pub(super) fn unix(&self) -> Option<i64> {
+        self.clock.to_unix(self.wall)
+    }
+}
+
+// "PT1H30M", "P1DT2H" or "P2W" -> seconds.
+pub(super) fn parse_ics_duration(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let (sign, rest) = match value.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let mut total = 0;
+    let mut number = String::new();
+    for c in rest.strip_prefix('P')?.chars() {
+        let unit = match c {
+            '0'..='9' => {
+                number.push(c);
+                continue;
+            }
+            'T' => continue,
+            'W' => 604_800,
+            'D' => 86_400,
+            'H' => 3600,
+            'M' => 60,
+            'S' => 1,
+            _ => return None,
+        };
+        total += number.parse::<i64>().ok()? * unit;
+        number.clear();
+    }
+    number.is_empty().then_some(sign * total)
+}
+
+// The parts of an RRULE the matcher expands: FREQ, INTERVAL, COUNT, UNTIL, BYDAY and
+// BYMONTH. Other parts (BYSETPOS, BYMONTHDAY, ...) are ignored.
+#[derive(Debug, Clone)]
+pub(super) struct RecurrenceRule {
+    pub(super) freq: String,
+    pub(super) interval: i64,
+    pub(super) count: Option<usize>,
+    pub(super) until: Option<i64>,
+    // (ordinal, weekday from Monday = 0); "-1FR" is the last Friday, a bare "MO" every Monday.
+    pub(super) by_day: Vec<(i64, i64)>,
+    pub(super) by_month: Vec<i64>,
+}
+
+impl RecurrenceRule {
+    pub(super) fn parse(value: &str, clock: &WallClock) -> Self {
+        let mut rule = RecurrenceRule {
+            freq: String::new(),
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month: Vec::new(),
+        };
+        for (key, value) in value.split(';').filter_map(|part| part.split_once('=')) {
+            match key.trim().to_uppercase().as_str() {
+                "FREQ" => rule.freq = value.trim().to_uppercase(),
+                "INTERVAL" => rule.interval = value.trim().parse().unwrap_or(1).max(1),
+                "COUNT" => rule.count = value.trim().parse().ok(),
+                // A date-only UNTIL includes that whole day.
+                "UNTIL" => {
+                    rule.until = IcsTime::parse(value, clock.clone()).and_then(|until| match until.date_only {
+                        true => until.clock.to_unix(until.wall + 86_399),
+                        false => until.unix(),
+                    })
+                }
+                "BYDAY" => rule.by_day = value.split(',').filter_map(parse_ics_weekday).collect(),
+                "BYMONTH" => rule.by_month = value.split(',').filter_map(|month| month.trim().parse().ok()).collect(),
+                _ => {}
+            }
+        }
+        rule
+    }
+}
+
+// "2MO", "-1SU" or "TU" -> (ordinal, weekday from Monday = 0).
+pub(super) fn parse_ics_weekday(value: &str) -> Option<(i64, i64)> {
+    let value = value.trim().to_uppercase();
+    let (ordinal, code) = value.split_at_checked(value.len().checked_sub(2)?)?;
+    let weekday = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"].iter().position(|day| *day == code)?;
+    let ordinal = match ordinal {
+        "" => 0,
+        ordinal => ordinal.parse().ok()?,
+    };
+    Some((ordinal, weekday as i64))
+}
+
+// Upper bound on the periods walked, so a daily rule from years ago still ends.
+pub(super) const MAX_RECURRENCE_PERIODS: i64 = 100_000;
+
+// Occurrence starts (unix seconds) of `rule` from `start`, before `to` and ending after `from`.
+pub(super) fn expand_rrule(rule: &RecurrenceRule, start: &IcsTime, length: i64, from: i64, to: i64) -> Vec<i64> {
+    let (first_day, time_of_day) = (start.wall.div_euclid(86_400), start.wall.rem_euclid(86_400));
+    let (year, month, day) = civil_date_from_unix(start.wall.max(0) as u64);
+    let first_month = year * 12 + i64::from(month) - 1;
+    let mut starts = Vec::new();
+    let mut seen = 0;
+    for period in 0..MAX_RECURRENCE_PERIODS {
+        let step = period * rule.interval;
+        let mut days = match rule.freq.as_str() {
+            "DAILY" => vec![first_day + step],
+            "WEEKLY" if rule.by_day.is_empty() => vec![first_day + 7 * step],
+            "WEEKLY" => {
+                let monday = first_day - weekday_from_days(first_day) + 7 * step;
+                rule.by_day.iter().map(|(_, weekday)| monday + weekday).collect()
+            }
+            "MONTHLY" => month_days(&rule.by_day, first_month + step, day),
+            "YEARLY" => {
+                let months = if rule.by_month.is_empty() { vec![i64::from(month)] } else { rule.by_month.clone() };
+                months
+                    .iter()
+                    .flat_map(|month| month_days(&rule.by_day, (year + step) * 12 + month - 1, day))
+                    .collect()
+            }
+            _ => return start.unix().into_iter().filter(|start| start + length > from).collect(),
+        };
+        days.sort_unstable();
+        days.dedup();
+        for day in days.into_iter().filter(|day| *day >= first_day) {
+            let Some(unix) = start.clock.to_unix(day * 86_400 + time_of_day) else {
+                continue;
+            };
+            if unix >= to || rule.until.is_some_and(|until| unix > until) || rule.count.is_some_and(|count| seen >= count) {
+                return starts;
+            }
+            seen += 1;
+            if unix + length > from {
+                starts.push(unix);
+            }
+        }
+    }
+    starts
+}
+
+// Days (since 1970-01-01) of the month `month_index` (year * 12 + month - 1) matched by
+// `by_day`, or the month's `day` when there is no BYDAY and the month has one.
+pub(super) fn month_days(by_day: &[(i64, i64)], month_index: i64, day: u32) -> Vec<i64> {
+    let (year, month) = (month_index.div_euclid(12), month_index.rem_euclid(12) + 1);
+    let first = days_from_civil(year, month, 1);
+    let length = days_from_civil(year + month / 12, month % 12 + 1, 1) - first;
+    if by_day.is_empty() {
+        return (i64::from(day) <= length).then(|| first + i64::from(day) - 1).into_iter().collect();
+    }
+    by_day
+        .iter()
+        .flat_map(|&(ordinal, weekday)| {
+            let hits: Vec<i64> = (first..first + length).filter(|day| weekday_from_days(*day) == weekday).collect();
+            match ordinal {
+                0 => hits,
+                1.. => hits.get(ordinal as usize - 1).copied().into_iter().collect(),
+                _ => hits
+                    .len()
+                    .checked_sub(ordinal.unsigned_abs() as usize)
+                    .and_then(|index| hits.get(index))
+                    .copied()
+                    .into_iter()
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+// Monday = 0; 1970-01-01 was a Thursday.
+pub(super) fn weekday_from_days(days: i64) -> i64 {
+    (days + 3).rem_euclid(7)
+}
+
+// One STANDARD or DAYLIGHT part of a VTIMEZONE: from the wall-clock `onset` on, and again
+// each year its RRULE comes round, clocks read UTC + `offset`.
+#[derive(Debug, Clone)]
+pub(super) struct ZoneObservance {
+    pub(super) onset: i64,
+    pub(super) offset: i64,
+    pub(super) rule: Option<RecurrenceRule>,
+}
+
+impl ZoneObservance {
+    pub(super) fn onset_in(&self, year: i64) -> Option<i64> {
+        let Some(rule) = &self.rule else {
+            return Some(self.onset);
+        };
+        let (_, month, day) = civil_date_from_unix(self.onset.max(0) as u64);
+        let month = rule.by_month.first().copied().unwrap_or(i64::from(month));
+        let onset = *month_days(&rule.by_day, year * 12 + month - 1, day).first()? * 86_400 + self.onset.rem_euclid(86_400);
+        (onset >= self.onset).then_some(onset)
+    }
+}
+
+// VTIMEZONE definitions by TZID.
+pub(super) fn parse_ics_timezones(lines: &[String]) -> HashMap<String, Vec<ZoneObservance>> {
+    let mut zones = HashMap::new();
+    let mut current: Option<(String, Vec<ZoneObservance>)> = None;
+    let mut observance: Option<ZoneObservance> = None;
+    for line in lines {
+        let (name, _, value) = ics_property(line);
+        let value = value.trim();
+        match (name.as_str(), current.as_mut(), observance.as_mut()) {
+            ("BEGIN", None, _) if value.eq_ignore_ascii_case("VTIMEZONE") => current = Some((String::new(), Vec::new())),
+            ("BEGIN", Some(_), None) => observance = Some(ZoneObservance { onset: 0, offset: 0, rule: None }),
+            ("END", Some((_, observances)), Some(_)) => observances.extend(observance.take()),
+            ("END", Some(_), None) => zones.extend(current.take()),
+            ("TZID", Some((tzid, _)), None) => *tzid = value.to_string(),
+            ("DTSTART", Some(_), Some(part)) => {
+                part.onset = IcsTime::parse(value, WallClock::Fixed(0)).map_or(0, |time| time.wall);
+            }
+            ("TZOFFSETTO", Some(_), Some(part)) => part.offset = parse_utc_offset(value).unwrap_or(0),
+            ("RRULE", Some(_), Some(part)) => part.rule = Some(RecurrenceRule::parse(value, &WallClock::Fixed(0))),
+            _ => {}
+        }
+    }
+    zones
+}
+
+// Offset of the latest observance that began by wall-clock time `wall`.
+pub(super) fn observed_offset(observances: &[ZoneObservance], wall: i64) -> i64 {
+    let (year, _, _) = civil_date_from_unix(wall.max(0) as u64);
+    observances
+        .iter()
+        .flat_map(|part| [year - 1, year].map(|year| (part.onset_in(year), part.offset)))
+        .filter_map(|(onset, offset)| Some((onset.filter(|onset| *onset <= wall)?, offset)))
+        .max_by_key(|(onset, _)| *onset)
+        .or_else(|| observances.iter().map(|part| (part.onset, part.offset)).min_by_key(|(onset, _)| *onset))
+        .map_or(0, |(_, offset)| offset)
+}
+
+// How a time without a UTC offset is read: on the system zone, an IANA zone, a file's
+// VTIMEZONE or a fixed offset. All but the last follow daylight saving time.
+#[derive(Debug, Clone)]
+pub(super) enum WallClock {
+    Local,
+    Zone(chrono_tz::Tz),
+    Observances(Vec<ZoneObservance>),
+    Fixed(i64),
+}
+
+impl WallClock {
+    // TZIDs are mostly IANA names, sometimes behind a prefix ("/mozilla.org/20050126_1/Europe/Berlin").
+    // Others, like Outlook's "W. Europe Standard Time", come from the file's VTIMEZONEs.
+    pub(super) fn from_tzid(tzid: &str, zones: &HashMap<String, Vec<ZoneObservance>>) -> Option<Self> {
+        let tzid = tzid.trim();
+        std::iter::once(tzid)
+            .chain(tzid.match_indices('/').map(|(pos, _)| &tzid[pos + 1..]))
+            .find_map(|name| name.parse::<chrono_tz::Tz>().ok())
+            .map(WallClock::Zone)
+            .or_else(|| zones.get(tzid).map(|observances| WallClock::Observances(observances.clone())))
+    }
+
+    // Unix seconds of `wall`, in seconds since 1970-01-01T00:00 on this clock. A time that
+    // falls in a spring-forward gap is read an hour later.
+    pub(super) fn to_unix(&self, wall: i64) -> Option<i64> {
+        fn resolve<Z: TimeZone>(zone: &Z, wall: chrono::NaiveDateTime) -> Option<i64> {
+            zone.from_local_datetime(&wall)
+                .earliest()
+                .or_else(|| zone.from_local_datetime(&(wall + chrono::TimeDelta::hours(1))).earliest())
+                .map(|time| time.timestamp())
+        }
+        let naive = chrono::DateTime::from_timestamp(wall, 0)?.naive_utc();
+        match self {
+            WallClock::Local => resolve(&chrono::Local, naive),
+            WallClock::Zone(zone) => resolve(zone, naive),
+            WallClock::Observances(observances) => Some(wall - observed_offset(observances, wall)),
+            WallClock::Fixed(offset) => Some(wall - offset),
+        }
+    }
+
+    // Seconds east of UTC at `unix`.
+    pub(super) fn offset_at(&self, unix: i64) -> i64 {
+        fn offset<Z: TimeZone>(zone: &Z, unix: i64) -> i64 {
+            zone.timestamp_opt(unix, 0)
+                .single()
+                .map_or(0, |time| i64::from(time.offset().fix().local_minus_utc()))
+        }
+        match self {
+            WallClock::Local => offset(&chrono::Local, unix),
+            WallClock::Zone(zone) => offset(zone, unix),
+            WallClock::Observances(observances) => observed_offset(observances, unix + observed_offset(observances, unix)),
+            WallClock::Fixed(offset) => *offset,
+        }
+    }
+}
+
+// RFC 5545 folds long lines by starting the continuation with a space or tab.
+pub(super) fn unfold_ics_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.lines() {
+        let raw = raw.trim_end_matches('\r');
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+// "ATTENDEE;CN=\"Doe, Jane\";ROLE=CHAIR:mailto:jane@example.com" -> name, params, value.
+pub(super) fn ics_property(line: &str) -> (String, Vec<(String, String)>, String) {
+    let mut in_quotes = false;
+    let split = line
+        .char_indices()
+        .find(|(_, c)| {
+            if *c == '"' {
+                in_quotes = !in_quotes;
+            }
+            *c == ':' && !in_quotes
+        })
+        .map(|(pos, _)| pos)
+        .unwrap_or(line.len());
+    let (head, value) = (&line[..split], line.get(split + 1..).unwrap_or_default());
+    let mut parts = head.split(';');
+    let name = parts.next().unwrap_or_default().trim().to_uppercase();
+    let params = parts
+        .filter_map(|part| part.split_once('='))
+        .map(|(key, value)| (key.trim().to_uppercase(), value.trim().trim_matches('"').to_string()))
+        .collect();
+    (name, params, value.to_string())
+}
+
+pub(super) fn unescape_ics_text(value: &str) -> String {
+    value
+        .replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+        .trim()
+        .to_string()
+}
+
+// Parses "YYYY-MM-DD[T| ]HH:MM[:SS[.fff]][Z|±HH[:]MM]"; a time without a zone is read on `clock`.
+pub(super) fn parse_iso_timestamp(value: &str, clock: &WallClock) -> Option<u64> {
+    let value = value.trim();
+    if value.get(4..5) != Some("-") || value.get(7..8) != Some("-") {
+        return None;
+    }
+    let field = |range: std::ops::Range<usize>| -> Option<i64> { value.get(range)?.parse().ok() };
+    let (year, month, day) = (field(0..4)?, field(5..7)?, field(8..10)?);
+    let rest = value.get(10..)?;
+    let rest = rest.strip_prefix(['T', ' ']).unwrap_or(rest);
+    let time_len = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == ':' || c == '.'))
+        .unwrap_or(rest.len());
+    let (time, zone) = rest.split_at(time_len);
+    let mut fields = time.split(':').filter(|field| !field.is_empty());
+    let hours: i64 = fields.next().map_or(Some(0), |field| field.parse().ok())?;
+    let minutes: i64 = fields.next().map_or(Some(0), |field| field.parse().ok())?;
+    let seconds = fields.next().map_or(Some(0.0), |field| field.parse::<f64>().ok())? as i64;
+    let wall = days_from_civil(year, month, day) * 86_400 + hours * 3600 + minutes * 60 + seconds;
+    let secs = match zone.trim() {
+        "" => clock.to_unix(wall)?,
+        "Z" | "z" => wall,
+        zone => wall - parse_utc_offset(zone)?,
+    };
+    u64::try_from(secs).ok()
+}
+
+// "+0200", "+02:00" or "-05" -> seconds east of UTC.
+pub(super) fn parse_utc_offset(value: &str) -> Option<i64> {
+    let (sign, digits) = match value.trim().split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    let digits = digits.replace(':', "");
+    let hours: i64 = digits.get(..2)?.parse().ok()?;
+    let minutes: i64 = match digits.get(2..).filter(|rest| !rest.is_empty()) {
+        Some(rest) => rest.parse().ok()?,
+        None => 0,
+    };
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+// Inverse of civil_date_from_unix, in days since 1970-01-01.
+pub(super) fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
@@ -0,0 +1,433 @@
+// `voicenote transcribe` and `voicenote mcp`: argument parsing, the headless transcription run
+// and the MCP server that answers from finished notes.
+
+use super::*;
+
+pub const CLI_USAGE: &str = "usage: voicenote transcribe <file>... [--model <size>] [--language <code>] [--export] [--target <export target id>] [--full-power] [--stall-timeout <minutes>]\n       voicenote mcp";
+
+pub(super) const CLI_POLL: std::time::Duration = std::time::Duration::from_millis(500);
+
+// How long `transcribe` waits on a job whose status, progress, log and summary all stay put.
+pub(super) const CLI_STALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+
+// `voicenote transcribe …`: the files go through the same queue and pipeline as in the window,
+// with the flags applied the way a watch folder preset is.
+#[derive(Debug, Clone, Default)]
+pub struct CliTranscribe {
+    pub files: Vec<PathBuf>,
+    pub options: JobOptions,
+    // Write the Obsidian note once the job (and its summary) is done.
+    pub export: bool,
+    // Give up on a job that makes no progress for this long; None waits forever.
+    pub stall_timeout: Option<std::time::Duration>,
+}
+
+#[derive(Debug, Clone)]
+pub enum CliCommand {
+    Transcribe(CliTranscribe),
+    // MCP server on stdin/stdout; see run_mcp_server.
+    Mcp,
+}
+
+// Ok(None) when the arguments are not a CLI command, so the window opens as usual.
+pub fn parse_cli_args(args: &[String]) -> Result<Option<CliCommand>, String> {
+    let Some((command, rest)) = args.split_first() else {
+        return Ok(None);
+    };
+    match command.as_str() {
+        "transcribe" => parse_cli_transcribe(rest).map(|cli| Some(CliCommand::Transcribe(cli))),
+        "mcp" if rest.is_empty() => Ok(Some(CliCommand::Mcp)),
+        "mcp" => Err(CLI_USAGE.to_string()),
+        _ => Ok(None),
+    }
+}
+
+pub(super) fn parse_cli_transcribe(rest: &[String]) -> Result<CliTranscribe, String> {
+    let mut cli = CliTranscribe {
+        stall_timeout: Some(CLI_STALL_TIMEOUT),
+        ..CliTranscribe::default()
+    };
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        let mut value = || {
+            rest.next()
+                .filter(|value| !value.starts_with("--"))
+                .cloned()
+                .ok_or_else(|| format!("{arg} needs a value\n{CLI_USAGE}"))
+        };
+        match arg.as_str() {
+            "--model" => cli.options.model_size = Some(value()?),
+            "--language" => cli.options.language = Some(value()?),
+            "--target" => cli.options.export_target = Some(value()?),
+            "--export" => cli.export = true,
+            "--full-power" => cli.options.ignore_power_state = true,
+            "--stall-timeout" => {
+                let minutes: u64 = value()?
+                    .parse()
+                    .map_err(|_| format!("--stall-timeout takes whole minutes\n{CLI_USAGE}"))?;
+                cli.stall_timeout = (minutes > 0).then(|| std::time::Duration::from_secs(minutes * 60));
+            }
+            "-h" | "--help" => return Err(CLI_USAGE.to_string()),
+            flag if flag.starts_with('-') => return Err(format!("unknown option {flag}\n{CLI_USAGE}")),
+            file => cli.files.push(PathBuf::from(file)),
+        }
+    }
+    if cli.files.is_empty() {
+        return Err(CLI_USAGE.to_string());
+    }
+    Ok(cli)
+}
+
+// The state the window's setup manages, as far as the command needs it.
+pub(super) fn start_cli_core(app: &Core, command: &CliCommand) -> Result<(), String> {
+    app.manage(ConfigState::load(app)?);
+    app.manage(JobIndexState::load(app)?);
+    if let CliCommand::Transcribe(_) = command {
+        lock_job_store(app)?;
+        app.manage(ModelDownloadState::load(app)?);
+        app.manage(spawn_summary_workers(app));
+        app.manage(spawn_worker(app));
+        app.manage(RecordingState::new());
+        app.manage(ResummarizeState::new());
+        app.manage(SummaryCancelState::new());
+    }
+    Ok(())
+}
+
+// Returns the number of files that failed.
+pub(super) fn run_cli_transcribe(app: &Core, cli: &CliTranscribe) -> Result<usize, String> {
+    let state = app.state::<JobIndexState>();
+    let queue = app.state::<JobQueueState>();
+    let mut failed = 0;
+    let mut jobs = Vec::new();
+    for path in &cli.files {
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+        let created = create_job_from_path_inner(app, state, path.to_string_lossy().to_string(), cli.options.clone())
+            .and_then(|job| queue.enqueue(job.id.clone()).map(|_| job.id));
+        match created {
+            Ok(job_id) => jobs.push((path, job_id)),
+            Err(err) => {
+                eprintln!("{}: {err}", path.display());
+                failed += 1;
+            }
+        }
+    }
+    for (path, job_id) in jobs {
+        let job = wait_for_cli_job(state, &job_id, cli.stall_timeout)
+            .map_err(|err| format!("{}: {err}", path.display()))?;
+        if job.status != JobStatus::Done {
+            let reason = job.logs.last().cloned().unwrap_or_else(|| job.status.to_string());
+            eprintln!("{}: {reason}", path.display());
+            failed += 1;
+            continue;
+        }
+        println!("{}: {}", path.display(), job.transcript_txt_path);
+        if cli.export {
+            let config = app
+                .state::<ConfigState>()
+                .config
+                .lock()
+                .map_err(|_| "config mutex poisoned".to_string())?
+                .clone();
+            match export_obsidian_note(app, &config, &job_id) {
+                Ok(note) => println!("{}: exported to {}", path.display(), note.display()),
+                Err(err) => {
+                    eprintln!("{}: {err}", path.display());
+                    failed += 1;
+                }
+            }
+        }
+    }
+    Ok(failed)
+}
+
+// Waits until the job has finished, including an automatic summary that is still queued or running.
+// Fails once nothing about the job has changed for `stall_timeout`, so a stuck whisper or LLM
+// doesn't hang a script; the job is left for the window to recover on its next launch.
+pub(super) fn wait_for_cli_job(
+    state: &JobIndexState,
+    job_id: &str,
+    stall_timeout: Option<std::time::Duration>,
+) -> Result<Job, String> {
+    let mut last_seen = None;
+    let mut last_change = std::time::Instant::now();
+    loop {
+        let job = find_job(state, job_id)?;
+        let transcribing = job.status.is_active();
+        let summarizing = matches!(job.summary_status.as_deref(), Some("queued" | "running"));
+        if !transcribing && !summarizing {
+            return Ok(job);
+        }
+        let seen = (
+            job.status,
+            job.stage.clone(),
+            job.progress.to_bits(),
+            job.logs.len(),
+            job.logs.last().cloned(),
+            job.summary_status.clone(),
+            job.summary_md.as_ref().map(String::len),
+        );
+        if last_seen.as_ref() != Some(&seen) {
+            last_seen = Some(seen);
+            last_change = std::time::Instant::now();
+        } else if let Some(timeout) = stall_timeout.filter(|timeout| last_change.elapsed() >= *timeout) {
+            return Err(format!(
+                "no progress for {} minutes while {}; giving up",
+                timeout.as_secs().div_ceil(60),
+                if transcribing { job.stage.as_str() } else { "summarizing" }
+            ));
+        }
+        thread::sleep(CLI_POLL);
+    }
+}
+
+// MCP revision implemented below; a client that asks for another one gets this one back and
+// decides whether to go on.
+pub(super) const MCP_PROTOCOL_VERSION: &str = "2024-11-05";
+
+pub(super) const MCP_DEFAULT_LIMIT: usize = 10;
+
+pub(super) const MCP_SNIPPETS_PER_NOTE: usize = 5;
+
+pub(super) const MCP_SNIPPET_CHARS: usize = 240;
+
+pub(super) const MCP_RESOURCE_PREFIX: &str = "voicenote://job/";
+
+// Model Context Protocol server on stdin/stdout, one JSON-RPC message per line. Claude Desktop
+// and other MCP clients start `voicenote mcp` and get read-only tools and resources over the
+// finished notes. Jobs are read from the store for every request, so notes the window finishes
+// in the meantime show up without a restart.
+pub(super) fn run_mcp_server(app: &Core) -> Result<(), String> {
+    let state = app.state::<JobIndexState>();
+    let load_jobs = || state.store.load().map(|index| index.jobs);
+    let mut stdout = std::io::stdout();
+    for line in std::io::stdin().lock().lines() {
+        let line = line.map_err(|err| format!("failed to read stdin: {err}"))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<serde_json::Value>(&line) {
+            Ok(request) => mcp_response(&request, load_jobs),
+            Err(err) => Some(mcp_error(serde_json::Value::Null, -32700, &format!("Parse error: {err}"))),
+        };
+        if let Some(response) = response {
+            writeln!(stdout, "{response}")
+                .and_then(|_| stdout.flush())
+                .map_err(|err| format!("failed to write stdout: {err}"))?;
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn mcp_error(id: serde_json::Value, code: i64, message: &str) -> serde_json::Value {
+    serde_json::json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+// None for notifications, which get no reply.
+pub(super) fn mcp_response(
+    request: &serde_json::Value,
+    load_jobs: impl Fn() -> Result<Vec<Job>, String>,
+) -> Option<serde_json::Value> {
+    let id = request.get("id")?.clone();
+    let method = request.get("method").and_then(|method| method.as_str()).unwrap_or("");
+    let params = request.get("params").cloned().unwrap_or_default();
+    let result = match method {
+        "initialize" => Ok(serde_json::json!({
+            "protocolVersion": MCP_PROTOCOL_VERSION,
+            "capabilities": { "tools": {}, "resources": {} },
+            "serverInfo": { "name": "voicenote", "version": env!("CARGO_PKG_VERSION") },
+        })),
+        "ping" => Ok(serde_json::json!({})),
+        "tools/list" => Ok(serde_json::json!({ "tools": mcp_tools() })),
+        "tools/call" => {
+            let name = params.get("name").and_then(|name| name.as_str()).unwrap_or("");
+            let arguments = params.get("arguments").cloned().unwrap_or_default();
+            // A failed tool is a result the model reads, not a protocol error.
+            let (text, is_error) = match load_jobs().and_then(|jobs| mcp_call_tool(&jobs, name, &arguments)) {
+                Ok(text) => (text, false),
+                Err(err) => (err, true),
+            };
+            Ok(serde_json::json!({ "content": [{ "type": "text", "text": text }], "isError": is_error }))
+        }
+        "resources/list" => load_jobs().map(|jobs| {
+            let resources: Vec<serde_json::Value> = mcp_notes(&jobs)
+                .into_iter()
+                .map(|job| {
+                    serde_json::json!({
+                        "uri": format!("{MCP_RESOURCE_PREFIX}{}", job.id),
+                        "name": job_title(job),
+                        "mimeType": "text/markdown",
+                    })
+                })
+                .collect();
+            serde_json::json!({ "resources": resources })
+        }),
+        "resources/read" => {
+            let uri = params.get("uri").and_then(|uri| uri.as_str()).unwrap_or("");
+            uri.strip_prefix(MCP_RESOURCE_PREFIX)
+                .ok_or_else(|| format!("Unknown resource: {uri}"))
+                .and_then(|id| {
+                    let jobs = load_jobs()?;
+                    let job = mcp_note(&jobs, id)?;
+                    Ok(serde_json::json!({
+                        "contents": [{ "uri": uri, "mimeType": "text/markdown", "text": mcp_note_markdown(job) }],
+                    }))
+                })
+        }
+        other => return Some(mcp_error(id, -32601, &format!("Method not found: {other}"))),
+    };
+    Some(match result {
+        Ok(result) => serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(err) => mcp_error(id, -32603, &err),
+    })
+}
+
+pub(super) fn mcp_tools() -> serde_json::Value {
+    serde_json::json!([
+        {
+            "name": "list_notes",
+            "description": "List transcribed voice notes, newest first, optionally filtered by text in the title, file name or notes, or by tag.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "tag": { "type": "string" },
+                    "limit": { "type": "integer", "minimum": 1 },
+                },
+            },
+        },
+        {
+            "name": "search_transcripts",
+            "description": "Find voice notes whose transcript contains the text (case-insensitive) and return the matching lines.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "limit": { "type": "integer", "minimum": 1 },
+                },
+                "required": ["query"],
+            },
+        },
+        {
+            "name": "get_transcript",
+            "description": "Full transcript of a voice note by id.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "id": { "type": "string" } },
+                "required": ["id"],
+            },
+        },
+        {
+            "name": "get_summary",
+            "description": "Summary of a voice note by id.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "id": { "type": "string" } },
+                "required": ["id"],
+            },
+        },
+    ])
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct McpSearchHit {
+    pub(super) id: String,
+    pub(super) title: String,
+    pub(super) created_at: String,
+    pub(super) lines: Vec<String>,
+}
+
+pub(super) fn mcp_call_tool(jobs: &[Job], name: &str, arguments: &serde_json::Value) -> Result<String, String> {
+    let text = |key: &str| arguments.get(key).and_then(|value| value.as_str()).unwrap_or("").trim().to_string();
+    let limit = arguments
+        .get("limit")
+        .and_then(|value| value.as_u64())
+        .map(|limit| limit.max(1) as usize)
+        .unwrap_or(MCP_DEFAULT_LIMIT);
+    match name {
+        "list_notes" => {
+            let query = JobQuery {
+                filter: JobFilter {
+                    statuses: vec!["done".to_string()],
+                    tags: Some(text("tag")).filter(|tag| !tag.is_empty()).into_iter().collect(),
+                    search: text("query"),
+                    ..JobFilter::default()
+                },
+                limit,
+                ..JobQuery::default()
+            };
+            serde_json::to_string_pretty(&query.run(jobs)?.jobs).map_err(|err| err.to_string())
+        }
+        "search_transcripts" => {
+            let needle = text("query").to_lowercase();
+            if needle.is_empty() {
+                return Err("query is required.".to_string());
+            }
+            let mut hits: Vec<McpSearchHit> = mcp_notes(jobs)
+                .into_iter()
+                .filter_map(|job| {
+                    let transcript = read_transcript_text(&job.transcript_txt_path).ok()?;
+                    let lines: Vec<String> = transcript
+                        .lines()
+                        .filter(|line| line.to_lowercase().contains(&needle))
+                        .map(|line| line.trim().chars().take(MCP_SNIPPET_CHARS).collect())
+                        .collect();
+                    (!lines.is_empty()).then(|| McpSearchHit {
+                        id: job.id.clone(),
+                        title: job_title(job),
+                        created_at: job.created_at.clone(),
+                        lines,
+                    })
+                })
+                .collect();
+            // Most matching lines first; the sort is stable, so ties stay newest first.
+            hits.sort_by_key(|hit| std::cmp::Reverse(hit.lines.len()));
+            hits.truncate(limit);
+            for hit in &mut hits {
+                hit.lines.truncate(MCP_SNIPPETS_PER_NOTE);
+            }
+            serde_json::to_string_pretty(&hits).map_err(|err| err.to_string())
+        }
+        "get_transcript" => {
+            let job = mcp_note(jobs, &text("id"))?;
+            read_transcript_text(&job.transcript_txt_path)
+        }
+        "get_summary" => {
+            let job = mcp_note(jobs, &text("id"))?;
+            let summary = stored_summary(job);
+            if summary.trim().is_empty() {
+                return Err(format!("No summary for {} yet.", job.id));
+            }
+            Ok(summary)
+        }
+        other => Err(format!("Unknown tool: {other}")),
+    }
+}
+
+// Finished notes outside the trash, newest first.
+pub(super) fn mcp_notes(jobs: &[Job]) -> Vec<&Job> {
+    let mut notes: Vec<&Job> = jobs
+        .iter()
+        .filter(|job| job.status == JobStatus::Done && job.trashed_at.is_none())
+        .collect();
+    notes.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    notes
+}
+
+pub(super) fn mcp_note<'a>(jobs: &'a [Job], id: &str) -> Result<&'a Job, String> {
+    mcp_notes(jobs)
+        .into_iter()
+        .find(|job| job.id == id)
+        .ok_or_else(|| format!("No transcribed note with id {id:?}."))
+}
+
+pub(super) fn mcp_note_markdown(job: &Job) -> String {
+    let mut markdown = format!("# {}\n", job_title(job));
+    let summary = stored_summary(job);
+    if !summary.trim().is_empty() {
+        markdown.push_str(&format!("\n## Summary\n\n{}\n", summary.trim()));
+    }
+    let transcript = read_transcript_text(&job.transcript_txt_path).unwrap_or_default();
+    markdown.push_str(&format!("\n## Transcript\n\n{}\n", transcript.trim()));
+    markdown
+}
@@ -0,0 +1,1576 @@
+// Writing jobs out: Obsidian notes and their frontmatter, daily notes, export routes, subtitles,
+// export targets such as Joplin and the bilingual export. share.rs renders the other formats.
+
+use super::*;
+
+// Payload of the "export:batch" event emitted while export_all runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportBatchProgress {
+    pub total: usize,
+    pub processed: usize,
+    pub current_id: Option<String>,
+    pub finished: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportFailure {
+    pub id: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportAllSummary {
+    // Ids of jobs that got a note.
+    pub created: Vec<String>,
+    // Ids already exported into the current vault.
+    pub skipped: Vec<String>,
+    pub failed: Vec<ExportFailure>,
+}
+
+// YYYY, MM and DD are only placeholders as whole words ("Journal/YYYY/MM", "YYYY-MM-DD",
+// "YYYYMMDD"), so folder names that happen to contain the letters ("COMMS") are kept.
+pub(super) fn expand_subfolder_template(template: &str, created_at: &str) -> String {
+    let secs = created_at.trim().parse::<u64>().unwrap_or_else(|_| now_ts());
+    let (year, month, day) = civil_date_from_unix(secs);
+    let tokens = [
+        ("YYYY", format!("{year:04}")),
+        ("MM", format!("{month:02}")),
+        ("DD", format!("{day:02}")),
+    ];
+    let expand_word = |word: &str| -> Option<String> {
+        let mut rest = word;
+        let mut expanded = String::new();
+        while !rest.is_empty() {
+            let (token, value) = tokens.iter().find(|(token, _)| rest.starts_with(token))?;
+            expanded.push_str(value);
+            rest = &rest[token.len()..];
+        }
+        Some(expanded)
+    };
+    let mut result = String::with_capacity(template.len());
+    let mut word = String::new();
+    for ch in template.chars().chain(std::iter::once('\0')) {
+        if ch.is_alphanumeric() {
+            word.push(ch);
+            continue;
+        }
+        result.push_str(&expand_word(&word).unwrap_or_else(|| word.clone()));
+        word.clear();
+        if ch != '\0' {
+            result.push(ch);
+        }
+    }
+    result
+}
+
+pub(super) fn route_matches(route: &ExportRoute, job: &Job, profile: Option<&str>) -> bool {
+    let mut has_criteria = false;
+    if let Some(tag) = route.tag.as_deref().filter(|t| !t.trim().is_empty()) {
+        has_criteria = true;
+        if !job.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim())) {
+            return false;
+        }
+    }
+    if let Some(wanted) = route.profile.as_deref().filter(|p| !p.trim().is_empty()) {
+        has_criteria = true;
+        if profile != Some(wanted.trim()) {
+            return false;
+        }
+    }
+    if let Some(folder) = route.watch_folder.as_deref().filter(|f| !f.trim().is_empty()) {
+        has_criteria = true;
+        let source = job.source_path.as_deref().unwrap_or("");
+        if !std::path::Path::new(source).starts_with(folder.trim()) {
+            return false;
+        }
+    }
+    has_criteria
+}
+
+pub(super) fn resolve_export_subfolder(
+    routes: &[ExportRoute],
+    fallback: &str,
+    job: &Job,
+    profile: Option<&str>,
+) -> String {
+    let template = routes
+        .iter()
+        .find(|route| route_matches(route, job, profile))
+        .map(|route| route.subfolder.as_str())
+        .unwrap_or(fallback);
+    expand_subfolder_template(template.trim(), &job.created_at)
+}
+
+pub(super) fn write_summary_file(job_dir: &Path, content: &str) -> Result<String, String> {
+    let summary_path = job_dir.join("summary.md");
+    write_job_file(&summary_path, content)
+        .map_err(|err| format!("failed to write summary.md: {err}"))?;
+    Ok(summary_path.to_string_lossy().to_string())
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct SubtitleCue {
+    pub(super) start: f32,
+    pub(super) end: f32,
+    pub(super) lines: Vec<String>,
+    // Set when the speaker goes into a WebVTT voice tag instead of the text.
+    pub(super) speaker: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct SubtitleOptions {
+    pub(super) max_line_chars: usize,
+    pub(super) max_lines: usize,
+    pub(super) speaker_prefix: bool,
+    // Consecutive segments of the same speaker closer than this are merged; 0 disables.
+    pub(super) merge_gap_seconds: f32,
+    pub(super) voice_tags: bool,
+    pub(super) word_timing: bool,
+}
+
+// Merged cues never run longer than this, whatever the gap setting.
+pub(super) const SUBTITLE_MAX_MERGED_SECONDS: f32 = 7.0;
+
+// Caller-facing options for export_subtitles; unset fields fall back to the subtitle_* config.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubtitleExportOptions {
+    pub dest: String,
+    pub max_line_chars: Option<u32>,
+    pub max_lines: Option<u32>,
+    pub speaker_labels: Option<bool>,
+    pub merge_gap_seconds: f32,
+    // WebVTT only: per-word timestamps, interpolated across each cue.
+    pub word_timing: bool,
+}
+
+pub(super) fn format_subtitle_timestamp(seconds: f32, ms_separator: char) -> String {
+    let total_ms = (seconds.max(0.0) as f64 * 1000.0).round() as u64;
+    let hours = total_ms / 3_600_000;
+    let minutes = (total_ms % 3_600_000) / 60_000;
+    let secs = (total_ms % 60_000) / 1000;
+    let ms = total_ms % 1000;
+    format!("{hours:02}:{minutes:02}:{secs:02}{ms_separator}{ms:03}")
+}
+
+pub(super) fn wrap_subtitle_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let needed = if current.is_empty() {
+            word.chars().count()
+        } else {
+            current.chars().count() + 1 + word.chars().count()
+        };
+        if needed > max_chars && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+// Whisper cuts segments at pauses, which often leaves one- or two-word captions; join them
+// while the speaker stays the same and the result still fits on screen.
+pub(super) fn merge_subtitle_segments(segments: &[Segment], options: &SubtitleOptions) -> Vec<Segment> {
+    let budget = options.max_line_chars.max(1).saturating_mul(options.max_lines.max(1));
+    let mut merged: Vec<Segment> = Vec::new();
+    for seg in segments.iter().filter(|seg| !seg.text.trim().is_empty()) {
+        if let Some(prev) = merged.last_mut() {
+            let text = format!("{} {}", prev.text.trim(), seg.text.trim());
+            if options.merge_gap_seconds > 0.0
+                && prev.speaker == seg.speaker
+                && seg.start - prev.end <= options.merge_gap_seconds
+                && seg.end - prev.start <= SUBTITLE_MAX_MERGED_SECONDS
+                && text.chars().count() <= budget
+            {
+                prev.text = text;
+                prev.end = seg.end;
+                continue;
+            }
+        }
+        merged.push(seg.clone());
+    }
+    merged
+}
+
+pub(super) fn build_subtitle_cues(segments: &[Segment], options: &SubtitleOptions) -> Vec<SubtitleCue> {
+    let max_lines = options.max_lines.max(1);
+    let mut cues = Vec::new();
+    for seg in &merge_subtitle_segments(segments, options) {
+        let text = match seg.speaker.as_deref() {
+            Some(speaker) if options.speaker_prefix && !options.voice_tags => format!("{speaker}: {}", seg.text),
+            _ => seg.text.clone(),
+        };
+        let speaker = seg.speaker.clone().filter(|_| options.speaker_prefix && options.voice_tags);
+        let lines = wrap_subtitle_text(&text, options.max_line_chars.max(1));
+        if lines.is_empty() {
+            continue;
+        }
+        // Segments that overflow the line budget become several cues, with the segment's
+        // time split proportionally to the characters each cue carries.
+        let total_chars: usize = lines.iter().map(|l| l.chars().count()).sum::<usize>().max(1);
+        let duration = (seg.end - seg.start).max(0.0);
+        let mut cursor = seg.start;
+        let chunks: Vec<&[String]> = lines.chunks(max_lines).collect();
+        let last = chunks.len() - 1;
+        for (idx, chunk) in chunks.into_iter().enumerate() {
+            let chars: usize = chunk.iter().map(|l| l.chars().count()).sum();
+            let end = if idx == last {
+                seg.end
+            } else {
+                cursor + duration * chars as f32 / total_chars as f32
+            };
+            cues.push(SubtitleCue {
+                start: cursor,
+                end,
+                lines: chunk.to_vec(),
+                speaker: speaker.clone(),
+            });
+            cursor = end;
+        }
+    }
+    cues
+}
+
+pub(super) fn render_srt(cues: &[SubtitleCue]) -> String {
+    let mut out = String::new();
+    for (idx, cue) in cues.iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            idx + 1,
+            format_subtitle_timestamp(cue.start, ','),
+            format_subtitle_timestamp(cue.end, ','),
+            cue.lines.join("\n")
+        ));
+    }
+    out
+}
+
+pub(super) fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+// Whisper's segment JSON has no word timestamps, so word cues are spread across the cue in
+// proportion to the characters before each word.
+pub(super) fn vtt_word_timed_lines(cue: &SubtitleCue) -> Vec<String> {
+    let total: usize = cue.lines.iter().flat_map(|line| line.split_whitespace()).map(|w| w.chars().count()).sum();
+    let duration = (cue.end - cue.start).max(0.0);
+    let mut seen = 0usize;
+    cue.lines
+        .iter()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|word| {
+                    let text = if seen == 0 {
+                        escape_vtt(word)
+                    } else {
+                        let at = cue.start + duration * seen as f32 / total.max(1) as f32;
+                        format!("<{}>{}", format_subtitle_timestamp(at, '.'), escape_vtt(word))
+                    };
+                    seen += word.chars().count();
+                    text
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+pub(super) fn render_vtt(cues: &[SubtitleCue], word_timing: bool) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for cue in cues {
+        let mut lines = if word_timing {
+            vtt_word_timed_lines(cue)
+        } else {
+            cue.lines.iter().map(|line| escape_vtt(line)).collect()
+        };
+        if let (Some(speaker), Some(first)) = (cue.speaker.as_deref(), lines.first_mut()) {
+            *first = format!("<v {}>{first}", escape_vtt(speaker));
+        }
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_subtitle_timestamp(cue.start, '.'),
+            format_subtitle_timestamp(cue.end, '.'),
+            lines.join("\n")
+        ));
+    }
+    out
+}
+
+pub(super) fn segments_to_srt(segments: &[Segment]) -> String {
+    let options = SubtitleOptions {
+        max_line_chars: usize::MAX,
+        max_lines: usize::MAX,
+        speaker_prefix: true,
+        merge_gap_seconds: 0.0,
+        voice_tags: false,
+        word_timing: false,
+    };
+    render_srt(&build_subtitle_cues(segments, &options))
+}
+
+pub(super) fn parse_srt_timestamp(value: &str) -> Option<f32> {
+    let (clock, ms) = value.trim().split_once(',')?;
+    let mut parts = clock.split(':').map(|part| part.parse::<u64>().ok());
+    let (hours, minutes, secs) = (parts.next()??, parts.next()??, parts.next()??);
+    let ms = ms.parse::<u64>().ok()?;
+    Some(((hours * 3600 + minutes * 60 + secs) * 1000 + ms) as f32 / 1000.0)
+}
+
+// Moves every cue of an SRT document by `offset` seconds; other lines pass through untouched.
+pub(super) fn shift_srt_timestamps(srt: &str, offset: f32) -> String {
+    srt.split_inclusive('\n')
+        .map(|line| {
+            let body = line.trim_end_matches(['\r', '\n']);
+            let shifted = body.split_once(" --> ").and_then(|(from, to)| {
+                let from = parse_srt_timestamp(from)?;
+                let to = parse_srt_timestamp(to)?;
+                Some(format!(
+                    "{} --> {}",
+                    format_subtitle_timestamp(from + offset, ','),
+                    format_subtitle_timestamp(to + offset, ',')
+                ))
+            });
+            match shifted {
+                Some(timing) => format!("{timing}{}", &line[body.len()..]),
+                None => line.to_string(),
+            }
+        })
+        .collect()
+}
+
+pub(super) fn segments_to_text(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .map(|seg| match seg.speaker.as_deref() {
+            Some(speaker) => format!("{speaker}: {}\n", seg.text),
+            None => format!("{}\n", seg.text),
+        })
+        .collect()
+}
+
+pub(super) fn wrap_frontmatter(fields: Vec<String>) -> String {
+    if fields.is_empty() {
+        return String::new();
+    }
+    format!("---\n{}\n---\n\n", fields.join("\n"))
+}
+
+pub(super) fn frontmatter_fields(job: &Job) -> Vec<String> {
+    let meta = job.source_metadata.clone().unwrap_or_default();
+    let recorded_at = meta
+        .recorded_at
+        .or_else(|| job.media_info.as_ref().and_then(|info| info.recorded_at.clone()));
+    let mut fields = Vec::new();
+    if let Some(title) = preferred_title(job) {
+        fields.push(format!("title: \"{}\"", title.replace('"', "\\\"")));
+    }
+    if !job.tags.is_empty() {
+        fields.push(format!("tags: [{}]", job.tags.join(", ")));
+    }
+    if let Some(recorded_at) = recorded_at {
+        fields.push(format!("recorded: {recorded_at}"));
+    }
+    if let Some(location) = meta.location {
+        fields.push(format!("location: \"{}\"", location.replace('"', "\\\"")));
+    }
+    if let Some(event) = &job.calendar_event {
+        fields.push(format!("meeting: \"{}\"", event.title.replace('"', "\\\"")));
+        if !event.attendees.is_empty() {
+            let attendees: Vec<String> = event
+                .attendees
+                .iter()
+                .map(|name| format!("\"{}\"", name.replace('"', "\\\"")))
+                .collect();
+            fields.push(format!("attendees: [{}]", attendees.join(", ")));
+        }
+    }
+    fields
+}
+
+// Obsidian notes also carry the processing details useful for Dataview queries.
+pub(super) fn render_export_frontmatter(job: &Job, vars: &PromptVars) -> String {
+    let mut fields = frontmatter_fields(job);
+    fields.push(format!("date: {}", vars.date));
+    if !vars.duration.is_empty() {
+        fields.push(format!("duration: \"{}\"", vars.duration));
+    }
+    if let Some(model) = job.summary_model.as_deref().filter(|m| !m.is_empty()) {
+        fields.push(format!("model: \"{}\"", model.replace('"', "\\\"")));
+    }
+    wrap_frontmatter(fields)
+}
+
+pub(super) const NOTE_TEMPLATE_VARIABLES: &[&str] = &[
+    "frontmatter",
+    "title",
+    "notes",
+    "summary",
+    "action_items",
+    "templates",
+    "audio",
+    "transcript",
+    "date",
+    "duration",
+    "language",
+    "filename",
+    "tags",
+    "model",
+];
+
+pub(super) const PATH_TEMPLATE_VARIABLES: &[&str] = &["folder", "year", "month", "day", "date", "title", "filename", "id"];
+
+// Vault-relative note path from obsidian_path_template; `folder` is the routed subfolder.
+pub(super) fn expand_note_path_template(template: &str, job: &Job, vars: &PromptVars, folder: &str) -> Result<PathBuf, String> {
+    let mut date_parts = vars.date.splitn(3, '-');
+    let (year, month, day) = (
+        date_parts.next().unwrap_or(""),
+        date_parts.next().unwrap_or(""),
+        date_parts.next().unwrap_or(""),
+    );
+    let stem = Path::new(&job.filename)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(&job.id);
+    // Values are sanitized so a title can't add folders; only the template's own "/" nests.
+    let expanded = template
+        .trim()
+        .replace("{folder}", folder.trim_matches('/'))
+        .replace("{year}", year)
+        .replace("{month}", month)
+        .replace("{day}", day)
+        .replace("{date}", &vars.date)
+        .replace("{title}", &sanitize_filename(&vars.title))
+        .replace("{filename}", &sanitize_filename(stem))
+        .replace("{id}", &sanitize_filename(&job.id));
+    let mut path = PathBuf::new();
+    for part in expanded.split(['/', '\\']).map(str::trim).filter(|part| !part.is_empty()) {
+        if part == ".." || part == "." {
+            return Err("The note path template must stay inside the vault.".to_string());
+        }
+        path.push(part);
+    }
+    if path.as_os_str().is_empty() {
+        return Err("The note path template expands to an empty path.".to_string());
+    }
+    if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+        let name = format!("{}.md", path.file_name().and_then(|n| n.to_str()).unwrap_or_default());
+        path.set_file_name(name);
+    }
+    Ok(path)
+}
+
+// Where export_to_obsidian writes this job. Nothing is created on disk.
+pub(super) fn resolve_note_path(config: &AppConfig, job: &Job, vars: &PromptVars) -> Result<PathBuf, String> {
+    let vault = PathBuf::from(&config.vault_path);
+    let folder = resolve_export_subfolder(
+        &config.export_routes,
+        &config.output_subfolder,
+        job,
+        Some(config.active_profile.as_str()),
+    );
+    if config.daily_note_mode == "full" {
+        return Ok(vault.join(expand_note_path_template(&config.daily_note_path, job, vars, "")?));
+    }
+    if config.obsidian_path_template.trim().is_empty() {
+        let dir = if folder.is_empty() { vault } else { vault.join(folder) };
+        return Ok(unique_note_path(&dir, &job_title(job), job.exported_path.as_deref()));
+    }
+    let path = vault.join(expand_note_path_template(&config.obsidian_path_template, job, vars, &folder)?);
+    let dir = path.parent().map(Path::to_path_buf).unwrap_or(vault);
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default().to_string();
+    Ok(unique_note_path(&dir, &stem, job.exported_path.as_deref()))
+}
+
+pub(super) fn upsert_managed_block(existing: &str, name: &str, block: &str) -> String {
+    if let Some(updated) = replace_managed_section(existing, name, block) {
+        return updated;
+    }
+    if existing.trim().is_empty() {
+        return format!("{}\n", managed_block(name, block));
+    }
+    format!("{}\n\n{}\n", existing.trim_end(), managed_block(name, block))
+}
+
+// Daily notes hold one marked block per job, so re-exports replace it instead of appending again.
+pub(super) fn merge_daily_note(existing: &str, job_id: &str, block: &str) -> String {
+    upsert_managed_block(existing, &format!("job:{job_id}"), block)
+}
+
+// ("YYYY-MM-DD", "HH:MM") the recording started, on the system clock: recorded_at when
+// present, else the import time.
+pub(super) fn recording_local_time(job: &Job) -> (String, String) {
+    let (start, _) = recording_interval(job, &WallClock::Local);
+    let local = iso_from_unix((start as i64 + WallClock::Local.offset_at(start as i64)).max(0) as u64);
+    (local[..10].to_string(), local[11..16].to_string())
+}
+
+// First paragraph (or first few bullets) of a summary, flattened to one short line.
+pub(super) fn summary_gist(summary: &str) -> String {
+    let blocks = markdown_blocks(summary);
+    let gist = blocks
+        .iter()
+        .find_map(|block| match block {
+            DocBlock::Paragraph(text) => Some(text.clone()),
+            _ => None,
+        })
+        .unwrap_or_else(|| {
+            blocks
+                .iter()
+                .filter_map(|block| match block {
+                    DocBlock::Bullet(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .take(3)
+                .collect::<Vec<_>>()
+                .join("; ")
+        });
+    if gist.chars().count() > 200 {
+        format!("{}…", gist.chars().take(200).collect::<String>().trim_end())
+    } else {
+        gist
+    }
+}
+
+pub(super) fn daily_note_entry(job: &Job, note_link: &str) -> String {
+    let title = job_title(job);
+    let gist = summary_gist(&stored_summary(job));
+    let (_, time) = recording_local_time(job);
+    let mut entry = format!("- {time} [[{note_link}|{title}]]");
+    if !gist.is_empty() {
+        entry.push_str(&format!(" — {gist}"));
+    }
+    entry
+}
+
+// Adds (or refreshes) the job's entry in the recording date's daily note; returns its path.
+pub(super) fn append_to_daily_note(config: &AppConfig, job: &Job, note_path: &Path) -> Result<PathBuf, String> {
+    let vars = PromptVars::for_job(job, config);
+    let vault = PathBuf::from(&config.vault_path);
+    let daily_path = vault.join(expand_note_path_template(&config.daily_note_path, job, &vars, "")?);
+    // Wikilinks use "/" whatever the platform's separator.
+    let link: Vec<String> = note_path
+        .strip_prefix(&vault)
+        .unwrap_or(note_path)
+        .with_extension("")
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy().to_string())
+        .collect();
+    let entry = daily_note_entry(job, &link.join("/"));
+    if let Some(parent) = daily_path.parent() {
+        fs::create_dir_all(parent).map_err(|err| format!("failed to create daily note dir: {err}"))?;
+    }
+    let existing = fs::read_to_string(&daily_path).unwrap_or_default();
+    let updated = merge_daily_note(&existing, &job.id, &entry);
+    if updated != existing {
+        fs::write(&daily_path, updated).map_err(|err| format!("failed to write daily note: {err}"))?;
+    }
+    Ok(daily_path)
+}
+
+pub(super) fn update_daily_note(app: &Core, config: &AppConfig, job: &Job, note_path: &Path) {
+    if config.daily_note_mode != "link" {
+        return;
+    }
+    match append_to_daily_note(config, job, note_path) {
+        Ok(path) => emit_job_log(app, &job.id, &format!("Daily note updated: {}", path.to_string_lossy())),
+        Err(err) => emit_job_log(app, &job.id, &format!("Daily note not updated: {err}")),
+    }
+}
+
+// Rendered sections of an exported note; each is empty or ends with a blank line.
+#[derive(Debug, Default)]
+pub(super) struct NoteParts {
+    pub(super) frontmatter: String,
+    pub(super) title: String,
+    pub(super) notes: String,
+    pub(super) summary: String,
+    pub(super) action_items: String,
+    pub(super) templates: String,
+    pub(super) audio: String,
+    pub(super) transcript: String,
+}
+
+pub(super) fn render_note(template: &str, parts: &NoteParts, job: &Job, vars: &PromptVars) -> String {
+    if template.trim().is_empty() {
+        let mut note = parts.frontmatter.clone();
+        if !parts.title.is_empty() {
+            note.push_str(&format!("# {}\n\n", parts.title));
+        }
+        for section in [&parts.notes, &parts.summary, &parts.action_items, &parts.templates, &parts.audio, &parts.transcript] {
+            note.push_str(section);
+        }
+        return note;
+    }
+    // Sections go in last so text inside a transcript is never mistaken for a placeholder.
+    let sections = [
+        ("{frontmatter}", &parts.frontmatter),
+        ("{notes}", &parts.notes),
+        ("{summary}", &parts.summary),
+        ("{action_items}", &parts.action_items),
+        ("{templates}", &parts.templates),
+        ("{audio}", &parts.audio),
+        ("{transcript}", &parts.transcript),
+    ];
+    let mut note = vars
+        .apply(template)
+        .replace("{tags}", &job.tags.join(", "))
+        .replace("{model}", job.summary_model.as_deref().unwrap_or(""));
+    let mut markers = Vec::new();
+    for (i, (name, _)) in sections.iter().enumerate() {
+        let marker = format!("\u{0}section{i}\u{0}");
+        note = note.replace(name, &marker);
+        markers.push(marker);
+    }
+    for (marker, (_, content)) in markers.iter().zip(sections.iter()) {
+        note = note.replace(marker, content.trim_end());
+    }
+    note
+}
+
+// Keeps the note this job exported last; any other existing file gets a " (n)" suffix.
+pub(super) fn unique_note_path(dir: &Path, title: &str, previous: Option<&str>) -> PathBuf {
+    let mut n = 1;
+    loop {
+        let name = if n == 1 {
+            format!("{title}.md")
+        } else {
+            format!("{title} ({n}).md")
+        };
+        let path = dir.join(name);
+        if !path.exists() || previous == Some(path.to_string_lossy().as_ref()) {
+            return path;
+        }
+        n += 1;
+    }
+}
+
+// The audio section of an exported note plus what timestamp links should point at.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct ExportedAudio {
+    pub(super) section: String,
+    // A vault file name (copied audio, linked as a wikilink) or a file:// URL.
+    pub(super) link_target: Option<String>,
+}
+
+pub(super) fn export_audio(
+    mode: &str,
+    audio_path: &Path,
+    note_path: &Path,
+    attachments_dir: Option<&Path>,
+) -> Result<ExportedAudio, String> {
+    if mode == "none" || !audio_path.is_file() {
+        return Ok(ExportedAudio::default());
+    }
+    if mode == "copy" {
+        let dir = match attachments_dir {
+            Some(dir) => dir.to_path_buf(),
+            None => note_path
+                .parent()
+                .ok_or_else(|| "invalid note path".to_string())?
+                .join("attachments"),
+        };
+        fs::create_dir_all(&dir).map_err(|err| format!("failed to create attachments dir: {err}"))?;
+        let stem = note_path.file_stem().and_then(|s| s.to_str()).unwrap_or("audio");
+        let ext = audio_path.extension().and_then(|e| e.to_str()).unwrap_or("wav");
+        let name = format!("{stem}.{ext}");
+        let target = dir.join(&name);
+        let same_size = fs::metadata(&target)
+            .ok()
+            .zip(fs::metadata(audio_path).ok())
+            .is_some_and(|(a, b)| a.len() == b.len());
+        if file_is_sealed(Path::new(audio_path)) {
+            let data = read_job_file(audio_path).map_err(|err| format!("failed to read audio: {err}"))?;
+            fs::write(&target, data).map_err(|err| format!("failed to copy audio: {err}"))?;
+        } else if !same_size {
+            fs::copy(audio_path, &target).map_err(|err| format!("failed to copy audio: {err}"))?;
+        }
+        return Ok(ExportedAudio {
+            section: format!("## Audio\n\n![[{name}]]\n\n"),
+            link_target: Some(name),
+        });
+    }
+    let url = format!("file://{}", audio_path.display());
+    Ok(ExportedAudio {
+        section: format!("## Audio\n\n[Recording](<{url}>)\n\n"),
+        link_target: Some(url),
+    })
+}
+
+pub(super) fn timestamp_link(target: &str, seconds: f32) -> String {
+    let label = format_timestamp(seconds);
+    let offset = format!("{:.1}", seconds.max(0.0));
+    if target.starts_with("file://") {
+        format!("[{label}](<{target}#t={offset}>)")
+    } else {
+        format!("[[{target}#t={offset}|{label}]]")
+    }
+}
+
+pub(super) fn linked_transcript(segments: &[Segment], target: &str) -> String {
+    segments
+        .iter()
+        .filter(|seg| !seg.text.trim().is_empty())
+        .map(|seg| match seg.speaker.as_deref() {
+            Some(speaker) => format!("{} {speaker}: {}", timestamp_link(target, seg.start), seg.text.trim()),
+            None => format!("{} {}", timestamp_link(target, seg.start), seg.text.trim()),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Sections refreshed in a note the user edited since the last export; everything else is theirs.
+pub(super) const MANAGED_NOTE_SECTIONS: [&str; 2] = ["summary", "transcript"];
+
+pub(super) fn managed_block(name: &str, content: &str) -> String {
+    let content = content.trim_end();
+    if content.is_empty() {
+        format!("<!-- voicenote:{name} -->\n<!-- /voicenote:{name} -->")
+    } else {
+        format!("<!-- voicenote:{name} -->\n{content}\n<!-- /voicenote:{name} -->")
+    }
+}
+
+// Swaps the text between a section's markers; None when the user removed the markers.
+pub(super) fn replace_managed_section(note: &str, name: &str, content: &str) -> Option<String> {
+    let open = format!("<!-- voicenote:{name} -->");
+    let close = format!("<!-- /voicenote:{name} -->");
+    let start = note.find(&open)?;
+    let end = start + note[start..].find(&close)? + close.len();
+    Some(format!("{}{}{}", &note[..start], managed_block(name, content), &note[end..]))
+}
+
+// An Obsidian note rendered but not yet written, with the raw managed sections kept
+// around for in-place updates.
+pub(super) struct PreparedNote {
+    pub(super) path: PathBuf,
+    pub(super) note: String,
+    pub(super) sections: [String; 2],
+    // `note` is this job's block of a shared daily note rather than a whole note.
+    pub(super) daily: bool,
+}
+
+// `note_path` pins the destination for re-exports; otherwise a free name is picked.
+pub(super) fn prepare_obsidian_note(config: &AppConfig, job: &Job, note_path: Option<PathBuf>) -> Result<PreparedNote, String> {
+    let vault_path = config.vault_path.clone();
+    let enable_summarization = config.enable_summarization;
+    if vault_path.trim().is_empty() {
+        return Err("Obsidian vault path is not configured.".to_string());
+    }
+    let job_dir = job_dir_from_audio_path(&job.audio_path)
+        .ok_or_else(|| "job directory not found".to_string())?;
+
+    let transcript_path = if !job.transcript_txt_path.is_empty() {
+        job.transcript_txt_path.clone()
+    } else {
+        job_dir
+            .join("transcript.txt")
+            .to_string_lossy()
+            .to_string()
+    };
+    let transcript = if std::path::Path::new(&transcript_path).exists() {
+        read_transcript_text(&transcript_path)?
+    } else {
+        String::new()
+    };
+
+    let summary_from_job = job.summary_md.clone().unwrap_or_default();
+    let summary_path = job_dir.join("summary.md");
+    let summary_from_disk = if summary_path.exists() {
+        read_job_text(&summary_path)
+            .map_err(|err| format!("failed to read summary.md: {err}"))?
+    } else {
+        String::new()
+    };
+
+    let summary_status = job
+        .summary_status
+        .clone()
+        .unwrap_or_else(|| "not_started".to_string());
+    let vars = PromptVars::for_job(job, config);
+    let manual_prompt = if summary_from_job.trim().is_empty() {
+        if summary_from_disk.trim().is_empty()
+            && (summary_status == "skipped" || !enable_summarization)
+        {
+            build_summary_prompt(&config.summary_prompt, &transcript, &vars)
+        } else {
+            summary_from_disk.clone()
+        }
+    } else {
+        summary_from_job.clone()
+    };
+
+    let title = job_title(job);
+    let mut parts = NoteParts {
+        frontmatter: render_export_frontmatter(job, &vars),
+        title: title.clone(),
+        ..NoteParts::default()
+    };
+    if !job.notes.trim().is_empty() {
+        parts.notes = format!("## Notes\n\n{}\n\n", job.notes.trim_end());
+    }
+
+    let extractive = job.summary_model.as_deref() == Some(EXTRACTIVE_SUMMARY_MODEL)
+        && !summary_from_job.trim().is_empty();
+    if extractive {
+        parts.summary = format!("## TL;DR\n\n{}\n\n", summary_from_job.trim_end());
+    } else if summary_status == "skipped" || !enable_summarization {
+        if !manual_prompt.trim().is_empty() {
+            parts.summary = format!("## Summary prompt\n\n{manual_prompt}\n\n");
+        }
+    } else if !manual_prompt.trim().is_empty() {
+        parts.summary = format!("{}\n\n", manual_prompt.trim_end());
+    }
+
+    if let Some(items) = load_action_items(&job_dir).filter(|items| !items.is_empty()) {
+        parts.action_items = format!("## Action items\n\n{}\n", render_action_items(&items));
+    }
+
+    for template in &config.summary_templates {
+        if job.template_summaries.get(&template.id).map(|t| t.status.as_str()) != Some("done") {
+            continue;
+        }
+        let content = read_job_text(job_dir.join(format!("{}.md", sanitize_filename(&template.id))))
+            .unwrap_or_default();
+        if !content.trim().is_empty() {
+            parts
+                .templates
+                .push_str(&format!("## {}\n\n{}\n\n", template.name, demote_headings(content.trim_end())));
+        }
+    }
+
+    let target_path = match note_path {
+        Some(path) => path,
+        None => resolve_note_path(config, job, &vars)?,
+    };
+    let attachments_dir = Some(config.obsidian_attachments_folder.trim())
+        .filter(|folder| !folder.is_empty())
+        .map(|folder| PathBuf::from(&vault_path).join(folder));
+    let audio = export_audio(
+        &config.obsidian_audio_mode,
+        Path::new(&job.audio_path),
+        &target_path,
+        attachments_dir.as_deref(),
+    )?;
+    parts.audio = audio.section;
+
+    if (extractive || (summary_status != "skipped" && enable_summarization)) && !transcript.trim().is_empty() {
+        let segments = load_job_segments(job).unwrap_or_default();
+        let timestamped = match audio.link_target.as_deref() {
+            Some(target) if config.obsidian_timestamp_links => linked_transcript(&segments, target),
+            _ if config.include_timestamps => vars.segments_with_timestamps.clone(),
+            _ => String::new(),
+        };
+        let body = if timestamped.is_empty() { transcript.trim_end() } else { timestamped.as_str() };
+        parts.transcript = format!("## Transcript\n\n{body}\n");
+    }
+    let sections = [parts.summary.clone(), parts.transcript.clone()];
+    if config.daily_note_mode == "full" {
+        let body: String = [&parts.summary, &parts.action_items, &parts.templates, &parts.audio, &parts.transcript]
+            .into_iter()
+            .map(|section| demote_headings(section.trim_end()))
+            .filter(|section| !section.is_empty())
+            .map(|section| format!("{section}\n\n"))
+            .collect();
+        return Ok(PreparedNote {
+            path: target_path,
+            note: format!("## {title}\n\n{body}"),
+            sections,
+            daily: true,
+        });
+    }
+    parts.summary = format!("{}\n\n", managed_block(MANAGED_NOTE_SECTIONS[0], &sections[0]));
+    parts.transcript = format!("{}\n", managed_block(MANAGED_NOTE_SECTIONS[1], &sections[1]));
+    let note = render_note(&config.obsidian_note_template, &parts, job, &vars);
+    Ok(PreparedNote {
+        path: target_path,
+        note,
+        sections,
+        daily: false,
+    })
+}
+
+// `contents` is what was written; daily notes are shared with other jobs and carry no hash.
+pub(super) fn record_obsidian_export(app: &Core, id: &str, prepared: &PreparedNote, contents: &str, message: &str) -> Result<(), String> {
+    let exported_path = prepared.path.to_string_lossy().to_string();
+    let hash = (!prepared.daily).then(|| note_hash(contents));
+    update_job_and_emit(app, id, |job| {
+        job.exported_to_obsidian = true;
+        job.exported_path = Some(exported_path.clone());
+        job.exported_hash = hash.clone();
+        job.export_status = Some("exported".to_string());
+        job.export_error = None;
+        push_log(job, &format!("{message}: {exported_path}"));
+    })?;
+    emit_job_log(app, id, &format!("{message}: {exported_path}"));
+    Ok(())
+}
+
+pub(super) fn export_obsidian_note(app: &Core, config: &AppConfig, id: &str) -> Result<PathBuf, String> {
+    let job = find_job(app.state::<JobIndexState>(), id)?;
+    let prepared = prepare_obsidian_note(config, &job, None)?;
+    if let Some(parent) = prepared.path.parent() {
+        fs::create_dir_all(parent).map_err(|err| format!("failed to create export dir: {err}"))?;
+    }
+    let current = fs::read_to_string(&prepared.path).ok();
+    let contents = write_note_contents(app, &job, &prepared, current.as_deref());
+    fs::write(&prepared.path, &contents)
+        .map_err(|err| format!("failed to write note: {err}"))?;
+    record_obsidian_export(app, id, &prepared, &contents, "Exported to Obsidian")?;
+    update_daily_note(app, config, &job, &prepared.path);
+    Ok(prepared.path)
+}
+
+pub(super) fn note_hash(contents: &str) -> String {
+    to_hex(&Sha256::digest(contents.as_bytes()))
+}
+
+// Logs the sections merge_note_contents had to leave alone.
+pub(super) fn write_note_contents(app: &Core, job: &Job, prepared: &PreparedNote, current: Option<&str>) -> String {
+    let (contents, kept) = merge_note_contents(job, prepared, current);
+    for name in kept {
+        emit_job_log(app, &job.id, &format!("Note has no {name} markers; section left as is."));
+    }
+    contents
+}
+
+// What to write over the note at prepared.path: this job's block merged into a daily note,
+// the freshly rendered note when the file is missing or still as the last export left it,
+// or else (the user edited it in Obsidian) the file with only the managed sections swapped.
+// Also returns the managed sections whose markers the user removed.
+pub(super) fn merge_note_contents(job: &Job, prepared: &PreparedNote, current: Option<&str>) -> (String, Vec<&'static str>) {
+    let Some(current) = current else {
+        return (prepared.note.clone(), Vec::new());
+    };
+    if prepared.daily {
+        return (merge_daily_note(current, &job.id, &prepared.note), Vec::new());
+    }
+    if job.exported_hash.as_deref() == Some(note_hash(current).as_str()) {
+        return (prepared.note.clone(), Vec::new());
+    }
+    let mut merged = current.to_string();
+    let mut kept = Vec::new();
+    for (name, content) in MANAGED_NOTE_SECTIONS.iter().zip(prepared.sections.iter()) {
+        match replace_managed_section(&merged, name, content) {
+            Some(next) => merged = next,
+            None => kept.push(*name),
+        }
+    }
+    (merged, kept)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportPathPreview {
+    pub path: String,
+    // Vault-relative, for display.
+    pub relative_path: String,
+    pub exists: bool,
+    // The job would be appended to an existing or new daily note.
+    pub daily_note: bool,
+}
+
+pub(super) fn sync_obsidian_note(app: &Core, config: &AppConfig, id: &str) -> Result<String, String> {
+    let job = find_job(app.state::<JobIndexState>(), id)?;
+    let Some(path) = job.exported_path.clone().map(PathBuf::from).filter(|path| path.is_file()) else {
+        export_obsidian_note(app, config, id)?;
+        return Ok("exported".to_string());
+    };
+    let prepared = prepare_obsidian_note(config, &job, Some(path.clone()))?;
+    let current = fs::read_to_string(&path).map_err(|err| format!("failed to read note: {err}"))?;
+    let updated = write_note_contents(app, &job, &prepared, Some(&current));
+    if updated == current {
+        return Ok("unchanged".to_string());
+    }
+    fs::write(&path, &updated).map_err(|err| format!("failed to write note: {err}"))?;
+    record_obsidian_export(app, id, &prepared, &updated, "Updated Obsidian note")?;
+    update_daily_note(app, config, &job, &path);
+    Ok("updated".to_string())
+}
+
+// Pipeline hook for auto_export_after_*: exports (or refreshes) the note into the vault of the
+// profile the job was created under and records the outcome on the job instead of failing the
+// pipeline.
+pub(super) fn auto_export_job(app: &Core, job_id: &str, trigger: &str) {
+    let Ok(job) = find_job(app.state::<JobIndexState>(), job_id) else {
+        return;
+    };
+    let config = match app.state::<ConfigState>().config.lock() {
+        Ok(guard) => config_for_profile(&guard, job.profile.as_deref()),
+        Err(_) => return,
+    };
+    let result = if config.vault_path.trim().is_empty() {
+        Err("Obsidian vault path is not configured.".to_string())
+    } else {
+        sync_obsidian_note(app, &config, job_id)
+    };
+    match result {
+        Ok(outcome) => {
+            let _ = append_job_log(app, job_id, &format!("Auto-export after {trigger}: {outcome}."));
+        }
+        Err(err) => {
+            let _ = update_job_and_emit(app, job_id, |job| {
+                job.export_status = Some("error".to_string());
+                job.export_error = Some(err.clone());
+                push_log(job, &format!("Auto-export after {trigger} failed: {err}"));
+            });
+        }
+    }
+}
+
+pub(super) fn joplin_note_request(
+    target: &ExportTarget,
+    token: &str,
+    previous: Option<&str>,
+    title: &str,
+    body: &str,
+) -> Result<String, String> {
+    let base = Some(target.joplin_url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty())
+        .unwrap_or(JOPLIN_DEFAULT_URL);
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .map_err(|err| format!("Failed to build HTTP client: {err}"))?;
+    let send = |request: reqwest::blocking::RequestBuilder| {
+        request.send().map_err(|err| {
+            if err.is_connect() {
+                format!("Joplin is not reachable at {base}. Enable the Web Clipper service in Joplin.")
+            } else {
+                format!("Joplin request failed: {err}")
+            }
+        })
+    };
+    if let Some(note_id) = previous {
+        let resp = send(
+            client
+                .put(format!("{base}/notes/{note_id}"))
+                .query(&[("token", token)])
+                .json(&serde_json::json!({ "title": title, "body": body })),
+        )?;
+        if resp.status().is_success() {
+            return Ok(note_id.to_string());
+        }
+        // A note deleted in Joplin is recreated below; anything else is an error.
+        if resp.status() != reqwest::StatusCode::NOT_FOUND {
+            return Err(format!("Joplin error: {} {}", resp.status(), resp.text().unwrap_or_default()));
+        }
+    }
+    let mut payload = serde_json::json!({ "title": title, "body": body });
+    if !target.joplin_notebook_id.trim().is_empty() {
+        payload["parent_id"] = serde_json::Value::String(target.joplin_notebook_id.trim().to_string());
+    }
+    let resp = send(client.post(format!("{base}/notes")).query(&[("token", token)]).json(&payload))?;
+    if !resp.status().is_success() {
+        return Err(format!("Joplin error: {} {}", resp.status(), resp.text().unwrap_or_default()));
+    }
+    let json: serde_json::Value = resp.json().map_err(|err| format!("Invalid Joplin response: {err}"))?;
+    json.get("id")
+        .and_then(|id| id.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| "Joplin did not return a note id.".to_string())
+}
+
+// Writes the job to one export target and returns where it went (file path or Joplin note id).
+pub(super) fn export_job_to_target(
+    app: &Core,
+    target: &ExportTarget,
+    job: &Job,
+    config: &AppConfig,
+) -> Result<String, String> {
+    let doc = build_share_document(job, config)?;
+    let previous = job.target_exports.get(&target.id).map(String::as_str);
+    match target.kind.as_str() {
+        "markdown" => {
+            let vars = PromptVars::for_job(job, config);
+            let template = Some(target.path_template.trim()).filter(|t| !t.is_empty()).unwrap_or("{title}.md");
+            let relative = expand_note_path_template(template, job, &vars, "")?;
+            let path = Path::new(&target.path).join(relative);
+            let dir = path.parent().map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from(&target.path));
+            let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default().to_string();
+            let path = unique_note_path(&dir, &stem, previous);
+            fs::create_dir_all(&dir).map_err(|err| format!("failed to create export dir: {err}"))?;
+            let note = format!("{}{}", render_export_frontmatter(job, &vars), render_share_markdown(&doc));
+            fs::write(&path, note).map_err(|err| format!("failed to write note: {err}"))?;
+            Ok(path.to_string_lossy().to_string())
+        }
+        "logseq" => {
+            let date = PromptVars::for_job(job, config).date.replace('-', "_");
+            let journals = Path::new(&target.path).join("journals");
+            fs::create_dir_all(&journals).map_err(|err| format!("failed to create journals dir: {err}"))?;
+            let path = journals.join(format!("{date}.md"));
+            let existing = fs::read_to_string(&path).unwrap_or_default();
+            let merged = merge_logseq_journal(&existing, &job.id, &render_logseq_block(&doc, &job.id));
+            fs::write(&path, merged).map_err(|err| format!("failed to write journal: {err}"))?;
+            Ok(path.to_string_lossy().to_string())
+        }
+        "joplin" => {
+            let token = load_secret(app, EXPORT_KEYCHAIN_SERVICE, &target.id, &export_target_token_file(&target.id))
+                .ok_or_else(|| format!("No Joplin token set for \"{}\".", target.name))?;
+            joplin_note_request(target, &token, previous, &doc.title, &render_share_markdown(&doc))
+        }
+        other => Err(format!("Unsupported export target kind: {other}")),
+    }
+}
+
+pub(super) fn export_to_target_by_id(app: &Core, config: &AppConfig, job_id: &str, target_id: &str) -> Result<String, String> {
+    let job = find_job(app.state::<JobIndexState>(), job_id)?;
+    let target = config
+        .export_targets
+        .iter()
+        .find(|target| target.id == target_id)
+        .cloned()
+        .ok_or_else(|| "export target not found".to_string())?;
+    let location = export_job_to_target(app, &target, &job, config)?;
+    let message = format!("Exported to {}: {location}", target.name);
+    update_job_and_emit(app, job_id, |job| {
+        job.target_exports.insert(target.id.clone(), location.clone());
+        push_log(job, &message);
+    })?;
+    emit_job_log(app, job_id, &message);
+    Ok(location)
+}
+
+pub(super) fn clipboard_transcript(job: &Job, with_timestamps: bool) -> Result<String, String> {
+    let segments = load_job_segments(job).unwrap_or_default();
+    let text = if with_timestamps && !segments.is_empty() {
+        timestamped_lines(&segments)
+    } else if !job.transcript_txt_path.is_empty() && Path::new(&job.transcript_txt_path).exists() {
+        read_transcript_text(&job.transcript_txt_path)?
+    } else {
+        segments.iter().map(|seg| seg.text.trim()).collect::<Vec<_>>().join(" ")
+    };
+    if text.trim().is_empty() {
+        return Err("No transcript to copy yet.".to_string());
+    }
+    Ok(text.trim().to_string())
+}
+
+pub(super) fn load_or_translate_segments(
+    job_dir: &Path,
+    segments: &[Segment],
+    target_language: &str,
+    llm: &LlmSettings,
+) -> Result<Vec<String>, String> {
+    let cache_path = job_dir.join(format!(
+        "translation.{}.json",
+        sanitize_filename(&target_language.to_lowercase())
+    ));
+    if let Ok(contents) = read_job_text(&cache_path) {
+        if let Ok(cached) = serde_json::from_str::<Vec<String>>(&contents) {
+            if cached.len() == segments.len() {
+                return Ok(cached);
+            }
+        }
+    }
+    let translations = translate_segments(llm, segments, target_language)?;
+    let json = serde_json::to_string_pretty(&translations)
+        .map_err(|err| format!("failed to serialize translation: {err}"))?;
+    write_job_file(&cache_path, json).map_err(|err| format!("failed to write translation: {err}"))?;
+    Ok(translations)
+}
+
+pub(super) fn render_bilingual_markdown(
+    title: &str,
+    segments: &[Segment],
+    translations: &[String],
+    clip_links: &[Option<String>],
+) -> String {
+    let mut out = format!("# {title}\n\n");
+    for (i, seg) in segments.iter().enumerate() {
+        let stamp = format_clock(seg.start as f64).replace('.', ":");
+        out.push_str(&format!("**{stamp}** {}\n", seg.text.trim()));
+        if let Some(translation) = translations.get(i).filter(|t| !t.trim().is_empty()) {
+            out.push_str(&format!("> {}\n", translation.trim()));
+        }
+        if let Some(Some(link)) = clip_links.get(i) {
+            out.push_str(&format!("![[{link}]]\n"));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+pub(super) fn clip_codec_args(format: &str) -> Result<(&'static str, Vec<&'static str>), String> {
+    match format.trim().to_lowercase().as_str() {
+        "m4a" => Ok(("m4a", vec!["-c:a", "aac", "-b:a", "160k"])),
+        "mp3" => Ok((
+            "mp3",
+            vec!["-c:a", "libmp3lame", "-q:a", "2", "-id3v2_version", "3"],
+        )),
+        "wav" => Ok(("wav", vec!["-c:a", "pcm_s16le"])),
+        other => Err(format!("Unsupported clip format: {other}")),
+    }
+}
+
+pub(super) fn clip_metadata(title: &str, filename: &str, start: f64, end: f64) -> Vec<(String, String)> {
+    let range = format!(
+        "{}-{}",
+        format_clock(start).replace('.', ":"),
+        format_clock(end).replace('.', ":")
+    );
+    vec![
+        ("title".to_string(), format!("{title} ({range})")),
+        ("album".to_string(), title.to_string()),
+        (
+            "comment".to_string(),
+            format!("Clip from {filename} at {range}"),
+        ),
+    ]
+}
+
+// A job counts as exported only if its note still exists inside the configured vault, so
+// changing the vault path makes every job eligible again.
+pub(super) fn exported_into_vault(job: &Job, vault_path: &str) -> bool {
+    job.exported_path
+        .as_deref()
+        .map(Path::new)
+        .is_some_and(|path| path.is_file() && path.starts_with(vault_path))
+}
+
+pub(super) fn export_all_blocking(app: &Core, filter: &JobFilter) -> Result<ExportAllSummary, String> {
+    let config = app
+        .state::<ConfigState>()
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    if config.vault_path.trim().is_empty() {
+        return Err("Obsidian vault path is not configured.".to_string());
+    }
+    let jobs: Vec<Job> = app
+        .state::<JobIndexState>()
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .iter()
+        .filter(|job| job.status == JobStatus::Done && filter.matches(job))
+        .cloned()
+        .collect();
+    let mut summary = ExportAllSummary::default();
+    let mut progress = ExportBatchProgress {
+        total: jobs.len(),
+        ..ExportBatchProgress::default()
+    };
+    for job in &jobs {
+        progress.current_id = Some(job.id.clone());
+        let _ = app.emit("export:batch", progress.clone());
+        if exported_into_vault(job, &config.vault_path) {
+            summary.skipped.push(job.id.clone());
+        } else {
+            match export_obsidian_note(app, &config, &job.id) {
+                Ok(_) => summary.created.push(job.id.clone()),
+                Err(error) => {
+                    let _ = update_job_and_emit(app, &job.id, |job| {
+                        job.export_status = Some("error".to_string());
+                        job.export_error = Some(error.clone());
+                    });
+                    summary.failed.push(ExportFailure {
+                        id: job.id.clone(),
+                        error,
+                    });
+                }
+            }
+        }
+        progress.processed += 1;
+    }
+    progress.current_id = None;
+    progress.finished = true;
+    let _ = app.emit("export:batch", progress);
+    Ok(summary)
+}
+
+pub(super) fn export_to_obsidian_inner(
+    app: &Core,
+    config_state: &ConfigState,
+    id: String,
+) -> Result<bool, String> {
+    let config = config_state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    export_obsidian_note(app, &config, &id)?;
+    Ok(true)
+}
+
+pub(super) fn preview_export_path_inner(
+    state: &JobIndexState,
+    config_state: &ConfigState,
+    id: String,
+) -> Result<ExportPathPreview, String> {
+    let job = find_job(state, &id)?;
+    let config = config_state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    if config.vault_path.trim().is_empty() {
+        return Err("Obsidian vault path is not configured.".to_string());
+    }
+    validate_prompt_templates(&config)?;
+    let path = resolve_note_path(&config, &job, &PromptVars::for_job(&job, &config))?;
+    let relative_path = path
+        .strip_prefix(&config.vault_path)
+        .unwrap_or(&path)
+        .to_string_lossy()
+        .to_string();
+    Ok(ExportPathPreview {
+        exists: path.exists(),
+        path: path.to_string_lossy().to_string(),
+        relative_path,
+        daily_note: config.daily_note_mode == "full",
+    })
+}
+
+pub(super) fn export_subtitles_inner(
+    state: &JobIndexState,
+    config_state: &ConfigState,
+    id: String,
+    format: String,
+    options: SubtitleExportOptions,
+) -> Result<String, String> {
+    let job = find_job(state, &id)?;
+    let format = format.trim().to_lowercase();
+    if format != "srt" && format != "vtt" {
+        return Err(format!("Unsupported subtitle format: {format}"));
+    }
+    if options.dest.trim().is_empty() {
+        return Err("Choose a folder to export subtitles to.".to_string());
+    }
+    let cue_options = {
+        let guard = config_state
+            .config
+            .lock()
+            .map_err(|_| "config mutex poisoned".to_string())?;
+        SubtitleOptions {
+            max_line_chars: options.max_line_chars.unwrap_or(guard.subtitle_max_line_chars) as usize,
+            max_lines: options.max_lines.unwrap_or(guard.subtitle_max_lines) as usize,
+            speaker_prefix: options.speaker_labels.unwrap_or(guard.subtitle_speaker_prefix),
+            merge_gap_seconds: options.merge_gap_seconds.max(0.0),
+            voice_tags: format == "vtt",
+            word_timing: options.word_timing && format == "vtt",
+        }
+    };
+    let segments = load_job_segments(&job)?;
+    if segments.is_empty() {
+        return Err("No transcript segments to export.".to_string());
+    }
+    let cues = build_subtitle_cues(&segments, &cue_options);
+    let contents = if format == "vtt" {
+        render_vtt(&cues, cue_options.word_timing)
+    } else {
+        render_srt(&cues)
+    };
+    let dest_dir = PathBuf::from(&options.dest);
+    fs::create_dir_all(&dest_dir)
+        .map_err(|err| format!("failed to create export dir: {err}"))?;
+    let path = dest_dir.join(format!("{}.{format}", job_title(&job)));
+    fs::write(&path, contents)
+        .map_err(|err| format!("failed to write {format} file: {err}"))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+pub(super) fn export_job_inner(
+    state: &JobIndexState,
+    config_state: &ConfigState,
+    id: String,
+    format: String,
+    dest: String,
+) -> Result<String, String> {
+    let job = find_job(state, &id)?;
+    let config = config_state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    let format = format.trim().to_lowercase();
+    if !["html", "docx", "pdf"].contains(&format.as_str()) {
+        return Err(format!("Unsupported export format: {format}"));
+    }
+    let doc = build_share_document(&job, &config)?;
+    let contents = match format.as_str() {
+        "html" => render_share_html(&doc).into_bytes(),
+        "docx" => render_share_docx(&doc)?,
+        _ => render_share_pdf(&doc, load_pdf_font().as_deref()),
+    };
+    let dest_dir = PathBuf::from(dest);
+    fs::create_dir_all(&dest_dir)
+        .map_err(|err| format!("failed to create export dir: {err}"))?;
+    let path = dest_dir.join(format!("{}.{format}", doc.title));
+    fs::write(&path, contents).map_err(|err| format!("failed to write {format} file: {err}"))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+pub(super) fn export_to_target_inner(
+    app: &Core,
+    state: &JobIndexState,
+    config_state: &ConfigState,
+    id: String,
+    target_id: String,
+) -> Result<String, String> {
+    find_job(state, &id)?;
+    let config = config_state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    export_to_target_by_id(app, &config, &id, &target_id)
+}
+
+pub(super) fn export_bilingual_inner(
+    app: &Core,
+    state: &JobIndexState,
+    config_state: &ConfigState,
+    id: String,
+    dest: String,
+    target_language: Option<String>,
+    include_clips: bool,
+) -> Result<String, String> {
+    let job = {
+        let guard = state
+            .index
+            .lock()
+            .map_err(|_| "job index mutex poisoned".to_string())?;
+        guard
+            .jobs
+            .iter()
+            .find(|job| job.id == id)
+            .cloned()
+            .ok_or_else(|| "job not found".to_string())?
+    };
+    let config = config_state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    let llm = LlmSettings::resolve(app, &config);
+    let target_language = target_language
+        .filter(|lang| !lang.trim().is_empty())
+        .unwrap_or(config.translation_target_language);
+    let job_dir = job_dir_from_audio_path(&job.audio_path)
+        .ok_or_else(|| "missing job directory".to_string())?;
+    let segments = load_job_segments(&job)?;
+    if segments.is_empty() {
+        return Err("No transcript segments to export.".to_string());
+    }
+    emit_job_log(app, &id, &format!("Translating transcript into {target_language}..."));
+    let translations = load_or_translate_segments(
+        &job_dir,
+        &segments,
+        &target_language,
+        &llm,
+    )?;
+
+    let dest_dir = PathBuf::from(dest);
+    fs::create_dir_all(&dest_dir)
+        .map_err(|err| format!("failed to create export dir: {err}"))?;
+    let title = job_title(&job);
+    let mut clip_links = vec![None; segments.len()];
+    if include_clips {
+        let ffmpeg_path = resolve_ffmpeg_path(app)?;
+        let clips_folder = format!("{title} clips");
+        let clips_dir = dest_dir.join(&clips_folder);
+        fs::create_dir_all(&clips_dir)
+            .map_err(|err| format!("failed to create clips dir: {err}"))?;
+        let scratch = ScratchAudio::new(&format!("{id}-bilingual"))?;
+        for (i, seg) in segments.iter().enumerate() {
+            let clip = ensure_clip(
+                &ffmpeg_path,
+                &scratch,
+                &job.audio_path,
+                &job_dir,
+                seg.start as f64,
+                seg.end as f64,
+            )?;
+            // ensure_clip falls back to the full recording when ffmpeg fails; don't copy that.
+            if clip == job.audio_path {
+                continue;
+            }
+            let name = format!("{:03}.wav", i + 1);
+            if fs::copy(&clip, clips_dir.join(&name)).is_ok() {
+                clip_links[i] = Some(format!("{clips_folder}/{name}"));
+            }
+        }
+    }
+    let markdown = render_bilingual_markdown(&title, &segments, &translations, &clip_links);
+    let path = dest_dir.join(format!("{title} ({target_language}).md"));
+    fs::write(&path, markdown).map_err(|err| format!("failed to write bilingual export: {err}"))?;
+    emit_job_log(app, &id, &format!("Bilingual transcript exported to {}", path.display()));
+    Ok(path.to_string_lossy().to_string())
+}
+
+pub(super) fn export_clip_inner(
+    app: &Core,
+    state: &JobIndexState,
+    id: String,
+    start: f64,
+    end: f64,
+    format: String,
+    dest: String,
+) -> Result<String, String> {
+    if end <= start {
+        return Err("Clip end must be after start.".to_string());
+    }
+    let job = {
+        let guard = state
+            .index
+            .lock()
+            .map_err(|_| "job index mutex poisoned".to_string())?;
+        guard
+            .jobs
+            .iter()
+            .find(|job| job.id == id)
+            .cloned()
+            .ok_or_else(|| "job not found".to_string())?
+    };
+    let (ext, codec_args) = clip_codec_args(&format)?;
+    let ffmpeg_path = resolve_ffmpeg_path(app)?;
+    // LAME is not part of the bundled LGPL build; a system ffmpeg may still have it.
+    if ext == "mp3" && !ffmpeg_supports(&ffmpeg_path, "-encoders", "libmp3lame")? {
+        return Err("This ffmpeg build can't encode MP3; export the clip as m4a or wav instead.".to_string());
+    }
+    let dest_dir = PathBuf::from(dest);
+    fs::create_dir_all(&dest_dir)
+        .map_err(|err| format!("failed to create export dir: {err}"))?;
+    let title = job_title(&job);
+    let clip_path = dest_dir.join(format!(
+        "{title} {}-{}.{ext}",
+        format_clock(start),
+        format_clock(end)
+    ));
+
+    let scratch = ScratchAudio::new(&format!("{id}-export-clip"))?;
+    let input = scratch.open(Path::new(&job.audio_path))?;
+    let mut cmd = Command::new(&ffmpeg_path);
+    apply_ffmpeg_env(&mut cmd, &ffmpeg_path);
+    cmd.args(["-y", "-i"])
+        .arg(&input)
+        .args(["-ss", &start.to_string(), "-to", &end.to_string()])
+        .args(["-vn", "-map_metadata", "-1"])
+        .args(&codec_args);
+    for (key, value) in clip_metadata(&title, &job.filename, start, end) {
+        cmd.arg("-metadata").arg(format!("{key}={value}"));
+    }
+    let status = cmd
+        .arg(&clip_path)
+        .status()
+        .map_err(|err| format!("failed to run ffmpeg: {err}"))?;
+    if !status.success() {
+        let _ = fs::remove_file(&clip_path);
+        return Err(format!("ffmpeg could not export the clip as {ext}"));
+    }
+    emit_job_log(
+        app,
+        &id,
+        &format!("Exported clip to {}", clip_path.display()),
+    );
+    Ok(clip_path.to_string_lossy().to_string())
+}
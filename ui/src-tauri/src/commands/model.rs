@@ -0,0 +1,949 @@
+// The config, job and result types shared by the core and the UI. They mirror the TS shapes in
+// ui/src/api/types.ts field for field.
+
+use super::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub initialized: bool,
+    // Name of the profile whose settings are in the fields below; the other profiles are kept
+    // in `profiles` and swapped in by switch_profile.
+    pub active_profile: String,
+    pub profiles: Vec<ConfigProfile>,
+    pub vault_path: String,
+    pub output_subfolder: String,
+    pub export_routes: Vec<ExportRoute>,
+    pub model_size: String,
+    pub preload_model: bool,
+    pub language: Option<String>,
+    pub enable_summarization: bool,
+    pub auto_summarize_after_transcription: bool,
+    // Push finished notes into the vault without a manual export; the second flag re-exports
+    // once the summary is ready. Failures land in Job.export_status/export_error.
+    pub auto_export_after_transcription: bool,
+    pub auto_export_after_summarization: bool,
+    // "auto" keeps the transcript's language; otherwise a language code or name.
+    pub summary_language: String,
+    pub llm_provider: String,
+    pub ollama_base_url: String,
+    pub ollama_model: String,
+    pub openai_base_url: String,
+    pub openai_model: String,
+    // Name of a secret (set_secret) holding the API key; empty uses the set_llm_api_key key.
+    pub openai_api_key_secret: String,
+    pub llm_context_tokens: u32,
+    pub llm_timeout_seconds: u32,
+    // Transient LLM failures (timeouts, connection errors, 5xx) are retried this many times.
+    pub llm_max_retries: u32,
+    // Summaries run on this many worker threads; changes apply between tasks.
+    pub summary_concurrency: u32,
+    // Write a TextRank summary when no LLM is reachable or summarization is disabled.
+    pub extractive_fallback: bool,
+    pub semantic_index: bool,
+    pub embedding_model: String,
+    pub summary_prompt: String,
+    // Extra named prompts run on demand; "summary" always uses summary_prompt.
+    pub summary_templates: Vec<SummaryTemplate>,
+    pub extract_action_items: bool,
+    pub auto_title_tags: bool,
+    pub include_timestamps: bool,
+    // Layout of exported notes; empty uses the built-in order. See NOTE_TEMPLATE_VARIABLES.
+    pub obsidian_note_template: String,
+    // "none", "copy" (into an attachments folder next to the note) or "link" (file:// URL).
+    pub obsidian_audio_mode: String,
+    // Vault-relative folder for copied audio; empty puts it in "attachments" next to the note.
+    pub obsidian_attachments_folder: String,
+    // Prefix transcript lines with links that seek the recording (Media Extended "#t=" syntax).
+    pub obsidian_timestamp_links: bool,
+    // Vault-relative note path, e.g. "{year}/{month}/{date} - {title}.md"; empty keeps
+    // "<routed subfolder>/<title>.md". See PATH_TEMPLATE_VARIABLES.
+    pub obsidian_path_template: String,
+    // What an export puts in the daily note of the recording date, found via daily_note_path
+    // (PATH_TEMPLATE_VARIABLES): "off"; "link", a one-line entry (time, link, gist of the
+    // summary) next to the job's own note; or "full", the whole job as a section instead of
+    // a note of its own. Either way the job has one block there, replaced on re-export.
+    pub daily_note_mode: String,
+    pub daily_note_path: String,
+    // Non-Obsidian destinations for export_to_target.
+    pub export_targets: Vec<ExportTarget>,
+    // Match recordings against calendar events (see match_calendar_event). The source is an
+    // .ics file path, an .ics/webcal URL or a CalDAV calendar collection URL; the CalDAV
+    // password is stored via set_calendar_password.
+    pub calendar_enabled: bool,
+    pub calendar_source: String,
+    pub calendar_username: String,
+    pub vault_vocabulary_enabled: bool,
+    pub subtitle_max_line_chars: u32,
+    pub subtitle_max_lines: u32,
+    pub subtitle_speaker_prefix: bool,
+    pub watch_inbox_enabled: bool,
+    pub inbox_poll_seconds: u32,
+    // Folders the inbox watcher imports from, each with its own preset; empty means
+    // <app data>/inbox with the global settings.
+    pub watch_folders: Vec<WatchFolder>,
+    // What happens to a watched file once its job exists: "keep", "move" (into a processed/
+    // subfolder) or "rename" (adds an .imported suffix).
+    pub inbox_after_import: String,
+    // A watched file is only imported once its size held for this many scans and it was last
+    // modified at least inbox_min_age_seconds ago; guards against files still syncing in.
+    pub inbox_stable_polls: u32,
+    pub inbox_min_age_seconds: u64,
+    // Imports new Apple Voice Memos recordings on every inbox scan, even with
+    // watch_inbox_enabled off. An empty path looks for the library in its usual places.
+    pub voice_memos_import_enabled: bool,
+    pub voice_memos_path: String,
+    // Closing the window hides it and leaves the app running in the menu bar.
+    pub close_to_tray: bool,
+    // What to do on battery, in Low Power Mode or under thermal pressure: "off", "pause" the
+    // queue, "reduce_threads" to power_saving_threads, or "defer_large" medium/large jobs.
+    pub power_saving_mode: String,
+    pub power_saving_threads: u32,
+    pub normalize_loudness: bool,
+    pub reduce_noise: bool,
+    pub whisper_json_retention: String,
+    pub split_stereo_channels: bool,
+    pub left_channel_label: String,
+    pub right_channel_label: String,
+    pub clip_cache_max_mb: u32,
+    pub notify_on_success: bool,
+    pub notify_on_failure: bool,
+    pub notify_on_summary: bool,
+    pub sound_on_success: bool,
+    pub sound_on_failure: bool,
+    pub respect_do_not_disturb: bool,
+    pub mock_pipeline: bool,
+    pub audio_retention: String,
+    pub translation_target_language: String,
+    pub whisper_binary_url: Option<String>,
+    pub ffmpeg_binary_url: Option<String>,
+    // Fall back to ffmpeg and whisper-cli on PATH or in Homebrew's bin folders before asking
+    // for a download; see detect_system_binaries.
+    pub use_system_binaries: bool,
+    // Folder for whisper models, e.g. ~/whisper-models shared with other tools; empty uses the
+    // app data folder. Models already in the app data folder are still found.
+    pub models_dir: String,
+    // Applied to model, whisper and ffmpeg downloads (see DownloadNetwork). The proxy is an
+    // http://, https://, socks5:// or socks5h:// URL; no_proxy is a comma-separated host list.
+    // Mirrors replace https://huggingface.co and https://github.com, e.g. https://hf-mirror.com.
+    pub download_proxy: String,
+    pub download_no_proxy: String,
+    pub huggingface_mirror: String,
+    pub github_mirror: String,
+    // Download speed cap in KB/s, 0 = unlimited. Takes effect on running downloads too.
+    pub download_speed_limit_kbps: u64,
+    // Deleted jobs older than this are purged from trash/ at startup; 0 keeps them until
+    // empty_trash.
+    pub trash_retention_days: u64,
+    // Seal transcripts, summaries and other text artifacts in job folders (see JobEncryption);
+    // the second flag seals the audio too, which stops playback until it is turned off again.
+    pub encrypt_job_files: bool,
+    pub encrypt_job_audio: bool,
+    // Keep a secret in a 0600 file in the app data folder when the system keychain refuses it,
+    // instead of failing the save.
+    pub allow_plaintext_secrets: bool,
+}
+
+// The per-profile part of AppConfig (e.g. "work" and "personal" vaults), stored for profiles
+// that aren't active.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigProfile {
+    pub name: String,
+    pub vault_path: String,
+    pub output_subfolder: String,
+    pub export_routes: Vec<ExportRoute>,
+    pub summary_prompt: String,
+    pub summary_templates: Vec<SummaryTemplate>,
+    pub export_targets: Vec<ExportTarget>,
+}
+
+pub(super) const DEFAULT_PROFILE: &str = "default";
+
+impl ConfigProfile {
+    pub(super) fn capture(config: &AppConfig) -> Self {
+        Self {
+            name: config.active_profile.clone(),
+            vault_path: config.vault_path.clone(),
+            output_subfolder: config.output_subfolder.clone(),
+            export_routes: config.export_routes.clone(),
+            summary_prompt: config.summary_prompt.clone(),
+            summary_templates: config.summary_templates.clone(),
+            export_targets: config.export_targets.clone(),
+        }
+    }
+
+    pub(super) fn apply(self, config: &mut AppConfig) {
+        config.active_profile = self.name;
+        config.vault_path = self.vault_path;
+        config.output_subfolder = self.output_subfolder;
+        config.export_routes = self.export_routes;
+        config.summary_prompt = self.summary_prompt;
+        config.summary_templates = self.summary_templates;
+        config.export_targets = self.export_targets;
+    }
+}
+
+// The config as it looks with `profile` active; the config itself when that is the active
+// profile or no longer exists.
+pub(super) fn config_for_profile(config: &AppConfig, profile: Option<&str>) -> AppConfig {
+    let mut config = config.clone();
+    let stored = profile
+        .filter(|name| *name != config.active_profile)
+        .and_then(|name| config.profiles.iter().find(|stored| stored.name == name).cloned());
+    if let Some(stored) = stored {
+        stored.apply(&mut config);
+    }
+    config
+}
+
+// Stores the active profile and loads `name`; an unknown name becomes a new profile that
+// starts as a copy of the active one.
+pub(super) fn switch_config_profile(config: &mut AppConfig, name: &str) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name is empty.".to_string());
+    }
+    if name == config.active_profile {
+        return Ok(());
+    }
+    let current = ConfigProfile::capture(config);
+    let next = match config.profiles.iter().position(|profile| profile.name == name) {
+        Some(index) => config.profiles.remove(index),
+        None => ConfigProfile {
+            name: name.to_string(),
+            ..current.clone()
+        },
+    };
+    config.profiles.retain(|profile| profile.name != current.name);
+    config.profiles.push(current);
+    next.apply(config);
+    Ok(())
+}
+
+// Routes a job to a vault subfolder at export time. All populated criteria must match;
+// the subfolder may contain YYYY/MM/DD placeholders filled from the job creation date.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportRoute {
+    pub tag: Option<String>,
+    pub profile: Option<String>,
+    pub watch_folder: Option<String>,
+    pub subfolder: String,
+}
+
+// A folder the inbox watcher imports from and the preset its jobs get; unset fields fall back to
+// the config.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchFolder {
+    pub path: String,
+    pub model_size: Option<String>,
+    pub language: Option<String>,
+    // ExportTarget id the job is exported to once transcribed.
+    pub export_target: Option<String>,
+    // Deletes the file once its job holds a copy, instead of inbox_after_import.
+    pub delete_source: bool,
+}
+
+impl WatchFolder {
+    pub(super) fn job_options(&self) -> JobOptions {
+        let set = |value: &Option<String>| value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string);
+        JobOptions {
+            model_size: set(&self.model_size),
+            language: set(&self.language),
+            export_target: set(&self.export_target),
+            ..Default::default()
+        }
+    }
+}
+
+// A non-Obsidian export destination. `kind` is "markdown" (plain folder), "logseq" (journal
+// pages under <path>/journals) or "joplin" (Web Clipper API; token set via set_export_target_token).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportTarget {
+    pub id: String,
+    pub name: String,
+    pub kind: String,
+    // Destination folder (markdown) or graph folder (logseq).
+    pub path: String,
+    // markdown only; see PATH_TEMPLATE_VARIABLES. Empty = "{title}.md".
+    pub path_template: String,
+    // joplin only; empty = the default Web Clipper port on localhost.
+    pub joplin_url: String,
+    pub joplin_notebook_id: String,
+}
+
+pub(super) const EXPORT_TARGET_KINDS: [&str; 3] = ["markdown", "logseq", "joplin"];
+
+pub(super) const JOPLIN_DEFAULT_URL: &str = "http://127.0.0.1:41184";
+
+// Where a job is in the transcription pipeline. Status changes go through Job::transition, which
+// only allows the moves in can_become: a job cancelled while whisper runs stays cancelled when
+// the run finishes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    Error,
+    Cancelled,
+    // Was running when the app quit or crashed; resume_interrupted_jobs requeues it.
+    Stalled,
+}
+
+impl JobStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Done => "done",
+            Self::Error => "error",
+            Self::Cancelled => "cancelled",
+            Self::Stalled => "stalled",
+        }
+    }
+
+    // Waiting for or held by the worker: not to be deleted, sealed or moved.
+    pub(super) fn is_active(self) -> bool {
+        matches!(self, Self::Queued | Self::Running)
+    }
+
+    pub(super) fn can_become(self, next: JobStatus) -> bool {
+        matches!(
+            (self, next),
+            // Done straight from queued: a recovered job folder that already has a transcript.
+            (Self::Queued, Self::Running | Self::Done | Self::Cancelled)
+                | (Self::Running, Self::Done | Self::Error | Self::Cancelled | Self::Stalled)
+                | (Self::Stalled, Self::Queued | Self::Cancelled)
+                // Running it again, e.g. a skipped duplicate the user dismissed.
+                | (Self::Error | Self::Cancelled, Self::Queued)
+        )
+    }
+}
+
+impl std::fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct JobTransition {
+    pub from: JobStatus,
+    pub to: JobStatus,
+    // Unix seconds.
+    pub at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    pub filename: String,
+    pub status: JobStatus,
+    pub progress: f32,
+    pub stage: String,
+    pub logs: Vec<String>,
+    pub created_at: String,
+    pub audio_path: String,
+    pub transcript_txt_path: String,
+    pub transcript_json_path: String,
+    pub transcript_srt_path: String,
+    pub md_preview: Option<String>,
+    pub summary_status: Option<String>,
+    pub summary_model: Option<String>,
+    pub summary_error: Option<String>,
+    pub summary_md: Option<String>,
+    pub exported_to_obsidian: bool,
+    #[serde(default)]
+    pub media_info: Option<MediaInfo>,
+    #[serde(default)]
+    pub source_path: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub options: JobOptions,
+    #[serde(default)]
+    pub audio_sha256: Option<String>,
+    #[serde(default)]
+    pub duplicate_of: Option<String>,
+    #[serde(default)]
+    pub run_manifest: Option<RunManifest>,
+    #[serde(default)]
+    pub source_metadata: Option<SourceMetadata>,
+    #[serde(default)]
+    pub template_summaries: BTreeMap<String, TemplateSummary>,
+    #[serde(default)]
+    pub action_items_status: Option<String>,
+    // Derived from the transcript after transcription (LLM or keyword fallback).
+    #[serde(default)]
+    pub title: Option<String>,
+    // Language the summary was requested in; None when it followed the transcript.
+    #[serde(default)]
+    pub summary_language: Option<String>,
+    // Where the last Obsidian export wrote the note; reused so re-exports overwrite it.
+    #[serde(default)]
+    pub exported_path: Option<String>,
+    // sha256 of the note as the last export wrote it; a different file means the user edited it.
+    #[serde(default)]
+    pub exported_hash: Option<String>,
+    // Config profile active when the job was created; later pipeline exports use its vault
+    // and routes even after a switch. None for jobs from before profiles were recorded.
+    #[serde(default)]
+    pub profile: Option<String>,
+    // Export target id -> written file (markdown/logseq) or Joplin note id, for re-exports.
+    #[serde(default)]
+    pub target_exports: BTreeMap<String, String>,
+    // "exported" or "error"; unset until the first export.
+    #[serde(default)]
+    pub export_status: Option<String>,
+    #[serde(default)]
+    pub export_error: Option<String>,
+    // Calendar event overlapping the recording; its title names the job.
+    #[serde(default)]
+    pub calendar_event: Option<CalendarEvent>,
+    #[serde(default)]
+    pub favorite: bool,
+    // Set by rename_job; wins over the generated, calendar and Voice Memos titles.
+    #[serde(default)]
+    pub display_title: Option<String>,
+    // Free-form user notes, exported with the note and matched by search.
+    #[serde(default)]
+    pub notes: String,
+    // Audio and raw artifacts are packed into archive.zip; hidden from default listings.
+    #[serde(default)]
+    pub archived: bool,
+    // Unix seconds when delete_job moved the job to trash/; hidden everywhere but trash queries.
+    #[serde(default)]
+    pub trashed_at: Option<u64>,
+    // Every status change, oldest first; empty for jobs that haven't moved since they were created.
+    #[serde(default)]
+    pub transitions: Vec<JobTransition>,
+    // Lines push_log added that the job store hasn't written yet.
+    #[serde(skip)]
+    pub unsaved_logs: Vec<String>,
+}
+
+impl Job {
+    // The only way a job's status changes. A refused move leaves the job untouched.
+    pub(super) fn transition(&mut self, next: JobStatus) -> Result<(), String> {
+        if !self.status.can_become(next) {
+            return Err(format!("Job {} can't go from {} to {next}.", self.id, self.status));
+        }
+        self.transitions.push(JobTransition {
+            from: self.status,
+            to: next,
+            at: now_ts(),
+        });
+        self.status = next;
+        Ok(())
+    }
+}
+
+// For the pipeline, where a refused move means the user got there first (cancelled the job while
+// it ran): the refusal is logged and the caller leaves the job alone.
+pub(super) fn advance_job(job: &mut Job, next: JobStatus) -> bool {
+    match job.transition(next) {
+        Ok(()) => true,
+        Err(err) => {
+            tracing::warn!(job_id = %job.id, "{err}");
+            false
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CalendarEvent {
+    pub title: String,
+    // ISO 8601, UTC.
+    pub start: String,
+    pub end: String,
+    pub attendees: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub text: String,
+    pub owner: Option<String>,
+    // Free-form as spoken ("by Friday", "end of Q3"); not normalized to a date.
+    pub due: Option<String>,
+}
+
+// Metadata recovered from the app that produced the recording (currently Apple Voice Memos).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SourceMetadata {
+    pub title: Option<String>,
+    pub recorded_at: Option<String>,
+    pub location: Option<String>,
+}
+
+// Exactly how the transcript was produced, so results that differ across machines or
+// versions can be explained and rerun by hand.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunManifest {
+    pub app_version: String,
+    pub runs: Vec<WhisperRun>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhisperRun {
+    pub binary_path: String,
+    pub binary_version: Option<String>,
+    pub binary_sha256: Option<String>,
+    pub model_path: String,
+    pub model_sha256: Option<String>,
+    pub args: Vec<String>,
+    pub started_at: String,
+    pub duration_seconds: f64,
+    pub exit_code: Option<i32>,
+}
+
+// Per-job processing options chosen at import time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JobOptions {
+    // Optional time range (seconds) to transcribe instead of the whole recording.
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+    // Watch-folder preset overrides of AppConfig::model_size / language.
+    pub model_size: Option<String>,
+    pub language: Option<String>,
+    // ExportTarget id the job is exported to after transcription.
+    pub export_target: Option<String>,
+    // Run at full speed whatever power_saving_mode says.
+    pub ignore_power_state: bool,
+}
+
+impl JobOptions {
+    pub(super) fn trim_range(&self) -> Option<(f64, Option<f64>)> {
+        let start = self.start.unwrap_or(0.0).max(0.0);
+        let end = self.end.filter(|end| *end > start);
+        if start > 0.0 || end.is_some() {
+            Some((start, end))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MediaInfo {
+    pub duration_seconds: Option<f64>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u32>,
+    pub codec: Option<String>,
+    pub bit_rate: Option<u64>,
+    pub recorded_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Segment {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+    // Only set for stereo recordings transcribed per channel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chapter {
+    pub start: f64,
+    pub end: f64,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Waveform {
+    pub samples_per_second: u32,
+    pub duration_seconds: f64,
+    pub peaks: Vec<f32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SummaryTemplate {
+    pub id: String,
+    pub name: String,
+    pub prompt: String,
+    // "minutes" runs on the speaker-labelled transcript and adds an attendee list; empty is a
+    // plain prompt over the transcript text.
+    pub mode: String,
+}
+
+pub(super) fn default_summary_templates() -> Vec<SummaryTemplate> {
+    vec![
+        SummaryTemplate {
+            id: "action_items".to_string(),
+            name: "Action items".to_string(),
+            prompt: "List every action item from the transcript as a Markdown checklist \
+                     (- [ ] task — owner, due date if mentioned).\n\n{text}"
+                .to_string(),
+            mode: String::new(),
+        },
+        SummaryTemplate {
+            id: "decisions".to_string(),
+            name: "Decisions".to_string(),
+            prompt: "List the decisions made in the transcript as bullet points, each with a \
+                     short rationale if one was given.\n\n{text}"
+                .to_string(),
+            mode: String::new(),
+        },
+        SummaryTemplate {
+            id: "minutes".to_string(),
+            name: "Minutes".to_string(),
+            prompt: "Write meeting minutes for the transcript below. Each line starts with the \
+                     speaker's name. Use exactly these Markdown sections:\n\
+                     ## Key points by speaker\n(one ### heading per speaker with bullet points)\n\
+                     ## Decisions\n## Action items\n(- [ ] task — owner, due date if mentioned)\n\
+                     Write \"None\" under a section with nothing to report.\n\n{text}"
+                .to_string(),
+            mode: MINUTES_MODE.to_string(),
+        },
+    ]
+}
+
+pub(super) const MINUTES_MODE: &str = "minutes";
+
+// Consecutive lines from the same speaker are merged so the model sees turns, not fragments.
+pub(super) fn speaker_transcript(segments: &[Segment]) -> (Vec<String>, String) {
+    let mut speakers: Vec<String> = Vec::new();
+    let mut turns: Vec<(String, String)> = Vec::new();
+    for seg in segments {
+        let (Some(speaker), text) = (seg.speaker.as_deref(), seg.text.trim()) else {
+            continue;
+        };
+        if text.is_empty() {
+            continue;
+        }
+        if !speakers.iter().any(|s| s == speaker) {
+            speakers.push(speaker.to_string());
+        }
+        match turns.last_mut() {
+            Some((last, line)) if last == speaker => {
+                line.push(' ');
+                line.push_str(text);
+            }
+            _ => turns.push((speaker.to_string(), text.to_string())),
+        }
+    }
+    let text = turns
+        .iter()
+        .map(|(speaker, line)| format!("{speaker}: {line}\n"))
+        .collect();
+    (speakers, text)
+}
+
+// The attendee list comes from the diarization labels rather than the model, so any
+// Attendees section the model wrote is replaced.
+pub(super) fn render_minutes(speakers: &[String], output: &str) -> String {
+    let mut minutes = String::from("## Attendees\n\n");
+    for speaker in speakers {
+        minutes.push_str(&format!("- {speaker}\n"));
+    }
+    minutes.push('\n');
+    let mut skipping = false;
+    for line in output.trim().lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            skipping = heading.trim().eq_ignore_ascii_case("attendees");
+        }
+        if !skipping {
+            minutes.push_str(line);
+            minutes.push('\n');
+        }
+    }
+    minutes
+}
+
+// Nests a template's own sections under the note heading it is exported with.
+pub(super) fn demote_headings(markdown: &str) -> String {
+    markdown
+        .lines()
+        .map(|line| {
+            if line.starts_with('#') {
+                format!("#{line}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Status of a non-default template run; the output itself lives in <template id>.md.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateSummary {
+    pub status: String,
+    pub model: Option<String>,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummaryResponse {
+    pub summary_status: String,
+    pub summary_model: String,
+    pub summary_error: Option<String>,
+    pub summary_md: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnswerCitation {
+    pub index: usize,
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptAnswer {
+    pub answer: String,
+    pub model: String,
+    pub citations: Vec<AnswerCitation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemanticMatch {
+    pub job_id: String,
+    pub title: String,
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct EmbeddedChunk {
+    pub(super) start: f32,
+    pub(super) end: f32,
+    pub(super) text: String,
+    pub(super) vector: Vec<f32>,
+}
+
+// One row per job in the embeddings table of jobs.db; rebuilt when the model or the transcript
+// changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct EmbeddingIndex {
+    pub(super) model: String,
+    pub(super) transcript_modified_at: Option<u64>,
+    pub(super) chunks: Vec<EmbeddedChunk>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummaryVersion {
+    pub version: u32,
+    pub path: String,
+    pub modified_at: Option<u64>,
+    pub summary_md: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelDownloadStatus {
+    pub state: String,
+    pub model_size: String,
+    pub repo_id: String,
+    pub total_bytes: u64,
+    pub downloaded_bytes: u64,
+    pub message: Option<String>,
+    pub started_at: Option<u64>,
+    pub finished_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelVerification {
+    pub ok: bool,
+    pub model_size: String,
+    pub path: String,
+    pub size_bytes: u64,
+    pub expected_min_bytes: u64,
+    pub message: Option<String>,
+}
+
+// A ggml model in the app's models folder, as shown by list_installed_models.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstalledModel {
+    // "small", "large-v3", ...; None for files that don't follow the ggml-<size>.bin naming.
+    pub model_size: Option<String>,
+    pub name: String,
+    pub path: String,
+    pub bytes: u64,
+    // Unix seconds of the last transcription that used it; None if never used since tracking began.
+    pub last_used: Option<u64>,
+    // Catalog name of a custom model.
+    pub display_name: Option<String>,
+    // False for files in a shared models folder that the app didn't download.
+    pub deletable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct JobLogEvent {
+    pub(super) id: String,
+    pub(super) line: String,
+}
+
+pub struct ModelDownloadState {
+    // AppConfig::models_dir when set, else <app data>/models; changes with update_config.
+    pub(super) models_dir: Mutex<PathBuf>,
+    // <app data>/models, still searched for models after models_dir points elsewhere.
+    pub(super) default_models_dir: PathBuf,
+    pub(super) whisper_dir: PathBuf,
+    pub(super) ffmpeg_dir: PathBuf,
+    pub(super) statuses: Arc<Mutex<HashMap<String, ModelDownloadStatus>>>,
+    // Model files already verified this session, so the check runs once per launch.
+    pub(super) verified_models: Mutex<HashSet<PathBuf>>,
+    // Status key -> cancel flag of the download in flight, checked by download_to_file.
+    pub(super) cancel_flags: DownloadCancelFlags,
+}
+
+pub(super) type DownloadCancelFlags = Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>;
+
+// The cancel flag of one download. It leaves cancel_flags when dropped at the end of the
+// download, so the map only ever holds downloads in flight.
+pub(super) struct DownloadCancel {
+    pub(super) flags: DownloadCancelFlags,
+    pub(super) key: String,
+    pub(super) flag: Arc<AtomicBool>,
+}
+
+impl DownloadCancel {
+    pub(super) fn register(flags: &DownloadCancelFlags, key: &str) -> Self {
+        let flag = Arc::new(AtomicBool::new(false));
+        if let Ok(mut flags) = flags.lock() {
+            flags.insert(key.to_string(), flag.clone());
+        }
+        Self {
+            flags: flags.clone(),
+            key: key.to_string(),
+            flag,
+        }
+    }
+}
+
+impl std::ops::Deref for DownloadCancel {
+    type Target = AtomicBool;
+
+    fn deref(&self) -> &AtomicBool {
+        &self.flag
+    }
+}
+
+impl Drop for DownloadCancel {
+    fn drop(&mut self) {
+        let mut flags = self.flags.lock().unwrap_or_else(|e| e.into_inner());
+        // A newer download under the same key keeps its own flag.
+        if flags.get(&self.key).is_some_and(|flag| Arc::ptr_eq(flag, &self.flag)) {
+            flags.remove(&self.key);
+        }
+    }
+}
+
+// bizenlabs repackages whisper.cpp for macOS; elsewhere whisper.cpp's own releases are used.
+pub(super) fn default_whisper_binary_url() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "https://github.com/bizenlabs/whisper-cpp-macos-bin/releases/latest"
+    } else {
+        "https://github.com/ggml-org/whisper.cpp/releases/latest"
+    }
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            initialized: false,
+            active_profile: DEFAULT_PROFILE.to_string(),
+            profiles: Vec::new(),
+            vault_path: String::new(),
+            output_subfolder: "VoiceNote".to_string(),
+            export_routes: Vec::new(),
+            model_size: "small".to_string(),
+            preload_model: false,
+            language: Some("en".to_string()),
+            enable_summarization: true,
+            auto_summarize_after_transcription: true,
+            auto_export_after_transcription: false,
+            auto_export_after_summarization: false,
+            summary_language: "auto".to_string(),
+            llm_provider: "ollama".to_string(),
+            ollama_base_url: "http://127.0.0.1:11434".to_string(),
+            ollama_model: "qwen2.5:7b-instruct".to_string(),
+            openai_base_url: "http://127.0.0.1:1234/v1".to_string(),
+            openai_model: String::new(),
+            openai_api_key_secret: String::new(),
+            llm_context_tokens: 8192,
+            llm_timeout_seconds: 120,
+            llm_max_retries: 2,
+            summary_concurrency: 1,
+            extractive_fallback: true,
+            semantic_index: false,
+            embedding_model: "nomic-embed-text".to_string(),
+            summary_prompt: "Summarize the transcript.".to_string(),
+            summary_templates: default_summary_templates(),
+            extract_action_items: true,
+            auto_title_tags: true,
+            include_timestamps: true,
+            obsidian_note_template: String::new(),
+            obsidian_audio_mode: "link".to_string(),
+            obsidian_attachments_folder: String::new(),
+            obsidian_timestamp_links: false,
+            obsidian_path_template: String::new(),
+            daily_note_mode: "off".to_string(),
+            daily_note_path: "{date}.md".to_string(),
+            calendar_enabled: false,
+            calendar_source: String::new(),
+            calendar_username: String::new(),
+            export_targets: Vec::new(),
+            vault_vocabulary_enabled: false,
+            subtitle_max_line_chars: 42,
+            subtitle_max_lines: 2,
+            subtitle_speaker_prefix: true,
+            watch_inbox_enabled: false,
+            inbox_poll_seconds: 10,
+            watch_folders: Vec::new(),
+            inbox_after_import: "keep".to_string(),
+            inbox_stable_polls: 2,
+            inbox_min_age_seconds: 5,
+            voice_memos_import_enabled: false,
+            voice_memos_path: String::new(),
+            close_to_tray: false,
+            power_saving_mode: "off".to_string(),
+            power_saving_threads: 2,
+            normalize_loudness: false,
+            reduce_noise: false,
+            whisper_json_retention: "full".to_string(),
+            clip_cache_max_mb: 512,
+            notify_on_success: false,
+            notify_on_failure: false,
+            notify_on_summary: false,
+            sound_on_success: false,
+            sound_on_failure: false,
+            respect_do_not_disturb: true,
+            mock_pipeline: false,
+            audio_retention: "keep_all".to_string(),
+            translation_target_language: "English".to_string(),
+            split_stereo_channels: false,
+            left_channel_label: "Caller".to_string(),
+            right_channel_label: "Callee".to_string(),
+            whisper_binary_url: Some(default_whisper_binary_url().to_string()),
+            ffmpeg_binary_url: Some(format!(
+                "https://github.com/ravaru/voicenoteapp/releases/latest/download/ffmpeg-{}-lgpl.zip",
+                Platform::current().asset_tag()
+            )),
+            use_system_binaries: true,
+            models_dir: String::new(),
+            download_proxy: String::new(),
+            download_no_proxy: String::new(),
+            huggingface_mirror: String::new(),
+            github_mirror: String::new(),
+            download_speed_limit_kbps: 0,
+            trash_retention_days: 30,
+            encrypt_job_files: false,
+            encrypt_job_audio: false,
+            allow_plaintext_secrets: false,
+        }
+    }
+}
@@ -0,0 +1,644 @@
+// Renders a job for sharing outside Obsidian: the ShareDocument model and its HTML, DOCX,
+// PDF, markdown and Logseq forms. export_job and export_to_target write them.
+
+use super::*;
+
+// Block-level view of a summary shared by the HTML, DOCX and PDF exports; inline markdown
+// (emphasis, code, links) is flattened to plain text.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum DocBlock {
+    Heading(usize, String),
+    Bullet(String),
+    Paragraph(String),
+}
+
+fn strip_inline_markdown(text: &str) -> String {
+    let mut out = text.replace("**", "").replace("__", "").replace('`', "");
+    // [label](url) -> label
+    while let Some(open) = out.find('[') {
+        let Some(mid) = out[open..].find("](").map(|i| open + i) else { break };
+        let Some(close) = out[mid..].find(')').map(|i| mid + i) else { break };
+        out = format!("{}{}{}", &out[..open], &out[open + 1..mid], &out[close + 1..]);
+    }
+    out.trim().to_string()
+}
+
+pub(super) fn markdown_blocks(markdown: &str) -> Vec<DocBlock> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let flush = |paragraph: &mut Vec<String>, blocks: &mut Vec<DocBlock>| {
+        if !paragraph.is_empty() {
+            blocks.push(DocBlock::Paragraph(strip_inline_markdown(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+    };
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        let level = trimmed.chars().take_while(|ch| *ch == '#').count();
+        if level > 0 && trimmed[level..].starts_with(' ') {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(DocBlock::Heading(level, strip_inline_markdown(&trimmed[level..])));
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .or_else(|| {
+                let digits = trimmed.chars().take_while(|ch| ch.is_ascii_digit()).count();
+                (digits > 0).then(|| trimmed[digits..].strip_prefix(". ")).flatten()
+            })
+        {
+            flush(&mut paragraph, &mut blocks);
+            let item = item.strip_prefix("[ ] ").or_else(|| item.strip_prefix("[x] ")).unwrap_or(item);
+            blocks.push(DocBlock::Bullet(strip_inline_markdown(item)));
+        } else if trimmed.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+        } else {
+            paragraph.push(trimmed.to_string());
+        }
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+// Same layout for every export_job format: title, metadata table, summary, timestamped transcript.
+#[derive(Debug, Clone, Default)]
+pub(super) struct ShareDocument {
+    pub(super) title: String,
+    pub(super) metadata: Vec<(String, String)>,
+    pub(super) summary: Vec<DocBlock>,
+    // The summary as written, for markdown targets that keep its formatting.
+    pub(super) summary_markdown: String,
+    // (timestamp, text); the timestamp is empty when only the plain transcript exists.
+    pub(super) transcript: Vec<(String, String)>,
+}
+
+pub(super) fn build_share_document(job: &Job, config: &AppConfig) -> Result<ShareDocument, String> {
+    let vars = PromptVars::for_job(job, config);
+    let mut metadata = vec![("Date".to_string(), vars.date.clone())];
+    if !vars.duration.is_empty() {
+        metadata.push(("Duration".to_string(), vars.duration.clone()));
+    }
+    if vars.language != "auto" {
+        metadata.push(("Language".to_string(), vars.language.clone()));
+    }
+    metadata.push(("Source".to_string(), job.filename.clone()));
+    if !job.tags.is_empty() {
+        metadata.push(("Tags".to_string(), job.tags.join(", ")));
+    }
+    if let Some(model) = job.summary_model.as_ref().filter(|model| !model.is_empty()) {
+        metadata.push(("Summary model".to_string(), model.clone()));
+    }
+    if !job.notes.trim().is_empty() {
+        metadata.push(("Notes".to_string(), job.notes.trim().to_string()));
+    }
+
+    let summary = stored_summary(job);
+    let segments = load_job_segments(job).unwrap_or_default();
+    let transcript: Vec<(String, String)> = if segments.is_empty() {
+        let text = if job.transcript_txt_path.is_empty() {
+            String::new()
+        } else {
+            read_transcript_text(&job.transcript_txt_path).unwrap_or_default()
+        };
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| (String::new(), line.to_string()))
+            .collect()
+    } else {
+        segments
+            .iter()
+            .filter(|seg| !seg.text.trim().is_empty())
+            .map(|seg| {
+                let text = match seg.speaker.as_deref() {
+                    Some(speaker) => format!("{speaker}: {}", seg.text.trim()),
+                    None => seg.text.trim().to_string(),
+                };
+                (format_timestamp(seg.start), text)
+            })
+            .collect()
+    };
+    if summary.trim().is_empty() && transcript.is_empty() {
+        return Err("Nothing to export: the job has no transcript or summary yet.".to_string());
+    }
+    Ok(ShareDocument {
+        title: job_title(job),
+        metadata,
+        summary: markdown_blocks(&summary),
+        summary_markdown: summary.trim().to_string(),
+        transcript,
+    })
+}
+
+// Drops what XML 1.0 can't carry even escaped: C0 controls other than tab and line breaks,
+// and U+FFFE/U+FFFF. Transcripts pick them up from bad decodes and pasted text.
+pub(super) fn xml_text(text: &str) -> String {
+    text.chars()
+        .filter(|ch| !matches!(ch, '\u{0}'..='\u{8}' | '\u{b}' | '\u{c}' | '\u{e}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}'))
+        .collect()
+}
+
+pub(super) fn escape_xml(text: &str) -> String {
+    xml_text(text)
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub(super) fn render_share_html(doc: &ShareDocument) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n\
+         body {{ font-family: -apple-system, \"Segoe UI\", Helvetica, Arial, sans-serif; max-width: 760px; margin: 40px auto; padding: 0 16px; line-height: 1.5; color: #222; }}\n\
+         table {{ border-collapse: collapse; margin-bottom: 24px; }}\n\
+         th, td {{ border: 1px solid #ddd; padding: 4px 10px; text-align: left; }}\n\
+         th {{ background: #f5f5f5; }}\n\
+         .ts {{ color: #888; font-variant-numeric: tabular-nums; margin-right: 8px; }}\n\
+         .transcript p {{ margin: 4px 0; }}\n\
+         </style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        escape_xml(&doc.title),
+        escape_xml(&doc.title)
+    );
+    if !doc.metadata.is_empty() {
+        out.push_str("<table>\n");
+        for (key, value) in &doc.metadata {
+            out.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", escape_xml(key), escape_xml(value)));
+        }
+        out.push_str("</table>\n");
+    }
+    if !doc.summary.is_empty() {
+        out.push_str("<h2>Summary</h2>\n");
+        let mut in_list = false;
+        for block in &doc.summary {
+            let is_bullet = matches!(block, DocBlock::Bullet(_));
+            if in_list && !is_bullet {
+                out.push_str("</ul>\n");
+            } else if !in_list && is_bullet {
+                out.push_str("<ul>\n");
+            }
+            in_list = is_bullet;
+            match block {
+                DocBlock::Heading(level, text) => {
+                    let level = (level + 1).min(6);
+                    out.push_str(&format!("<h{level}>{}</h{level}>\n", escape_xml(text)));
+                }
+                DocBlock::Bullet(text) => out.push_str(&format!("<li>{}</li>\n", escape_xml(text))),
+                DocBlock::Paragraph(text) => out.push_str(&format!("<p>{}</p>\n", escape_xml(text))),
+            }
+        }
+        if in_list {
+            out.push_str("</ul>\n");
+        }
+    }
+    if !doc.transcript.is_empty() {
+        out.push_str("<h2>Transcript</h2>\n<div class=\"transcript\">\n");
+        for (timestamp, text) in &doc.transcript {
+            if timestamp.is_empty() {
+                out.push_str(&format!("<p>{}</p>\n", escape_xml(text)));
+            } else {
+                out.push_str(&format!(
+                    "<p><span class=\"ts\">[{timestamp}]</span>{}</p>\n",
+                    escape_xml(text)
+                ));
+            }
+        }
+        out.push_str("</div>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+// docx-rs escapes markup but passes control characters through, so text goes via xml_text.
+fn docx_run(text: &str) -> docx_rs::Run {
+    docx_rs::Run::new().add_text(xml_text(text))
+}
+
+fn docx_paragraph(style: &str, run: docx_rs::Run) -> docx_rs::Paragraph {
+    docx_rs::Paragraph::new().style(style).add_run(run)
+}
+
+pub(super) fn render_share_docx(doc: &ShareDocument) -> Result<Vec<u8>, String> {
+    use docx_rs::{
+        Docx, LineSpacing, PageMargin, Paragraph, RunFonts, SpecialIndentType, Style, StyleType, Table, TableBorder,
+        TableBorderPosition, TableCell, TableRow, WidthType,
+    };
+
+    let spacing = |before: u32, after: u32| LineSpacing::new().before(before).after(after);
+    let heading = |id: &str, name: &str, size: usize, level: usize, before: u32| {
+        Style::new(id, StyleType::Paragraph)
+            .name(name)
+            .based_on("Normal")
+            .bold()
+            .size(size)
+            .outline_lvl(level)
+            .line_spacing(spacing(before, 120))
+    };
+    let mut docx = Docx::new()
+        .page_size(11906, 16838)
+        .page_margin(PageMargin::new().top(1134).right(1134).bottom(1134).left(1134))
+        .default_fonts(RunFonts::new().ascii("Calibri").hi_ansi("Calibri").cs("Calibri"))
+        .default_size(22)
+        .add_style(Style::new("Normal", StyleType::Paragraph).name("Normal").line_spacing(spacing(0, 120)))
+        .add_style(
+            Style::new("Title", StyleType::Paragraph)
+                .name("Title")
+                .based_on("Normal")
+                .bold()
+                .size(40)
+                .line_spacing(spacing(0, 240)),
+        )
+        .add_style(heading("Heading1", "heading 1", 30, 0, 240))
+        .add_style(heading("Heading2", "heading 2", 26, 1, 200))
+        .add_style(
+            Style::new("ListBullet", StyleType::Paragraph)
+                .name("List Bullet")
+                .based_on("Normal")
+                .indent(Some(360), Some(SpecialIndentType::Hanging(360)), None, None),
+        )
+        .add_paragraph(docx_paragraph("Title", docx_run(&doc.title)));
+    if !doc.metadata.is_empty() {
+        let rows = doc
+            .metadata
+            .iter()
+            .map(|(key, value)| {
+                TableRow::new(vec![
+                    TableCell::new()
+                        .width(2200, WidthType::Dxa)
+                        .add_paragraph(Paragraph::new().add_run(docx_run(key).bold())),
+                    TableCell::new()
+                        .width(6800, WidthType::Dxa)
+                        .add_paragraph(Paragraph::new().add_run(docx_run(value))),
+                ])
+            })
+            .collect();
+        let mut table = Table::new(rows).set_grid(vec![2200, 6800]);
+        for position in [
+            TableBorderPosition::Top,
+            TableBorderPosition::Left,
+            TableBorderPosition::Bottom,
+            TableBorderPosition::Right,
+            TableBorderPosition::InsideH,
+            TableBorderPosition::InsideV,
+        ] {
+            table = table.set_border(TableBorder::new(position).size(4).color("DDDDDD"));
+        }
+        docx = docx.add_table(table);
+    }
+    if !doc.summary.is_empty() {
+        docx = docx.add_paragraph(docx_paragraph("Heading1", docx_run("Summary")).keep_next(true));
+        for block in &doc.summary {
+            docx = docx.add_paragraph(match block {
+                DocBlock::Heading(_, text) => docx_paragraph("Heading2", docx_run(text)).keep_next(true),
+                DocBlock::Bullet(text) => docx_paragraph("ListBullet", docx_run("•").add_tab().add_text(xml_text(text))),
+                DocBlock::Paragraph(text) => docx_paragraph("Normal", docx_run(text)),
+            });
+        }
+    }
+    if !doc.transcript.is_empty() {
+        docx = docx.add_paragraph(docx_paragraph("Heading1", docx_run("Transcript")).keep_next(true));
+        for (timestamp, text) in &doc.transcript {
+            let mut paragraph = Paragraph::new().style("Normal");
+            if !timestamp.is_empty() {
+                paragraph = paragraph.add_run(docx_run(&format!("[{timestamp}] ")).color("888888"));
+            }
+            docx = docx.add_paragraph(paragraph.add_run(docx_run(text)));
+        }
+    }
+    let mut out = std::io::Cursor::new(Vec::new());
+    docx.build()
+        .pack(&mut out)
+        .map_err(|err| format!("failed to write docx: {err}"))?;
+    Ok(out.into_inner())
+}
+
+// TrueType fonts with Cyrillic coverage tried for PDF export; without one the PDF falls back
+// to Helvetica, which only covers Latin-1.
+const PDF_FONT_CANDIDATES: [&str; 5] = [
+    "/System/Library/Fonts/Supplemental/Arial.ttf",
+    "/Library/Fonts/Arial.ttf",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+];
+
+pub(super) fn load_pdf_font() -> Option<Vec<u8>> {
+    PDF_FONT_CANDIDATES.iter().find_map(|path| {
+        let data = fs::read(path).ok()?;
+        let face = ttf_parser::Face::parse(&data, 0).ok()?;
+        // Only glyf-based fonts can be embedded as FontFile2.
+        face.tables().glyf?;
+        Some(data)
+    })
+}
+
+// Encodes and measures text for the single font a PDF export uses.
+enum PdfFont<'a> {
+    Embedded(Box<ttf_parser::Face<'a>>),
+    Helvetica,
+}
+
+impl PdfFont<'_> {
+    // Advance width in thousandths of the font size.
+    fn char_width(&self, ch: char) -> f32 {
+        match self {
+            PdfFont::Embedded(face) => {
+                let gid = face.glyph_index(ch).unwrap_or_default();
+                face.glyph_hor_advance(gid).unwrap_or(0) as f32 * 1000.0 / face.units_per_em() as f32
+            }
+            PdfFont::Helvetica => match ch {
+                ' ' | 'i' | 'j' | 'l' | '.' | ',' | ':' | ';' | '\'' | '!' | '|' => 278.0,
+                'm' | 'w' | 'M' | 'W' => 833.0,
+                ch if ch.is_uppercase() => 667.0,
+                _ => 556.0,
+            },
+        }
+    }
+
+    fn text_width(&self, text: &str, size: f32) -> f32 {
+        text.chars().map(|ch| self.char_width(ch)).sum::<f32>() * size / 1000.0
+    }
+
+    fn encode(&self, text: &str, used: &mut BTreeMap<u16, char>) -> Vec<u8> {
+        match self {
+            PdfFont::Embedded(face) => text
+                .chars()
+                .flat_map(|ch| {
+                    let gid = face.glyph_index(ch).unwrap_or_default().0;
+                    used.entry(gid).or_insert(ch);
+                    gid.to_be_bytes()
+                })
+                .collect(),
+            // WinAnsi matches Latin-1 for the printable range we care about.
+            PdfFont::Helvetica => text
+                .chars()
+                .map(|ch| match ch as u32 {
+                    code @ (0x20..=0x7e | 0xa0..=0xff) => code as u8,
+                    _ => b'?',
+                })
+                .collect(),
+        }
+    }
+}
+
+fn wrap_pdf_text(font: &PdfFont, text: &str, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{current} {word}")
+        };
+        if !current.is_empty() && font.text_width(&candidate, size) > max_width {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        } else {
+            current = candidate;
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+pub(super) fn render_share_pdf(doc: &ShareDocument, font_data: Option<&[u8]>) -> Vec<u8> {
+    use pdf_writer::types::{CidFontType, FontFlags, SystemInfo, UnicodeCmap};
+    use pdf_writer::{Content, Filter, Finish, Name, Pdf, Rect, Ref, Str};
+
+    const PAGE_WIDTH: f32 = 595.0;
+    const PAGE_HEIGHT: f32 = 842.0;
+    const MARGIN: f32 = 56.0;
+    const BODY: f32 = 10.5;
+    let max_width = PAGE_WIDTH - 2.0 * MARGIN;
+    let font = font_data
+        .and_then(|data| ttf_parser::Face::parse(data, 0).ok())
+        .map(|face| PdfFont::Embedded(Box::new(face)))
+        .unwrap_or(PdfFont::Helvetica);
+
+    // (size, indent, gray, gap before, text)
+    let mut lines: Vec<(f32, f32, f32, f32, String)> = Vec::new();
+    let mut push = |size: f32, indent: f32, gray: f32, gap: f32, text: &str| {
+        for (i, line) in wrap_pdf_text(&font, text, size, max_width - indent).into_iter().enumerate() {
+            lines.push((size, indent, gray, if i == 0 { gap } else { 0.0 }, line));
+        }
+    };
+    push(18.0, 0.0, 0.0, 0.0, &doc.title);
+    for (i, (key, value)) in doc.metadata.iter().enumerate() {
+        push(BODY, 0.0, 0.35, if i == 0 { 8.0 } else { 0.0 }, &format!("{key}: {value}"));
+    }
+    if !doc.summary.is_empty() {
+        push(14.0, 0.0, 0.0, 14.0, "Summary");
+        for block in &doc.summary {
+            match block {
+                DocBlock::Heading(_, text) => push(12.0, 0.0, 0.0, 8.0, text),
+                DocBlock::Bullet(text) => push(BODY, 12.0, 0.0, 2.0, &format!("• {text}")),
+                DocBlock::Paragraph(text) => push(BODY, 0.0, 0.0, 4.0, text),
+            }
+        }
+    }
+    if !doc.transcript.is_empty() {
+        push(14.0, 0.0, 0.0, 14.0, "Transcript");
+        for (timestamp, text) in &doc.transcript {
+            if timestamp.is_empty() {
+                push(BODY, 0.0, 0.0, 3.0, text);
+            } else {
+                push(BODY, 0.0, 0.0, 3.0, &format!("[{timestamp}] {text}"));
+            }
+        }
+    }
+
+    let mut used = BTreeMap::new();
+    let mut pages: Vec<Vec<u8>> = Vec::new();
+    let mut content = Content::new();
+    let mut y = PAGE_HEIGHT - MARGIN;
+    for (size, indent, gray, gap, text) in &lines {
+        let advance = gap + size * 1.4;
+        if y - advance < MARGIN && y < PAGE_HEIGHT - MARGIN {
+            pages.push(std::mem::replace(&mut content, Content::new()).finish());
+            y = PAGE_HEIGHT - MARGIN;
+        }
+        y -= advance;
+        let encoded = font.encode(text, &mut used);
+        content
+            .set_fill_gray(*gray)
+            .begin_text()
+            .set_font(Name(b"F1"), *size)
+            .next_line(MARGIN + indent, y)
+            .show(Str(&encoded))
+            .end_text();
+    }
+    pages.push(content.finish());
+
+    let mut pdf = Pdf::new();
+    let catalog_id = Ref::new(1);
+    let tree_id = Ref::new(2);
+    let font_id = Ref::new(3);
+    let mut next = 4;
+    let mut alloc = || {
+        next += 1;
+        Ref::new(next - 1)
+    };
+    pdf.catalog(catalog_id).pages(tree_id);
+    let page_ids: Vec<(Ref, Ref)> = pages.iter().map(|_| (alloc(), alloc())).collect();
+    pdf.pages(tree_id)
+        .kids(page_ids.iter().map(|(page, _)| *page))
+        .count(page_ids.len() as i32);
+    for ((page_id, content_id), data) in page_ids.iter().zip(&pages) {
+        let mut page = pdf.page(*page_id);
+        page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+            .parent(tree_id)
+            .contents(*content_id);
+        page.resources().fonts().pair(Name(b"F1"), font_id);
+        page.finish();
+        pdf.stream(*content_id, data);
+    }
+
+    match (&font, font_data) {
+        (PdfFont::Embedded(face), Some(data)) => {
+            let (cid_id, descriptor_id, file_id, cmap_id) = (alloc(), alloc(), alloc(), alloc());
+            let base_font = Name(b"VoiceNoteSans");
+            let system_info = SystemInfo {
+                registry: Str(b"Adobe"),
+                ordering: Str(b"Identity"),
+                supplement: 0,
+            };
+            let scale = 1000.0 / face.units_per_em() as f32;
+            pdf.type0_font(font_id)
+                .base_font(base_font)
+                .encoding_predefined(Name(b"Identity-H"))
+                .descendant_font(cid_id)
+                .to_unicode(cmap_id);
+            let mut cid = pdf.cid_font(cid_id);
+            cid.subtype(CidFontType::Type2)
+                .base_font(base_font)
+                .system_info(system_info)
+                .font_descriptor(descriptor_id)
+                .cid_to_gid_map_predefined(Name(b"Identity"));
+            let mut widths = cid.widths();
+            for gid in used.keys() {
+                let advance = face.glyph_hor_advance(ttf_parser::GlyphId(*gid)).unwrap_or(0);
+                widths.consecutive(*gid, [advance as f32 * scale]);
+            }
+            widths.finish();
+            cid.finish();
+            let bbox = face.global_bounding_box();
+            pdf.font_descriptor(descriptor_id)
+                .name(base_font)
+                .flags(FontFlags::NON_SYMBOLIC)
+                .bbox(Rect::new(
+                    bbox.x_min as f32 * scale,
+                    bbox.y_min as f32 * scale,
+                    bbox.x_max as f32 * scale,
+                    bbox.y_max as f32 * scale,
+                ))
+                .italic_angle(0.0)
+                .ascent(face.ascender() as f32 * scale)
+                .descent(face.descender() as f32 * scale)
+                .cap_height(face.capital_height().unwrap_or(face.ascender()) as f32 * scale)
+                .stem_v(80.0)
+                .font_file2(file_id);
+            // Only the outlines of the glyphs in use are kept (glyph ids don't change), and the
+            // zeroed rest compresses away.
+            let glyphs: Vec<u16> = used.keys().copied().collect();
+            let font_file = match subsetter::subset(data, 0, subsetter::Profile::pdf(&glyphs)) {
+                Ok(subset) => subset,
+                Err(err) => {
+                    tracing::warn!("font subsetting failed, embedding the whole font: {err}");
+                    data.to_vec()
+                }
+            };
+            pdf.stream(file_id, &miniz_oxide::deflate::compress_to_vec_zlib(&font_file, 6))
+                .filter(Filter::FlateDecode)
+                .pair(Name(b"Length1"), font_file.len() as i32);
+            let mut cmap = UnicodeCmap::new(Name(b"VoiceNote-UTF16"), system_info);
+            for (gid, ch) in &used {
+                cmap.pair(*gid, *ch);
+            }
+            pdf.cmap(cmap_id, &cmap.finish());
+        }
+        _ => {
+            pdf.type1_font(font_id)
+                .base_font(Name(b"Helvetica"))
+                .encoding_predefined(Name(b"WinAnsiEncoding"));
+        }
+    }
+    pdf.finish()
+}
+
+pub(super) fn render_share_markdown(doc: &ShareDocument) -> String {
+    let mut out = format!("# {}\n\n", doc.title);
+    if !doc.metadata.is_empty() {
+        out.push_str("| | |\n|---|---|\n");
+        for (key, value) in &doc.metadata {
+            out.push_str(&format!("| {key} | {} |\n", value.replace('|', "\\|")));
+        }
+        out.push('\n');
+    }
+    if !doc.summary_markdown.is_empty() {
+        out.push_str(&format!("## Summary\n\n{}\n\n", demote_headings(&doc.summary_markdown)));
+    }
+    if !doc.transcript.is_empty() {
+        out.push_str("## Transcript\n\n");
+        for (timestamp, text) in &doc.transcript {
+            if timestamp.is_empty() {
+                out.push_str(&format!("{text}\n\n"));
+            } else {
+                out.push_str(&format!("**[{timestamp}]** {text}\n\n"));
+            }
+        }
+    }
+    format!("{}\n", out.trim_end())
+}
+
+// One top-level journal block per job; the voicenote-id property lets re-exports find it.
+pub(super) fn render_logseq_block(doc: &ShareDocument, job_id: &str) -> String {
+    let mut out = format!("- {}\n  voicenote-id:: {job_id}\n", doc.title);
+    for (key, value) in &doc.metadata {
+        out.push_str(&format!("  {}:: {value}\n", key.to_lowercase().replace(' ', "-")));
+    }
+    if !doc.summary.is_empty() {
+        out.push_str("\t- Summary\n");
+        for block in &doc.summary {
+            match block {
+                DocBlock::Heading(_, text) => out.push_str(&format!("\t\t- **{text}**\n")),
+                DocBlock::Bullet(text) | DocBlock::Paragraph(text) => out.push_str(&format!("\t\t- {text}\n")),
+            }
+        }
+    }
+    if !doc.transcript.is_empty() {
+        out.push_str("\t- Transcript\n\t  collapsed:: true\n");
+        for (timestamp, text) in &doc.transcript {
+            if timestamp.is_empty() {
+                out.push_str(&format!("\t\t- {text}\n"));
+            } else {
+                out.push_str(&format!("\t\t- [{timestamp}] {text}\n"));
+            }
+        }
+    }
+    out
+}
+
+pub(super) fn merge_logseq_journal(existing: &str, job_id: &str, block: &str) -> String {
+    let lines: Vec<&str> = existing.lines().collect();
+    let marker = format!("voicenote-id:: {job_id}");
+    let block_end = |start: usize| {
+        lines[start + 1..]
+            .iter()
+            .position(|line| line.starts_with("- "))
+            .map_or(lines.len(), |offset| start + 1 + offset)
+    };
+    let found = (0..lines.len())
+        .filter(|&i| lines[i].starts_with("- "))
+        .find(|&i| lines[i + 1..block_end(i)].iter().any(|line| line.trim() == marker));
+    match found {
+        Some(start) => {
+            let mut out: String = lines[..start].iter().map(|line| format!("{line}\n")).collect();
+            out.push_str(block);
+            for line in &lines[block_end(start)..] {
+                out.push_str(&format!("{line}\n"));
+            }
+            out
+        }
+        None if existing.trim().is_empty() => block.to_string(),
+        None => format!("{}\n{block}", existing.trim_end()),
+    }
+}
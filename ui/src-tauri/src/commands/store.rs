@@ -0,0 +1,3598 @@
+// Where jobs and settings live: config on disk, the job store and its index, job folders and
+// their encryption, secrets, imports, trash, bundles and the app data folder.
+
+use super::*;
+
+// Written next to the platform app data folder by move_app_data; holds the new location.
+pub(super) const APP_DATA_LOCATION_FILE: &str = "voicenote.location";
+
+// Set while move_app_data copies the folder, until the restart. Summary workers, the inbox and
+// config watchers and the log file hold off so nothing writes into a tree being copied away.
+pub(super) static APP_DATA_MOVING: AtomicBool = AtomicBool::new(false);
+
+pub(super) fn app_data_moving() -> bool {
+    APP_DATA_MOVING.load(Ordering::SeqCst)
+}
+
+// <app data>/voicenote, or wherever move_app_data relocated it.
+pub(super) fn app_data_root(app: &Core) -> Result<PathBuf, String> {
+    let base_dir = app.app_data_dir()?;
+    let Some(root) = relocated_app_data_root(&base_dir) else {
+        return Ok(base_dir.join("voicenote"));
+    };
+    // Creating it afresh would silently start an empty library on the internal disk.
+    if !root.is_dir() {
+        return Err(format!(
+            "App data was moved to {} which is not available. Connect the drive, or delete {} to start with an empty library.",
+            root.display(),
+            base_dir.join(APP_DATA_LOCATION_FILE).display()
+        ));
+    }
+    Ok(root)
+}
+
+pub(super) fn relocated_app_data_root(base_dir: &Path) -> Option<PathBuf> {
+    let location = fs::read_to_string(base_dir.join(APP_DATA_LOCATION_FILE)).ok()?;
+    Some(PathBuf::from(location.trim())).filter(|path| path.is_absolute())
+}
+
+// AppConfig::models_dir with a leading ~/ expanded, or `default` when it is empty.
+pub(super) fn configured_models_dir(config: &AppConfig, default: &Path) -> PathBuf {
+    let value = config.models_dir.trim();
+    if value.is_empty() {
+        return default.to_path_buf();
+    }
+    match (value.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(value),
+    }
+}
+
+pub(super) fn emit_job_updated(app: &Core, job: &Job) {
+    // Fire-and-forget so UI can update without polling in Tauri mode.
+    let revision = app.state::<JobIndexState>().record_change(&job.id, false);
+    let _ = app.emit("job:updated", Revisioned { value: job, revision });
+}
+
+pub(super) fn emit_job_log(app: &Core, job_id: &str, line: &str) {
+    // Lines kept in the job are logged by push_log; this is every line the UI was shown.
+    tracing::debug!(job_id, "{line}");
+    // Small payload so UI can append to its log buffer.
+    let payload = JobLogEvent {
+        id: job_id.to_string(),
+        line: line.to_string(),
+    };
+    let _ = app.emit("job:log", payload);
+}
+
+// Job log lines stay in jobs.db (sealed when encryption is on); they name files and can quote
+// the transcript, so they are not copied into the tracing log files.
+pub(super) fn push_log(job: &mut Job, line: &str) {
+    // Keep a bounded in-memory log buffer to avoid unbounded growth.
+    job.logs.push(line.to_string());
+    job.unsaved_logs.push(line.to_string());
+    if job.logs.len() > JOB_LOG_LIMIT {
+        let excess = job.logs.len() - JOB_LOG_LIMIT;
+        job.logs.drain(0..excess);
+    }
+}
+
+pub struct ConfigState {
+    pub(super) path: PathBuf,
+    pub(super) config: Mutex<AppConfig>,
+    // AppConfig::download_speed_limit_kbps, shared with downloads in flight.
+    pub(super) download_speed_limit: Arc<AtomicU64>,
+    // "user:password" for download_proxy, kept in the keychain rather than config.json.
+    pub(super) proxy_credentials: Mutex<Option<String>>,
+    // STATE_REVISION at the last apply_config; 0 until the config first changes.
+    pub(super) revision: AtomicU64,
+}
+
+impl ConfigState {
+    pub fn load(app: &Core) -> Result<Self, String> {
+        let app_dir = app_data_root(app)?;
+        fs::create_dir_all(&app_dir)
+            .map_err(|err| format!("failed to create app data dir: {err}"))?;
+        let path = app_dir.join("config.json");
+        let mut config = load_config_from_disk(&path)?;
+        sync_secret_storage(&config);
+        // Older versions kept the proxy credentials in the URL in config.json.
+        if store_proxy_credentials(app, &mut config)? {
+            save_config_to_disk(&path, &config)?;
+        }
+        sync_job_encryption(app, &config)?;
+        Ok(Self {
+            path,
+            download_speed_limit: Arc::new(AtomicU64::new(config.download_speed_limit_kbps)),
+            proxy_credentials: Mutex::new(load_proxy_credentials(app)),
+            config: Mutex::new(config),
+            revision: AtomicU64::new(0),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct JobIndex {
+    pub(super) jobs: Vec<Job>,
+}
+
+pub struct JobIndexState {
+    pub(super) store: JobStore,
+    pub(super) jobs_dir: PathBuf,
+    pub(super) index: Mutex<JobIndex>,
+    // Latest revision per job id touched since launch, removals included; see get_changes_since.
+    pub(super) changes: Mutex<HashMap<String, JobChange>>,
+}
+
+// One counter shared by the job index and the config, so a window can catch up on both with a
+// single number. It starts at 0 on every launch; windows never outlive the process.
+pub(super) static STATE_REVISION: AtomicU64 = AtomicU64::new(0);
+
+pub(super) fn next_state_revision() -> u64 {
+    STATE_REVISION.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct JobChange {
+    pub(super) revision: u64,
+    pub(super) removed: bool,
+}
+
+// The payload of job:updated and config:updated (and of get_config): the value's own fields
+// plus the revision it was current at, so existing listeners keep reading the same shape.
+#[derive(Debug, Clone, Serialize)]
+pub struct Revisioned<T> {
+    #[serde(flatten)]
+    pub value: T,
+    pub revision: u64,
+}
+
+// What changed after the revision a window last saw; `revision` is the one to ask from next.
+#[derive(Debug, Clone, Serialize)]
+pub struct StateChanges {
+    pub revision: u64,
+    pub jobs: Vec<Job>,
+    pub removed_job_ids: Vec<String>,
+    // Only set when the config changed too.
+    pub config: Option<AppConfig>,
+}
+
+impl JobIndexState {
+    pub fn load(app: &Core) -> Result<Self, String> {
+        let app_dir = app_data_root(app)?;
+        fs::create_dir_all(&app_dir)
+            .map_err(|err| format!("failed to create app data dir: {err}"))?;
+        let jobs_dir = app_dir.join("jobs");
+        fs::create_dir_all(&jobs_dir)
+            .map_err(|err| format!("failed to create jobs dir: {err}"))?;
+        let store = JobStore::open(&app_dir.join(JOB_STORE_FILE))?;
+        store.migrate_from_json(&app_dir.join("index.json"))?;
+        let index = store.load()?;
+        Ok(Self {
+            store,
+            jobs_dir,
+            index: Mutex::new(index),
+            changes: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub(super) fn record_change(&self, job_id: &str, removed: bool) -> u64 {
+        let revision = next_state_revision();
+        if let Ok(mut changes) = self.changes.lock() {
+            changes.insert(job_id.to_string(), JobChange { revision, removed });
+        }
+        revision
+    }
+
+    // Current copies of the jobs changed after `since` and the ids of those removed since then.
+    pub(super) fn changes_since(&self, since: u64) -> Result<(Vec<Job>, Vec<String>), String> {
+        let changed: Vec<(String, JobChange)> = self
+            .changes
+            .lock()
+            .map_err(|_| "job changes mutex poisoned".to_string())?
+            .iter()
+            .filter(|(_, change)| change.revision > since)
+            .map(|(id, change)| (id.clone(), *change))
+            .collect();
+        let guard = self
+            .index
+            .lock()
+            .map_err(|_| "job index mutex poisoned".to_string())?;
+        let mut jobs = Vec::new();
+        let mut removed = Vec::new();
+        for (id, change) in changed {
+            match guard.jobs.iter().find(|job| job.id == id) {
+                Some(job) if !change.removed => jobs.push(job.clone()),
+                _ => removed.push(id),
+            }
+        }
+        // Same newest-first order as list_jobs.
+        jobs.sort_by_key(|job| guard.jobs.iter().position(|other| other.id == job.id));
+        removed.sort();
+        Ok((jobs, removed))
+    }
+}
+
+// Selects jobs for the job list (query_jobs) and for bulk operations. Empty fields match
+// everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JobFilter {
+    pub ids: Vec<String>,
+    pub statuses: Vec<String>,
+    pub stages: Vec<String>,
+    // Jobs carrying any of these tags.
+    pub tags: Vec<String>,
+    pub favorite: Option<bool>,
+    // None matches archived and unarchived jobs; query_jobs hides archived ones unless `search`
+    // is set.
+    pub archived: Option<bool>,
+    // true selects only the trash; otherwise trashed jobs are left out.
+    pub trashed: bool,
+    // Unix seconds, compared against the job's created_at.
+    pub created_after: Option<u64>,
+    pub created_before: Option<u64>,
+    // "not_started" matches jobs that were never summarized.
+    pub summary_statuses: Vec<String>,
+    // Case-insensitive match against the filename, title and notes.
+    pub search: String,
+}
+
+impl JobFilter {
+    pub(super) fn matches(&self, job: &Job) -> bool {
+        let created = job.created_at.parse::<u64>().ok();
+        let search = self.search.trim().to_lowercase();
+        (self.ids.is_empty() || self.ids.contains(&job.id))
+            && (self.statuses.is_empty() || self.statuses.iter().any(|status| status == job.status.as_str()))
+            && (self.stages.is_empty() || self.stages.contains(&job.stage))
+            && (self.tags.is_empty() || self.tags.iter().any(|tag| job.tags.contains(tag)))
+            && self.favorite.is_none_or(|favorite| job.favorite == favorite)
+            && self.archived.is_none_or(|archived| job.archived == archived)
+            && job.trashed_at.is_some() == self.trashed
+            && self
+                .created_after
+                .is_none_or(|after| created.is_some_and(|c| c >= after))
+            && self
+                .created_before
+                .is_none_or(|before| created.is_some_and(|c| c < before))
+            && (self.summary_statuses.is_empty()
+                || self
+                    .summary_statuses
+                    .iter()
+                    .any(|status| status == job.summary_status.as_deref().unwrap_or("not_started")))
+            && (search.is_empty()
+                || job.filename.to_lowercase().contains(&search)
+                || job_title(job).to_lowercase().contains(&search)
+                || job.notes.to_lowercase().contains(&search))
+    }
+}
+
+// query_jobs parameters: the filter's fields plus sorting and paging. A zero `limit` returns
+// every match.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JobQuery {
+    #[serde(flatten)]
+    pub filter: JobFilter,
+    // "newest" (default), "oldest", "filename" or "title".
+    pub sort: String,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+// A job list row: everything the list shows, without logs or summary text.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobSummary {
+    pub id: String,
+    pub filename: String,
+    pub title: String,
+    pub status: JobStatus,
+    pub stage: String,
+    pub progress: f32,
+    pub created_at: String,
+    pub summary_status: Option<String>,
+    pub tags: Vec<String>,
+    pub favorite: bool,
+    pub archived: bool,
+    pub trashed_at: Option<u64>,
+    pub duration_seconds: Option<f64>,
+    pub exported_to_obsidian: bool,
+    pub export_status: Option<String>,
+    pub duplicate_of: Option<String>,
+    pub log_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobPage {
+    pub jobs: Vec<JobSummary>,
+    // Matches before pagination.
+    pub total: usize,
+    // STATE_REVISION the page was read at; pass it to get_changes_since to stay current.
+    pub revision: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobLogPage {
+    pub lines: Vec<String>,
+    pub offset: usize,
+    pub total: usize,
+}
+
+impl JobSummary {
+    pub(super) fn from_job(job: &Job) -> Self {
+        Self {
+            id: job.id.clone(),
+            filename: job.filename.clone(),
+            title: job_title(job),
+            status: job.status,
+            stage: job.stage.clone(),
+            progress: job.progress,
+            created_at: job.created_at.clone(),
+            summary_status: job.summary_status.clone(),
+            tags: job.tags.clone(),
+            favorite: job.favorite,
+            archived: job.archived,
+            trashed_at: job.trashed_at,
+            duration_seconds: job.media_info.as_ref().and_then(|info| info.duration_seconds),
+            exported_to_obsidian: job.exported_to_obsidian,
+            export_status: job.export_status.clone(),
+            duplicate_of: job.duplicate_of.clone(),
+            log_count: job.logs.len(),
+        }
+    }
+}
+
+impl JobQuery {
+    // The list keeps archived jobs out of the way until they are asked for or searched.
+    pub(super) fn matches(&self, job: &Job) -> bool {
+        self.filter.matches(job)
+            && (self.filter.archived.is_some() || !job.archived || !self.filter.search.trim().is_empty())
+    }
+
+    // `jobs` is in index order, which is newest first.
+    pub(super) fn run(&self, jobs: &[Job]) -> Result<JobPage, String> {
+        let mut matched: Vec<&Job> = jobs.iter().filter(|job| self.matches(job)).collect();
+        match self.sort.as_str() {
+            "" | "newest" => {}
+            "oldest" => matched.reverse(),
+            "filename" => matched.sort_by_cached_key(|job| job.filename.to_lowercase()),
+            "title" => matched.sort_by_cached_key(|job| job_title(job).to_lowercase()),
+            other => return Err(format!("Unknown sort order: {other}")),
+        }
+        let total = matched.len();
+        let limit = if self.limit == 0 { usize::MAX } else { self.limit };
+        let jobs = matched
+            .into_iter()
+            .skip(self.offset)
+            .take(limit)
+            .map(JobSummary::from_job)
+            .collect();
+        Ok(JobPage {
+            jobs,
+            total,
+            revision: STATE_REVISION.load(Ordering::SeqCst),
+        })
+    }
+}
+
+pub(super) fn load_config_from_disk(path: &Path) -> Result<AppConfig, String> {
+    if !path.exists() {
+        return Ok(AppConfig::default());
+    }
+    let mut file =
+        File::open(path).map_err(|err| format!("failed to open config.json: {err}"))?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .map_err(|err| format!("failed to read config.json: {err}"))?;
+    if contents.trim().is_empty() {
+        return Ok(AppConfig::default());
+    }
+    serde_json::from_str(&contents).map_err(|err| format!("invalid config.json: {err}"))
+}
+
+pub(super) fn save_config_to_disk(path: &Path, config: &AppConfig) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(config)
+        .map_err(|err| format!("failed to serialize config.json: {err}"))?;
+    let mut file =
+        File::create(path).map_err(|err| format!("failed to write config.json: {err}"))?;
+    file.write_all(json.as_bytes())
+        .map_err(|err| format!("failed to save config.json: {err}"))
+}
+
+pub(super) fn load_index_from_disk(path: &Path) -> Result<JobIndex, String> {
+    if !path.exists() {
+        return Ok(JobIndex { jobs: Vec::new() });
+    }
+    let mut file =
+        File::open(path).map_err(|err| format!("failed to open index.json: {err}"))?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .map_err(|err| format!("failed to read index.json: {err}"))?;
+    if contents.trim().is_empty() {
+        return Ok(JobIndex { jobs: Vec::new() });
+    }
+    serde_json::from_str(&contents).map_err(|err| format!("invalid index.json: {err}"))
+}
+
+pub(super) const JOB_STORE_FILE: &str = "jobs.db";
+
+// Sibling of jobs/ holding the folders of deleted jobs until they are purged.
+pub(super) const TRASH_DIR: &str = "trash";
+
+pub(super) const JOB_LOG_LIMIT: usize = 2000;
+
+// Write-through persistence behind JobIndexState: one row per job plus a separate log table,
+// so a log line is a single INSERT instead of a rewrite of the whole job list. Reads are
+// served from the in-memory JobIndex.
+pub(super) struct JobStore {
+    pub(super) conn: Mutex<rusqlite::Connection>,
+    // The embeddings table, read once and kept until it changes, so a search only embeds the query.
+    pub(super) embeddings: Mutex<Option<HashMap<String, EmbeddingIndex>>>,
+}
+
+pub(super) fn job_store_error(err: rusqlite::Error) -> String {
+    format!("job store error: {err}")
+}
+
+// The job as stored in the `data` column; its logs live in job_logs.
+pub(super) fn job_row(job: &mut Job) -> Result<String, String> {
+    let logs = std::mem::take(&mut job.logs);
+    // With encryption on, the summary text only lives in the sealed summary.md.
+    let hidden = job_encryption()
+        .enabled
+        .then(|| (job.summary_md.take(), job.md_preview.take()));
+    let data = serde_json::to_string(job);
+    job.logs = logs;
+    if let Some((summary_md, md_preview)) = hidden {
+        job.summary_md = summary_md;
+        job.md_preview = md_preview;
+    }
+    // Titles, notes, tags and meeting attendees are as private as the transcript.
+    seal_row(data.map_err(|err| format!("failed to serialize job: {err}"))?)
+}
+
+pub(super) fn insert_job_logs(conn: &rusqlite::Connection, job_id: &str, lines: &[String]) -> Result<(), String> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+    let mut stmt = conn
+        .prepare_cached("INSERT INTO job_logs (job_id, line) VALUES (?1, ?2)")
+        .map_err(job_store_error)?;
+    for line in lines {
+        stmt.execute(rusqlite::params![job_id, seal_row(line.clone())?])
+            .map_err(job_store_error)?;
+    }
+    // Same bound push_log keeps in memory.
+    conn.execute(
+        "DELETE FROM job_logs WHERE job_id = ?1 AND seq NOT IN \
+         (SELECT seq FROM job_logs WHERE job_id = ?1 ORDER BY seq DESC LIMIT ?2)",
+        rusqlite::params![job_id, JOB_LOG_LIMIT as i64],
+    )
+    .map_err(job_store_error)?;
+    Ok(())
+}
+
+impl JobStore {
+    pub(super) fn open(path: &Path) -> Result<Self, String> {
+        let conn = rusqlite::Connection::open(path)
+            .map_err(|err| format!("failed to open {}: {err}", path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous = NORMAL;
+             PRAGMA secure_delete = ON;
+             CREATE TABLE IF NOT EXISTS jobs (
+                 id TEXT PRIMARY KEY,
+                 position INTEGER NOT NULL,
+                 status TEXT NOT NULL,
+                 created_at TEXT NOT NULL,
+                 data TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS jobs_position ON jobs (position);
+             CREATE INDEX IF NOT EXISTS jobs_status ON jobs (status);
+             CREATE TABLE IF NOT EXISTS job_logs (
+                 seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                 job_id TEXT NOT NULL,
+                 line TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS job_logs_job ON job_logs (job_id, seq);
+             CREATE TABLE IF NOT EXISTS embeddings (
+                 job_id TEXT PRIMARY KEY,
+                 data TEXT NOT NULL
+             );",
+        )
+        .map_err(job_store_error)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            embeddings: Mutex::new(None),
+        })
+    }
+
+    pub(super) fn conn(&self) -> Result<std::sync::MutexGuard<'_, rusqlite::Connection>, String> {
+        self.conn.lock().map_err(|_| "job store mutex poisoned".to_string())
+    }
+
+    // Newest first, the order the index has always kept.
+    pub(super) fn load(&self) -> Result<JobIndex, String> {
+        let conn = self.conn()?;
+        let mut jobs = Vec::new();
+        let mut stmt = conn
+            .prepare("SELECT data FROM jobs ORDER BY position")
+            .map_err(job_store_error)?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(job_store_error)?;
+        for data in rows {
+            let data = open_row(data.map_err(job_store_error)?)?;
+            jobs.push(serde_json::from_str::<Job>(&data).map_err(|err| format!("invalid job in {JOB_STORE_FILE}: {err}"))?);
+        }
+        let mut logs: HashMap<String, Vec<String>> = HashMap::new();
+        let mut stmt = conn
+            .prepare("SELECT job_id, line FROM job_logs ORDER BY seq")
+            .map_err(job_store_error)?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(job_store_error)?;
+        for row in rows {
+            let (job_id, line) = row.map_err(job_store_error)?;
+            logs.entry(job_id).or_default().push(open_row(line)?);
+        }
+        for job in &mut jobs {
+            job.logs = logs.remove(&job.id).unwrap_or_default();
+        }
+        Ok(JobIndex { jobs })
+    }
+
+    // Upserts the job (new jobs go to the top of the list) and appends the log lines
+    // push_log added since the last save.
+    pub(super) fn save_job(&self, job: &mut Job) -> Result<(), String> {
+        let data = job_row(job)?;
+        let pending = std::mem::take(&mut job.unsaved_logs);
+        let mut conn = self.conn()?;
+        let result = (|| {
+            let tx = conn.transaction().map_err(job_store_error)?;
+            tx.execute(
+                "INSERT INTO jobs (id, position, status, created_at, data)
+                 VALUES (?1, (SELECT COALESCE(MIN(position), 0) - 1 FROM jobs), ?2, ?3, ?4)
+                 ON CONFLICT(id) DO UPDATE SET
+                     status = excluded.status, created_at = excluded.created_at, data = excluded.data",
+                rusqlite::params![job.id, job.status.as_str(), job.created_at, data],
+            )
+            .map_err(job_store_error)?;
+            insert_job_logs(&tx, &job.id, &pending)?;
+            tx.commit().map_err(job_store_error)
+        })();
+        if result.is_err() {
+            job.unsaved_logs = pending;
+        }
+        result
+    }
+
+    pub(super) fn append_logs(&self, job: &mut Job) -> Result<(), String> {
+        let pending = std::mem::take(&mut job.unsaved_logs);
+        let mut conn = self.conn()?;
+        let result = conn
+            .transaction()
+            .map_err(job_store_error)
+            .and_then(|tx| {
+                insert_job_logs(&tx, &job.id, &pending)?;
+                tx.commit().map_err(job_store_error)
+            });
+        if result.is_err() {
+            job.unsaved_logs = pending;
+        }
+        result
+    }
+
+    // Writes the job's log lines again, sealed or not to match the current settings.
+    pub(super) fn rewrite_logs(&self, job: &Job) -> Result<(), String> {
+        let mut conn = self.conn()?;
+        let tx = conn.transaction().map_err(job_store_error)?;
+        tx.execute("DELETE FROM job_logs WHERE job_id = ?1", [&job.id])
+            .map_err(job_store_error)?;
+        insert_job_logs(&tx, &job.id, &job.logs)?;
+        tx.commit().map_err(job_store_error)
+    }
+
+    pub(super) fn delete_job(&self, job_id: &str) -> Result<(), String> {
+        let mut conn = self.conn()?;
+        let tx = conn.transaction().map_err(job_store_error)?;
+        tx.execute("DELETE FROM jobs WHERE id = ?1", [job_id]).map_err(job_store_error)?;
+        tx.execute("DELETE FROM job_logs WHERE job_id = ?1", [job_id]).map_err(job_store_error)?;
+        tx.execute("DELETE FROM embeddings WHERE job_id = ?1", [job_id]).map_err(job_store_error)?;
+        tx.commit().map_err(job_store_error)?;
+        // load_embeddings takes the cache before the connection; never hold both the other way.
+        drop(conn);
+        if let Ok(mut cached) = self.embeddings.lock() {
+            if let Some(cached) = cached.as_mut() {
+                cached.remove(job_id);
+            }
+        }
+        Ok(())
+    }
+
+    // Runs `f` on every job's embeddings by job id, read from the store on first use. The rows
+    // are sealed like the job rows.
+    pub(super) fn with_embeddings<T>(&self, f: impl FnOnce(&HashMap<String, EmbeddingIndex>) -> T) -> Result<T, String> {
+        let mut cached = self
+            .embeddings
+            .lock()
+            .map_err(|_| "embeddings mutex poisoned".to_string())?;
+        if let Some(cached) = cached.as_ref() {
+            return Ok(f(cached));
+        }
+        let conn = self.conn()?;
+        let mut stmt = conn
+            .prepare("SELECT job_id, data FROM embeddings")
+            .map_err(job_store_error)?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(job_store_error)?;
+        let mut loaded = HashMap::new();
+        for row in rows {
+            let (job_id, data) = row.map_err(job_store_error)?;
+            let index = serde_json::from_str::<EmbeddingIndex>(&open_row(data)?)
+                .map_err(|err| format!("invalid embeddings in {JOB_STORE_FILE}: {err}"))?;
+            loaded.insert(job_id, index);
+        }
+        Ok(f(cached.insert(loaded)))
+    }
+
+    pub(super) fn save_embeddings(&self, job_id: &str, index: &EmbeddingIndex) -> Result<(), String> {
+        let data = serde_json::to_string(index).map_err(|err| format!("failed to serialize embeddings: {err}"))?;
+        self.conn()?
+            .execute(
+                "INSERT INTO embeddings (job_id, data) VALUES (?1, ?2)
+                 ON CONFLICT(job_id) DO UPDATE SET data = excluded.data",
+                rusqlite::params![job_id, seal_row(data)?],
+            )
+            .map_err(job_store_error)?;
+        if let Ok(mut cached) = self.embeddings.lock() {
+            if let Some(cached) = cached.as_mut() {
+                cached.insert(job_id.to_string(), index.clone());
+            }
+        }
+        Ok(())
+    }
+
+    // Writes the job's embeddings again, sealed or not to match the current settings.
+    pub(super) fn rewrite_embeddings(&self, job_id: &str) -> Result<(), String> {
+        match self.with_embeddings(|stored| stored.get(job_id).cloned())? {
+            Some(index) => self.save_embeddings(job_id, &index),
+            None => Ok(()),
+        }
+    }
+
+    // One-time import of the index.json jobs used to live in. The file is kept as
+    // index.json.migrated; a re-run (e.g. the rename failed) replaces rather than duplicates.
+    pub(super) fn migrate_from_json(&self, path: &Path) -> Result<usize, String> {
+        if !path.exists() {
+            return Ok(0);
+        }
+        let index = load_index_from_disk(path)?;
+        {
+            let mut conn = self.conn()?;
+            let tx = conn.transaction().map_err(job_store_error)?;
+            for (position, job) in index.jobs.iter().enumerate() {
+                let mut job = job.clone();
+                let data = job_row(&mut job)?;
+                tx.execute(
+                    "INSERT OR REPLACE INTO jobs (id, position, status, created_at, data) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    rusqlite::params![job.id, position as i64, job.status.as_str(), job.created_at, data],
+                )
+                .map_err(job_store_error)?;
+                tx.execute("DELETE FROM job_logs WHERE job_id = ?1", [&job.id])
+                    .map_err(job_store_error)?;
+                insert_job_logs(&tx, &job.id, &job.logs)?;
+            }
+            tx.commit().map_err(job_store_error)?;
+        }
+        fs::rename(path, path.with_extension("json.migrated"))
+            .map_err(|err| format!("failed to retire index.json: {err}"))?;
+        Ok(index.jobs.len())
+    }
+
+    // Used by move_app_data: job rows hold absolute paths under the app data folder.
+    pub(super) fn rewrite_paths(&self, old: &Path, new: &Path) -> Result<(), String> {
+        let (old, new) = (old.to_string_lossy(), new.to_string_lossy());
+        let mut conn = self.conn()?;
+        let tx = conn.transaction().map_err(job_store_error)?;
+        let rows = {
+            let mut stmt = tx.prepare("SELECT id, data FROM jobs").map_err(job_store_error)?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+                .map_err(job_store_error)?;
+            rows.collect::<Result<Vec<_>, _>>().map_err(job_store_error)?
+        };
+        for (id, data) in rows {
+            let mut value: serde_json::Value = serde_json::from_str(&open_row(data)?)
+                .map_err(|err| format!("invalid job in {JOB_STORE_FILE}: {err}"))?;
+            rewrite_path_prefix(&mut value, &old, &new);
+            tx.execute("UPDATE jobs SET data = ?1 WHERE id = ?2", rusqlite::params![seal_row(value.to_string())?, id])
+                .map_err(job_store_error)?;
+        }
+        tx.commit().map_err(job_store_error)
+    }
+
+    // Folds the WAL into jobs.db so a plain file copy carries every committed change.
+    pub(super) fn checkpoint(&self) -> Result<(), String> {
+        self.conn()?
+            .execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")
+            .map_err(job_store_error)
+    }
+}
+
+pub(super) fn generate_job_id() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_micros())
+        .unwrap_or(0);
+    let pid = std::process::id();
+    format!("job_{now}_{pid}")
+}
+
+pub(super) fn unix_timestamp_string() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    now.to_string()
+}
+
+pub(super) fn build_job_audio_path(jobs_dir: &Path, job_id: &str, source_path: &str) -> Result<PathBuf, String> {
+    let job_dir = jobs_dir.join(job_id);
+    fs::create_dir_all(&job_dir)
+        .map_err(|err| format!("failed to create job dir: {err}"))?;
+    let ext = std::path::Path::new(source_path)
+        .extension()
+        .and_then(|value| value.to_str())
+        .unwrap_or("");
+    let filename = if ext.is_empty() {
+        "audio.original".to_string()
+    } else {
+        format!("audio.original.{ext}")
+    };
+    Ok(job_dir.join(filename))
+}
+
+pub(super) fn job_dir_from_audio_path(audio_path: &str) -> Option<PathBuf> {
+    std::path::Path::new(audio_path).parent().map(|p| p.to_path_buf())
+}
+
+pub(super) fn write_stub_artifacts(job_dir: &Path) -> Result<(String, String, String), String> {
+    fs::create_dir_all(job_dir)
+        .map_err(|err| format!("failed to create job dir: {err}"))?;
+    let transcript_path = job_dir.join("transcript.txt");
+    let segments_path = job_dir.join("segments.json");
+    let srt_path = job_dir.join("transcript.srt");
+    let transcript = "Stub transcript from Rust core.\n";
+    let segments = r#"[{"start":0.0,"end":1.5,"text":"Stub segment one."},{"start":1.6,"end":3.2,"text":"Stub segment two."}]"#;
+    write_job_file(&transcript_path, transcript)
+        .map_err(|err| format!("failed to write transcript.txt: {err}"))?;
+    write_job_file(&segments_path, segments)
+        .map_err(|err| format!("failed to write segments.json: {err}"))?;
+    write_job_file(&srt_path, "")
+        .map_err(|err| format!("failed to write transcript.srt: {err}"))?;
+    Ok((
+        transcript_path.to_string_lossy().to_string(),
+        segments_path.to_string_lossy().to_string(),
+        srt_path.to_string_lossy().to_string(),
+    ))
+}
+
+pub(super) fn read_transcript_text(path: &str) -> Result<String, String> {
+    let content =
+        read_job_text(path).map_err(|err| format!("failed to read transcript: {err}"))?;
+    Ok(content)
+}
+
+// Human title for notes and exported files: the user's rename, then the recorder's title, then
+// the derived one. A blank one is skipped so it can't hide the next; job_title falls back to
+// the imported filename.
+pub(super) fn preferred_title(job: &Job) -> Option<&str> {
+    [
+        job.display_title.as_deref(),
+        job.source_metadata.as_ref().and_then(|meta| meta.title.as_deref()),
+        job.title.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|title| !title.trim().is_empty())
+}
+
+pub(super) fn job_title(job: &Job) -> String {
+    let title = preferred_title(job).unwrap_or_else(|| {
+        Path::new(&job.filename)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(&job.id)
+    });
+    sanitize_filename(title)
+}
+
+pub(super) fn iso_from_unix(secs: u64) -> String {
+    let (year, month, day) = civil_date_from_unix(secs);
+    let rem = secs % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60
+    )
+}
+
+pub(super) fn civil_date_from_unix(secs: u64) -> (i64, u32, u32) {
+    // Howard Hinnant's days-to-civil conversion; avoids pulling in a date crate for YYYY/MM/DD.
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+pub(super) const KEYCHAIN_SERVICE: &str = "voicenote.llm";
+
+pub(super) const KEYCHAIN_ACCOUNT: &str = "openai-compatible";
+
+pub(super) const EXPORT_KEYCHAIN_SERVICE: &str = "voicenote.export";
+
+// Secrets live in the system keychain: Keychain on macOS, Credential Manager on Windows and
+// Secret Service on Linux. A 0600 file in app data is only used where no keychain is running
+// (a bare Linux session), and files left by older versions move to the keychain on first read.
+pub(super) fn secret_file(app: &Core, file_name: &str) -> Option<PathBuf> {
+    Some(app_data_root(app).ok()?.join(file_name))
+}
+
+pub(super) fn load_keychain_secret(service: &str, account: &str) -> Option<String> {
+    keyring::Entry::new(service, account).ok()?.get_password().ok()
+}
+
+// An empty `key` deletes the entry; deleting one that was never stored is not an error.
+pub(super) fn store_keychain_secret(service: &str, account: &str, key: &str) -> Result<(), String> {
+    let entry = keyring::Entry::new(service, account).map_err(|err| format!("keychain unavailable: {err}"))?;
+    let result = if key.is_empty() {
+        match entry.delete_credential() {
+            Err(keyring::Error::NoEntry) => Ok(()),
+            result => result,
+        }
+    } else {
+        entry.set_password(key)
+    };
+    result.map_err(|err| format!("failed to store secret in the keychain: {err}"))
+}
+
+pub(super) fn load_secret(app: &Core, service: &str, account: &str, file_name: &str) -> Option<String> {
+    let key = match load_keychain_secret(service, account) {
+        Some(key) => key,
+        None => {
+            let path = secret_file(app, file_name)?;
+            let key = fs::read_to_string(&path).ok()?;
+            if store_keychain_secret(service, account, key.trim()).is_ok() {
+                let _ = fs::remove_file(&path);
+            }
+            key
+        }
+    };
+    let key = key.trim().to_string();
+    (!key.is_empty()).then_some(key)
+}
+
+// AppConfig::allow_plaintext_secrets, process-wide for the same reason as JOB_ENCRYPTION:
+// ConfigState::load stores the proxy credentials before the state exists.
+pub(super) static SECRET_FILE_FALLBACK: AtomicBool = AtomicBool::new(false);
+
+pub(super) fn sync_secret_storage(config: &AppConfig) {
+    SECRET_FILE_FALLBACK.store(config.allow_plaintext_secrets, Ordering::SeqCst);
+}
+
+// The keychain's error is returned as is unless allow_plaintext_secrets opts into the file.
+pub(super) fn store_secret(app: &Core, service: &str, account: &str, file_name: &str, key: &str) -> Result<(), String> {
+    let key = key.trim();
+    let path = secret_file(app, file_name).ok_or_else(|| "app data dir unavailable".to_string())?;
+    match store_keychain_secret(service, account, key) {
+        Ok(()) => {
+            let _ = fs::remove_file(&path);
+            Ok(())
+        }
+        Err(_) if key.is_empty() => {
+            let _ = fs::remove_file(&path);
+            Ok(())
+        }
+        Err(err) if SECRET_FILE_FALLBACK.load(Ordering::SeqCst) => {
+            tracing::warn!("{err}; keeping the secret in {}", path.display());
+            write_secret_file(&path, key)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+pub(super) fn write_secret_file(path: &Path, key: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| format!("failed to create secrets folder: {err}"))?;
+    }
+    // Created 0600 rather than restricted after the write, so the secret is never readable by
+    // others, not even briefly. On Windows the app data folder is already private to the user.
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options
+        .open(path)
+        .map_err(|err| format!("failed to store secret: {err}"))?;
+    // A file left over from an older version may still have looser permissions.
+    #[cfg(unix)]
+    file.set_permissions(fs::Permissions::from_mode(0o600))
+        .map_err(|err| format!("failed to restrict secret permissions: {err}"))?;
+    file.write_all(key.as_bytes())
+        .map_err(|err| format!("failed to store secret: {err}"))?;
+    Ok(())
+}
+
+pub(super) fn load_llm_api_key(app: &Core) -> Option<String> {
+    load_secret(app, KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT, "llm_api_key")
+}
+
+pub(super) fn store_llm_api_key(app: &Core, key: &str) -> Result<(), String> {
+    store_secret(app, KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT, "llm_api_key", key)
+}
+
+pub(super) fn export_target_token_file(target_id: &str) -> String {
+    format!("export_token_{}", sanitize_filename(target_id))
+}
+
+// Secrets referenced from config by name (e.g. openai_api_key_secret), so API keys and
+// webhook tokens stay out of config.json.
+pub(super) const SECRETS_KEYCHAIN_SERVICE: &str = "voicenote.secrets";
+
+pub(super) fn validate_secret_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid secret name \"{name}\": use up to 64 letters, digits, '-', '_' or '.'."
+        ))
+    }
+}
+
+pub(super) fn named_secret_file(name: &str) -> String {
+    format!("secret_{name}")
+}
+
+pub(super) fn load_named_secret(app: &Core, name: &str) -> Option<String> {
+    validate_secret_name(name).ok()?;
+    load_secret(app, SECRETS_KEYCHAIN_SERVICE, name, &named_secret_file(name))
+}
+
+// Opt-in encryption at rest for job folders. A file written whole is ENCRYPTED_FILE_MAGIC, a
+// random 24-byte nonce and the XChaCha20-Poly1305 ciphertext. seal_job_dir writes
+// ENCRYPTED_STREAM_MAGIC, a 19-byte nonce and the file in ENCRYPTION_CHUNK_LEN chunks (the
+// STREAM construction), so hours of audio never sit in memory. The key is in the system keychain.
+pub(super) const ENCRYPTION_KEYCHAIN_SERVICE: &str = "voicenote.encryption";
+
+pub(super) const ENCRYPTION_KEYCHAIN_ACCOUNT: &str = "job-files";
+
+pub(super) const ENCRYPTED_FILE_MAGIC: &[u8] = b"VNENC1\n";
+
+pub(super) const ENCRYPTED_STREAM_MAGIC: &[u8] = b"VNENC2\n";
+
+pub(super) const ENCRYPTION_NONCE_LEN: usize = 24;
+
+pub(super) const ENCRYPTION_STREAM_NONCE_LEN: usize = 19;
+
+pub(super) const ENCRYPTION_CHUNK_LEN: usize = 1 << 20;
+
+pub(super) const ENCRYPTION_TAG_LEN: usize = 16;
+
+// jobs.db rows and log lines sealed while encryption is on: the prefix and the sealed bytes in hex.
+pub(super) const SEALED_ROW_PREFIX: &str = "sealed:";
+
+pub(super) const JOB_AUDIO_EXTENSIONS: &[&str] = &["wav", "opus", "m4a", "mp3", "flac", "ogg", "aac", "webm", "mp4"];
+
+pub(super) const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "m4v", "mkv", "webm", "avi"];
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct JobEncryption {
+    pub(super) key: Option<[u8; 32]>,
+    pub(super) enabled: bool,
+    pub(super) audio: bool,
+}
+
+// Process-wide rather than app state because read_transcript_text and the other artifact
+// readers have no AppHandle. The key stays loaded while encryption is off so files sealed
+// earlier can still be read.
+pub(super) static JOB_ENCRYPTION: Mutex<JobEncryption> = Mutex::new(JobEncryption {
+    key: None,
+    enabled: false,
+    audio: false,
+});
+
+pub(super) fn job_encryption() -> JobEncryption {
+    JOB_ENCRYPTION.lock().map(|guard| *guard).unwrap_or_default()
+}
+
+pub(super) fn parse_encryption_key(hex: &str) -> Option<[u8; 32]> {
+    let hex = hex.trim();
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+    let mut key = [0u8; 32];
+    for (index, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).ok()?;
+    }
+    Some(key)
+}
+
+// The key is created the first time encryption is turned on and never rotated; losing it
+// loses every sealed file.
+pub(super) fn sync_job_encryption(app: &Core, config: &AppConfig) -> Result<(), String> {
+    let mut encryption = job_encryption();
+    if encryption.key.is_none() {
+        encryption.key = load_secret(app, ENCRYPTION_KEYCHAIN_SERVICE, ENCRYPTION_KEYCHAIN_ACCOUNT, "job_files_key")
+            .and_then(|hex| parse_encryption_key(&hex));
+    }
+    if config.encrypt_job_files && encryption.key.is_none() {
+        let key: [u8; 32] = XChaCha20Poly1305::generate_key(&mut OsRng).into();
+        // Unlike other secrets the key never falls back to a file: it would sit right next to
+        // the data it protects.
+        store_keychain_secret(ENCRYPTION_KEYCHAIN_SERVICE, ENCRYPTION_KEYCHAIN_ACCOUNT, &to_hex(&key))
+            .map_err(|err| format!("Encryption needs the system keychain: {err}"))?;
+        encryption.key = Some(key);
+    }
+    encryption.enabled = config.encrypt_job_files;
+    encryption.audio = config.encrypt_job_files && config.encrypt_job_audio;
+    *JOB_ENCRYPTION
+        .lock()
+        .map_err(|_| "encryption mutex poisoned".to_string())? = encryption;
+    Ok(())
+}
+
+pub(super) fn seal_bytes(key: &[u8; 32], plain: &[u8]) -> Result<Vec<u8>, String> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(&nonce, plain)
+        .map_err(|_| "failed to encrypt file".to_string())?;
+    let mut sealed = Vec::with_capacity(ENCRYPTED_FILE_MAGIC.len() + nonce.len() + ciphertext.len());
+    sealed.extend_from_slice(ENCRYPTED_FILE_MAGIC);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+// Plaintext passes through, so files written before encryption was turned on still read.
+pub(super) fn open_bytes(data: Vec<u8>, encryption: &JobEncryption) -> Result<Vec<u8>, String> {
+    if data.starts_with(ENCRYPTED_STREAM_MAGIC) {
+        let mut plain = Vec::new();
+        open_to(data.as_slice(), &mut plain, encryption)?;
+        return Ok(plain);
+    }
+    let Some(body) = data.strip_prefix(ENCRYPTED_FILE_MAGIC) else {
+        return Ok(data);
+    };
+    let key = encryption_key(encryption)?;
+    if body.len() < ENCRYPTION_NONCE_LEN {
+        return Err("encrypted file is truncated".to_string());
+    }
+    let (nonce, ciphertext) = body.split_at(ENCRYPTION_NONCE_LEN);
+    XChaCha20Poly1305::new(Key::from_slice(&key))
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "failed to decrypt file: wrong key or corrupted data".to_string())
+}
+
+pub(super) fn encryption_key(encryption: &JobEncryption) -> Result<[u8; 32], String> {
+    encryption
+        .key
+        .ok_or_else(|| "file is encrypted and the encryption key is missing".to_string())
+}
+
+// Fills `buf` unless the reader runs out first; returns how much was read.
+pub(super) fn read_chunk(reader: &mut impl Read, buf: &mut [u8]) -> Result<usize, String> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(format!("failed to read file: {err}")),
+        }
+    }
+    Ok(filled)
+}
+
+pub(super) fn seal_to(key: &[u8; 32], mut reader: impl Read, mut writer: impl Write) -> Result<(), String> {
+    let mut nonce = [0u8; ENCRYPTION_STREAM_NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let mut encryptor =
+        EncryptorBE32::from_aead(XChaCha20Poly1305::new(Key::from_slice(key)), nonce.as_ref().into());
+    let write_err = |err: std::io::Error| format!("failed to write file: {err}");
+    writer.write_all(ENCRYPTED_STREAM_MAGIC).map_err(write_err)?;
+    writer.write_all(&nonce).map_err(write_err)?;
+    let mut current = vec![0u8; ENCRYPTION_CHUNK_LEN];
+    let mut next = vec![0u8; ENCRYPTION_CHUNK_LEN];
+    let mut len = read_chunk(&mut reader, &mut current)?;
+    loop {
+        // Only the last chunk may be short, and it's sealed as the last one so a file cut at a
+        // chunk boundary doesn't open.
+        let next_len = read_chunk(&mut reader, &mut next)?;
+        if next_len == 0 {
+            let sealed = encryptor
+                .encrypt_last(&current[..len])
+                .map_err(|_| "failed to encrypt file".to_string())?;
+            return writer.write_all(&sealed).map_err(write_err);
+        }
+        let sealed = encryptor
+            .encrypt_next(&current[..len])
+            .map_err(|_| "failed to encrypt file".to_string())?;
+        writer.write_all(&sealed).map_err(write_err)?;
+        std::mem::swap(&mut current, &mut next);
+        len = next_len;
+    }
+}
+
+// Copies `reader` to `writer`, opening it on the way if it's sealed in either format.
+pub(super) fn open_to(mut reader: impl Read, mut writer: impl Write, encryption: &JobEncryption) -> Result<(), String> {
+    let write_err = |err: std::io::Error| format!("failed to write file: {err}");
+    let mut magic = [0u8; ENCRYPTED_STREAM_MAGIC.len()];
+    let magic_len = read_chunk(&mut reader, &mut magic)?;
+    let magic = &magic[..magic_len];
+    if magic == ENCRYPTED_FILE_MAGIC {
+        let mut data = magic.to_vec();
+        reader
+            .read_to_end(&mut data)
+            .map_err(|err| format!("failed to read file: {err}"))?;
+        return writer.write_all(&open_bytes(data, encryption)?).map_err(write_err);
+    }
+    if magic != ENCRYPTED_STREAM_MAGIC {
+        writer.write_all(magic).map_err(write_err)?;
+        return std::io::copy(&mut reader, &mut writer).map(|_| ()).map_err(write_err);
+    }
+    let key = encryption_key(encryption)?;
+    let mut nonce = [0u8; ENCRYPTION_STREAM_NONCE_LEN];
+    if read_chunk(&mut reader, &mut nonce)? < nonce.len() {
+        return Err("encrypted file is truncated".to_string());
+    }
+    let mut decryptor =
+        DecryptorBE32::from_aead(XChaCha20Poly1305::new(Key::from_slice(&key)), nonce.as_ref().into());
+    let open_err = |_| "failed to decrypt file: wrong key or corrupted data".to_string();
+    let mut current = vec![0u8; ENCRYPTION_CHUNK_LEN + ENCRYPTION_TAG_LEN];
+    let mut next = vec![0u8; ENCRYPTION_CHUNK_LEN + ENCRYPTION_TAG_LEN];
+    let mut len = read_chunk(&mut reader, &mut current)?;
+    loop {
+        let next_len = read_chunk(&mut reader, &mut next)?;
+        if next_len == 0 {
+            let plain = decryptor.decrypt_last(&current[..len]).map_err(open_err)?;
+            return writer.write_all(&plain).map_err(write_err);
+        }
+        let plain = decryptor.decrypt_next(&current[..len]).map_err(open_err)?;
+        writer.write_all(&plain).map_err(write_err)?;
+        std::mem::swap(&mut current, &mut next);
+        len = next_len;
+    }
+}
+
+// Seals or opens `path` through a temporary file next to it, so an interrupted run leaves the
+// original as it was.
+pub(super) fn rewrite_job_file(path: &Path, seal: bool, encryption: &JobEncryption) -> Result<(), String> {
+    let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let temp = path.with_file_name(format!(".{name}.tmp"));
+    let result = (|| {
+        let reader = BufReader::new(File::open(path).map_err(|err| format!("failed to read {name}: {err}"))?);
+        let mut writer =
+            std::io::BufWriter::new(File::create(&temp).map_err(|err| format!("failed to write {name}: {err}"))?);
+        if seal {
+            seal_to(&encryption_key(encryption)?, reader, &mut writer)?;
+        } else {
+            open_to(reader, &mut writer, encryption)?;
+        }
+        writer.flush().map_err(|err| format!("failed to write {name}: {err}"))?;
+        fs::rename(&temp, path).map_err(|err| format!("failed to replace {name}: {err}"))
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result.map_err(|err| format!("{name}: {err}"))
+}
+
+pub(super) fn file_is_sealed(path: &Path) -> bool {
+    let mut magic = [0u8; ENCRYPTED_FILE_MAGIC.len()];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|_| magic == ENCRYPTED_FILE_MAGIC || magic == ENCRYPTED_STREAM_MAGIC)
+}
+
+pub(super) fn seal_row(text: String) -> Result<String, String> {
+    match job_encryption() {
+        JobEncryption { enabled: true, key: Some(key), .. } => {
+            Ok(format!("{SEALED_ROW_PREFIX}{}", to_hex(&seal_bytes(&key, text.as_bytes())?)))
+        }
+        _ => Ok(text),
+    }
+}
+
+pub(super) fn open_row(text: String) -> Result<String, String> {
+    let Some(hex) = text.strip_prefix(SEALED_ROW_PREFIX) else {
+        return Ok(text);
+    };
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|index| hex.get(index..index + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| format!("invalid sealed row in {JOB_STORE_FILE}"))?;
+    String::from_utf8(open_bytes(bytes, &job_encryption())?)
+        .map_err(|_| format!("invalid sealed row in {JOB_STORE_FILE}"))
+}
+
+// Drop-in replacements for fs::read/fs::read_to_string/fs::write on job artifacts.
+pub(super) fn read_job_file(path: impl AsRef<Path>) -> std::io::Result<Vec<u8>> {
+    open_bytes(fs::read(path)?, &job_encryption()).map_err(std::io::Error::other)
+}
+
+pub(super) fn read_job_text(path: impl AsRef<Path>) -> std::io::Result<String> {
+    String::from_utf8(read_job_file(path)?)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+}
+
+pub(super) fn write_job_file(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    match job_encryption() {
+        JobEncryption { enabled: true, key: Some(key), .. } => {
+            fs::write(path, seal_bytes(&key, contents.as_ref()).map_err(std::io::Error::other)?)
+        }
+        _ => fs::write(path, contents),
+    }
+}
+
+// What was said: transcripts, subtitles, summaries and the JSON derived from them. Waveform
+// and fingerprint caches and the import marker hold nothing readable and stay as they are.
+pub(super) fn is_sensitive_job_file(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    match Path::new(&name).extension().and_then(|ext| ext.to_str()) {
+        Some("txt" | "srt" | "vtt" | "md") => true,
+        Some("json") => !matches!(name.as_str(), "waveform.json" | "fingerprint.json" | IMPORT_MARKER),
+        _ => false,
+    }
+}
+
+pub(super) fn is_job_audio_file(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| JOB_AUDIO_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+// Seals or opens the files in a job folder to match `encryption` and returns how many
+// changed. Only the top level is touched; clips and attachments are exports.
+pub(super) fn seal_job_dir(job_dir: &Path, encryption: &JobEncryption) -> Result<usize, String> {
+    let entries = fs::read_dir(job_dir).map_err(|err| format!("failed to read job folder: {err}"))?;
+    let mut changed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let sensitive = is_sensitive_job_file(&name);
+        let audio = is_job_audio_file(&name);
+        if !sensitive && !audio {
+            continue;
+        }
+        let want_sealed = encryption.enabled && (sensitive || encryption.audio);
+        if file_is_sealed(&path) == want_sealed {
+            continue;
+        }
+        rewrite_job_file(&path, want_sealed, encryption)?;
+        changed += 1;
+    }
+    Ok(changed)
+}
+
+// ffmpeg and whisper need the audio as is. Sealed audio is opened into a private folder under
+// the system temp dir for the run instead of in place, and the folder goes away with it. The
+// folder is only created once something sealed is opened.
+pub(super) struct ScratchAudio {
+    pub(super) dir: PathBuf,
+}
+
+impl ScratchAudio {
+    pub(super) fn new(job_id: &str) -> Result<Self, String> {
+        let dir = std::env::temp_dir().join(format!("voicenote-{job_id}"));
+        // Left behind by a run that crashed.
+        let _ = fs::remove_dir_all(&dir);
+        Ok(Self { dir })
+    }
+
+    // The private folder itself, for output cut from sealed audio that must not be kept.
+    pub(super) fn dir(&self) -> Result<&Path, String> {
+        fs::create_dir_all(&self.dir).map_err(|err| format!("failed to create scratch folder: {err}"))?;
+        #[cfg(unix)]
+        fs::set_permissions(&self.dir, fs::Permissions::from_mode(0o700))
+            .map_err(|err| format!("failed to restrict scratch folder: {err}"))?;
+        Ok(&self.dir)
+    }
+
+    // The path to hand to ffmpeg or whisper: `path` itself unless it's sealed. A file opened
+    // earlier in the same scratch is reused.
+    pub(super) fn open(&self, path: &Path) -> Result<PathBuf, String> {
+        if !file_is_sealed(path) {
+            return Ok(path.to_path_buf());
+        }
+        let name = path.file_name().ok_or_else(|| "audio path has no file name".to_string())?;
+        let opened = self.dir()?.join(name);
+        if opened.is_file() {
+            return Ok(opened);
+        }
+        let reader = BufReader::new(File::open(path).map_err(|err| format!("failed to read audio: {err}"))?);
+        let mut writer = std::io::BufWriter::new(
+            File::create(&opened).map_err(|err| format!("failed to write scratch audio: {err}"))?,
+        );
+        open_to(reader, &mut writer, &job_encryption())?;
+        writer
+            .flush()
+            .map_err(|err| format!("failed to write scratch audio: {err}"))?;
+        Ok(opened)
+    }
+}
+
+impl Drop for ScratchAudio {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+pub(super) fn apply_job_encryption_blocking(app: &Core) -> Result<usize, String> {
+    let state = app.state::<JobIndexState>();
+    let encryption = job_encryption();
+    let job_ids: Vec<String> = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .iter()
+        .map(|job| job.id.clone())
+        .collect();
+    let mut changed = 0;
+    for job_id in job_ids {
+        let job_dir = {
+            let guard = state
+                .index
+                .lock()
+                .map_err(|_| "job index mutex poisoned".to_string())?;
+            match guard.jobs.iter().find(|job| job.id == job_id) {
+                Some(job) if !job.status.is_active() => job_dir_from_audio_path(&job.audio_path),
+                _ => continue,
+            }
+        };
+        if let Some(job_dir) = job_dir.filter(|dir| dir.is_dir()) {
+            changed += seal_job_dir(&job_dir, &encryption)?;
+            // Cached clips are plain cuts of the audio that was just sealed.
+            if encryption.enabled && encryption.audio {
+                let _ = fs::remove_dir_all(job_dir.join("clips"));
+            }
+        }
+        let mut guard = state
+            .index
+            .lock()
+            .map_err(|_| "job index mutex poisoned".to_string())?;
+        if let Some(job) = guard.jobs.iter_mut().find(|job| job.id == job_id) {
+            // Re-saving drops the summary text from the job store and seals or opens the row
+            // and its log lines.
+            state.store.save_job(job)?;
+            state.store.rewrite_logs(job)?;
+            state.store.rewrite_embeddings(&job.id)?;
+        }
+    }
+    Ok(changed)
+}
+
+pub(super) fn update_job_and_emit<F>(app: &Core, job_id: &str, mutator: F) -> Result<(), String>
+where
+    F: FnOnce(&mut Job),
+{
+    let index_state = app.state::<JobIndexState>();
+    let mut guard = index_state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?;
+    let mut snapshot: Option<Job> = None;
+    if let Some(job) = guard.jobs.iter_mut().find(|job| job.id == job_id) {
+        mutator(job);
+        index_state.store.save_job(job)?;
+        snapshot = Some(job.clone());
+    }
+    if let Some(job) = snapshot {
+        emit_job_updated(app, &job);
+    }
+    Ok(())
+}
+
+pub(super) fn append_job_log(app: &Core, job_id: &str, line: &str) -> Result<(), String> {
+    let index_state = app.state::<JobIndexState>();
+    let snapshot = {
+        let mut guard = index_state
+            .index
+            .lock()
+            .map_err(|_| "job index mutex poisoned".to_string())?;
+        match guard.jobs.iter_mut().find(|job| job.id == job_id) {
+            Some(job) => {
+                push_log(job, line);
+                // Only the new line is written; the job row itself is unchanged.
+                index_state.store.append_logs(job)?;
+                Some(job.clone())
+            }
+            None => None,
+        }
+    };
+    if let Some(job) = snapshot {
+        emit_job_updated(app, &job);
+    }
+    emit_job_log(app, job_id, line);
+    Ok(())
+}
+
+pub(super) fn validate_export_targets(cfg: &AppConfig) -> Result<(), String> {
+    let mut ids = HashSet::new();
+    for target in &cfg.export_targets {
+        let label = if target.name.trim().is_empty() { target.id.as_str() } else { target.name.as_str() };
+        if target.id.trim().is_empty() || !ids.insert(target.id.trim()) {
+            return Err(format!("Export target \"{label}\" needs a unique id."));
+        }
+        if !EXPORT_TARGET_KINDS.contains(&target.kind.as_str()) {
+            return Err(format!(
+                "Export target \"{label}\" has unknown kind \"{}\". Supported: {}.",
+                target.kind,
+                EXPORT_TARGET_KINDS.join(", ")
+            ));
+        }
+        if target.kind != "joplin" && target.path.trim().is_empty() {
+            return Err(format!("Export target \"{label}\" needs a folder."));
+        }
+        let unknown = unknown_placeholders(&target.path_template, PATH_TEMPLATE_VARIABLES);
+        if !unknown.is_empty() {
+            return Err(format!("Unknown placeholder {{{}}} in export target \"{label}\".", unknown[0]));
+        }
+    }
+    Ok(())
+}
+
+// A problem with one AppConfig field; `field` is the AppConfig key so Settings can show the
+// message next to the matching input. Warnings are shown but don't stop the config from saving.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigFieldError {
+    pub field: String,
+    pub message: String,
+    #[serde(default)]
+    pub warning: bool,
+}
+
+impl ConfigFieldError {
+    pub(super) fn new(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.into(),
+            warning: false,
+        }
+    }
+
+    pub(super) fn warning(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            warning: true,
+            ..Self::new(field, message)
+        }
+    }
+}
+
+// Writes and removes a probe file, since permission bits don't tell about read-only volumes
+// or sandbox restrictions.
+pub(super) fn dir_is_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".voicenote-write-test-{}", std::process::id()));
+    let writable = File::create(&probe).is_ok();
+    let _ = fs::remove_file(&probe);
+    writable
+}
+
+// Every field-level problem with `cfg`; empty when it can be saved.
+pub(super) fn config_field_errors(cfg: &AppConfig) -> Vec<ConfigFieldError> {
+    let mut errors = Vec::new();
+    let vault = cfg.vault_path.trim();
+    if !vault.is_empty() {
+        let path = Path::new(vault);
+        // An unplugged drive or a folder from another machine; exports fail until it's back.
+        if !path.is_dir() {
+            errors.push(ConfigFieldError::warning("vault_path", format!("Vault folder {vault} does not exist.")));
+        } else if !dir_is_writable(path) {
+            errors.push(ConfigFieldError::new("vault_path", format!("Vault folder {vault} is not writable.")));
+        }
+    }
+    if cfg.inbox_poll_seconds < 1 {
+        errors.push(ConfigFieldError::new("inbox_poll_seconds", "Inbox poll interval must be at least 1 second."));
+    }
+    for folder in &cfg.watch_folders {
+        let options = folder.job_options();
+        let problem = if folder.path.trim().is_empty() {
+            Some("Watched folders need a path.".to_string())
+        } else if let Some(Err(err)) = options.model_size.as_deref().map(model_filename) {
+            Some(format!("{}: {err}", folder.path.trim()))
+        } else {
+            options
+                .export_target
+                .filter(|id| !cfg.export_targets.iter().any(|target| &target.id == id))
+                .map(|id| format!("{}: export target \"{id}\" does not exist.", folder.path.trim()))
+        };
+        if let Some(problem) = problem {
+            errors.push(ConfigFieldError::new("watch_folders", problem));
+        }
+    }
+    if !INBOX_AFTER_IMPORT_ACTIONS.contains(&cfg.inbox_after_import.as_str()) {
+        errors.push(ConfigFieldError::new(
+            "inbox_after_import",
+            format!("Unknown inbox action \"{}\"; use keep, move or rename.", cfg.inbox_after_import),
+        ));
+    }
+    if !POWER_SAVING_MODES.contains(&cfg.power_saving_mode.as_str()) {
+        errors.push(ConfigFieldError::new(
+            "power_saving_mode",
+            format!(
+                "Unknown power saving mode \"{}\"; use off, pause, reduce_threads or defer_large.",
+                cfg.power_saving_mode
+            ),
+        ));
+    }
+    if cfg.power_saving_threads == 0 {
+        errors.push(ConfigFieldError::new("power_saving_threads", "Use at least one thread."));
+    }
+    if let Err(err) = model_filename(&cfg.model_size) {
+        errors.push(ConfigFieldError::new("model_size", err));
+    }
+    let net = DownloadNetwork::from_config(cfg);
+    let calendar_url = Some(cfg.calendar_source.trim()).filter(|source| source.contains("://"));
+    let urls = [
+        ("ollama_base_url", Some(cfg.ollama_base_url.trim())),
+        ("openai_base_url", Some(cfg.openai_base_url.trim())),
+        ("whisper_binary_url", cfg.whisper_binary_url.as_deref().map(str::trim)),
+        ("ffmpeg_binary_url", cfg.ffmpeg_binary_url.as_deref().map(str::trim)),
+        ("huggingface_mirror", Some(net.huggingface_mirror.as_str())),
+        ("github_mirror", Some(net.github_mirror.as_str())),
+        ("calendar_source", calendar_url),
+    ];
+    for (field, url) in urls {
+        let Some(url) = url.filter(|url| !url.is_empty()) else {
+            continue;
+        };
+        // "localhost:11434" parses with "localhost" as the scheme, so the scheme is checked too.
+        match reqwest::Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https" | "webcal") => {}
+            Ok(_) => errors.push(ConfigFieldError::new(
+                field,
+                format!("URL \"{url}\" must start with http:// or https://."),
+            )),
+            Err(err) => errors.push(ConfigFieldError::new(field, format!("Invalid URL \"{url}\": {err}."))),
+        }
+    }
+    if let Err(err) = net.client() {
+        errors.push(ConfigFieldError::new("download_proxy", err));
+    }
+    let secret = cfg.openai_api_key_secret.trim();
+    if !secret.is_empty() {
+        if let Err(err) = validate_secret_name(secret) {
+            errors.push(ConfigFieldError::new("openai_api_key_secret", err));
+        }
+    }
+    errors.extend(template_errors(cfg));
+    if let Err(err) = validate_export_targets(cfg) {
+        errors.push(ConfigFieldError::new("export_targets", err));
+    }
+    let mut names = HashSet::from([cfg.active_profile.trim()]);
+    if cfg.active_profile.trim().is_empty() || !cfg.profiles.iter().all(|p| !p.name.trim().is_empty() && names.insert(p.name.trim())) {
+        errors.push(ConfigFieldError::new("profiles", "Profile names must be unique and not empty."));
+    }
+    errors
+}
+
+pub(super) fn check_config(cfg: &AppConfig) -> Result<(), String> {
+    let errors: Vec<_> = config_field_errors(cfg)
+        .into_iter()
+        .filter(|error| !error.warning)
+        .map(|error| error.message)
+        .collect();
+    if errors.is_empty() {
+        return Ok(());
+    }
+    Err(errors.join("\n"))
+}
+
+// Makes `cfg` the live config without saving it: pushes the settings long-lived state caches
+// (models folder, download speed cap, summary workers) and emits "config:updated".
+pub(super) fn apply_config(app: &Core, mut cfg: AppConfig) -> Result<AppConfig, String> {
+    let state = app.state::<ConfigState>();
+    sync_secret_storage(&cfg);
+    let moved_credentials = store_proxy_credentials(app, &mut cfg)?;
+    if let Ok(mut credentials) = state.proxy_credentials.lock() {
+        *credentials = load_proxy_credentials(app);
+    }
+    if let Some(model_state) = app.try_state::<ModelDownloadState>() {
+        model_state.set_models_dir(&cfg)?;
+    }
+    state
+        .download_speed_limit
+        .store(cfg.download_speed_limit_kbps, Ordering::Relaxed);
+    if let Some(queue) = app.try_state::<SummaryQueueState>() {
+        queue.set_concurrency(app, cfg.summary_concurrency);
+    }
+    sync_job_encryption(app, &cfg)?;
+    let applied = {
+        let mut guard = state
+            .config
+            .lock()
+            .map_err(|_| "config mutex poisoned".to_string())?;
+        *guard = cfg;
+        state.revision.store(next_state_revision(), Ordering::SeqCst);
+        guard.clone()
+    };
+    // A config.json edited by hand may still hold them; they shouldn't stay on disk.
+    if moved_credentials {
+        save_config_to_disk(&state.path, &applied)?;
+    }
+    let revision = state.revision.load(Ordering::SeqCst);
+    let _ = app.emit("config:updated", Revisioned { value: &applied, revision });
+    Ok(applied)
+}
+
+pub(super) fn store_config(app: &Core, cfg: AppConfig) -> Result<AppConfig, String> {
+    let config = apply_config(app, cfg)?;
+    save_config_to_disk(&app.state::<ConfigState>().path, &config)?;
+    Ok(config)
+}
+
+pub(super) const CONFIG_WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+// Picks up edits made to config.json outside the app (an editor, a dotfiles sync). Files that
+// don't parse or don't pass check_config are left alone and the live config stays as it was
+// until they do; the app's own saves compare equal and are skipped.
+pub fn spawn_config_watcher(app: &Core) {
+    let app = app.clone();
+    thread::spawn(move || {
+        let path = app.state::<ConfigState>().path.clone();
+        let mut last_modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+        loop {
+            thread::sleep(CONFIG_WATCH_INTERVAL);
+            if app_data_moving() {
+                continue;
+            }
+            let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+            if modified.is_none() || modified == last_modified {
+                continue;
+            }
+            last_modified = modified;
+            let Ok(on_disk) = load_config_from_disk(&path) else {
+                continue;
+            };
+            if let Err(err) = check_config(&on_disk) {
+                tracing::warn!("ignoring the edited {}: {err}", path.display());
+                continue;
+            }
+            let current = app.state::<ConfigState>().config.lock().ok().map(|config| config.clone());
+            if current.is_some_and(|current| config_differs(&current, &on_disk)) {
+                let _ = apply_config(&app, on_disk);
+            }
+        }
+    });
+}
+
+// AppConfig has no PartialEq; its JSON form is what ends up on disk anyway.
+pub(super) fn config_differs(a: &AppConfig, b: &AppConfig) -> bool {
+    serde_json::to_value(a).ok() != serde_json::to_value(b).ok()
+}
+
+// The config as written by export_settings. Proxy credentials are in the keychain with the
+// other secrets by now; a config that predates that still has them dropped here.
+pub(super) fn exportable_config(config: &AppConfig) -> AppConfig {
+    let mut config = config.clone();
+    if let Some((address, _)) = split_proxy_credentials(&config.download_proxy) {
+        config.download_proxy = address;
+    }
+    config
+}
+
+// Settings from another machine; models_dir stays local since it names a folder on this disk.
+pub(super) fn imported_config(contents: &str, current: &AppConfig) -> Result<AppConfig, String> {
+    let mut config: AppConfig =
+        serde_json::from_str(contents).map_err(|err| format!("Not a VoiceNote settings file: {err}"))?;
+    config.initialized = true;
+    config.models_dir = current.models_dir.clone();
+    Ok(config)
+}
+
+// The warnings an imported config is saved with (typically a vault folder that only exists on
+// the other machine); anything check_config rejects rejects the import too.
+pub(super) fn imported_config_errors(config: &AppConfig) -> Result<Vec<ConfigFieldError>, String> {
+    check_config(config)?;
+    Ok(config_field_errors(config))
+}
+
+// The imported config plus whatever validate_config finds wrong with it on this machine
+// (typically a vault path that doesn't exist here), so Settings can point at it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsImport {
+    pub config: AppConfig,
+    pub errors: Vec<ConfigFieldError>,
+}
+
+pub(super) const RESET_SCOPES: [&str; 4] = ["transcription", "summarization", "export", "all"];
+
+// Restores one section of the config to its defaults. The vault path, profiles and the
+// first-run flag survive even "all", so a reset never sends the user back to the wizard.
+pub(super) fn reset_config_section(config: &mut AppConfig, scope: &str) -> Result<(), String> {
+    let defaults = AppConfig::default();
+    match scope {
+        "transcription" => {
+            config.model_size = defaults.model_size;
+            config.preload_model = defaults.preload_model;
+            config.language = defaults.language;
+            config.normalize_loudness = defaults.normalize_loudness;
+            config.reduce_noise = defaults.reduce_noise;
+            config.whisper_json_retention = defaults.whisper_json_retention;
+            config.split_stereo_channels = defaults.split_stereo_channels;
+            config.left_channel_label = defaults.left_channel_label;
+            config.right_channel_label = defaults.right_channel_label;
+            config.vault_vocabulary_enabled = defaults.vault_vocabulary_enabled;
+            config.whisper_binary_url = defaults.whisper_binary_url;
+            config.ffmpeg_binary_url = defaults.ffmpeg_binary_url;
+            config.models_dir = defaults.models_dir;
+            config.download_proxy = defaults.download_proxy;
+            config.download_no_proxy = defaults.download_no_proxy;
+            config.huggingface_mirror = defaults.huggingface_mirror;
+            config.github_mirror = defaults.github_mirror;
+            config.download_speed_limit_kbps = defaults.download_speed_limit_kbps;
+        }
+        "summarization" => {
+            config.enable_summarization = defaults.enable_summarization;
+            config.auto_summarize_after_transcription = defaults.auto_summarize_after_transcription;
+            config.summary_language = defaults.summary_language;
+            config.llm_provider = defaults.llm_provider;
+            config.ollama_base_url = defaults.ollama_base_url;
+            config.ollama_model = defaults.ollama_model;
+            config.openai_base_url = defaults.openai_base_url;
+            config.openai_model = defaults.openai_model;
+            config.openai_api_key_secret = defaults.openai_api_key_secret;
+            config.llm_context_tokens = defaults.llm_context_tokens;
+            config.llm_timeout_seconds = defaults.llm_timeout_seconds;
+            config.llm_max_retries = defaults.llm_max_retries;
+            config.summary_concurrency = defaults.summary_concurrency;
+            config.extractive_fallback = defaults.extractive_fallback;
+            config.semantic_index = defaults.semantic_index;
+            config.embedding_model = defaults.embedding_model;
+            config.summary_prompt = defaults.summary_prompt;
+            config.summary_templates = defaults.summary_templates;
+            config.extract_action_items = defaults.extract_action_items;
+            config.auto_title_tags = defaults.auto_title_tags;
+            config.translation_target_language = defaults.translation_target_language;
+        }
+        "export" => {
+            config.output_subfolder = defaults.output_subfolder;
+            config.export_routes = defaults.export_routes;
+            config.auto_export_after_transcription = defaults.auto_export_after_transcription;
+            config.auto_export_after_summarization = defaults.auto_export_after_summarization;
+            config.include_timestamps = defaults.include_timestamps;
+            config.obsidian_note_template = defaults.obsidian_note_template;
+            config.obsidian_audio_mode = defaults.obsidian_audio_mode;
+            config.obsidian_attachments_folder = defaults.obsidian_attachments_folder;
+            config.obsidian_timestamp_links = defaults.obsidian_timestamp_links;
+            config.obsidian_path_template = defaults.obsidian_path_template;
+            config.daily_note_mode = defaults.daily_note_mode;
+            config.daily_note_path = defaults.daily_note_path;
+            config.export_targets = defaults.export_targets;
+            config.subtitle_max_line_chars = defaults.subtitle_max_line_chars;
+            config.subtitle_max_lines = defaults.subtitle_max_lines;
+            config.subtitle_speaker_prefix = defaults.subtitle_speaker_prefix;
+        }
+        "all" => {
+            *config = AppConfig {
+                initialized: config.initialized,
+                vault_path: std::mem::take(&mut config.vault_path),
+                active_profile: std::mem::take(&mut config.active_profile),
+                profiles: std::mem::take(&mut config.profiles),
+                ..defaults
+            };
+        }
+        other => {
+            return Err(format!("Unknown reset scope \"{other}\". Expected {}.", RESET_SCOPES.join(", ")));
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn tag_counts(jobs: &[Job]) -> Vec<TagCount> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for tag in jobs
+        .iter()
+        .filter(|job| job.trashed_at.is_none())
+        .flat_map(|job| &job.tags)
+    {
+        *counts.entry(tag).or_default() += 1;
+    }
+    let mut tags: Vec<TagCount> = counts
+        .into_iter()
+        .map(|(tag, count)| TagCount { tag: tag.to_string(), count })
+        .collect();
+    tags.sort_by_key(|tag| std::cmp::Reverse(tag.count));
+    tags
+}
+
+// Written before copying audio into a new job folder and removed once the job is indexed,
+// so a crash mid-copy can be detected and resumed on the next launch.
+pub(super) const IMPORT_MARKER: &str = "import.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct ImportMarker {
+    pub(super) source_path: String,
+    pub(super) filename: String,
+    #[serde(default)]
+    pub(super) options: JobOptions,
+}
+
+pub(super) fn write_import_marker(job_dir: &Path, marker: &ImportMarker) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(marker)
+        .map_err(|err| format!("failed to serialize import marker: {err}"))?;
+    fs::write(job_dir.join(IMPORT_MARKER), json)
+        .map_err(|err| format!("failed to write import marker: {err}"))
+}
+
+pub(super) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+pub(super) fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|err| format!("failed to open file: {err}"))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 1024 * 64];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|err| format!("failed to read file: {err}"))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(to_hex(&hasher.finalize()))
+}
+
+pub(super) fn copy_with_checksum(src: &Path, dest: &Path) -> Result<(u64, String), String> {
+    // Copy through a .part file, hashing the source as it streams, then re-hash the
+    // result so a truncated or corrupted copy is never promoted to the job folder.
+    let expected_size = fs::metadata(src)
+        .map_err(|err| format!("failed to read source audio: {err}"))?
+        .len();
+    let part_path = dest.with_extension(format!(
+        "{}.part",
+        dest.extension().and_then(|e| e.to_str()).unwrap_or("")
+    ));
+    let mut input =
+        File::open(src).map_err(|err| format!("failed to open source audio: {err}"))?;
+    let mut output = File::create(&part_path)
+        .map_err(|err| format!("failed to copy audio into job folder: {err}"))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 1024 * 64];
+    let mut copied = 0u64;
+    loop {
+        let read = input
+            .read(&mut buffer)
+            .map_err(|err| format!("failed to read source audio: {err}"))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        output
+            .write_all(&buffer[..read])
+            .map_err(|err| format!("failed to copy audio into job folder: {err}"))?;
+        copied += read as u64;
+    }
+    output
+        .sync_all()
+        .map_err(|err| format!("failed to flush audio copy: {err}"))?;
+    drop(output);
+    let source_hash = to_hex(&hasher.finalize());
+    let copied_hash = sha256_file(&part_path)?;
+    if copied != expected_size || copied_hash != source_hash {
+        let _ = fs::remove_file(&part_path);
+        return Err(format!(
+            "Audio copy incomplete ({copied} of {expected_size} bytes); please re-import."
+        ));
+    }
+    fs::rename(&part_path, dest)
+        .map_err(|err| format!("failed to finalize audio copy: {err}"))?;
+    Ok((copied, source_hash))
+}
+
+pub fn recover_interrupted_imports(app: &Core) {
+    let state = app.state::<JobIndexState>();
+    let queue = app.state::<JobQueueState>();
+    let known: HashSet<String> = match state.index.lock() {
+        Ok(guard) => guard.jobs.iter().map(|job| job.id.clone()).collect(),
+        Err(_) => return,
+    };
+    let entries = match fs::read_dir(&state.jobs_dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let job_dir = entry.path();
+        let marker_path = job_dir.join(IMPORT_MARKER);
+        if !marker_path.exists() {
+            continue;
+        }
+        let job_id = entry.file_name().to_string_lossy().to_string();
+        if known.contains(&job_id) {
+            // Crashed after indexing but before the marker was cleared.
+            let _ = fs::remove_file(&marker_path);
+            continue;
+        }
+        let marker = fs::read_to_string(&marker_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<ImportMarker>(&contents).ok());
+        let resumed = marker.and_then(|marker| {
+            if !Path::new(&marker.source_path).exists() {
+                return None;
+            }
+            let _ = fs::remove_dir_all(&job_dir);
+            create_job_from_path_inner(app, state, marker.source_path, marker.options).ok()
+        });
+        match resumed {
+            Some(job) => {
+                let _ = queue.enqueue(job.id.clone());
+                emit_job_log(app, &job.id, "Resumed interrupted import.");
+            }
+            None => {
+                let _ = fs::remove_dir_all(&job_dir);
+            }
+        }
+    }
+}
+
+// Every process that runs jobs (the window, `voicenote transcribe`) holds a shared lock on this
+// file next to jobs.db for as long as it runs.
+pub(super) const JOB_STORE_LOCK_FILE: &str = "jobs.lock";
+
+pub struct JobStoreLock {
+    pub(super) _file: File,
+}
+
+// Takes the shared lock and returns whether this process got the store to itself first. Only
+// then were jobs still marked running cut off by a quit or crash; otherwise another process
+// may be running them right now and resume_interrupted_jobs must leave them alone.
+pub(super) fn acquire_job_store_lock(path: &Path) -> Result<(File, bool), String> {
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+        .map_err(|err| format!("failed to open {}: {err}", path.display()))?;
+    let alone = match file.try_lock() {
+        Ok(()) => true,
+        Err(std::fs::TryLockError::WouldBlock) => false,
+        Err(std::fs::TryLockError::Error(err)) => return Err(format!("failed to lock {}: {err}", path.display())),
+    };
+    // Converting a held lock is platform dependent, so let go before taking the shared one.
+    if alone {
+        file.unlock().map_err(|err| format!("failed to unlock {}: {err}", path.display()))?;
+    }
+    file.lock_shared()
+        .map_err(|err| format!("failed to lock {}: {err}", path.display()))?;
+    Ok((file, alone))
+}
+
+pub fn lock_job_store(app: &Core) -> Result<bool, String> {
+    let (file, alone) = acquire_job_store_lock(&app_data_root(app)?.join(JOB_STORE_LOCK_FILE))?;
+    app.manage(JobStoreLock { _file: file });
+    Ok(alone)
+}
+
+pub(super) const SUMMARY_INTERRUPTED: &str = "Interrupted: the app quit before this finished.";
+
+// The summary queue lives in memory, so summaries, template runs and action items it held
+// when the app quit will never finish. They are marked failed so they can be run again and
+// don't hold off an update install forever. Returns whether anything changed.
+pub(super) fn reset_interrupted_summaries(job: &mut Job) -> bool {
+    let stale = |status: Option<&str>| matches!(status, Some("queued" | "running"));
+    let mut changed = false;
+    if stale(job.summary_status.as_deref()) {
+        job.summary_status = Some("error".to_string());
+        job.summary_error = Some(SUMMARY_INTERRUPTED.to_string());
+        changed = true;
+    }
+    for template in job.template_summaries.values_mut() {
+        if stale(Some(template.status.as_str())) {
+            template.status = "error".to_string();
+            template.error = Some(SUMMARY_INTERRUPTED.to_string());
+            changed = true;
+        }
+    }
+    if stale(job.action_items_status.as_deref()) {
+        job.action_items_status = Some("error".to_string());
+        changed = true;
+    }
+    changed
+}
+
+// Jobs the last run left in the pipeline. Running ones were cut off by a quit or crash: they
+// are marked stalled, then queued again with everything that was still waiting, oldest first.
+pub fn resume_interrupted_jobs(app: &Core) {
+    let state = app.state::<JobIndexState>();
+    let mut changed = Vec::new();
+    let mut queued = Vec::new();
+    {
+        let Ok(mut guard) = state.index.lock() else {
+            return;
+        };
+        for job in guard.jobs.iter_mut().rev().filter(|job| job.trashed_at.is_none()) {
+            let mut dirty = false;
+            if reset_interrupted_summaries(job) {
+                push_log(job, "Summarization was interrupted by the app quitting; run it again.");
+                dirty = true;
+            }
+            if job.status == JobStatus::Running && advance_job(job, JobStatus::Stalled) {
+                push_log(job, "Interrupted: the app quit while this job was running.");
+            }
+            if job.status == JobStatus::Stalled && advance_job(job, JobStatus::Queued) {
+                job.stage = "import".to_string();
+                job.progress = 0.0;
+                push_log(job, "Queued again after the interruption.");
+                dirty = true;
+            }
+            if dirty {
+                if let Err(err) = state.store.save_job(job) {
+                    tracing::warn!(job_id = %job.id, "failed to save the resumed job: {err}");
+                }
+                changed.push(job.clone());
+            }
+            if job.status == JobStatus::Queued {
+                queued.push(job.id.clone());
+            }
+        }
+    }
+    for job in &changed {
+        emit_job_updated(app, job);
+    }
+    let queue = app.state::<JobQueueState>();
+    for job_id in queued {
+        let _ = queue.enqueue(job_id);
+    }
+}
+
+// Everything needed to index a job whose audio already sits in its job folder.
+pub(super) struct JobImport {
+    pub(super) job_id: String,
+    pub(super) filename: String,
+    pub(super) audio_path: PathBuf,
+    pub(super) source_path: Option<String>,
+    pub(super) options: JobOptions,
+    pub(super) audio_sha256: Option<String>,
+    pub(super) tags: Vec<String>,
+    pub(super) duplicate_of: Option<String>,
+    pub(super) source_metadata: Option<SourceMetadata>,
+}
+
+pub(super) fn register_job(app: &Core, state: &JobIndexState, import: JobImport) -> Result<Job, String> {
+    let JobImport {
+        job_id,
+        filename,
+        audio_path,
+        source_path,
+        options,
+        audio_sha256,
+        tags,
+        duplicate_of,
+        source_metadata,
+    } = import;
+    let profile = app
+        .state::<ConfigState>()
+        .config
+        .lock()
+        .ok()
+        .map(|config| config.active_profile.clone());
+    let mut job = Job {
+        id: job_id,
+        filename,
+        status: JobStatus::Queued,
+        progress: 0.0,
+        stage: "import".to_string(),
+        logs: Vec::new(),
+        created_at: unix_timestamp_string(),
+        audio_path: audio_path.to_string_lossy().to_string(),
+        transcript_txt_path: String::new(),
+        transcript_json_path: String::new(),
+        transcript_srt_path: String::new(),
+        md_preview: None,
+        summary_status: Some("not_started".to_string()),
+        summary_model: None,
+        summary_error: None,
+        summary_md: None,
+        exported_to_obsidian: false,
+        media_info: None,
+        source_path,
+        tags,
+        options,
+        audio_sha256,
+        duplicate_of,
+        run_manifest: None,
+        source_metadata,
+        template_summaries: BTreeMap::new(),
+        action_items_status: None,
+        title: None,
+        summary_language: None,
+        exported_path: None,
+        exported_hash: None,
+        profile,
+        target_exports: BTreeMap::new(),
+        export_status: None,
+        export_error: None,
+        calendar_event: None,
+        favorite: false,
+        display_title: None,
+        notes: String::new(),
+        archived: false,
+        trashed_at: None,
+        transitions: Vec::new(),
+        unsaved_logs: Vec::new(),
+    };
+    push_log(&mut job, "Queued for processing.");
+    // Probing is best-effort: a missing ffprobe should never block the import.
+    match resolve_ffmpeg_path(app).and_then(|ffmpeg| probe_media_info(&ffmpeg, &job.audio_path)) {
+        Ok(info) => job.media_info = Some(info),
+        Err(err) => push_log(&mut job, &format!("Media probe skipped: {err}")),
+    }
+    let mut guard = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?;
+    state.store.save_job(&mut job)?;
+    guard.jobs.insert(0, job.clone());
+    emit_job_updated(app, &job);
+    emit_job_log(app, &job.id, "Queued for processing.");
+    Ok(job)
+}
+
+// Applies AppConfig::trash_retention_days once per launch.
+pub fn purge_expired_trash(app: &Core) {
+    let days = match app.state::<ConfigState>().config.lock() {
+        Ok(guard) => guard.trash_retention_days,
+        Err(_) => return,
+    };
+    if days > 0 {
+        if let Err(err) = purge_trash(app.state::<JobIndexState>(), Some(days)) {
+            tracing::warn!("failed to purge trash: {err}");
+        }
+    }
+}
+
+pub(super) fn purge_trash(state: &JobIndexState, older_than_days: Option<u64>) -> Result<usize, String> {
+    let cutoff = older_than_days.map(|days| now_ts().saturating_sub(days.saturating_mul(86_400)));
+    let expired: Vec<Job> = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .iter()
+        .filter(|job| job.trashed_at.is_some_and(|at| cutoff.is_none_or(|cutoff| at <= cutoff)))
+        .cloned()
+        .collect();
+    for job in &expired {
+        purge_trashed_job(state, job)?;
+    }
+    Ok(expired.len())
+}
+
+pub(super) fn purge_trashed_job(state: &JobIndexState, job: &Job) -> Result<(), String> {
+    // Only folders inside trash/ are removed; a job whose files never moved keeps them.
+    let trash_dir = state.jobs_dir.with_file_name(TRASH_DIR);
+    if let Some(job_dir) = job_dir_from_audio_path(&job.audio_path)
+        .filter(|dir| dir.starts_with(&trash_dir) && dir.is_dir())
+    {
+        fs::remove_dir_all(&job_dir).map_err(|err| format!("failed to delete {}: {err}", job_dir.display()))?;
+    }
+    remove_job_entry(state, &job.id)?;
+    Ok(())
+}
+
+// Points every path stored on the job at `to` after its folder moved from `from`.
+pub(super) fn relocate_job_paths(job: &mut Job, from: &Path, to: &Path) -> Result<(), String> {
+    let unsaved_logs = std::mem::take(&mut job.unsaved_logs);
+    let mut value = serde_json::to_value(&*job).map_err(|err| format!("failed to serialize job: {err}"))?;
+    rewrite_path_prefix(&mut value, &from.to_string_lossy(), &to.to_string_lossy());
+    *job = serde_json::from_value(value).map_err(|err| format!("failed to update job paths: {err}"))?;
+    job.unsaved_logs = unsaved_logs;
+    Ok(())
+}
+
+pub(super) fn remove_job_entry(state: &JobIndexState, id: &str) -> Result<bool, String> {
+    let mut guard = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?;
+    let before = guard.jobs.len();
+    guard.jobs.retain(|job| job.id != id);
+    if guard.jobs.len() != before {
+        state.store.delete_job(id)?;
+        state.record_change(id, true);
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+// A folder under jobs/ that no job points at, e.g. left by a crash or a restored backup.
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanedJobFolder {
+    pub id: String,
+    pub path: String,
+    pub bytes: u64,
+    // The audio file adopting the folder would use; None means it can only be deleted.
+    pub audio: Option<String>,
+    pub has_transcript: bool,
+}
+
+// A job whose folder or files were deleted outside the app.
+#[derive(Debug, Clone, Serialize)]
+pub struct BrokenJobEntry {
+    pub id: String,
+    pub title: String,
+    pub folder_missing: bool,
+    // "audio", "transcript", "srt" or "json".
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StorageReconciliation {
+    pub orphaned: Vec<OrphanedJobFolder>,
+    pub broken: Vec<BrokenJobEntry>,
+    // Job ids added back to the list ("adopt").
+    pub adopted: Vec<String>,
+    // Orphaned folders and folder-less jobs removed ("delete").
+    pub deleted: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+// Files a recovered folder's job would point at: audio, then the whisper (or stub) transcript
+// txt/srt/json when transcription had finished.
+pub(super) struct RecoveredArtifacts {
+    pub(super) audio: PathBuf,
+    pub(super) txt: Option<PathBuf>,
+    pub(super) srt: Option<PathBuf>,
+    pub(super) json: Option<PathBuf>,
+}
+
+pub(super) fn recovered_artifacts(job_dir: &Path) -> Option<RecoveredArtifacts> {
+    let mut names: Vec<String> = fs::read_dir(job_dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    let audio = names
+        .iter()
+        .find(|name| name.starts_with("audio.original.") && !name.ends_with(".part"))
+        .or_else(|| names.iter().find(|name| *name == "audio.opus"))
+        .or_else(|| names.iter().find(|name| *name == "audio.wav"))?;
+    let existing = |candidates: &[&str]| {
+        candidates
+            .iter()
+            .map(|name| job_dir.join(name))
+            .find(|path| path.is_file())
+    };
+    Some(RecoveredArtifacts {
+        audio: job_dir.join(audio),
+        txt: existing(&["whisper.txt", "transcript.txt"]),
+        srt: existing(&["whisper.srt", "transcript.srt"]),
+        json: existing(&["whisper.json", "segments.json"]),
+    })
+}
+
+// `recording` is the job folder start_recording is writing to; it gets an index entry only at
+// stop_recording.
+pub(super) fn scan_job_storage(
+    jobs_dir: &Path,
+    jobs: &[Job],
+    recording: Option<&str>,
+) -> (Vec<OrphanedJobFolder>, Vec<BrokenJobEntry>) {
+    let known: HashSet<&str> = jobs.iter().map(|job| job.id.as_str()).collect();
+    let mut orphaned = Vec::new();
+    for entry in fs::read_dir(jobs_dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let id = entry.file_name().to_string_lossy().to_string();
+        // Folders with an import marker belong to an import in progress (or to
+        // recover_interrupted_imports on the next launch).
+        if !path.is_dir()
+            || id.starts_with('.')
+            || known.contains(id.as_str())
+            || recording == Some(id.as_str())
+            || path.join(IMPORT_MARKER).exists()
+        {
+            continue;
+        }
+        let artifacts = recovered_artifacts(&path);
+        orphaned.push(OrphanedJobFolder {
+            id,
+            path: path.to_string_lossy().to_string(),
+            bytes: dir_size(&path),
+            audio: artifacts.as_ref().map(|found| found.audio.to_string_lossy().to_string()),
+            has_transcript: artifacts.is_some_and(|found| found.txt.is_some()),
+        });
+    }
+    orphaned.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut broken = Vec::new();
+    for job in jobs {
+        let folder_missing = job_dir_from_audio_path(&job.audio_path).is_none_or(|dir| !dir.is_dir());
+        let mut missing = Vec::new();
+        // An archived job's audio lives inside archive.zip.
+        if !job.archived && !Path::new(&job.audio_path).is_file() {
+            missing.push("audio".to_string());
+        }
+        for (name, path) in [
+            ("transcript", &job.transcript_txt_path),
+            ("srt", &job.transcript_srt_path),
+            ("json", &job.transcript_json_path),
+        ] {
+            if !path.is_empty() && !Path::new(path).is_file() {
+                missing.push(name.to_string());
+            }
+        }
+        if folder_missing || !missing.is_empty() {
+            broken.push(BrokenJobEntry {
+                id: job.id.clone(),
+                title: job_title(job),
+                folder_missing,
+                missing,
+            });
+        }
+    }
+    (orphaned, broken)
+}
+
+// Indexes an orphaned folder as a job again: finished when its transcript survived,
+// otherwise queued for transcription.
+pub(super) fn adopt_job_folder(app: &Core, state: &JobIndexState, folder: &OrphanedJobFolder) -> Result<Job, String> {
+    let job_dir = PathBuf::from(&folder.path);
+    let artifacts = recovered_artifacts(&job_dir).ok_or_else(|| format!("{} has no audio to recover", folder.id))?;
+    let extension = artifacts
+        .audio
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let job = register_job(
+        app,
+        state,
+        JobImport {
+            job_id: folder.id.clone(),
+            filename: format!("{}.{extension}", folder.id),
+            audio_path: artifacts.audio.clone(),
+            source_path: None,
+            options: JobOptions::default(),
+            audio_sha256: sha256_file(&artifacts.audio).ok(),
+            tags: vec!["recovered".to_string()],
+            duplicate_of: None,
+            source_metadata: None,
+        },
+    )?;
+    let Some(txt) = artifacts.txt else {
+        app.state::<JobQueueState>().enqueue(job.id.clone())?;
+        return Ok(job);
+    };
+    let path_string = |path: Option<PathBuf>| path.map(|path| path.to_string_lossy().to_string()).unwrap_or_default();
+    let has_summary = job_dir.join("summary.md").is_file();
+    update_job_and_emit(app, &job.id, |job| {
+        // Never enqueued, so still queued.
+        if !advance_job(job, JobStatus::Done) {
+            return;
+        }
+        job.stage = "done".to_string();
+        job.progress = 1.0;
+        job.transcript_txt_path = txt.to_string_lossy().to_string();
+        job.transcript_srt_path = path_string(artifacts.srt);
+        job.transcript_json_path = path_string(artifacts.json);
+        if has_summary {
+            job.summary_status = Some("done".to_string());
+        }
+        push_log(job, "Recovered from an orphaned job folder.");
+    })?;
+    find_job(state, &job.id)
+}
+
+pub(super) fn find_job(state: &JobIndexState, id: &str) -> Result<Job, String> {
+    state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .iter()
+        .find(|job| job.id == id)
+        .cloned()
+        .ok_or_else(|| "job not found".to_string())
+}
+
+pub(super) fn load_job_segments(job: &Job) -> Result<Vec<Segment>, String> {
+    if job.transcript_json_path.is_empty() {
+        return Ok(Vec::new());
+    }
+    let contents = read_job_text(&job.transcript_json_path)
+        .map_err(|err| format!("failed to read transcript json: {err}"))?;
+    let mut segments = parse_segments_json(&contents)?;
+    // Range transcriptions are timed from the trimmed WAV; shift back onto the original audio.
+    if let Some((start, _)) = job.options.trim_range() {
+        for seg in segments.iter_mut() {
+            seg.start += start as f32;
+            seg.end += start as f32;
+        }
+    }
+    Ok(segments)
+}
+
+// The job's finished summary (index first, then summary.md); empty when there is none.
+pub(super) fn stored_summary(job: &Job) -> String {
+    // A "skipped" summary holds the manual prompt, not a summary.
+    if job.summary_status.as_deref() == Some("skipped") {
+        return String::new();
+    }
+    job.summary_md
+        .clone()
+        .filter(|summary| !summary.trim().is_empty())
+        .or_else(|| {
+            job_dir_from_audio_path(&job.audio_path)
+                .and_then(|dir| read_job_text(dir.join("summary.md")).ok())
+        })
+        .unwrap_or_default()
+}
+
+pub(super) const JOB_BUNDLE_FORMAT: &str = "voicenote-job-bundle";
+
+pub(super) const JOB_BUNDLE_VERSION: u32 = 1;
+
+// The converted WAV is regenerated from the original audio on import, so bundles leave it out
+// unless it is the job's only audio ("converted_only" retention).
+pub(super) const JOB_BUNDLE_SKIPPED_FILES: [&str; 1] = ["audio.wav"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct BundleFile {
+    pub(super) name: String,
+    pub(super) size: u64,
+    pub(super) sha256: String,
+}
+
+// manifest.json of a job bundle. Paths inside `job` are file names within the bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct JobBundleManifest {
+    pub(super) format: String,
+    pub(super) version: u32,
+    pub(super) app_version: String,
+    pub(super) exported_at: String,
+    pub(super) job: Job,
+    pub(super) files: Vec<BundleFile>,
+}
+
+pub(super) fn bundle_file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+pub(super) fn write_job_bundle(job: &Job, job_dir: &Path, dest: &Path) -> Result<(), String> {
+    let audio = bundle_file_name(&job.audio_path);
+    let mut names: Vec<String> = fs::read_dir(job_dir)
+        .map_err(|err| format!("failed to read job dir: {err}"))?
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| *name == audio || !JOB_BUNDLE_SKIPPED_FILES.contains(&name.as_str()))
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    names.sort();
+
+    let mut bundled = job.clone();
+    bundled.audio_path = bundle_file_name(&job.audio_path);
+    bundled.transcript_txt_path = bundle_file_name(&job.transcript_txt_path);
+    bundled.transcript_json_path = bundle_file_name(&job.transcript_json_path);
+    bundled.transcript_srt_path = bundle_file_name(&job.transcript_srt_path);
+    // Bundles are meant for another machine, so sealed files go in decrypted.
+    let mut files = Vec::new();
+    for name in &names {
+        let path = job_dir.join(name);
+        let (size, sha256) = if file_is_sealed(&path) {
+            let data = read_job_file(&path).map_err(|err| format!("failed to read {name}: {err}"))?;
+            (data.len() as u64, to_hex(&Sha256::digest(&data)))
+        } else {
+            (fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0), sha256_file(&path)?)
+        };
+        files.push(BundleFile {
+            name: name.clone(),
+            size,
+            sha256,
+        });
+    }
+    let manifest = JobBundleManifest {
+        format: JOB_BUNDLE_FORMAT.to_string(),
+        version: JOB_BUNDLE_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: iso_from_unix(now_ts()),
+        job: bundled,
+        files,
+    };
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|err| format!("failed to serialize manifest: {err}"))?;
+
+    let file = File::create(dest).map_err(|err| format!("failed to create bundle: {err}"))?;
+    let mut writer = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default().large_file(true);
+    writer
+        .start_file("manifest.json", options)
+        .map_err(|err| format!("failed to write bundle: {err}"))?;
+    writer
+        .write_all(manifest_json.as_bytes())
+        .map_err(|err| format!("failed to write bundle: {err}"))?;
+    for name in &names {
+        writer
+            .start_file(name.as_str(), options)
+            .map_err(|err| format!("failed to write bundle: {err}"))?;
+        let path = job_dir.join(name);
+        if file_is_sealed(&path) {
+            let data = read_job_file(&path).map_err(|err| format!("failed to read {name}: {err}"))?;
+            writer.write_all(&data).map_err(|err| format!("failed to write bundle: {err}"))?;
+            continue;
+        }
+        let mut source = File::open(&path).map_err(|err| format!("failed to read {name}: {err}"))?;
+        std::io::copy(&mut source, &mut writer).map_err(|err| format!("failed to write bundle: {err}"))?;
+    }
+    writer.finish().map_err(|err| format!("failed to write bundle: {err}"))?;
+    Ok(())
+}
+
+// Unpacks a bundle into <jobs_dir>/<job_id> and returns the job pointing at the new files.
+// Export state is dropped because it refers to the other machine's vault.
+pub(super) fn read_job_bundle(bundle: &Path, jobs_dir: &Path, job_id: &str) -> Result<Job, String> {
+    let file = File::open(bundle).map_err(|err| format!("failed to open bundle: {err}"))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|err| format!("not a job bundle: {err}"))?;
+    let manifest: JobBundleManifest = {
+        let mut entry = archive
+            .by_name("manifest.json")
+            .map_err(|_| "not a job bundle: manifest.json is missing".to_string())?;
+        let mut contents = String::new();
+        entry
+            .read_to_string(&mut contents)
+            .map_err(|err| format!("failed to read manifest: {err}"))?;
+        serde_json::from_str(&contents).map_err(|err| format!("invalid manifest: {err}"))?
+    };
+    if manifest.format != JOB_BUNDLE_FORMAT || manifest.version > JOB_BUNDLE_VERSION {
+        return Err(format!(
+            "Unsupported bundle format {} v{}.",
+            manifest.format, manifest.version
+        ));
+    }
+
+    let job_dir = jobs_dir.join(job_id);
+    fs::create_dir_all(&job_dir).map_err(|err| format!("failed to create job dir: {err}"))?;
+    let extracted = (|| {
+        for entry in &manifest.files {
+            // Bundles only ever hold flat file names; anything else could escape the job dir.
+            if entry.name.is_empty() || bundle_file_name(&entry.name) != entry.name {
+                return Err(format!("invalid file name in bundle: {}", entry.name));
+            }
+            let target = job_dir.join(&entry.name);
+            {
+                let mut source = archive
+                    .by_name(&entry.name)
+                    .map_err(|_| format!("bundle is missing {}", entry.name))?;
+                let mut out = File::create(&target).map_err(|err| format!("failed to write {}: {err}", entry.name))?;
+                std::io::copy(&mut source, &mut out).map_err(|err| format!("failed to write {}: {err}", entry.name))?;
+            }
+            if sha256_file(&target)? != entry.sha256 {
+                return Err(format!("checksum mismatch for {}", entry.name));
+            }
+        }
+        Ok(())
+    })();
+    if let Err(err) = extracted {
+        let _ = fs::remove_dir_all(&job_dir);
+        return Err(err);
+    }
+
+    let local = |name: &str| {
+        if name.is_empty() {
+            String::new()
+        } else {
+            job_dir.join(name).to_string_lossy().to_string()
+        }
+    };
+    let mut job = manifest.job;
+    job.id = job_id.to_string();
+    job.audio_path = local(&job.audio_path);
+    job.transcript_txt_path = local(&job.transcript_txt_path);
+    job.transcript_json_path = local(&job.transcript_json_path);
+    job.transcript_srt_path = local(&job.transcript_srt_path);
+    job.exported_to_obsidian = false;
+    job.exported_path = None;
+    job.exported_hash = None;
+    job.target_exports.clear();
+    job.export_status = None;
+    job.export_error = None;
+    push_log(&mut job, &format!("Imported from bundle {}.", bundle_file_name(&bundle.to_string_lossy())));
+    Ok(job)
+}
+
+pub(super) const JOB_ARCHIVE_FILE: &str = "archive.zip";
+
+// Left unpacked in an archived job so transcript views, exports and search keep working.
+pub(super) const ARCHIVE_KEPT_FILES: [&str; 3] = ["segments.json", "actions.json", "chapters.json"];
+
+pub(super) fn archive_keeps(name: &str) -> bool {
+    ARCHIVE_KEPT_FILES.contains(&name)
+        || matches!(
+            Path::new(name).extension().and_then(|ext| ext.to_str()),
+            Some("md" | "txt" | "srt" | "vtt")
+        )
+}
+
+// Packs the audio and raw artifacts of a job folder into archive.zip and returns the bytes
+// freed. `keep` names another file to leave in place (the transcript JSON); the clip cache
+// is deleted, and so is audio.wav with `drop_wav` since it can be converted again.
+pub(super) fn archive_job_dir(job_dir: &Path, keep: &str, drop_wav: bool) -> Result<u64, String> {
+    let archive_path = job_dir.join(JOB_ARCHIVE_FILE);
+    if archive_path.exists() {
+        return Err("The job folder already has an archive.".to_string());
+    }
+    let before = dir_size(job_dir);
+    let mut names: Vec<String> = fs::read_dir(job_dir)
+        .map_err(|err| format!("failed to read job dir: {err}"))?
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.starts_with('.') && name != keep && !archive_keeps(name))
+        .filter(|name| !(drop_wav && name == "audio.wav"))
+        .collect();
+    names.sort();
+
+    let part_path = job_dir.join(format!("{JOB_ARCHIVE_FILE}.part"));
+    let written = (|| {
+        let file = File::create(&part_path).map_err(|err| format!("failed to create archive: {err}"))?;
+        let mut writer = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default().large_file(true);
+        for name in &names {
+            writer
+                .start_file(name.as_str(), options)
+                .map_err(|err| format!("failed to write archive: {err}"))?;
+            let mut source = File::open(job_dir.join(name)).map_err(|err| format!("failed to read {name}: {err}"))?;
+            std::io::copy(&mut source, &mut writer).map_err(|err| format!("failed to write archive: {err}"))?;
+        }
+        writer.finish().map_err(|err| format!("failed to write archive: {err}"))?;
+        fs::rename(&part_path, &archive_path).map_err(|err| format!("failed to write archive: {err}"))
+    })();
+    if let Err(err) = written {
+        let _ = fs::remove_file(&part_path);
+        return Err(err);
+    }
+    for name in &names {
+        let _ = fs::remove_file(job_dir.join(name));
+    }
+    if drop_wav {
+        let _ = fs::remove_file(job_dir.join("audio.wav"));
+    }
+    let _ = fs::remove_dir_all(job_dir.join("clips"));
+    Ok(before.saturating_sub(dir_size(job_dir)))
+}
+
+pub(super) fn unarchive_job_dir(job_dir: &Path) -> Result<(), String> {
+    let archive_path = job_dir.join(JOB_ARCHIVE_FILE);
+    let file = File::open(&archive_path).map_err(|err| format!("failed to open archive: {err}"))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|err| format!("invalid archive: {err}"))?;
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|err| format!("failed to read archive: {err}"))?;
+        let name = entry.name().to_string();
+        // Archives only ever hold flat file names; anything else could escape the job dir.
+        if name.is_empty() || bundle_file_name(&name) != name {
+            return Err(format!("invalid file name in archive: {name}"));
+        }
+        let mut out = File::create(job_dir.join(&name)).map_err(|err| format!("failed to write {name}: {err}"))?;
+        std::io::copy(&mut entry, &mut out).map_err(|err| format!("failed to write {name}: {err}"))?;
+    }
+    fs::remove_file(&archive_path).map_err(|err| format!("failed to remove archive: {err}"))
+}
+
+pub(super) fn archive_job_blocking(app: &Core, id: &str, drop_wav: Option<bool>) -> Result<u64, String> {
+    let job = find_job(app.state::<JobIndexState>(), id)?;
+    if job.archived {
+        return Err("The job is already archived.".to_string());
+    }
+    if job.status.is_active() {
+        return Err("Wait for the job to finish before archiving it.".to_string());
+    }
+    let job_dir = job_dir_from_audio_path(&job.audio_path)
+        .ok_or_else(|| "missing job directory".to_string())?;
+    // With "converted_only" retention audio.wav is the job's only audio.
+    let drop_wav = drop_wav.unwrap_or(false) && bundle_file_name(&job.audio_path) != "audio.wav";
+    let freed = archive_job_dir(&job_dir, &bundle_file_name(&job.transcript_json_path), drop_wav)?;
+    update_job_and_emit(app, id, |job| {
+        job.archived = true;
+        push_log(job, &format!("Archived; {} freed.", format_disk_bytes(freed)));
+    })?;
+    Ok(freed)
+}
+
+pub(super) fn unarchive_job_blocking(app: &Core, id: &str) -> Result<(), String> {
+    let job = find_job(app.state::<JobIndexState>(), id)?;
+    if !job.archived {
+        return Ok(());
+    }
+    let job_dir = job_dir_from_audio_path(&job.audio_path)
+        .ok_or_else(|| "missing job directory".to_string())?;
+    unarchive_job_dir(&job_dir)?;
+    update_job_and_emit(app, id, |job| {
+        job.archived = false;
+        push_log(job, "Restored from archive.");
+    })
+}
+
+pub(super) fn export_job_bundle_blocking(app: &Core, id: &str, dest: String) -> Result<String, String> {
+    let job = find_job(app.state::<JobIndexState>(), id)?;
+    if job.status.is_active() {
+        return Err("Wait for the job to finish before exporting it.".to_string());
+    }
+    let job_dir = job_dir_from_audio_path(&job.audio_path)
+        .ok_or_else(|| "missing job directory".to_string())?;
+    let dest_dir = PathBuf::from(dest);
+    fs::create_dir_all(&dest_dir).map_err(|err| format!("failed to create export dir: {err}"))?;
+    let path = dest_dir.join(format!("{}.voicenote.zip", job_title(&job)));
+    write_job_bundle(&job, &job_dir, &path)?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+pub(super) fn import_job_bundle_blocking(app: &Core, path: &str) -> Result<Job, String> {
+    let state = app.state::<JobIndexState>();
+    let mut job = read_job_bundle(Path::new(path), &state.jobs_dir, &generate_job_id())?;
+    match restore_bundle_wav(app, &job) {
+        Ok(true) => push_log(&mut job, "Converted the audio to audio.wav again."),
+        Ok(false) => {}
+        Err(err) => push_log(&mut job, &format!("audio.wav not restored: {err}")),
+    }
+    let mut guard = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?;
+    // The bundled log history is new to this store.
+    job.unsaved_logs = job.logs.clone();
+    state.store.save_job(&mut job)?;
+    guard.jobs.insert(0, job.clone());
+    emit_job_updated(app, &job);
+    Ok(job)
+}
+
+// Bundles leave the converted WAV out; the waveform, chapters and clips read it, so an imported
+// job that had one gets it back the way process_job made it. Returns whether it converted.
+pub(super) fn restore_bundle_wav(app: &Core, job: &Job) -> Result<bool, String> {
+    let job_dir = job_dir_from_audio_path(&job.audio_path).ok_or_else(|| "missing job directory".to_string())?;
+    let wav_path = job_dir.join("audio.wav");
+    if job.status != JobStatus::Done || wav_path.exists() || job.audio_path.is_empty() {
+        return Ok(false);
+    }
+    let (normalize, reduce_noise) = app
+        .state::<ConfigState>()
+        .config
+        .lock()
+        .map(|config| (config.normalize_loudness, config.reduce_noise))
+        .map_err(|_| "config mutex poisoned".to_string())?;
+    let ffmpeg_path = resolve_ffmpeg_path(app)?;
+    let scratch = ScratchAudio::new(&format!("{}-bundle", job.id))?;
+    let input = scratch.open(Path::new(&job.audio_path))?;
+    convert_to_wav(
+        &ffmpeg_path,
+        &input.to_string_lossy(),
+        &wav_path,
+        build_audio_filters(normalize, reduce_noise).as_deref(),
+        job.options.trim_range(),
+    )?;
+    let encryption = job_encryption();
+    if encryption.enabled && encryption.audio {
+        rewrite_job_file(&wav_path, true, &encryption)?;
+    }
+    Ok(true)
+}
+
+pub(super) fn job_artifact_path(job: &Job, artifact: Option<&str>) -> Result<PathBuf, String> {
+    let job_dir = job_dir_from_audio_path(&job.audio_path)
+        .ok_or_else(|| "missing job directory".to_string())?;
+    let path = match artifact.map(|a| a.trim()).filter(|a| !a.is_empty()) {
+        None | Some("dir") => job_dir,
+        Some("audio") => PathBuf::from(&job.audio_path),
+        Some("wav") => job_dir.join("audio.wav"),
+        Some("transcript") => PathBuf::from(&job.transcript_txt_path),
+        Some("json") => PathBuf::from(&job.transcript_json_path),
+        Some("srt") => PathBuf::from(&job.transcript_srt_path),
+        Some("summary") => job_dir.join("summary.md"),
+        Some(other) => return Err(format!("Unknown artifact: {other}")),
+    };
+    if path.as_os_str().is_empty() || !path.exists() {
+        return Err("Artifact not found on disk.".to_string());
+    }
+    Ok(path)
+}
+
+pub(super) fn reveal_path(path: &Path) -> Result<(), String> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("open");
+        if path.is_dir() {
+            cmd.arg(path);
+        } else {
+            cmd.arg("-R").arg(path);
+        }
+        cmd
+    } else if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("explorer");
+        if path.is_dir() {
+            cmd.arg(path);
+        } else {
+            cmd.arg(format!("/select,{}", path.display()));
+        }
+        cmd
+    } else {
+        // xdg-open cannot select a file, so open the containing folder instead.
+        let target = if path.is_dir() {
+            path
+        } else {
+            path.parent().unwrap_or(path)
+        };
+        let mut cmd = Command::new("xdg-open");
+        cmd.arg(target);
+        cmd
+    };
+    cmd.spawn()
+        .map(|_| ())
+        .map_err(|err| format!("failed to open file manager: {err}"))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobStorageUsage {
+    pub id: String,
+    pub filename: String,
+    pub total_bytes: u64,
+    pub clip_bytes: u64,
+    pub clip_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageUsage {
+    pub total_bytes: u64,
+    pub clip_cache_bytes: u64,
+    pub clip_cache_max_bytes: u64,
+    pub jobs: Vec<JobStorageUsage>,
+}
+
+// Whisper throughput for one model: audio seconds transcribed per second of processing.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelSpeedStats {
+    pub model: String,
+    pub runs: usize,
+    pub audio_seconds: f64,
+    pub processing_seconds: f64,
+    pub speed: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StorageByCategory {
+    pub audio: u64,
+    pub transcripts: u64,
+    pub summaries: u64,
+    pub clips: u64,
+    pub archives: u64,
+    pub other: u64,
+    pub models: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MonthlyStats {
+    // "YYYY-MM" of the recording date.
+    pub month: String,
+    pub notes: usize,
+    pub hours: f64,
+    pub words: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VoiceNoteStats {
+    pub jobs: usize,
+    // Jobs with a finished transcript.
+    pub notes: usize,
+    pub hours_transcribed: f64,
+    pub words_transcribed: u64,
+    pub summaries_done: usize,
+    pub summaries_failed: usize,
+    // done / (done + failed); None before any summary ran.
+    pub summary_success_rate: Option<f64>,
+    pub models: Vec<ModelSpeedStats>,
+    pub storage: StorageByCategory,
+    // Oldest month first, months without notes omitted.
+    pub monthly: Vec<MonthlyStats>,
+}
+
+pub(super) fn add_storage_category(storage: &mut StorageByCategory, job_dir: &Path) {
+    for entry in fs::read_dir(job_dir).into_iter().flatten().flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = entry.path();
+        let bytes = if path.is_dir() { dir_size(&path) } else { entry.metadata().map(|meta| meta.len()).unwrap_or(0) };
+        let extension = Path::new(&name).extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+        let bucket = if name == "clips" {
+            &mut storage.clips
+        } else if name == JOB_ARCHIVE_FILE {
+            &mut storage.archives
+        } else if name.starts_with("audio.") {
+            &mut storage.audio
+        } else if extension == "md" {
+            &mut storage.summaries
+        } else if name.starts_with("whisper.")
+            || name.starts_with("transcript.")
+            || name == "segments.json"
+            || matches!(extension, "txt" | "srt" | "vtt")
+        {
+            &mut storage.transcripts
+        } else {
+            &mut storage.other
+        };
+        *bucket += bytes;
+    }
+}
+
+// Totals over the jobs recorded in `year` (all jobs when None); word counts read each
+// finished transcript from disk.
+pub(super) struct CachedWordCount {
+    pub(super) path: String,
+    pub(super) size: u64,
+    // Nanoseconds since the Unix epoch.
+    pub(super) modified: u64,
+    pub(super) words: u64,
+}
+
+// Words in the job's transcript. Counts are kept by job id for the session and redone only
+// when the transcript's size or mtime changes, so get_stats doesn't reread every transcript.
+pub(super) fn transcript_word_count(job: &Job) -> u64 {
+    static COUNTS: Mutex<BTreeMap<String, CachedWordCount>> = Mutex::new(BTreeMap::new());
+    let path = &job.transcript_txt_path;
+    let Some((size, modified)) = fs::metadata(path).ok().and_then(|meta| {
+        let modified = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_nanos() as u64;
+        Some((meta.len(), modified))
+    }) else {
+        return 0;
+    };
+    let cached = COUNTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&job.id)
+        .filter(|count| &count.path == path && count.size == size && count.modified == modified)
+        .map(|count| count.words);
+    if let Some(words) = cached {
+        return words;
+    }
+    let words = read_transcript_text(path)
+        .map(|text| text.split_whitespace().count() as u64)
+        .unwrap_or(0);
+    COUNTS.lock().unwrap_or_else(|e| e.into_inner()).insert(
+        job.id.clone(),
+        CachedWordCount {
+            path: path.clone(),
+            size,
+            modified,
+            words,
+        },
+    );
+    words
+}
+
+pub(super) fn collect_stats(jobs: &[Job], year: Option<i64>, clock: &WallClock) -> VoiceNoteStats {
+    let mut stats = VoiceNoteStats::default();
+    let mut monthly: BTreeMap<String, MonthlyStats> = BTreeMap::new();
+    let mut models: BTreeMap<String, ModelSpeedStats> = BTreeMap::new();
+    for job in jobs {
+        let (start, _) = recording_interval(job, clock);
+        let (job_year, month, _) = civil_date_from_unix((start as i64 + clock.offset_at(start as i64)).max(0) as u64);
+        if job.trashed_at.is_some() || year.is_some_and(|year| year != job_year) {
+            continue;
+        }
+        stats.jobs += 1;
+        if let Some(job_dir) = job_dir_from_audio_path(&job.audio_path) {
+            add_storage_category(&mut stats.storage, &job_dir);
+        }
+        match job.summary_status.as_deref() {
+            Some("done") => stats.summaries_done += 1,
+            Some("error") => stats.summaries_failed += 1,
+            _ => {}
+        }
+        if job.status != JobStatus::Done || job.transcript_txt_path.is_empty() {
+            continue;
+        }
+        let seconds = job.media_info.as_ref().and_then(|info| info.duration_seconds).unwrap_or(0.0);
+        let words = transcript_word_count(job);
+        stats.notes += 1;
+        stats.hours_transcribed += seconds / 3600.0;
+        stats.words_transcribed += words;
+        let key = format!("{job_year:04}-{month:02}");
+        let entry = monthly.entry(key.clone()).or_insert_with(|| MonthlyStats {
+            month: key,
+            ..MonthlyStats::default()
+        });
+        entry.notes += 1;
+        entry.hours += seconds / 3600.0;
+        entry.words += words;
+        for run in job.run_manifest.iter().flat_map(|manifest| &manifest.runs) {
+            let model = Path::new(&run.model_path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().trim_start_matches("ggml-").to_string())
+                .unwrap_or_default();
+            let entry = models.entry(model.clone()).or_insert_with(|| ModelSpeedStats {
+                model,
+                runs: 0,
+                audio_seconds: 0.0,
+                processing_seconds: 0.0,
+                speed: 0.0,
+            });
+            entry.runs += 1;
+            entry.audio_seconds += seconds;
+            entry.processing_seconds += run.duration_seconds;
+        }
+    }
+    let attempted = stats.summaries_done + stats.summaries_failed;
+    stats.summary_success_rate = (attempted > 0).then(|| stats.summaries_done as f64 / attempted as f64);
+    stats.models = models
+        .into_values()
+        .map(|mut model| {
+            if model.processing_seconds > 0.0 {
+                model.speed = model.audio_seconds / model.processing_seconds;
+            }
+            model
+        })
+        .collect();
+    stats.monthly = monthly.into_values().collect();
+    stats
+}
+
+pub(super) fn stats_report(app: &Core, year: Option<i64>) -> Result<VoiceNoteStats, String> {
+    let jobs: Vec<Job> = app
+        .state::<JobIndexState>()
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .clone();
+    let mut stats = collect_stats(&jobs, year, &WallClock::Local);
+    stats.storage.models = dir_size(&app.state::<ModelDownloadState>().models_dir());
+    Ok(stats)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppDataMoveProgress {
+    // "copying", "rewriting", "cleaning_up" or "done".
+    pub stage: String,
+    pub copied_bytes: u64,
+    pub total_bytes: u64,
+}
+
+pub(super) fn emit_app_data_move(app: &Core, stage: &str, copied_bytes: u64, total_bytes: u64) {
+    let progress = AppDataMoveProgress {
+        stage: stage.to_string(),
+        copied_bytes,
+        total_bytes,
+    };
+    let _ = app.emit("app_data:move", progress);
+}
+
+// Copies a directory tree, keeping symlinks (the ffmpeg and whisper dylib links) as links.
+pub(super) fn copy_tree(src: &Path, dest: &Path, on_file: &mut dyn FnMut(u64)) -> Result<(), String> {
+    fs::create_dir_all(dest).map_err(|err| format!("Failed to create {}: {err}", dest.display()))?;
+    let entries = fs::read_dir(src).map_err(|err| format!("Failed to read {}: {err}", src.display()))?;
+    for entry in entries.flatten() {
+        let from = entry.path();
+        let to = dest.join(entry.file_name());
+        let meta = fs::symlink_metadata(&from).map_err(|err| format!("Failed to read {}: {err}", from.display()))?;
+        // Windows builds ship no such links; there fs::copy below copies what a link points to.
+        #[cfg(unix)]
+        if meta.file_type().is_symlink() {
+            let target = fs::read_link(&from).map_err(|err| format!("Failed to read {}: {err}", from.display()))?;
+            std::os::unix::fs::symlink(target, &to)
+                .map_err(|err| format!("Failed to link {}: {err}", to.display()))?;
+            continue;
+        }
+        if meta.is_dir() {
+            copy_tree(&from, &to, on_file)?;
+        } else {
+            let bytes = fs::copy(&from, &to).map_err(|err| format!("Failed to copy {}: {err}", from.display()))?;
+            on_file(bytes);
+        }
+    }
+    Ok(())
+}
+
+// Jobs store absolute paths (audio, transcripts, clips), so every string under the old root
+// is pointed at the new one.
+pub(super) fn rewrite_path_prefix(value: &mut serde_json::Value, old: &str, new: &str) {
+    match value {
+        serde_json::Value::String(text) => {
+            if let Some(rest) = text.strip_prefix(old).filter(|rest| rest.is_empty() || rest.starts_with('/')) {
+                *text = format!("{new}{rest}");
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|item| rewrite_path_prefix(item, old, new)),
+        serde_json::Value::Object(map) => map.values_mut().for_each(|item| rewrite_path_prefix(item, old, new)),
+        _ => {}
+    }
+}
+
+pub(super) fn rewrite_paths_in_file(path: &Path, old: &Path, new: &Path) -> Result<(), String> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Ok(());
+    };
+    let mut value: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|err| format!("Failed to parse {}: {err}", path.display()))?;
+    rewrite_path_prefix(&mut value, &old.to_string_lossy(), &new.to_string_lossy());
+    let json = serde_json::to_string_pretty(&value).map_err(|err| format!("Failed to serialize {}: {err}", path.display()))?;
+    fs::write(path, json).map_err(|err| format!("Failed to write {}: {err}", path.display()))
+}
+
+pub(super) fn rewrite_stored_paths(root: &Path, old: &Path, new: &Path) -> Result<(), String> {
+    rewrite_paths_in_file(&root.join("config.json"), old, new)?;
+    JobStore::open(&root.join(JOB_STORE_FILE))?.rewrite_paths(old, new)
+}
+
+// <new_location>/voicenote, after checking it is somewhere the data can actually go.
+pub(super) fn app_data_move_target(new_location: &Path, current: &Path) -> Result<PathBuf, String> {
+    if !new_location.is_absolute() || !new_location.is_dir() {
+        return Err(format!("{} is not an existing folder.", new_location.display()));
+    }
+    let target = new_location.join("voicenote");
+    if target == current {
+        return Err("App data is already in this folder.".to_string());
+    }
+    if target.starts_with(current) {
+        return Err("Choose a folder outside the current app data folder.".to_string());
+    }
+    if target.exists() {
+        return Err(format!("{} already exists; choose another folder.", target.display()));
+    }
+    Ok(target)
+}
+
+// Whether the summary queue still holds or runs something for this job.
+pub(super) fn summaries_in_flight(job: &Job) -> bool {
+    let busy = |status: Option<&str>| matches!(status, Some("queued" | "running"));
+    busy(job.summary_status.as_deref())
+        || busy(job.action_items_status.as_deref())
+        || job.template_summaries.values().any(|template| busy(Some(template.status.as_str())))
+}
+
+// Everything that writes under app data has to be idle; checked after the queue and the
+// background writers were told to hold off, so nothing new starts in between.
+pub(super) fn ensure_app_data_idle(app: &Core) -> Result<(), String> {
+    let (jobs, summaries) = {
+        let state = app.state::<JobIndexState>();
+        let guard = state
+            .index
+            .lock()
+            .map_err(|_| "index mutex poisoned".to_string())?;
+        (
+            guard.jobs.iter().any(|job| job.status.is_active()),
+            guard.jobs.iter().any(summaries_in_flight),
+        )
+    };
+    if jobs {
+        return Err("Wait for queued and running jobs to finish before moving app data.".to_string());
+    }
+    if summaries {
+        return Err("Wait for summaries in progress to finish before moving app data.".to_string());
+    }
+    let recording = app
+        .try_state::<RecordingState>()
+        .is_some_and(|recording| recording.active.lock().map(|active| active.is_some()).unwrap_or(true));
+    if recording {
+        return Err("Stop the recording before moving app data.".to_string());
+    }
+    let downloading = app
+        .try_state::<ModelDownloadState>()
+        .is_some_and(|downloads| downloads.cancel_flags.lock().map(|flags| !flags.is_empty()).unwrap_or(true));
+    if downloading {
+        return Err("Wait for downloads to finish before moving app data.".to_string());
+    }
+    Ok(())
+}
+
+pub(super) fn move_app_data_blocking(app: &Core, new_location: &str) -> Result<String, String> {
+    if APP_DATA_MOVING.swap(true, Ordering::SeqCst) {
+        return Err("App data is already being moved.".to_string());
+    }
+    let queue = app.state::<JobQueueState>();
+    let was_paused = queue.is_paused();
+    queue.paused.store(true, Ordering::SeqCst);
+    let result = ensure_app_data_idle(app).and_then(|()| relocate_app_data(app, new_location));
+    // After a successful move everything stays on hold until the restart.
+    if result.is_err() {
+        APP_DATA_MOVING.store(false, Ordering::SeqCst);
+        queue.paused.store(was_paused, Ordering::SeqCst);
+    }
+    result
+}
+
+pub(super) fn relocate_app_data(app: &Core, new_location: &str) -> Result<String, String> {
+    let state = app.state::<JobIndexState>();
+    let base_dir = app.app_data_dir()?;
+    let current = app_data_root(app)?;
+    let target = app_data_move_target(Path::new(new_location.trim()), &current)?;
+    state.store.checkpoint()?;
+    let total = dir_size(&current);
+    emit_app_data_move(app, "copying", 0, total);
+    // Same volume: a rename is instant and needs no extra space.
+    let copied = if fs::rename(&current, &target).is_ok() {
+        false
+    } else {
+        ensure_disk_space(&target, total, "move app data")?;
+        let mut copied_bytes = 0u64;
+        let result = copy_tree(&current, &target, &mut |bytes| {
+            copied_bytes += bytes;
+            emit_app_data_move(app, "copying", copied_bytes, total);
+        });
+        if let Err(err) = result {
+            let _ = fs::remove_dir_all(&target);
+            return Err(err);
+        }
+        true
+    };
+    emit_app_data_move(app, "rewriting", total, total);
+    let finish = || -> Result<(), String> {
+        rewrite_stored_paths(&target, &current, &target)?;
+        let pointer = base_dir.join(APP_DATA_LOCATION_FILE);
+        if target == base_dir.join("voicenote") {
+            let _ = fs::remove_file(&pointer);
+            Ok(())
+        } else {
+            fs::write(&pointer, target.to_string_lossy().as_bytes())
+                .map_err(|err| format!("Failed to record the new location: {err}"))
+        }
+    };
+    // Without the pointer the next launch would look in the old place, so put things back.
+    if let Err(err) = finish() {
+        if copied {
+            let _ = fs::remove_dir_all(&target);
+        } else {
+            let _ = rewrite_stored_paths(&target, &target, &current);
+            let _ = fs::rename(&target, &current);
+        }
+        return Err(err);
+    }
+    if copied {
+        emit_app_data_move(app, "cleaning_up", total, total);
+        let _ = fs::remove_dir_all(&current);
+    }
+    emit_app_data_move(app, "done", total, total);
+    // Every state struct holds paths under the old root, so reload them all by restarting.
+    let core = app.clone();
+    thread::spawn(move || {
+        thread::sleep(std::time::Duration::from_secs(1));
+        core.restart();
+    });
+    Ok(target.to_string_lossy().to_string())
+}
+
+pub(super) fn get_run_manifest_inner(
+    state: &JobIndexState,
+    id: String,
+) -> Result<Option<RunManifest>, String> {
+    let guard = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?;
+    guard
+        .jobs
+        .iter()
+        .find(|job| job.id == id)
+        .map(|job| job.run_manifest.clone())
+        .ok_or_else(|| "job not found".to_string())
+}
+
+pub(super) fn get_changes_since_inner(
+    config_state: &ConfigState,
+    index_state: &JobIndexState,
+    revision: u64,
+) -> Result<StateChanges, String> {
+    // Read first: anything recorded while collecting is either included or newer than this.
+    let current = STATE_REVISION.load(Ordering::SeqCst);
+    let (jobs, removed_job_ids) = index_state.changes_since(revision)?;
+    let config = if config_state.revision.load(Ordering::SeqCst) > revision {
+        Some(
+            config_state
+                .config
+                .lock()
+                .map_err(|_| "config mutex poisoned".to_string())?
+                .clone(),
+        )
+    } else {
+        None
+    };
+    Ok(StateChanges {
+        revision: current,
+        jobs,
+        removed_job_ids,
+        config,
+    })
+}
+
+pub(super) fn import_settings_inner(
+    app: &Core,
+    state: &ConfigState,
+    path: String,
+) -> Result<SettingsImport, String> {
+    let contents = fs::read_to_string(&path).map_err(|err| format!("Failed to read {path}: {err}"))?;
+    let current = state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    let config = imported_config(&contents, &current)?;
+    let errors = imported_config_errors(&config)?;
+    let config = store_config(app, config)?;
+    Ok(SettingsImport { config, errors })
+}
+
+pub(super) fn reset_to_defaults_inner(
+    app: &Core,
+    state: &ConfigState,
+    scope: String,
+) -> Result<AppConfig, String> {
+    let mut config = state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    reset_config_section(&mut config, scope.trim())?;
+    store_config(app, config)
+}
+
+pub(super) fn list_jobs_inner(state: &JobIndexState) -> Result<Vec<Job>, String> {
+    let guard = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?;
+    // Archived and trashed jobs are only reachable through query_jobs.
+    Ok(guard
+        .jobs
+        .iter()
+        .filter(|job| !job.archived && job.trashed_at.is_none())
+        .cloned()
+        .collect())
+}
+
+pub(super) fn get_job_logs_inner(
+    state: &JobIndexState,
+    id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<JobLogPage, String> {
+    let guard = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?;
+    let job = guard
+        .jobs
+        .iter()
+        .find(|job| job.id == id)
+        .ok_or_else(|| "job not found".to_string())?;
+    let offset = offset.unwrap_or(0).min(job.logs.len());
+    let end = match limit.unwrap_or(0) {
+        0 => job.logs.len(),
+        limit => offset.saturating_add(limit).min(job.logs.len()),
+    };
+    Ok(JobLogPage {
+        lines: job.logs[offset..end].to_vec(),
+        offset,
+        total: job.logs.len(),
+    })
+}
+
+pub(super) fn delete_job_inner(app: &Core, id: String) -> Result<bool, String> {
+    let state = app.state::<JobIndexState>();
+    let Ok(job) = find_job(state, &id) else {
+        return Ok(false);
+    };
+    if job.trashed_at.is_some() {
+        purge_trashed_job(state, &job)?;
+        return Ok(true);
+    }
+    if job.status.is_active() {
+        return Err("Cancel the job before deleting it.".to_string());
+    }
+    let trash_dir = state.jobs_dir.with_file_name(TRASH_DIR);
+    let moved = match job_dir_from_audio_path(&job.audio_path).filter(|dir| dir.is_dir()) {
+        Some(job_dir) => {
+            let name = job_dir
+                .file_name()
+                .ok_or_else(|| "missing job directory".to_string())?;
+            let target = trash_dir.join(name);
+            fs::create_dir_all(&trash_dir).map_err(|err| format!("failed to create trash folder: {err}"))?;
+            if target.exists() {
+                fs::remove_dir_all(&target).map_err(|err| format!("failed to clear trash folder: {err}"))?;
+            }
+            fs::rename(&job_dir, &target).map_err(|err| format!("failed to move job to trash: {err}"))?;
+            Some((job_dir, target))
+        }
+        None => None,
+    };
+    let mut relocated = Ok(());
+    update_job_and_emit(app, &id, |job| {
+        if let Some((from, to)) = &moved {
+            relocated = relocate_job_paths(job, from, to);
+        }
+        job.trashed_at = Some(now_ts());
+        push_log(job, "Moved to trash.");
+    })?;
+    relocated.map(|_| true)
+}
+
+pub(super) fn restore_job_inner(app: &Core, id: String) -> Result<Job, String> {
+    let state = app.state::<JobIndexState>();
+    let job = find_job(state, &id)?;
+    if job.trashed_at.is_none() {
+        return Ok(job);
+    }
+    let moved = match job_dir_from_audio_path(&job.audio_path).filter(|dir| dir.is_dir()) {
+        Some(trashed_dir) => {
+            let name = trashed_dir
+                .file_name()
+                .ok_or_else(|| "missing job directory".to_string())?;
+            let target = state.jobs_dir.join(name);
+            if target.exists() {
+                return Err(format!("{} already exists.", target.display()));
+            }
+            fs::rename(&trashed_dir, &target).map_err(|err| format!("failed to restore job: {err}"))?;
+            Some((trashed_dir, target))
+        }
+        None => None,
+    };
+    let mut relocated = Ok(());
+    update_job_and_emit(app, &id, |job| {
+        if let Some((from, to)) = &moved {
+            relocated = relocate_job_paths(job, from, to);
+        }
+        job.trashed_at = None;
+        push_log(job, "Restored from trash.");
+    })?;
+    relocated?;
+    find_job(state, &id)
+}
+
+pub(super) fn reconcile_storage_inner(
+    app: &Core,
+    action: Option<String>,
+) -> Result<StorageReconciliation, String> {
+    let state = app.state::<JobIndexState>();
+    let jobs = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .clone();
+    let recording = app
+        .try_state::<RecordingState>()
+        .and_then(|recording| recording.active.lock().ok()?.as_ref().map(|active| active.job_id.clone()));
+    let (orphaned, broken) = scan_job_storage(&state.jobs_dir, &jobs, recording.as_deref());
+    let mut report = StorageReconciliation {
+        orphaned,
+        broken,
+        ..StorageReconciliation::default()
+    };
+    match action.as_deref().unwrap_or("report") {
+        "report" => {}
+        "adopt" => {
+            for folder in report.orphaned.iter().filter(|folder| folder.audio.is_some()) {
+                match adopt_job_folder(app, state, folder) {
+                    Ok(job) => report.adopted.push(job.id),
+                    Err(err) => report.errors.push(format!("{}: {err}", folder.id)),
+                }
+            }
+        }
+        "delete" => {
+            for folder in &report.orphaned {
+                match fs::remove_dir_all(&folder.path) {
+                    Ok(()) => report.deleted.push(folder.id.clone()),
+                    Err(err) => report.errors.push(format!("{}: {err}", folder.id)),
+                }
+            }
+            for entry in report.broken.iter().filter(|entry| entry.folder_missing) {
+                match remove_job_entry(state, &entry.id) {
+                    Ok(_) => report.deleted.push(entry.id.clone()),
+                    Err(err) => report.errors.push(format!("{}: {err}", entry.id)),
+                }
+            }
+        }
+        other => return Err(format!("Unknown reconcile action: {other}")),
+    }
+    Ok(report)
+}
+
+pub(super) fn get_segments_inner(state: &JobIndexState, id: String) -> Result<Vec<Segment>, String> {
+    let guard = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?;
+    let job = guard
+        .jobs
+        .iter()
+        .find(|job| job.id == id)
+        .cloned()
+        .ok_or_else(|| "job not found".to_string())?;
+    load_job_segments(&job)
+}
+
+pub(super) fn reveal_job_in_finder_inner(
+    state: &JobIndexState,
+    id: String,
+    artifact: Option<String>,
+) -> Result<bool, String> {
+    let job = {
+        let guard = state
+            .index
+            .lock()
+            .map_err(|_| "job index mutex poisoned".to_string())?;
+        guard
+            .jobs
+            .iter()
+            .find(|job| job.id == id)
+            .cloned()
+            .ok_or_else(|| "job not found".to_string())?
+    };
+    let path = job_artifact_path(&job, artifact.as_deref())?;
+    reveal_path(&path)?;
+    Ok(true)
+}
+
+pub(super) fn get_storage_usage_inner(
+    state: &JobIndexState,
+    config_state: &ConfigState,
+) -> Result<StorageUsage, String> {
+    let max_mb = config_state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clip_cache_max_mb;
+    let jobs: Vec<Job> = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .clone();
+    let mut usage = StorageUsage {
+        total_bytes: 0,
+        clip_cache_bytes: 0,
+        clip_cache_max_bytes: max_mb as u64 * 1024 * 1024,
+        jobs: Vec::new(),
+    };
+    for job in jobs {
+        let Some(job_dir) = job_dir_from_audio_path(&job.audio_path) else {
+            continue;
+        };
+        let clips = collect_clip_files(&job_dir.join("clips"));
+        let entry = JobStorageUsage {
+            id: job.id,
+            filename: job.filename,
+            total_bytes: dir_size(&job_dir),
+            clip_bytes: clips.iter().map(|clip| clip.size).sum(),
+            clip_count: clips.len(),
+        };
+        usage.total_bytes += entry.total_bytes;
+        usage.clip_cache_bytes += entry.clip_bytes;
+        usage.jobs.push(entry);
+    }
+    Ok(usage)
+}
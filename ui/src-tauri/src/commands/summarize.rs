@@ -0,0 +1,2808 @@
+// Everything that talks to an LLM: prompts, chunked summaries, the summary queue and versions,
+// Q&A, embeddings, translation, titles and action items, plus the extractive fallback.
+
+use super::*;
+
+#[derive(Default)]
+pub struct ResummarizeState {
+    pub(super) running: Arc<std::sync::atomic::AtomicBool>,
+    pub(super) cancel: Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl ResummarizeState {
+    pub fn new() -> Self {
+        Self {
+            running: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            cancel: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResummarizeProgress {
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub current_id: Option<String>,
+    pub finished: bool,
+}
+
+// Placeholders a summary template may use; anything else in braces is rejected on save.
+pub(super) const PROMPT_VARIABLES: &[&str] = &[
+    "text",
+    "title",
+    "date",
+    "duration",
+    "language",
+    "segments_with_timestamps",
+    "filename",
+    "meeting",
+    "attendees",
+];
+
+// Job details substituted into summary templates alongside {text}.
+#[derive(Debug, Clone, Default)]
+pub(super) struct PromptVars {
+    pub(super) title: String,
+    pub(super) date: String,
+    pub(super) duration: String,
+    pub(super) language: String,
+    pub(super) filename: String,
+    pub(super) segments_with_timestamps: String,
+    // Set when summaries should be written in a fixed language (summary_language).
+    pub(super) output_language: Option<String>,
+    // From the matched calendar event; empty when there is none.
+    pub(super) meeting: String,
+    pub(super) attendees: String,
+}
+
+impl PromptVars {
+    pub(super) fn for_job(job: &Job, config: &AppConfig) -> Self {
+        let (date, _) = recording_local_time(job);
+        let duration = job
+            .media_info
+            .as_ref()
+            .and_then(|info| info.duration_seconds)
+            .map(format_duration_words)
+            .unwrap_or_default();
+        let segments_with_timestamps = timestamped_lines(&load_job_segments(job).unwrap_or_default());
+        Self {
+            title: job_title(job),
+            date,
+            duration,
+            language: config.language.clone().unwrap_or_else(|| "auto".to_string()),
+            filename: job.filename.clone(),
+            segments_with_timestamps,
+            output_language: summary_language(config),
+            meeting: job.calendar_event.as_ref().map(|event| event.title.clone()).unwrap_or_default(),
+            attendees: job
+                .calendar_event
+                .as_ref()
+                .map(|event| event.attendees.join(", "))
+                .unwrap_or_default(),
+        }
+    }
+
+    // Fills in everything except {text}, which build_summary_prompt places itself.
+    pub(super) fn apply(&self, template: &str) -> String {
+        template
+            .replace("{title}", &self.title)
+            .replace("{date}", &self.date)
+            .replace("{duration}", &self.duration)
+            .replace("{language}", &self.language)
+            .replace("{filename}", &self.filename)
+            .replace("{segments_with_timestamps}", &self.segments_with_timestamps)
+            .replace("{meeting}", &self.meeting)
+            .replace("{attendees}", &self.attendees)
+    }
+}
+
+pub(super) fn timestamped_lines(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .filter(|seg| !seg.text.trim().is_empty())
+        .map(|seg| match seg.speaker.as_deref() {
+            Some(speaker) => format!("[{}] {speaker}: {}", format_timestamp(seg.start), seg.text.trim()),
+            None => format!("[{}] {}", format_timestamp(seg.start), seg.text.trim()),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub(super) fn format_duration_words(seconds: f64) -> String {
+    let total = seconds.max(0.0).round() as u64;
+    let (h, m, s) = (total / 3600, (total % 3600) / 60, total % 60);
+    if h > 0 {
+        format!("{h} h {m} min")
+    } else if m > 0 {
+        format!("{m} min")
+    } else {
+        format!("{s} s")
+    }
+}
+
+pub(super) fn unknown_placeholders(template: &str, allowed: &[&str]) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        rest = &rest[open + 1..];
+        let Some(close) = rest.find('}') else {
+            break;
+        };
+        let name = &rest[..close];
+        // Only identifier-like names count, so JSON examples in a prompt are left alone.
+        let is_identifier = !name.is_empty()
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !name.starts_with(|c: char| c.is_ascii_digit());
+        if is_identifier && !allowed.contains(&name) && !unknown.iter().any(|u| u == name) {
+            unknown.push(name.to_string());
+        }
+    }
+    unknown
+}
+
+// Unknown placeholders and out-of-vault paths in the prompt, note and path templates.
+pub(super) fn template_errors(cfg: &AppConfig) -> Vec<ConfigFieldError> {
+    let prompts = std::iter::once((
+        "summary_prompt",
+        "summary prompt".to_string(),
+        cfg.summary_prompt.as_str(),
+        PROMPT_VARIABLES,
+    ))
+    .chain(cfg.summary_templates.iter().map(|t| {
+        ("summary_templates", format!("template \"{}\"", t.name), t.prompt.as_str(), PROMPT_VARIABLES)
+    }))
+    .chain(std::iter::once((
+        "obsidian_note_template",
+        "note template".to_string(),
+        cfg.obsidian_note_template.as_str(),
+        NOTE_TEMPLATE_VARIABLES,
+    )))
+    .chain(std::iter::once((
+        "obsidian_path_template",
+        "note path template".to_string(),
+        cfg.obsidian_path_template.as_str(),
+        PATH_TEMPLATE_VARIABLES,
+    )))
+    .chain(std::iter::once((
+        "daily_note_path",
+        "daily note path".to_string(),
+        cfg.daily_note_path.as_str(),
+        PATH_TEMPLATE_VARIABLES,
+    )));
+    let mut errors = Vec::new();
+    for (field, label, prompt, allowed) in prompts {
+        let unknown = unknown_placeholders(prompt, allowed);
+        if !unknown.is_empty() {
+            let names: Vec<String> = unknown.iter().map(|name| format!("{{{name}}}")).collect();
+            errors.push(ConfigFieldError::new(
+                field,
+                format!(
+                    "Unknown placeholder {} in {label}. Supported: {}.",
+                    names.join(", "),
+                    allowed
+                        .iter()
+                        .map(|name| format!("{{{name}}}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ));
+        }
+    }
+    for (field, template) in [
+        ("obsidian_path_template", &cfg.obsidian_path_template),
+        ("daily_note_path", &cfg.daily_note_path),
+    ] {
+        if template.split(['/', '\\']).any(|part| part.trim() == "..") {
+            errors.push(ConfigFieldError::new(
+                field,
+                "The note path template must stay inside the vault (no \"..\").",
+            ));
+        }
+    }
+    if !["off", "link", "full"].contains(&cfg.daily_note_mode.as_str()) {
+        errors.push(ConfigFieldError::new(
+            "daily_note_mode",
+            "Daily note mode must be \"off\", \"link\" or \"full\".",
+        ));
+    }
+    errors
+}
+
+pub(super) fn validate_prompt_templates(cfg: &AppConfig) -> Result<(), String> {
+    match template_errors(cfg).into_iter().next() {
+        Some(error) => Err(error.message),
+        None => Ok(()),
+    }
+}
+
+pub(super) fn build_summary_prompt(template: &str, transcript: &str, vars: &PromptVars) -> String {
+    // Templates that place {meeting} themselves get no extra context line.
+    let meeting_context = !vars.meeting.is_empty() && !template.contains("{meeting}");
+    let template = vars.apply(template);
+    let mut prompt = if template.contains("{text}") {
+        template.replace("{text}", transcript)
+    } else {
+        format!("{template}\n\n{text}\n", template = template, text = transcript)
+    };
+    if meeting_context {
+        prompt.push_str(&format!("\nThis recording is of the meeting \"{}\".", vars.meeting));
+        if !vars.attendees.is_empty() {
+            prompt.push_str(&format!(" Attendees: {}.", vars.attendees));
+        }
+        prompt.push('\n');
+    }
+    if let Some(language) = vars.output_language.as_deref() {
+        prompt.push_str(&format!(
+            "\nWrite the response in {}, even if the transcript is in another language.\n",
+            language_name(language)
+        ));
+    }
+    prompt
+}
+
+// None means "same as the transcript", which needs no extra instruction.
+pub(super) fn summary_language(config: &AppConfig) -> Option<String> {
+    let language = config.summary_language.trim();
+    (!language.is_empty() && !language.eq_ignore_ascii_case("auto")).then(|| language.to_string())
+}
+
+// Whisper-style codes read better to the model as names; anything else passes through.
+pub(super) fn language_name(code: &str) -> String {
+    let name = match code.trim().to_lowercase().as_str() {
+        "en" => "English",
+        "ru" => "Russian",
+        "de" => "German",
+        "fr" => "French",
+        "es" => "Spanish",
+        "it" => "Italian",
+        "pt" => "Portuguese",
+        "uk" => "Ukrainian",
+        "pl" => "Polish",
+        "nl" => "Dutch",
+        "zh" => "Chinese",
+        "ja" => "Japanese",
+        "ko" => "Korean",
+        _ => return code.trim().to_string(),
+    };
+    name.to_string()
+}
+
+pub(super) fn sanitize_filename(input: &str) -> String {
+    input
+        .chars()
+        .map(|ch| match ch {
+            '/' | '\\' | ':' | '\0' => '-',
+            _ => ch,
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+// Which LLM backend to call for summaries and translations, resolved from config.
+#[derive(Debug, Clone)]
+pub(super) struct LlmSettings {
+    pub(super) provider: String,
+    pub(super) base_url: String,
+    pub(super) model: String,
+    pub(super) api_key: Option<String>,
+    pub(super) context_tokens: u32,
+    pub(super) timeout_seconds: u64,
+    pub(super) max_retries: u32,
+    // Set by cancel_summary; checked while waiting on the HTTP request.
+    pub(super) cancel: Option<Arc<std::sync::atomic::AtomicBool>>,
+    // Set for the summary itself, not translations or other calls.
+    pub(super) stream: Option<SummaryStream>,
+}
+
+impl LlmSettings {
+    pub(super) fn from_config(config: &AppConfig) -> Self {
+        if config.llm_provider == "openai" {
+            Self {
+                provider: "openai".to_string(),
+                base_url: config.openai_base_url.clone(),
+                model: config.openai_model.clone(),
+                api_key: None,
+                context_tokens: config.llm_context_tokens,
+                timeout_seconds: config.llm_timeout_seconds.max(1) as u64,
+                max_retries: config.llm_max_retries,
+                cancel: None,
+                stream: None,
+            }
+        } else {
+            Self {
+                provider: "ollama".to_string(),
+                base_url: config.ollama_base_url.clone(),
+                model: config.ollama_model.clone(),
+                api_key: None,
+                context_tokens: config.llm_context_tokens,
+                timeout_seconds: config.llm_timeout_seconds.max(1) as u64,
+                max_retries: config.llm_max_retries,
+                cancel: None,
+                stream: None,
+            }
+        }
+    }
+
+    pub(super) fn with_cancel(&self, cancel: Arc<std::sync::atomic::AtomicBool>) -> Self {
+        Self {
+            cancel: Some(cancel),
+            ..self.clone()
+        }
+    }
+
+    pub(super) fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|flag| flag.load(std::sync::atomic::Ordering::SeqCst))
+    }
+
+    // Checked before a finished run writes anything, so an answer that arrives after cancel_summary
+    // doesn't overwrite the cancellation.
+    pub(super) fn ensure_not_cancelled(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            return Err(SUMMARY_CANCELLED.to_string());
+        }
+        Ok(())
+    }
+
+    pub(super) fn resolve(app: &Core, config: &AppConfig) -> Self {
+        let mut settings = Self::from_config(config);
+        if settings.provider == "openai" {
+            settings.api_key = match config.openai_api_key_secret.trim() {
+                "" => load_llm_api_key(app),
+                name => load_named_secret(app, name),
+            };
+        }
+        settings
+    }
+
+    pub(super) fn with_stream(&self, stream: SummaryStream) -> Self {
+        Self {
+            stream: Some(stream),
+            ..self.clone()
+        }
+    }
+}
+
+pub(super) const SUMMARY_CANCELLED: &str = "Summarization cancelled.";
+
+// Timeouts, refused connections and server-side errors are worth another try; bad
+// requests, auth failures and unknown models are not.
+pub(super) fn is_retryable_llm_error(err: &str) -> bool {
+    err.contains("timeout after")
+        || err.contains("not reachable")
+        || err.starts_with("LLM error: 5")
+        || err.starts_with("Ollama error: 5")
+        || err.starts_with("Ollama response interrupted")
+}
+
+pub(super) fn generate_text(llm: &LlmSettings, prompt: &str) -> Result<String, String> {
+    let mut attempt = 0;
+    loop {
+        match generate_text_once(llm, prompt) {
+            Err(err) if attempt < llm.max_retries && is_retryable_llm_error(&err) && !llm.is_cancelled() => {
+                attempt += 1;
+                thread::sleep(std::time::Duration::from_secs(2 * attempt as u64));
+            }
+            Err(err) if attempt > 0 => return Err(format!("{err} (after {} attempts)", attempt + 1)),
+            result => return result,
+        }
+    }
+}
+
+pub(super) fn generate_text_once(llm: &LlmSettings, prompt: &str) -> Result<String, String> {
+    if llm.model.trim().is_empty() {
+        return Err("No LLM model configured.".to_string());
+    }
+    if llm.is_cancelled() {
+        return Err(SUMMARY_CANCELLED.to_string());
+    }
+    if let Some(stream) = &llm.stream {
+        stream.restart();
+    }
+    // Blocking reqwest calls can't be interrupted, so the request runs on its own thread
+    // and we stop waiting for it on cancel; its late result is simply dropped.
+    let (sender, receiver) = mpsc::channel();
+    let request_llm = llm.clone();
+    let prompt = prompt.to_string();
+    thread::spawn(move || {
+        let llm = request_llm;
+        let result = match llm.provider.as_str() {
+            "openai" => complete_with_openai(
+                &llm.base_url,
+                &llm.model,
+                llm.api_key.as_deref(),
+                &prompt,
+                llm.timeout_seconds,
+            ),
+            _ => summarize_with_ollama(&llm.base_url, &ollama_generate_body(&llm, &prompt), llm.timeout_seconds, &mut |token: &str| {
+                // Stops reading once cancelled, so no more text reaches the job.
+                if llm.is_cancelled() {
+                    return false;
+                }
+                if let Some(stream) = &llm.stream {
+                    stream.push(token);
+                }
+                true
+            }),
+        };
+        let _ = sender.send(result);
+    });
+    loop {
+        match receiver.recv_timeout(std::time::Duration::from_millis(200)) {
+            Ok(result) => return result,
+            Err(mpsc::RecvTimeoutError::Timeout) if llm.is_cancelled() => {
+                return Err(SUMMARY_CANCELLED.to_string());
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err("LLM request thread exited unexpectedly".to_string());
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SummaryCancelState {
+    // One flag per running summary queue task; a job can have several (summary, templates,
+    // title, action items) when summary_concurrency allows.
+    pub(super) flags: Mutex<HashMap<String, Vec<Arc<std::sync::atomic::AtomicBool>>>>,
+    // The last SUMMARY_TICKET handed out when each job was cancelled; tasks queued up to then
+    // are skipped.
+    pub(super) cancelled: Mutex<HashMap<String, u64>>,
+}
+
+impl SummaryCancelState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(super) fn skips(&self, job_id: &str, ticket: u64) -> bool {
+        self.cancelled
+            .lock()
+            .is_ok_and(|cancelled| cancelled.get(job_id).is_some_and(|&last| ticket <= last))
+    }
+}
+
+// Numbers summary tasks in the order they were queued; see SummaryCancelState::cancelled.
+pub(super) static SUMMARY_TICKET: AtomicU64 = AtomicU64::new(0);
+
+pub(super) fn register_summary_cancel(app: &Core, job_id: &str) -> Arc<std::sync::atomic::AtomicBool> {
+    let flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    if let Ok(mut flags) = app.state::<SummaryCancelState>().flags.lock() {
+        flags.entry(job_id.to_string()).or_default().push(flag.clone());
+    }
+    flag
+}
+
+pub(super) fn unregister_summary_cancel(app: &Core, job_id: &str, flag: &Arc<std::sync::atomic::AtomicBool>) {
+    if let Ok(mut flags) = app.state::<SummaryCancelState>().flags.lock() {
+        if let Some(running) = flags.get_mut(job_id) {
+            running.retain(|other| !Arc::ptr_eq(other, flag));
+            if running.is_empty() {
+                flags.remove(job_id);
+            }
+        }
+    }
+}
+
+// Rough budget: ~4 characters per token, with half the window kept for the
+// instructions and the model's answer.
+pub(super) fn chunk_budget_chars(context_tokens: u32, template_chars: usize) -> usize {
+    (context_tokens as usize * 2)
+        .saturating_sub(template_chars)
+        .max(1000)
+}
+
+// Splits on line breaks where possible, then sentence ends, then hard character cuts.
+pub(super) fn split_transcript(text: &str, max_chars: usize) -> Vec<String> {
+    let mut pieces: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        if line.chars().count() <= max_chars {
+            pieces.push(line);
+            continue;
+        }
+        pieces.extend(line.split_inclusive(['.', '!', '?']));
+    }
+    let mut chunks: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for piece in pieces {
+        let piece = piece.trim();
+        if piece.is_empty() {
+            continue;
+        }
+        if !current.is_empty() && current.chars().count() + piece.chars().count() + 1 > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if piece.chars().count() > max_chars {
+            let chars: Vec<char> = piece.chars().collect();
+            for part in chars.chunks(max_chars) {
+                chunks.push(part.iter().collect());
+            }
+            continue;
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(piece);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+// The leading whole lines of `text` that fit in `max_chars`, with a marker when some were cut.
+pub(super) fn truncate_lines(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut kept = String::new();
+    let mut used = 0;
+    for line in text.lines() {
+        let len = line.chars().count() + 1;
+        if used + len > max_chars {
+            break;
+        }
+        kept.push_str(line);
+        kept.push('\n');
+        used += len;
+    }
+    kept.push_str("[…]");
+    kept
+}
+
+// Transcripts that fit the model's context are summarized in one call. Longer ones are
+// summarized section by section, the section notes are condensed until they fit, and
+// the user's template is applied to the result.
+pub(super) fn summarize_text(
+    llm: &LlmSettings,
+    prompt_template: &str,
+    transcript: &str,
+    vars: &PromptVars,
+) -> Result<String, String> {
+    // A template built around the timestamped lines summarizes those instead of the plain
+    // text, chunked the same way when they don't fit.
+    let swapped;
+    let (prompt_template, transcript) = if !prompt_template.contains("{text}")
+        && prompt_template.contains("{segments_with_timestamps}")
+        && !vars.segments_with_timestamps.is_empty()
+    {
+        swapped = prompt_template.replace("{segments_with_timestamps}", "{text}");
+        (swapped.as_str(), vars.segments_with_timestamps.as_str())
+    } else {
+        (prompt_template, transcript)
+    };
+    // Used next to {text}, the timestamped lines repeat the transcript; they get at most half
+    // of the budget, cut at a line, and {text} is chunked against what is left.
+    let capped;
+    let vars = if prompt_template.contains("{segments_with_timestamps}") {
+        let without_lines = PromptVars {
+            segments_with_timestamps: String::new(),
+            ..vars.clone()
+        };
+        let limit = chunk_budget_chars(llm.context_tokens, without_lines.apply(prompt_template).chars().count()) / 2;
+        capped = PromptVars {
+            segments_with_timestamps: truncate_lines(&vars.segments_with_timestamps, limit),
+            ..without_lines
+        };
+        &capped
+    } else {
+        vars
+    };
+    let budget = chunk_budget_chars(llm.context_tokens, vars.apply(prompt_template).chars().count());
+    if transcript.chars().count() <= budget {
+        return generate_text(llm, &build_summary_prompt(prompt_template, transcript, vars));
+    }
+    // Only the final call writes the summary; the section notes aren't streamed.
+    let notes_llm = LlmSettings {
+        stream: None,
+        ..llm.clone()
+    };
+    let mut notes = transcript.to_string();
+    // Each round shrinks the text; the cap guards against a model that won't condense.
+    for _ in 0..4 {
+        if notes.chars().count() <= budget {
+            break;
+        }
+        let chunks = split_transcript(&notes, budget);
+        let total = chunks.len();
+        let mut partials = Vec::with_capacity(total);
+        for (i, chunk) in chunks.iter().enumerate() {
+            let prompt = format!(
+                "This is part {} of {total} of a longer transcript. Write concise notes covering \
+                 the key points, names, numbers, decisions and action items. Use the language \
+                 of the transcript.\n\n{chunk}\n",
+                i + 1
+            );
+            partials.push(generate_text(&notes_llm, &prompt)?);
+        }
+        notes = partials.join("\n\n");
+    }
+    generate_text(llm, &build_summary_prompt(prompt_template, &notes, vars))
+}
+
+pub(super) fn openai_chat_url(base_url: &str) -> String {
+    let base = base_url.trim_end_matches('/');
+    if base.ends_with("/chat/completions") {
+        base.to_string()
+    } else {
+        format!("{base}/chat/completions")
+    }
+}
+
+pub(super) fn complete_with_openai(
+    base_url: &str,
+    model: &str,
+    api_key: Option<&str>,
+    prompt: &str,
+    timeout_seconds: u64,
+) -> Result<String, String> {
+    let url = openai_chat_url(base_url);
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(timeout_seconds))
+        .build()
+        .map_err(|err| format!("Failed to build HTTP client: {err}"))?;
+    let payload = serde_json::json!({
+        "model": model,
+        "messages": [{ "role": "user", "content": prompt }],
+        "stream": false
+    });
+    let mut request = client.post(&url).json(&payload);
+    if let Some(key) = api_key {
+        request = request.bearer_auth(key);
+    }
+    let resp = request.send().map_err(|err| {
+        if err.is_timeout() {
+            format!("LLM timeout after {timeout_seconds}s at {url}")
+        } else if err.is_connect() {
+            format!("LLM endpoint not reachable at {url}.")
+        } else {
+            format!("LLM request failed: {err}")
+        }
+    })?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().unwrap_or_default();
+        return Err(format!("LLM error: {status} {body}"));
+    }
+    let json: serde_json::Value = resp
+        .json()
+        .map_err(|err| format!("Invalid LLM response: {err}"))?;
+    let response = json
+        .pointer("/choices/0/message/content")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    if response.trim().is_empty() {
+        return Err("LLM returned empty response.".to_string());
+    }
+    Ok(response)
+}
+
+// Ollama loads models with a 2048-token window unless told otherwise, silently cutting off
+// the chunks chunk_budget_chars sized for llm_context_tokens.
+pub(super) fn ollama_generate_body(llm: &LlmSettings, prompt: &str) -> serde_json::Value {
+    serde_json::json!({
+        "model": llm.model,
+        "prompt": prompt,
+        "stream": true,
+        "options": { "num_ctx": llm.context_tokens }
+    })
+}
+
+// Streams the answer, passing each token to `on_token` as it arrives; `on_token` returns false
+// to stop reading.
+pub(super) fn summarize_with_ollama(
+    base_url: &str,
+    request: &serde_json::Value,
+    timeout_seconds: u64,
+    on_token: &mut dyn FnMut(&str) -> bool,
+) -> Result<String, String> {
+    let url = format!("{}/api/generate", base_url.trim_end_matches('/'));
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(timeout_seconds))
+        .build()
+        .map_err(|err| format!("Failed to build HTTP client: {err}"))?;
+    let resp = client
+        .post(&url)
+        .json(request)
+        .send()
+        .map_err(|err| {
+            if err.is_timeout() {
+                format!("Ollama timeout after {timeout_seconds}s at {url}")
+            } else if err.is_connect() {
+                format!("Ollama not reachable at {url}. Is Ollama running?")
+            } else {
+                format!("Ollama request failed: {err}")
+            }
+        })?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().unwrap_or_default();
+        return Err(format!("Ollama error: {status} {body}"));
+    }
+    let response = read_ollama_stream(BufReader::new(resp), on_token)?;
+    if response.trim().is_empty() {
+        return Err("Ollama returned empty response.".to_string());
+    }
+    Ok(response)
+}
+
+// Ollama streams one JSON object per line: {"response": "<token>", "done": false}, and a last
+// one with "done": true. A failure mid-answer comes as {"error": "..."}.
+pub(super) fn read_ollama_stream(reader: impl BufRead, on_token: &mut dyn FnMut(&str) -> bool) -> Result<String, String> {
+    let mut response = String::new();
+    for line in reader.lines() {
+        let line = line.map_err(|err| format!("Ollama response interrupted: {err}"))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let json: serde_json::Value =
+            serde_json::from_str(&line).map_err(|err| format!("Invalid Ollama response: {err}"))?;
+        if let Some(error) = json.get("error").and_then(|v| v.as_str()) {
+            return Err(format!("Ollama error: {error}"));
+        }
+        let token = json.get("response").and_then(|v| v.as_str()).unwrap_or("");
+        if !token.is_empty() {
+            if !on_token(token) {
+                return Err(SUMMARY_CANCELLED.to_string());
+            }
+            response.push_str(token);
+        }
+        if json.get("done").and_then(|v| v.as_bool()) == Some(true) {
+            return Ok(response);
+        }
+    }
+    Err("Ollama response interrupted before it was done.".to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OllamaModel {
+    pub name: String,
+    pub size: u64,
+    pub modified_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmConnectionStatus {
+    pub provider: String,
+    pub base_url: String,
+    pub model: String,
+    pub reachable: bool,
+    pub model_available: bool,
+    pub available_models: Vec<String>,
+    pub error: Option<String>,
+}
+
+pub(super) fn parse_ollama_tags(json: &serde_json::Value) -> Vec<OllamaModel> {
+    let mut models: Vec<OllamaModel> = json
+        .get("models")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let name = item.get("name").and_then(|v| v.as_str())?;
+                    Some(OllamaModel {
+                        name: name.to_string(),
+                        size: item.get("size").and_then(|v| v.as_u64()).unwrap_or(0),
+                        modified_at: item
+                            .get("modified_at")
+                            .and_then(|v| v.as_str())
+                            .map(|v| v.to_string()),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+    models
+}
+
+// Ollama resolves an untagged model name to ":latest".
+pub(super) fn model_is_available(model: &str, available: &[String]) -> bool {
+    let model = model.trim();
+    !model.is_empty()
+        && available
+            .iter()
+            .any(|name| name == model || (!model.contains(':') && *name == format!("{model}:latest")))
+}
+
+pub(super) fn fetch_ollama_models(base_url: &str) -> Result<Vec<OllamaModel>, String> {
+    let url = format!("{}/api/tags", base_url.trim_end_matches('/'));
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .map_err(|err| format!("Failed to build HTTP client: {err}"))?;
+    let resp = client.get(&url).send().map_err(|err| {
+        if err.is_connect() || err.is_timeout() {
+            format!("Ollama not reachable at {url}. Is Ollama running?")
+        } else {
+            format!("Ollama request failed: {err}")
+        }
+    })?;
+    if !resp.status().is_success() {
+        return Err(format!("Ollama error: {}", resp.status()));
+    }
+    let json: serde_json::Value = resp
+        .json()
+        .map_err(|err| format!("Invalid Ollama response: {err}"))?;
+    Ok(parse_ollama_tags(&json))
+}
+
+pub(super) fn fetch_openai_models(base_url: &str, api_key: Option<&str>) -> Result<Vec<String>, String> {
+    let base = base_url.trim_end_matches('/');
+    let base = base.strip_suffix("/chat/completions").unwrap_or(base);
+    let url = format!("{base}/models");
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .map_err(|err| format!("Failed to build HTTP client: {err}"))?;
+    let mut request = client.get(&url);
+    if let Some(key) = api_key {
+        request = request.bearer_auth(key);
+    }
+    let resp = request.send().map_err(|err| {
+        if err.is_connect() || err.is_timeout() {
+            format!("LLM endpoint not reachable at {url}.")
+        } else {
+            format!("LLM request failed: {err}")
+        }
+    })?;
+    if !resp.status().is_success() {
+        return Err(format!("LLM error: {}", resp.status()));
+    }
+    let json: serde_json::Value = resp
+        .json()
+        .map_err(|err| format!("Invalid LLM response: {err}"))?;
+    let mut models: Vec<String> = json
+        .get("data")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.get("id").and_then(|v| v.as_str()))
+                .map(|id| id.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    models.sort();
+    Ok(models)
+}
+
+pub(super) fn llm_connection_status(llm: &LlmSettings) -> LlmConnectionStatus {
+    let listed = if llm.provider == "openai" {
+        fetch_openai_models(&llm.base_url, llm.api_key.as_deref())
+    } else {
+        fetch_ollama_models(&llm.base_url)
+            .map(|models| models.into_iter().map(|model| model.name).collect())
+    };
+    let mut status = LlmConnectionStatus {
+        provider: llm.provider.clone(),
+        base_url: llm.base_url.clone(),
+        model: llm.model.clone(),
+        reachable: false,
+        model_available: false,
+        available_models: Vec::new(),
+        error: None,
+    };
+    match listed {
+        Ok(models) => {
+            status.reachable = true;
+            status.model_available = model_is_available(&llm.model, &models);
+            if llm.model.trim().is_empty() {
+                status.error = Some("No LLM model configured.".to_string());
+            } else if !status.model_available {
+                status.error = Some(format!("Model {} is not available at {}.", llm.model, llm.base_url));
+            }
+            status.available_models = models;
+        }
+        Err(err) => status.error = Some(err),
+    }
+    status
+}
+
+// Passages handed to the model are a few segments long so citations stay precise.
+pub(super) const QA_PASSAGE_CHARS: usize = 600;
+
+pub(super) fn group_passages(segments: &[Segment], max_chars: usize) -> Vec<AnswerCitation> {
+    let mut passages: Vec<AnswerCitation> = Vec::new();
+    for seg in segments {
+        let text = seg.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        match passages.last_mut() {
+            Some(last) if last.text.chars().count() + text.chars().count() < max_chars => {
+                last.text.push(' ');
+                last.text.push_str(text);
+                last.end = seg.end;
+            }
+            _ => passages.push(AnswerCitation {
+                index: passages.len() + 1,
+                start: seg.start,
+                end: seg.end,
+                text: text.to_string(),
+            }),
+        }
+    }
+    passages
+}
+
+pub(super) const QUESTION_STOPWORDS: &[&str] = &[
+    "the", "and", "for", "are", "was", "were", "did", "does", "what", "when", "where", "which",
+    "who", "why", "how", "about", "this", "that", "with", "что", "как", "где", "когда", "кто",
+    "почему", "это", "был", "была", "были", "про", "для",
+];
+
+// Prefix stems make the keyword match tolerate inflection ("budget"/"budgets",
+// "бюджет"/"бюджета") without a language-specific stemmer.
+pub(super) fn question_terms(question: &str) -> Vec<String> {
+    let mut terms: Vec<String> = question
+        .split(|c: char| !c.is_alphanumeric())
+        .map(|word| word.to_lowercase())
+        .filter(|word| word.chars().count() >= 3 && !QUESTION_STOPWORDS.contains(&word.as_str()))
+        .map(|word| word.chars().take(5).collect())
+        .collect();
+    terms.sort();
+    terms.dedup();
+    terms
+}
+
+pub(super) fn passage_score(passage: &str, terms: &[String]) -> usize {
+    let words: Vec<String> = passage
+        .split(|c: char| !c.is_alphanumeric())
+        .map(|word| word.to_lowercase())
+        .collect();
+    terms
+        .iter()
+        .map(|term| words.iter().filter(|word| word.starts_with(term.as_str())).count())
+        .sum()
+}
+
+// Picks the best-scoring passages that fit the budget and returns them in transcript order.
+// Without any keyword hits the opening of the transcript is used instead.
+pub(super) fn select_passages(passages: &[AnswerCitation], question: &str, budget: usize) -> Vec<AnswerCitation> {
+    let terms = question_terms(question);
+    let mut ranked: Vec<(usize, &AnswerCitation)> = passages
+        .iter()
+        .map(|p| (passage_score(&p.text, &terms), p))
+        .collect();
+    if ranked.iter().any(|(score, _)| *score > 0) {
+        ranked.retain(|(score, _)| *score > 0);
+        ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.index.cmp(&b.1.index)));
+    }
+    let mut used = 0;
+    let mut picked: Vec<AnswerCitation> = Vec::new();
+    for (_, passage) in ranked {
+        let len = passage.text.chars().count();
+        if used + len > budget && !picked.is_empty() {
+            continue;
+        }
+        used += len;
+        picked.push(passage.clone());
+    }
+    picked.sort_by_key(|p| p.index);
+    picked
+}
+
+pub(super) fn format_timestamp(seconds: f32) -> String {
+    let total = seconds.max(0.0).round() as u64;
+    let (h, m, s) = (total / 3600, (total % 3600) / 60, total % 60);
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{m:02}:{s:02}")
+    }
+}
+
+pub(super) fn build_question_prompt(passages: &[AnswerCitation], question: &str) -> String {
+    let mut prompt = String::from(
+        "Answer the question using only the transcript excerpts below. Cite the excerpts you \
+         rely on as [n]. If the excerpts do not contain the answer, say so. Answer in the \
+         language of the question.\n\n",
+    );
+    for passage in passages {
+        prompt.push_str(&format!(
+            "[{}] ({}-{}) {}\n",
+            passage.index,
+            format_timestamp(passage.start),
+            format_timestamp(passage.end),
+            passage.text
+        ));
+    }
+    prompt.push_str(&format!("\nQuestion: {}\n", question.trim()));
+    prompt
+}
+
+pub(super) fn cited_passages(answer: &str, passages: &[AnswerCitation]) -> Vec<AnswerCitation> {
+    passages
+        .iter()
+        .filter(|p| answer.contains(&format!("[{}]", p.index)))
+        .cloned()
+        .collect()
+}
+
+pub(super) fn ask_transcript_blocking(app: &Core, id: &str, question: &str) -> Result<TranscriptAnswer, String> {
+    if question.trim().is_empty() {
+        return Err("Question is empty.".to_string());
+    }
+    let job = app
+        .state::<JobIndexState>()
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .iter()
+        .find(|job| job.id == id)
+        .cloned()
+        .ok_or_else(|| "job not found".to_string())?;
+    let segments = load_job_segments(&job)?;
+    if segments.is_empty() {
+        return Err("Transcript not found.".to_string());
+    }
+    let config = app
+        .state::<ConfigState>()
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    let llm = LlmSettings::resolve(app, &config);
+    let budget = chunk_budget_chars(llm.context_tokens, question.chars().count() + 300);
+    let passages = select_passages(&group_passages(&segments, QA_PASSAGE_CHARS), question, budget);
+    let answer = generate_text(&llm, &build_question_prompt(&passages, question))?;
+    let citations = cited_passages(&answer, &passages);
+    Ok(TranscriptAnswer {
+        answer: answer.trim().to_string(),
+        model: llm.model,
+        citations,
+    })
+}
+
+pub(super) fn embed_with_ollama(base_url: &str, model: &str, text: &str) -> Result<Vec<f32>, String> {
+    let url = format!("{}/api/embeddings", base_url.trim_end_matches('/'));
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(60))
+        .build()
+        .map_err(|err| format!("Failed to build HTTP client: {err}"))?;
+    let payload = serde_json::json!({ "model": model, "prompt": text });
+    let resp = client.post(&url).json(&payload).send().map_err(|err| {
+        if err.is_connect() {
+            format!("Ollama not reachable at {url}. Is Ollama running?")
+        } else {
+            format!("Ollama request failed: {err}")
+        }
+    })?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().unwrap_or_default();
+        return Err(format!("Ollama error: {status} {body}"));
+    }
+    let json: serde_json::Value = resp
+        .json()
+        .map_err(|err| format!("Invalid Ollama response: {err}"))?;
+    let vector: Vec<f32> = json
+        .get("embedding")
+        .and_then(|v| v.as_array())
+        .map(|values| values.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect())
+        .unwrap_or_default();
+    if vector.is_empty() {
+        return Err(format!("Ollama returned no embedding for model {model}."));
+    }
+    Ok(vector)
+}
+
+pub(super) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+pub(super) fn file_modified_secs(path: &Path) -> Option<u64> {
+    fs::metadata(path)
+        .ok()?
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+// Where embeddings were kept per job before they moved into jobs.db.
+pub(super) const LEGACY_EMBEDDINGS_FILE: &str = "embeddings.json";
+
+// The job's embeddings from the store, or from a LEGACY_EMBEDDINGS_FILE that is then moved into
+// the store so it isn't embedded again.
+pub(super) fn stored_embeddings(store: &JobStore, job: &Job) -> Result<Option<EmbeddingIndex>, String> {
+    if let Some(index) = store.with_embeddings(|stored| stored.get(&job.id).cloned())? {
+        return Ok(Some(index));
+    }
+    let Some(legacy) = job_dir_from_audio_path(&job.audio_path).map(|dir| dir.join(LEGACY_EMBEDDINGS_FILE)) else {
+        return Ok(None);
+    };
+    let Some(index) = read_job_text(&legacy)
+        .ok()
+        .and_then(|contents| serde_json::from_str::<EmbeddingIndex>(&contents).ok())
+    else {
+        return Ok(None);
+    };
+    store.save_embeddings(&job.id, &index)?;
+    let _ = fs::remove_file(legacy);
+    Ok(Some(index))
+}
+
+// Returns the number of chunks embedded, or 0 when the stored index is still current.
+pub(super) fn index_job_embeddings(store: &JobStore, job: &Job, base_url: &str, model: &str) -> Result<usize, String> {
+    let transcript_modified_at = file_modified_secs(Path::new(&job.transcript_json_path));
+    if let Some(existing) = stored_embeddings(store, job)? {
+        if existing.model == model && existing.transcript_modified_at == transcript_modified_at {
+            return Ok(0);
+        }
+    }
+    let segments = load_job_segments(job)?;
+    let mut chunks = Vec::new();
+    for passage in group_passages(&segments, QA_PASSAGE_CHARS) {
+        let vector = embed_with_ollama(base_url, model, &passage.text)?;
+        chunks.push(EmbeddedChunk {
+            start: passage.start,
+            end: passage.end,
+            text: passage.text,
+            vector,
+        });
+    }
+    let count = chunks.len();
+    let index = EmbeddingIndex {
+        model: model.to_string(),
+        transcript_modified_at,
+        chunks,
+    };
+    store.save_embeddings(&job.id, &index)?;
+    Ok(count)
+}
+
+pub(super) fn index_embeddings_for(app: &Core, jobs: &[Job], base_url: &str, model: &str) {
+    let store = &app.state::<JobIndexState>().store;
+    for job in jobs {
+        match index_job_embeddings(store, job, base_url, model) {
+            Ok(0) => {}
+            Ok(count) => {
+                let _ = append_job_log(app, &job.id, &format!("Indexed {count} chunk(s) for semantic search."));
+            }
+            Err(err) => {
+                let _ = append_job_log(app, &job.id, &format!("Semantic indexing failed: {err}"));
+            }
+        }
+    }
+}
+
+pub(super) fn rank_semantic_matches(
+    query: &[f32],
+    indexed: &[(&Job, &EmbeddingIndex)],
+    limit: usize,
+) -> Vec<SemanticMatch> {
+    let mut matches: Vec<SemanticMatch> = indexed
+        .iter()
+        .flat_map(|(job, index)| {
+            index.chunks.iter().map(move |chunk| SemanticMatch {
+                job_id: job.id.clone(),
+                title: job_title(job),
+                start: chunk.start,
+                end: chunk.end,
+                text: chunk.text.clone(),
+                score: cosine_similarity(query, &chunk.vector),
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+    matches.truncate(limit);
+    matches
+}
+
+pub(super) fn semantic_search_blocking(app: &Core, query: &str, limit: Option<usize>) -> Result<Vec<SemanticMatch>, String> {
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let config = app
+        .state::<ConfigState>()
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    let state = app.state::<JobIndexState>();
+    let jobs: Vec<Job> = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .iter()
+        .filter(|job| job.trashed_at.is_none())
+        .cloned()
+        .collect();
+    let model = &config.embedding_model;
+    if state.store.with_embeddings(|stored| searchable_embeddings(&jobs, stored, model).is_empty())? {
+        return Ok(Vec::new());
+    }
+    let vector = embed_with_ollama(&config.ollama_base_url, model, query.trim())?;
+    let limit = limit.unwrap_or(20).max(1);
+    state
+        .store
+        .with_embeddings(|stored| rank_semantic_matches(&vector, &searchable_embeddings(&jobs, stored, model), limit))
+}
+
+// Vectors from another model live in a different space and can't be compared.
+pub(super) fn searchable_embeddings<'a>(
+    jobs: &'a [Job],
+    stored: &'a HashMap<String, EmbeddingIndex>,
+    model: &str,
+) -> Vec<(&'a Job, &'a EmbeddingIndex)> {
+    jobs.iter()
+        .filter_map(|job| stored.get(&job.id).map(|index| (job, index)))
+        .filter(|(_, index)| index.model == model)
+        .collect()
+}
+
+// Recorded as summary_model for summaries produced without an LLM.
+pub(super) const EXTRACTIVE_SUMMARY_MODEL: &str = "extractive";
+
+pub(super) fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    for line in text.lines() {
+        let mut current = String::new();
+        let mut chars = line.chars().peekable();
+        while let Some(ch) = chars.next() {
+            current.push(ch);
+            if matches!(ch, '.' | '!' | '?' | '…') && chars.peek().is_none_or(|next| next.is_whitespace()) {
+                sentences.push(current.trim().to_string());
+                current.clear();
+            }
+        }
+        if !current.trim().is_empty() {
+            sentences.push(current.trim().to_string());
+        }
+    }
+    sentences.retain(|s| s.split_whitespace().count() >= 4);
+    sentences
+}
+
+pub(super) fn sentence_terms(sentence: &str) -> HashSet<String> {
+    sentence
+        .split(|c: char| !c.is_alphanumeric())
+        .map(|word| word.to_lowercase())
+        .filter(|word| word.chars().count() >= 3 && !QUESTION_STOPWORDS.contains(&word.as_str()))
+        .map(|word| word.chars().take(5).collect())
+        .collect()
+}
+
+// TextRank: sentences are nodes, shared terms are edge weights, and PageRank picks the
+// sentences most of the transcript "agrees" with. They are returned in spoken order.
+pub(super) fn extractive_summary(transcript: &str, max_sentences: usize) -> Vec<String> {
+    let sentences = split_sentences(transcript);
+    if sentences.len() <= max_sentences {
+        return sentences;
+    }
+    let terms: Vec<HashSet<String>> = sentences.iter().map(|s| sentence_terms(s)).collect();
+    let n = sentences.len();
+    // Edges are kept per sentence and found through the terms, so an hour of transcript
+    // costs memory for the pairs that share a term rather than an n×n matrix.
+    let mut postings: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, set) in terms.iter().enumerate().filter(|(_, set)| set.len() >= 2) {
+        for term in set {
+            postings.entry(term.as_str()).or_default().push(i);
+        }
+    }
+    let mut edges: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+    for i in (0..n).filter(|&i| terms[i].len() >= 2) {
+        let mut shared: BTreeMap<usize, usize> = BTreeMap::new();
+        for term in &terms[i] {
+            for &j in postings.get(term.as_str()).into_iter().flatten().filter(|&&j| j > i) {
+                *shared.entry(j).or_default() += 1;
+            }
+        }
+        for (j, count) in shared {
+            let weight = count as f64 / ((terms[i].len() as f64).ln() + (terms[j].len() as f64).ln());
+            edges[i].push((j, weight));
+            edges[j].push((i, weight));
+        }
+    }
+    let totals: Vec<f64> = edges.iter().map(|row| row.iter().map(|(_, weight)| weight).sum()).collect();
+    let damping = 0.85;
+    let mut scores = vec![1.0f64; n];
+    for _ in 0..30 {
+        let next: Vec<f64> = edges
+            .iter()
+            .map(|row| {
+                let incoming: f64 = row.iter().map(|&(j, weight)| weight / totals[j] * scores[j]).sum();
+                (1.0 - damping) + damping * incoming
+            })
+            .collect();
+        scores = next;
+    }
+    let mut ranked: Vec<usize> = (0..n).collect();
+    ranked.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]).then(a.cmp(b)));
+    ranked.truncate(max_sentences);
+    ranked.sort();
+    ranked.into_iter().map(|i| sentences[i].clone()).collect()
+}
+
+pub(super) fn render_extractive_summary(transcript: &str) -> String {
+    let count = (split_sentences(transcript).len() / 10).clamp(3, 7);
+    extractive_summary(transcript, count)
+        .iter()
+        .map(|sentence| format!("- {sentence}\n"))
+        .collect()
+}
+
+pub(super) fn write_extractive_summary(app: &Core, job_id: &str, reason: &str) -> Result<String, String> {
+    let job = app
+        .state::<JobIndexState>()
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .iter()
+        .find(|job| job.id == job_id)
+        .cloned()
+        .ok_or_else(|| "job not found".to_string())?;
+    let job_dir = job_dir_from_audio_path(&job.audio_path)
+        .ok_or_else(|| "Job directory missing.".to_string())?;
+    let summary = render_extractive_summary(&read_transcript_text(&job.transcript_txt_path)?);
+    if summary.is_empty() {
+        return Err("Transcript too short for an extractive summary.".to_string());
+    }
+    write_summary_file(&job_dir, &summary)?;
+    update_job_and_emit(app, job_id, |job| {
+        job.summary_status = Some("done".to_string());
+        job.summary_md = Some(summary.clone());
+        job.summary_error = None;
+        job.summary_model = Some(EXTRACTIVE_SUMMARY_MODEL.to_string());
+        job.md_preview = Some(summary.clone());
+        push_log(job, &format!("Wrote extractive summary ({reason})."));
+    })?;
+    emit_job_log(app, job_id, &format!("Wrote extractive summary ({reason})."));
+    Ok(summary)
+}
+
+pub(super) const TRANSLATION_BATCH_SIZE: usize = 20;
+
+pub(super) fn build_translation_prompt(texts: &[&str], target_language: &str) -> String {
+    let mut prompt = format!(
+        "Translate each numbered line into {target_language}. Reply with exactly the same \
+         numbering, one line per item, and nothing else.\n\n"
+    );
+    for (i, text) in texts.iter().enumerate() {
+        prompt.push_str(&format!("{}. {}\n", i + 1, text.trim()));
+    }
+    prompt
+}
+
+// Maps "N. text" / "N) text" lines back onto their inputs; missing items stay empty.
+pub(super) fn parse_numbered_translations(response: &str, count: usize) -> Vec<String> {
+    let mut out = vec![String::new(); count];
+    for line in response.lines() {
+        let line = line.trim();
+        let digits: String = line.chars().take_while(|c| c.is_ascii_digit()).collect();
+        let Ok(number) = digits.parse::<usize>() else {
+            continue;
+        };
+        let rest = line[digits.len()..].trim_start_matches(['.', ')', ':']).trim();
+        if (1..=count).contains(&number) && out[number - 1].is_empty() {
+            out[number - 1] = rest.to_string();
+        }
+    }
+    out
+}
+
+pub(super) fn translate_segments(
+    llm: &LlmSettings,
+    segments: &[Segment],
+    target_language: &str,
+) -> Result<Vec<String>, String> {
+    let mut translations = Vec::with_capacity(segments.len());
+    for batch in segments.chunks(TRANSLATION_BATCH_SIZE) {
+        let texts: Vec<&str> = batch.iter().map(|seg| seg.text.as_str()).collect();
+        let prompt = build_translation_prompt(&texts, target_language);
+        let response = generate_text(llm, &prompt)?;
+        translations.extend(parse_numbered_translations(&response, batch.len()));
+    }
+    Ok(translations)
+}
+
+pub(super) const TITLE_TAGS_PROMPT: &str = "Read the recording's summary or transcript below and reply with only a \
+JSON object {\"title\": string, \"tags\": [string]}: a short descriptive title (at most 8 words, in \
+the text's language) and 3 to 5 lowercase topic tags.\n\n{text}";
+
+pub(super) const KEYWORD_STOPWORDS: &[&str] = &[
+    "about", "after", "also", "because", "been", "before", "being", "could", "does", "doing",
+    "from", "have", "here", "just", "like", "maybe", "more", "much", "okay", "really", "should",
+    "that", "their", "them", "then", "there", "these", "they", "thing", "things", "this", "those",
+    "very", "want", "were", "what", "when", "where", "which", "will", "with", "would", "yeah",
+    "your", "было", "если", "есть", "когда", "может", "надо", "нужно", "очень", "потом", "потому",
+    "просто", "сейчас", "тоже", "чтобы", "этот", "этого", "этом", "быть", "будет", "только",
+];
+
+// Obsidian tags: lowercase, no spaces or '#', limited to word characters, '-', '_' and '/'.
+pub(super) fn normalize_tag(tag: &str) -> Option<String> {
+    let tag: String = tag
+        .trim()
+        .trim_start_matches('#')
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'))
+        .collect();
+    (!tag.is_empty() && !tag.chars().all(|c| c.is_ascii_digit())).then_some(tag)
+}
+
+// Used when no LLM is configured: the opening sentence as title, most frequent
+// non-trivial words as tags.
+pub(super) fn keyword_title_and_tags(transcript: &str) -> (Option<String>, Vec<String>) {
+    let title = Some(first_sentence(transcript)).filter(|t| !t.is_empty());
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for word in transcript.split(|c: char| !c.is_alphanumeric()) {
+        let word = word.to_lowercase();
+        if word.chars().count() < 4 || KEYWORD_STOPWORDS.contains(&word.as_str()) {
+            continue;
+        }
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    let mut ranked: Vec<(String, usize)> = counts.into_iter().filter(|(_, n)| *n >= 2).collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let tags = ranked
+        .into_iter()
+        .filter_map(|(word, _)| normalize_tag(&word))
+        .take(5)
+        .collect();
+    (title, tags)
+}
+
+pub(super) fn parse_title_and_tags(response: &str) -> Result<(Option<String>, Vec<String>), String> {
+    let start = response.find('{').ok_or_else(|| "LLM reply contains no JSON object".to_string())?;
+    let end = response.rfind('}').ok_or_else(|| "LLM reply contains no JSON object".to_string())?;
+    if end < start {
+        return Err("LLM reply contains no JSON object".to_string());
+    }
+    let value: serde_json::Value = serde_json::from_str(&response[start..=end])
+        .map_err(|err| format!("LLM reply is not valid JSON: {err}"))?;
+    let title = value
+        .get("title")
+        .and_then(|v| v.as_str())
+        .map(|t| t.trim().trim_matches('"').to_string())
+        .filter(|t| !t.is_empty());
+    let tags = value
+        .get("tags")
+        .and_then(|v| v.as_array())
+        .map(|tags| {
+            tags.iter()
+                .filter_map(|tag| tag.as_str().and_then(normalize_tag))
+                .take(5)
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok((title, tags))
+}
+
+// The LLM reads the finished summary when there is one, which is shorter than the transcript
+// and already says what the recording is about.
+pub(super) fn derive_title_and_tags(app: &Core, job_id: &str, llm: Option<&LlmSettings>) -> Result<(), String> {
+    let (transcript_path, summary) = app
+        .state::<JobIndexState>()
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .iter()
+        .find(|job| job.id == job_id)
+        .map(|job| (job.transcript_txt_path.clone(), job.summary_md.clone().unwrap_or_default()))
+        .ok_or_else(|| "job not found".to_string())?;
+    let transcript = read_transcript_text(&transcript_path)?;
+    if transcript.trim().is_empty() {
+        return Ok(());
+    }
+    let source = if summary.trim().is_empty() { &transcript } else { &summary };
+    let (title, tags) = match llm {
+        Some(llm) => match summarize_text(llm, TITLE_TAGS_PROMPT, source, &PromptVars::default())
+            .and_then(|reply| parse_title_and_tags(&reply))
+        {
+            Ok(result) => result,
+            Err(err) if llm.is_cancelled() => {
+                emit_job_log(app, job_id, &format!("Title generation: {err}"));
+                return Err(err);
+            }
+            Err(err) => {
+                emit_job_log(app, job_id, &format!("Title generation fell back to keywords: {err}"));
+                keyword_title_and_tags(&transcript)
+            }
+        },
+        None => keyword_title_and_tags(&transcript),
+    };
+    if let Some(llm) = llm {
+        llm.ensure_not_cancelled()?;
+    }
+    update_job_and_emit(app, job_id, |job| {
+        // A matched calendar event already named the job after the meeting.
+        if title.is_some() && job.calendar_event.is_none() {
+            job.title = title.clone();
+        }
+        for tag in &tags {
+            if !job.tags.contains(tag) {
+                job.tags.push(tag.clone());
+            }
+        }
+        push_log(job, &format!("Title and tags generated ({} tag(s)).", tags.len()));
+    })
+}
+
+pub(super) enum SummaryKind {
+    Summary {
+        prompt: String,
+        force: bool,
+        // Regeneration keeps the previous summary as summary.vN.md.
+        archive_previous: bool,
+        // Derive the title and tags from the finished summary in the same task, before any
+        // auto-export names the note.
+        title_tags: bool,
+    },
+    Template(SummaryTemplate),
+    TitleTags,
+    ActionItems,
+    // Embeddings come from Ollama whatever the summary provider is.
+    Embeddings {
+        base_url: String,
+        model: String,
+    },
+}
+
+pub(super) struct SummaryTask {
+    pub(super) job_id: String,
+    pub(super) llm: LlmSettings,
+    pub(super) kind: SummaryKind,
+    // Lets a caller (the re-summarize batch) wait for this task; true on success.
+    pub(super) done: Option<mpsc::Sender<bool>>,
+}
+
+pub struct SummaryQueueState {
+    // Tasks with their SUMMARY_TICKET.
+    pub(super) sender: mpsc::Sender<(u64, SummaryTask)>,
+    pub(super) receiver: Arc<Mutex<mpsc::Receiver<(u64, SummaryTask)>>>,
+    // summary_concurrency; workers numbered at or above it idle until it grows again.
+    pub(super) limit: Arc<AtomicU64>,
+    pub(super) spawned: Mutex<u64>,
+}
+
+impl SummaryQueueState {
+    pub(super) fn enqueue(&self, task: SummaryTask) -> Result<(), String> {
+        let ticket = SUMMARY_TICKET.fetch_add(1, Ordering::SeqCst) + 1;
+        self.sender
+            .send((ticket, task))
+            .map_err(|err| format!("failed to enqueue summary: {err}"))
+    }
+
+    // Takes effect between tasks: extra workers finish what they are running, then idle.
+    pub(super) fn set_concurrency(&self, app: &Core, concurrency: u32) {
+        let limit = u64::from(concurrency.max(1));
+        self.limit.store(limit, Ordering::Relaxed);
+        let mut spawned = self.spawned.lock().unwrap_or_else(|e| e.into_inner());
+        while *spawned < limit {
+            spawn_summary_worker(app, *spawned, self.receiver.clone(), self.limit.clone());
+            *spawned += 1;
+        }
+    }
+}
+
+pub(super) const SUMMARY_WORKER_POLL: std::time::Duration = std::time::Duration::from_millis(500);
+
+pub(super) fn spawn_summary_worker(
+    app: &Core,
+    index: u64,
+    receiver: Arc<Mutex<mpsc::Receiver<(u64, SummaryTask)>>>,
+    limit: Arc<AtomicU64>,
+) {
+    let handle = app.clone();
+    thread::spawn(move || loop {
+        if index >= limit.load(Ordering::Relaxed) || app_data_moving() {
+            thread::sleep(SUMMARY_WORKER_POLL);
+            continue;
+        }
+        // A timeout rather than recv() so a lowered limit is noticed while the queue is empty.
+        let (ticket, task) = match receiver.lock() {
+            Ok(guard) => match guard.recv_timeout(SUMMARY_WORKER_POLL) {
+                Ok(queued) => queued,
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            },
+            Err(_) => break,
+        };
+        let ok = run_summary_task(&handle, ticket, &task);
+        if let Some(done) = task.done {
+            let _ = done.send(ok);
+        }
+    });
+}
+
+// Summaries used to get a thread each, so a burst of finished jobs would hit the LLM
+// server all at once. They now share a pool sized by summary_concurrency, which
+// apply_config resizes when the setting changes.
+pub fn spawn_summary_workers(app: &Core) -> SummaryQueueState {
+    let concurrency = app
+        .state::<ConfigState>()
+        .config
+        .lock()
+        .map(|guard| guard.summary_concurrency)
+        .unwrap_or(1);
+    let (sender, receiver) = mpsc::channel::<(u64, SummaryTask)>();
+    let queue = SummaryQueueState {
+        sender,
+        receiver: Arc::new(Mutex::new(receiver)),
+        limit: Arc::new(AtomicU64::new(1)),
+        spawned: Mutex::new(0),
+    };
+    queue.set_concurrency(app, concurrency);
+    queue
+}
+
+// Registers the task with cancel_summary for as long as it runs.
+pub(super) fn run_summary_task(app: &Core, ticket: u64, task: &SummaryTask) -> bool {
+    if app.state::<SummaryCancelState>().skips(&task.job_id, ticket) {
+        return false;
+    }
+    let cancel = register_summary_cancel(app, &task.job_id);
+    let llm = task.llm.with_cancel(cancel.clone());
+    let ok = run_summary_kind(app, &task.job_id, &llm, &task.kind);
+    unregister_summary_cancel(app, &task.job_id, &cancel);
+    ok
+}
+
+pub(super) fn run_summary_kind(app: &Core, job_id: &str, llm: &LlmSettings, kind: &SummaryKind) -> bool {
+    match kind {
+        SummaryKind::Template(template) => run_template_summary(app, job_id, llm, template).is_ok(),
+        SummaryKind::TitleTags => derive_title_and_tags(app, job_id, Some(llm)).is_ok(),
+        SummaryKind::ActionItems => extract_action_items_internal(app, job_id, llm).is_ok(),
+        SummaryKind::Embeddings { base_url, model } => {
+            let job = app
+                .state::<JobIndexState>()
+                .index
+                .lock()
+                .ok()
+                .and_then(|guard| guard.jobs.iter().find(|job| job.id == job_id).cloned());
+            let Some(job) = job else {
+                return false;
+            };
+            index_embeddings_for(app, &[job], base_url, model);
+            true
+        }
+        SummaryKind::Summary {
+            prompt,
+            force,
+            archive_previous,
+            title_tags,
+        } => {
+            let job = app
+                .state::<JobIndexState>()
+                .index
+                .lock()
+                .ok()
+                .and_then(|guard| guard.jobs.iter().find(|job| job.id == job_id).cloned());
+            let Some(job) = job else {
+                return false;
+            };
+            if job.summary_status.as_deref() == Some("cancelled") {
+                return false;
+            }
+            let archived = if *archive_previous {
+                let archived = job_dir_from_audio_path(&job.audio_path)
+                    .ok_or_else(|| "missing job directory".to_string())
+                    .and_then(|dir| archive_summary(&dir));
+                match archived {
+                    Ok(archived) => archived,
+                    Err(err) => {
+                        let _ = update_job_and_emit(app, job_id, |job| {
+                            job.summary_status = Some("error".to_string());
+                            job.summary_error = Some(err.clone());
+                        });
+                        return false;
+                    }
+                }
+            } else {
+                None
+            };
+            if let Some(name) = archived.as_ref().and_then(|p| p.file_name()).and_then(|n| n.to_str()) {
+                let _ = append_job_log(app, job_id, &format!("Previous summary saved as {name}."));
+            }
+            let ok = summarize_job_internal(app, job_id, llm, prompt, *force).is_ok();
+            if *title_tags && !llm.is_cancelled() {
+                let _ = derive_title_and_tags(app, job_id, Some(llm));
+            }
+            notify_summary_finished(app, job_id);
+            // A failed run leaves summary.md untouched, so the archived copy would be a duplicate.
+            if !ok {
+                if let Some(path) = archived {
+                    let _ = fs::remove_file(path);
+                }
+            }
+            let auto_export = app
+                .state::<ConfigState>()
+                .config
+                .lock()
+                .map(|config| config.auto_export_after_summarization)
+                .unwrap_or(false);
+            if ok && auto_export {
+                auto_export_job(app, job_id, "summarization");
+            }
+            ok
+        }
+    }
+}
+
+pub(super) fn enqueue_summary(app: &Core, task: SummaryTask) -> Result<(), String> {
+    let job_id = task.job_id.clone();
+    let model = task.llm.model.clone();
+    match &task.kind {
+        SummaryKind::Summary { .. } => update_job_and_emit(app, &job_id, |job| {
+            job.summary_status = Some("queued".to_string());
+            job.summary_model = Some(model.clone());
+            job.summary_error = None;
+        })?,
+        SummaryKind::Template(template) => update_job_and_emit(app, &job_id, |job| {
+            job.template_summaries.insert(
+                template.id.clone(),
+                TemplateSummary {
+                    status: "queued".to_string(),
+                    model: Some(model.clone()),
+                    ..TemplateSummary::default()
+                },
+            );
+        })?,
+        SummaryKind::ActionItems => update_job_and_emit(app, &job_id, |job| {
+            job.action_items_status = Some("queued".to_string());
+        })?,
+        SummaryKind::TitleTags | SummaryKind::Embeddings { .. } => {}
+    }
+    app.state::<SummaryQueueState>().enqueue(task)
+}
+
+pub(super) const ACTION_ITEMS_PROMPT: &str = "Extract the action items from the transcript below. Reply with \
+only a JSON array, no prose. Each element must be an object with keys \"text\" (string, the task), \
+\"owner\" (string or null) and \"due\" (string or null, the deadline as spoken). Reply [] if \
+there are none.\n\n{text}";
+
+// Models often wrap JSON in prose or code fences; take the outermost array and validate each
+// item, dropping entries without task text rather than failing the whole extraction.
+pub(super) fn parse_action_items(response: &str) -> Result<Vec<ActionItem>, String> {
+    let start = response.find('[').ok_or_else(|| "LLM reply contains no JSON array".to_string())?;
+    let end = response.rfind(']').ok_or_else(|| "LLM reply contains no JSON array".to_string())?;
+    if end < start {
+        return Err("LLM reply contains no JSON array".to_string());
+    }
+    let value: serde_json::Value = serde_json::from_str(&response[start..=end])
+        .map_err(|err| format!("LLM reply is not valid JSON: {err}"))?;
+    let items = value
+        .as_array()
+        .ok_or_else(|| "LLM reply is not a JSON array".to_string())?;
+    let optional = |item: &serde_json::Value, key: &str| {
+        item.get(key)
+            .and_then(|v| v.as_str())
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    Ok(items
+        .iter()
+        .filter_map(|item| {
+            let text = optional(item, "text")?;
+            Some(ActionItem {
+                text,
+                owner: optional(item, "owner"),
+                due: optional(item, "due"),
+            })
+        })
+        .collect())
+}
+
+pub(super) fn render_action_items(items: &[ActionItem]) -> String {
+    items
+        .iter()
+        .map(|item| {
+            let mut line = format!("- [ ] {}", item.text);
+            if let Some(owner) = item.owner.as_deref() {
+                line.push_str(&format!(" — {owner}"));
+            }
+            if let Some(due) = item.due.as_deref() {
+                line.push_str(&format!(" (due: {due})"));
+            }
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+pub(super) fn load_action_items(job_dir: &Path) -> Option<Vec<ActionItem>> {
+    let contents = read_job_text(job_dir.join("actions.json")).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+pub(super) fn extract_action_items_internal(
+    app: &Core,
+    job_id: &str,
+    llm: &LlmSettings,
+) -> Result<Vec<ActionItem>, String> {
+    update_job_and_emit(app, job_id, |job| {
+        job.action_items_status = Some("running".to_string());
+    })?;
+    let job = app
+        .state::<JobIndexState>()
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .iter()
+        .find(|job| job.id == job_id)
+        .cloned()
+        .ok_or_else(|| "job not found".to_string())?;
+    let result = (|| -> Result<Vec<ActionItem>, String> {
+        let job_dir = job_dir_from_audio_path(&job.audio_path)
+            .ok_or_else(|| "missing job directory".to_string())?;
+        let transcript = read_transcript_text(&job.transcript_txt_path)?;
+        let response = summarize_text(llm, ACTION_ITEMS_PROMPT, &transcript, &PromptVars::default())?;
+        llm.ensure_not_cancelled()?;
+        let items = parse_action_items(&response)?;
+        let json = serde_json::to_string_pretty(&items)
+            .map_err(|err| format!("failed to serialize actions.json: {err}"))?;
+        write_job_file(job_dir.join("actions.json"), json)
+            .map_err(|err| format!("failed to write actions.json: {err}"))?;
+        Ok(items)
+    })();
+    let status = match &result {
+        Ok(_) => "done",
+        Err(_) if llm.is_cancelled() => "cancelled",
+        Err(_) => "error",
+    };
+    update_job_and_emit(app, job_id, |job| {
+        job.action_items_status = Some(status.to_string());
+    })?;
+    match &result {
+        Ok(items) => emit_job_log(app, job_id, &format!("Extracted {} action item(s).", items.len())),
+        Err(err) if llm.is_cancelled() => emit_job_log(app, job_id, &format!("Action item extraction: {err}")),
+        Err(err) => emit_job_log(app, job_id, &format!("Action item extraction failed: {err}")),
+    }
+    result
+}
+
+pub(super) fn template_summary_path(job: &Job, template_id: &str) -> Result<PathBuf, String> {
+    let job_dir = job_dir_from_audio_path(&job.audio_path)
+        .ok_or_else(|| "missing job directory".to_string())?;
+    Ok(job_dir.join(format!("{}.md", sanitize_filename(template_id))))
+}
+
+pub(super) fn template_summary_response(job: &Job, template_id: &str) -> Result<SummaryResponse, String> {
+    let entry = job.template_summaries.get(template_id).cloned().unwrap_or_default();
+    let content = read_job_text(template_summary_path(job, template_id)?).unwrap_or_default();
+    Ok(SummaryResponse {
+        summary_status: if entry.status.is_empty() {
+            "not_started".to_string()
+        } else {
+            entry.status
+        },
+        summary_model: entry.model.unwrap_or_default(),
+        summary_error: entry.error,
+        summary_md: content,
+    })
+}
+
+pub(super) fn run_template_summary(
+    app: &Core,
+    job_id: &str,
+    llm: &LlmSettings,
+    template: &SummaryTemplate,
+) -> Result<(), String> {
+    let config = app
+        .state::<ConfigState>()
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    let language = summary_language(&config);
+    let set_status = |status: &str, error: Option<String>| {
+        update_job_and_emit(app, job_id, |job| {
+            job.template_summaries.insert(
+                template.id.clone(),
+                TemplateSummary {
+                    status: status.to_string(),
+                    model: Some(llm.model.clone()),
+                    error: error.clone(),
+                    language: language.clone(),
+                },
+            );
+        })
+    };
+    set_status("running", None)?;
+    emit_job_log(app, job_id, &format!("{} started.", template.name));
+    let job = app
+        .state::<JobIndexState>()
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .iter()
+        .find(|job| job.id == job_id)
+        .cloned()
+        .ok_or_else(|| "job not found".to_string())?;
+    let result = (|| -> Result<(), String> {
+        let vars = PromptVars::for_job(&job, &config);
+        let output = if template.mode == MINUTES_MODE {
+            let (speakers, transcript) = speaker_transcript(&load_job_segments(&job)?);
+            if speakers.is_empty() {
+                return Err(
+                    "Minutes need speaker labels (diarization or per-channel transcription); \
+                     this transcript has none."
+                        .to_string(),
+                );
+            }
+            render_minutes(&speakers, &summarize_text(llm, &template.prompt, &transcript, &vars)?)
+        } else {
+            let transcript = read_transcript_text(&job.transcript_txt_path)?;
+            summarize_text(llm, &template.prompt, &transcript, &vars)?
+        };
+        llm.ensure_not_cancelled()?;
+        write_job_file(template_summary_path(&job, &template.id)?, output)
+            .map_err(|err| format!("failed to write {}.md: {err}", template.id))
+    })();
+    match result {
+        Ok(()) => {
+            set_status("done", None)?;
+            emit_job_log(app, job_id, &format!("{} finished.", template.name));
+            Ok(())
+        }
+        Err(err) if llm.is_cancelled() => {
+            set_status("cancelled", None)?;
+            emit_job_log(app, job_id, &format!("{}: {err}", template.name));
+            Err(err)
+        }
+        Err(err) => {
+            set_status("error", Some(err.clone()))?;
+            emit_job_log(app, job_id, &format!("{} failed: {err}", template.name));
+            Err(err)
+        }
+    }
+}
+
+pub(super) const SUMMARY_STREAM_SAVE_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+// Carried by "job:summary_chunk" while a summary is generated. `reset` drops the text so far:
+// the request is being retried.
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct SummaryChunkEvent {
+    pub(super) id: String,
+    pub(super) delta: String,
+    pub(super) reset: bool,
+}
+
+// A job's summary as the model writes it: every token goes out as "job:summary_chunk", and
+// summary_md catches up every SUMMARY_STREAM_SAVE_INTERVAL for windows that poll instead.
+#[derive(Clone)]
+pub(super) struct SummaryStream {
+    pub(super) app: Core,
+    pub(super) job_id: String,
+    // Text so far and when it was last written to the job.
+    pub(super) text: Arc<Mutex<(String, std::time::Instant)>>,
+}
+
+impl std::fmt::Debug for SummaryStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SummaryStream").field("job_id", &self.job_id).finish()
+    }
+}
+
+impl SummaryStream {
+    pub(super) fn new(app: &Core, job_id: &str) -> Self {
+        Self {
+            app: app.clone(),
+            job_id: job_id.to_string(),
+            text: Arc::new(Mutex::new((String::new(), std::time::Instant::now()))),
+        }
+    }
+
+    // Called before every attempt, so a retry starts from an empty summary.
+    pub(super) fn restart(&self) {
+        if let Ok(mut text) = self.text.lock() {
+            text.0.clear();
+        }
+        self.emit("", true);
+    }
+
+    pub(super) fn push(&self, delta: &str) {
+        self.emit(delta, false);
+        let Ok(mut text) = self.text.lock() else {
+            return;
+        };
+        text.0.push_str(delta);
+        if text.1.elapsed() >= SUMMARY_STREAM_SAVE_INTERVAL {
+            text.1 = std::time::Instant::now();
+            let summary = text.0.clone();
+            let _ = update_job_and_emit(&self.app, &self.job_id, |job| job.summary_md = Some(summary));
+        }
+    }
+
+    pub(super) fn emit(&self, delta: &str, reset: bool) {
+        let _ = self.app.emit(
+            "job:summary_chunk",
+            SummaryChunkEvent {
+                id: self.job_id.clone(),
+                delta: delta.to_string(),
+                reset,
+            },
+        );
+    }
+}
+
+pub(super) fn summarize_job_internal(
+    app: &Core,
+    job_id: &str,
+    llm: &LlmSettings,
+    prompt_template: &str,
+    force: bool,
+) -> Result<SummaryResponse, String> {
+    let model = llm.model.as_str();
+    let index_state = app.state::<JobIndexState>();
+    let mut transcript_path: Option<String> = None;
+    let mut job_dir: Option<PathBuf> = None;
+    let mut previous_summary: Option<String> = None;
+
+    if !force {
+        let guard = index_state
+            .index
+            .lock()
+            .map_err(|_| "job index mutex poisoned".to_string())?;
+        if let Some(job) = guard.jobs.iter().find(|job| job.id == job_id) {
+            let status = job.summary_status.clone().unwrap_or_else(|| "not_started".to_string());
+            if status == "running" {
+                return Ok(SummaryResponse {
+                    summary_status: status,
+                    summary_model: job.summary_model.clone().unwrap_or_else(|| model.to_string()),
+                    summary_error: job.summary_error.clone(),
+                    summary_md: job.summary_md.clone().unwrap_or_default(),
+                });
+            }
+            if status == "done" {
+                if let Some(summary) = job.summary_md.clone() {
+                    if !summary.trim().is_empty() {
+                        return Ok(SummaryResponse {
+                            summary_status: status,
+                            summary_model: job.summary_model.clone().unwrap_or_else(|| model.to_string()),
+                            summary_error: job.summary_error.clone(),
+                            summary_md: summary,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    update_job_and_emit(app, job_id, |job| {
+        job.summary_status = Some("running".to_string());
+        job.summary_model = Some(model.to_string());
+    })?;
+    emit_job_log(app, job_id, "Summarization started.");
+
+    {
+        let guard = index_state
+            .index
+            .lock()
+            .map_err(|_| "job index mutex poisoned".to_string())?;
+        if let Some(job) = guard.jobs.iter().find(|job| job.id == job_id) {
+            transcript_path = Some(job.transcript_txt_path.clone());
+            job_dir = job_dir_from_audio_path(&job.audio_path);
+            previous_summary = job.summary_md.clone();
+        }
+    }
+
+    let transcript_path = transcript_path.ok_or_else(|| "Transcript not found.".to_string())?;
+    if transcript_path.is_empty() {
+        return Err("Transcript path missing.".to_string());
+    }
+    let job_dir = job_dir.ok_or_else(|| "Job directory missing.".to_string())?;
+    let (vars, fallback) = {
+        let config = app
+            .state::<ConfigState>()
+            .config
+            .lock()
+            .map_err(|_| "config mutex poisoned".to_string())?
+            .clone();
+        let job = index_state
+            .index
+            .lock()
+            .map_err(|_| "job index mutex poisoned".to_string())?
+            .jobs
+            .iter()
+            .find(|job| job.id == job_id)
+            .cloned()
+            .ok_or_else(|| "job not found".to_string())?;
+        (PromptVars::for_job(&job, &config), config.extractive_fallback)
+    };
+    let result = (|| -> Result<String, String> {
+        let transcript = read_transcript_text(&transcript_path)?;
+        let streaming = llm.with_stream(SummaryStream::new(app, job_id));
+        let summary = summarize_text(&streaming, prompt_template, &transcript, &vars)?;
+        llm.ensure_not_cancelled()?;
+        let _summary_path = write_summary_file(&job_dir, &summary)?;
+        Ok(summary)
+    })();
+
+    let outcome = match result {
+        Ok(summary) => {
+            update_job_and_emit(app, job_id, |job| {
+                job.summary_status = Some("done".to_string());
+                job.summary_md = Some(summary.clone());
+                job.summary_error = None;
+                job.summary_model = Some(model.to_string());
+                job.summary_language = vars.output_language.clone();
+                job.md_preview = Some(summary.clone());
+            })?;
+            emit_job_log(app, job_id, "Summarization finished.");
+            let extract = app
+                .state::<ConfigState>()
+                .config
+                .lock()
+                .map(|guard| guard.extract_action_items)
+                .unwrap_or(false);
+            if extract {
+                let _ = extract_action_items_internal(app, job_id, llm);
+            }
+            Ok(SummaryResponse {
+                summary_status: "done".to_string(),
+                summary_model: model.to_string(),
+                summary_error: None,
+                summary_md: summary,
+            })
+        }
+        Err(err) if llm.is_cancelled() => {
+            update_job_and_emit(app, job_id, |job| {
+                job.summary_status = Some("cancelled".to_string());
+                job.summary_error = None;
+                job.summary_model = Some(model.to_string());
+                // Drop the half-streamed text.
+                job.summary_md = previous_summary.clone();
+                push_log(job, SUMMARY_CANCELLED);
+            })?;
+            emit_job_log(app, job_id, SUMMARY_CANCELLED);
+            Err(err)
+        }
+        Err(err) if fallback && err.contains("not reachable") => {
+            emit_job_log(app, job_id, &format!("Summarization failed: {err}"));
+            write_extractive_summary(app, job_id, "LLM unreachable").map(|summary| SummaryResponse {
+                summary_status: "done".to_string(),
+                summary_model: EXTRACTIVE_SUMMARY_MODEL.to_string(),
+                summary_error: None,
+                summary_md: summary,
+            })
+        }
+        Err(err) => {
+            update_job_and_emit(app, job_id, |job| {
+                job.summary_status = Some("error".to_string());
+                job.summary_error = Some(err.clone());
+                job.summary_model = Some(model.to_string());
+                job.summary_md = previous_summary.clone();
+            })?;
+            emit_job_log(app, job_id, &format!("Summarization failed: {err}"));
+            Err(err)
+        }
+    };
+    outcome
+}
+
+// The revision context goes before the user's template so its own {text} placement still
+// decides where the transcript lands.
+pub(super) fn build_regenerate_prompt(base_template: &str, previous: &str, instructions: &str) -> String {
+    let mut prompt = String::from(
+        "You are revising an earlier summary of the transcript below. Keep what is accurate, \
+         fix what is wrong and apply the revision instructions.\n\n",
+    );
+    prompt.push_str("Earlier summary:\n");
+    // Keep a stray placeholder in the old summary from swallowing the transcript.
+    prompt.push_str(&previous.trim().replace("{text}", "{ text }"));
+    prompt.push_str("\n\n");
+    if !instructions.trim().is_empty() {
+        prompt.push_str(&format!("Revision instructions: {}\n\n", instructions.trim()));
+    }
+    prompt.push_str(base_template);
+    prompt
+}
+
+pub(super) fn summary_version_number(name: &str) -> Option<u32> {
+    name.strip_prefix("summary.v")?
+        .strip_suffix(".md")?
+        .parse()
+        .ok()
+}
+
+pub(super) fn collect_summary_versions(job_dir: &Path) -> Vec<SummaryVersion> {
+    let mut versions: Vec<SummaryVersion> = fs::read_dir(job_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let version = summary_version_number(entry.file_name().to_str()?)?;
+                    let path = entry.path();
+                    let modified_at = entry
+                        .metadata()
+                        .ok()
+                        .and_then(|meta| meta.modified().ok())
+                        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                        .map(|d| d.as_secs());
+                    Some(SummaryVersion {
+                        version,
+                        summary_md: read_job_text(&path).unwrap_or_default(),
+                        path: path.to_string_lossy().to_string(),
+                        modified_at,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    versions.sort_by_key(|v| v.version);
+    versions
+}
+
+// Copies the current summary.md to the next summary.vN.md; the live file stays in place
+// until the regenerated summary overwrites it.
+pub(super) fn archive_summary(job_dir: &Path) -> Result<Option<PathBuf>, String> {
+    let current = job_dir.join("summary.md");
+    if !current.exists() {
+        return Ok(None);
+    }
+    let next = collect_summary_versions(job_dir)
+        .last()
+        .map(|v| v.version + 1)
+        .unwrap_or(1);
+    let target = job_dir.join(format!("summary.v{next}.md"));
+    fs::copy(&current, &target)
+        .map_err(|err| format!("failed to archive summary.md: {err}"))?;
+    Ok(Some(target))
+}
+
+// Pause between jobs so a large batch doesn't monopolize the Ollama server.
+pub(super) const RESUMMARIZE_PAUSE_MS: u64 = 500;
+
+pub(super) fn cancel_summary_inner(
+    app: &Core,
+    cancel_state: &SummaryCancelState,
+    id: String,
+) -> Result<bool, String> {
+    cancel_state
+        .cancelled
+        .lock()
+        .map_err(|_| "summary cancel mutex poisoned".to_string())?
+        .insert(id.clone(), SUMMARY_TICKET.load(Ordering::SeqCst));
+    let running = cancel_state
+        .flags
+        .lock()
+        .map_err(|_| "summary cancel mutex poisoned".to_string())?
+        .get(&id)
+        .cloned()
+        .unwrap_or_default();
+    for flag in &running {
+        flag.store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+    // Runs still waiting in the queue, or a stale "running" left by an app restart; the runs in
+    // progress record their own cancellation.
+    let waiting = |status: &str| status == "queued" || (running.is_empty() && status == "running");
+    update_job_and_emit(app, &id, |job| {
+        let mut cancelled = false;
+        for status in [&mut job.summary_status, &mut job.action_items_status].into_iter().flatten() {
+            if waiting(status) {
+                *status = "cancelled".to_string();
+                cancelled = true;
+            }
+        }
+        for summary in job.template_summaries.values_mut() {
+            if waiting(&summary.status) {
+                summary.status = "cancelled".to_string();
+                cancelled = true;
+            }
+        }
+        if cancelled {
+            push_log(job, SUMMARY_CANCELLED);
+        }
+    })?;
+    Ok(!running.is_empty())
+}
+
+pub(super) fn list_ollama_models_inner(
+    config_state: &ConfigState,
+    base_url: Option<String>,
+) -> Result<Vec<OllamaModel>, String> {
+    let base_url = match base_url.filter(|url| !url.trim().is_empty()) {
+        Some(url) => url,
+        None => config_state
+            .config
+            .lock()
+            .map_err(|_| "config mutex poisoned".to_string())?
+            .ollama_base_url
+            .clone(),
+    };
+    fetch_ollama_models(&base_url)
+}
+
+pub(super) fn index_embeddings_inner(
+    app: &Core,
+    state: &JobIndexState,
+    config_state: &ConfigState,
+    ids: Option<Vec<String>>,
+) -> Result<usize, String> {
+    let config = config_state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    let jobs: Vec<Job> = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .iter()
+        .filter(|job| job.status == JobStatus::Done && !job.transcript_json_path.is_empty())
+        .filter(|job| job.trashed_at.is_none())
+        .filter(|job| ids.as_ref().is_none_or(|ids| ids.contains(&job.id)))
+        .cloned()
+        .collect();
+    let count = jobs.len();
+    let llm = LlmSettings::resolve(app, &config);
+    for job in jobs {
+        enqueue_summary(
+            app,
+            SummaryTask {
+                job_id: job.id,
+                llm: llm.clone(),
+                kind: SummaryKind::Embeddings {
+                    base_url: config.ollama_base_url.clone(),
+                    model: config.embedding_model.clone(),
+                },
+                done: None,
+            },
+        )?;
+    }
+    Ok(count)
+}
+
+pub(super) fn get_summary_inner(
+    state: &JobIndexState,
+    config_state: &ConfigState,
+    id: String,
+    template: Option<String>,
+) -> Result<SummaryResponse, String> {
+    let guard = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?;
+    let job = guard
+        .jobs
+        .iter()
+        .find(|job| job.id == id)
+        .cloned()
+        .ok_or_else(|| "job not found".to_string())?;
+    if let Some(template_id) = template.as_deref().filter(|t| *t != "summary") {
+        return template_summary_response(&job, template_id);
+    }
+    let summary_status = job
+        .summary_status
+        .clone()
+        .unwrap_or_else(|| "not_started".to_string());
+    let default_model = {
+        let guard = config_state
+            .config
+            .lock()
+            .map_err(|_| "config mutex poisoned".to_string())?;
+        LlmSettings::from_config(&guard).model
+    };
+
+    if let Some(summary) = job.summary_md.clone() {
+        if !summary.trim().is_empty() {
+            return Ok(SummaryResponse {
+                summary_status: summary_status.clone(),
+                summary_model: job.summary_model.unwrap_or_else(|| default_model.clone()),
+                summary_error: job.summary_error,
+                summary_md: summary,
+            });
+        }
+    }
+
+    let job_dir = job_dir_from_audio_path(&job.audio_path)
+        .ok_or_else(|| "missing job directory".to_string())?;
+    let summary_path = job_dir.join("summary.md");
+    if summary_path.exists() {
+        let content = read_job_text(&summary_path)
+            .map_err(|err| format!("failed to read summary.md: {err}"))?;
+        return Ok(SummaryResponse {
+            summary_status: summary_status.clone(),
+            summary_model: job.summary_model.unwrap_or_else(|| default_model.clone()),
+            summary_error: job.summary_error,
+            summary_md: content,
+        });
+    }
+
+    if summary_status == "skipped" {
+        let (prompt_template, config) = {
+            let guard = config_state
+                .config
+                .lock()
+                .map_err(|_| "config mutex poisoned".to_string())?;
+            (guard.summary_prompt.clone(), guard.clone())
+        };
+        let transcript = if !job.transcript_txt_path.is_empty()
+            && std::path::Path::new(&job.transcript_txt_path).exists()
+        {
+            read_transcript_text(&job.transcript_txt_path)?
+        } else {
+            String::new()
+        };
+        let prompt = if transcript.is_empty() {
+            prompt_template
+        } else {
+            build_summary_prompt(&prompt_template, &transcript, &PromptVars::for_job(&job, &config))
+        };
+        return Ok(SummaryResponse {
+            summary_status: summary_status.clone(),
+            summary_model: job.summary_model.unwrap_or_else(|| default_model.clone()),
+            summary_error: job.summary_error,
+            summary_md: prompt,
+        });
+    }
+
+    Ok(SummaryResponse {
+        summary_status: summary_status.clone(),
+        summary_model: job.summary_model.unwrap_or_else(|| default_model.clone()),
+        summary_error: job.summary_error,
+        summary_md: "".to_string(),
+    })
+}
+
+pub(super) fn get_action_items_inner(
+    state: &JobIndexState,
+    id: String,
+) -> Result<Vec<ActionItem>, String> {
+    let guard = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?;
+    let job = guard
+        .jobs
+        .iter()
+        .find(|job| job.id == id)
+        .ok_or_else(|| "job not found".to_string())?;
+    let job_dir = job_dir_from_audio_path(&job.audio_path)
+        .ok_or_else(|| "missing job directory".to_string())?;
+    Ok(load_action_items(&job_dir).unwrap_or_default())
+}
+
+pub(super) fn extract_action_items_inner(app: &Core, id: String) -> Result<bool, String> {
+    let config = app
+        .state::<ConfigState>()
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    if !config.enable_summarization {
+        return Err("Summarization is disabled.".to_string());
+    }
+    enqueue_summary(
+        app,
+        SummaryTask {
+            job_id: id,
+            llm: LlmSettings::resolve(app, &config),
+            kind: SummaryKind::ActionItems,
+            done: None,
+        },
+    )?;
+    Ok(true)
+}
+
+pub(super) fn get_summaries_inner(
+    state: &JobIndexState,
+    config_state: &ConfigState,
+    id: String,
+) -> Result<BTreeMap<String, SummaryResponse>, String> {
+    let templates = config_state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .summary_templates
+        .clone();
+    let mut out = BTreeMap::new();
+    out.insert(
+        "summary".to_string(),
+        get_summary_inner(state, config_state, id.clone(), None)?,
+    );
+    let job = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .iter()
+        .find(|job| job.id == id)
+        .cloned()
+        .ok_or_else(|| "job not found".to_string())?;
+    for template in templates {
+        let response = template_summary_response(&job, &template.id)?;
+        out.insert(template.id, response);
+    }
+    Ok(out)
+}
+
+pub(super) fn summarize_job_inner(
+    app: &Core,
+    id: String,
+    template: Option<String>,
+) -> Result<SummaryResponse, String> {
+    let config_state = app.state::<ConfigState>();
+    let config = config_state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    if let Some(template_id) = template.as_deref().filter(|t| *t != "summary") {
+        if !config.enable_summarization {
+            return Err("Summarization is disabled.".to_string());
+        }
+        let template = config
+            .summary_templates
+            .iter()
+            .find(|t| t.id == template_id)
+            .cloned()
+            .ok_or_else(|| format!("Unknown summary template: {template_id}"))?;
+        let llm = LlmSettings::resolve(app, &config);
+        let model = llm.model.clone();
+        enqueue_summary(
+            app,
+            SummaryTask {
+                job_id: id.clone(),
+                llm,
+                kind: SummaryKind::Template(template),
+                done: None,
+            },
+        )?;
+        return Ok(SummaryResponse {
+            summary_status: "queued".to_string(),
+            summary_model: model,
+            summary_error: None,
+            summary_md: String::new(),
+        });
+    }
+    let (enable, prompt) = (config.enable_summarization, config.summary_prompt.clone());
+    let llm = LlmSettings::resolve(app, &config);
+    let model = llm.model.clone();
+    if !enable {
+        return Ok(SummaryResponse {
+            summary_status: "skipped".to_string(),
+            summary_model: model,
+            summary_error: None,
+            summary_md: "".to_string(),
+        });
+    }
+    let index_state = app.state::<JobIndexState>();
+    if let Ok(guard) = index_state.index.lock() {
+        if let Some(job) = guard.jobs.iter().find(|job| job.id == id) {
+            let status = job.summary_status.clone().unwrap_or_else(|| "not_started".to_string());
+            if status == "running" || status == "queued" {
+                return Ok(SummaryResponse {
+                    summary_status: status,
+                    summary_model: job.summary_model.clone().unwrap_or_else(|| model.clone()),
+                    summary_error: job.summary_error.clone(),
+                    summary_md: job.summary_md.clone().unwrap_or_default(),
+                });
+            }
+        }
+    }
+
+    enqueue_summary(
+        app,
+        SummaryTask {
+            job_id: id.clone(),
+            llm,
+            kind: SummaryKind::Summary {
+                prompt,
+                force: true,
+                archive_previous: false,
+                title_tags: false,
+            },
+            done: None,
+        },
+    )?;
+    emit_job_log(app, &id, "Summarization queued.");
+
+    Ok(SummaryResponse {
+        summary_status: "queued".to_string(),
+        summary_model: model,
+        summary_error: None,
+        summary_md: "".to_string(),
+    })
+}
+
+pub(super) fn regenerate_summary_inner(
+    app: &Core,
+    state: &JobIndexState,
+    id: String,
+    extra_instructions: String,
+) -> Result<SummaryResponse, String> {
+    let config = app
+        .state::<ConfigState>()
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    if !config.enable_summarization {
+        return Err("Summarization is disabled.".to_string());
+    }
+    let job = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?
+        .jobs
+        .iter()
+        .find(|job| job.id == id)
+        .cloned()
+        .ok_or_else(|| "job not found".to_string())?;
+    if matches!(job.summary_status.as_deref(), Some("running" | "queued")) {
+        return Err("Summarization already running.".to_string());
+    }
+    let job_dir = job_dir_from_audio_path(&job.audio_path)
+        .ok_or_else(|| "missing job directory".to_string())?;
+    // The file on disk wins over the index so edits made in the note are respected.
+    let previous = read_job_text(job_dir.join("summary.md"))
+        .ok()
+        .filter(|text| !text.trim().is_empty())
+        .or(job.summary_md.clone())
+        .unwrap_or_default();
+    let llm = LlmSettings::resolve(app, &config);
+    let model = llm.model.clone();
+    let prompt = if previous.trim().is_empty() {
+        config.summary_prompt.clone()
+    } else {
+        build_regenerate_prompt(&config.summary_prompt, &previous, &extra_instructions)
+    };
+
+    enqueue_summary(
+        app,
+        SummaryTask {
+            job_id: id.clone(),
+            llm,
+            kind: SummaryKind::Summary {
+                prompt,
+                force: true,
+                archive_previous: true,
+                title_tags: false,
+            },
+            done: None,
+        },
+    )?;
+
+    Ok(SummaryResponse {
+        summary_status: "queued".to_string(),
+        summary_model: model,
+        summary_error: None,
+        summary_md: String::new(),
+    })
+}
+
+pub(super) fn list_summary_versions_inner(
+    state: &JobIndexState,
+    id: String,
+) -> Result<Vec<SummaryVersion>, String> {
+    let guard = state
+        .index
+        .lock()
+        .map_err(|_| "job index mutex poisoned".to_string())?;
+    let job = guard
+        .jobs
+        .iter()
+        .find(|job| job.id == id)
+        .ok_or_else(|| "job not found".to_string())?;
+    let job_dir = job_dir_from_audio_path(&job.audio_path)
+        .ok_or_else(|| "missing job directory".to_string())?;
+    Ok(collect_summary_versions(&job_dir))
+}
+
+pub(super) fn resummarize_jobs_inner(
+    app: &Core,
+    state: &JobIndexState,
+    config_state: &ConfigState,
+    batch: &ResummarizeState,
+    filter: JobFilter,
+) -> Result<Vec<String>, String> {
+    use std::sync::atomic::Ordering;
+    let config = config_state
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    let (enable, prompt) = (config.enable_summarization, config.summary_prompt.clone());
+    let llm = LlmSettings::resolve(app, &config);
+    if !enable {
+        return Err("Summarization is disabled.".to_string());
+    }
+    let ids: Vec<String> = {
+        let guard = state
+            .index
+            .lock()
+            .map_err(|_| "job index mutex poisoned".to_string())?;
+        guard
+            .jobs
+            .iter()
+            .filter(|job| job.status == JobStatus::Done && !job.transcript_txt_path.is_empty())
+            .filter(|job| !matches!(job.summary_status.as_deref(), Some("running" | "queued")))
+            .filter(|job| filter.matches(job))
+            .map(|job| job.id.clone())
+            .collect()
+    };
+    if ids.is_empty() {
+        return Ok(ids);
+    }
+    if batch.running.swap(true, Ordering::SeqCst) {
+        return Err("A re-summarize batch is already running.".to_string());
+    }
+    batch.cancel.store(false, Ordering::SeqCst);
+    for id in &ids {
+        let _ = update_job_and_emit(app, id, |job| {
+            job.summary_status = Some("not_started".to_string());
+            push_log(job, "Queued for re-summarization.");
+        });
+    }
+
+    let running = batch.running.clone();
+    let cancel = batch.cancel.clone();
+    let app_handle = app.clone();
+    let queued = ids.clone();
+    thread::spawn(move || {
+        let mut progress = ResummarizeProgress {
+            total: queued.len(),
+            completed: 0,
+            failed: 0,
+            current_id: None,
+            finished: false,
+        };
+        for (i, id) in queued.iter().enumerate() {
+            if cancel.load(Ordering::SeqCst) {
+                break;
+            }
+            if i > 0 {
+                thread::sleep(std::time::Duration::from_millis(RESUMMARIZE_PAUSE_MS));
+            }
+            progress.current_id = Some(id.clone());
+            let _ = app_handle.emit("summary:batch", progress.clone());
+            // Goes through the shared queue so the batch doesn't add to the LLM's load.
+            let (done, finished) = mpsc::channel();
+            let task = SummaryTask {
+                job_id: id.clone(),
+                llm: llm.clone(),
+                kind: SummaryKind::Summary {
+                    prompt: prompt.clone(),
+                    force: true,
+                    archive_previous: false,
+                    title_tags: false,
+                },
+                done: Some(done),
+            };
+            let ok = enqueue_summary(&app_handle, task).is_ok() && finished.recv().unwrap_or(false);
+            if ok {
+                progress.completed += 1;
+            } else {
+                progress.failed += 1;
+            }
+        }
+        progress.current_id = None;
+        progress.finished = true;
+        let _ = app_handle.emit("summary:batch", progress);
+        running.store(false, Ordering::SeqCst);
+    });
+    Ok(ids)
+}
@@ -0,0 +1,590 @@
+// Diagnostics, the app log and the support bundle built from them.
+
+use super::*;
+
+// One line of the run_diagnostics report. `fix` names the action the onboarding screen offers:
+// "download_ffmpeg", "download_whisper", "download_model", "open_llm_settings",
+// "choose_vault" or "free_disk_space".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticCheck {
+    pub id: String,
+    // "ok", "warning", "error" or "skipped".
+    pub status: String,
+    pub message: String,
+    pub path: Option<String>,
+    pub fix: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticsReport {
+    // False when any check is an error; warnings don't block transcription.
+    pub ok: bool,
+    pub checks: Vec<DiagnosticCheck>,
+    pub checked_at: u64,
+}
+
+impl DiagnosticCheck {
+    pub(super) fn new(id: &str, status: &str, message: impl Into<String>) -> Self {
+        Self {
+            id: id.to_string(),
+            status: status.to_string(),
+            message: message.into(),
+            path: None,
+            fix: None,
+        }
+    }
+
+    pub(super) fn at(mut self, path: &Path) -> Self {
+        self.path = Some(path.to_string_lossy().to_string());
+        self
+    }
+
+    pub(super) fn fix(mut self, action: &str) -> Self {
+        self.fix = Some(action.to_string());
+        self
+    }
+}
+
+// Below this much free space a long recording can fail half-way through conversion.
+pub(super) const DISK_SPACE_ERROR_BYTES: u64 = 1_000_000_000;
+
+pub(super) const DISK_SPACE_WARNING_BYTES: u64 = 5_000_000_000;
+
+// Kept free on top of a preflight estimate so the check passing doesn't leave the disk full.
+pub(super) const DISK_SPACE_HEADROOM_BYTES: u64 = 200_000_000;
+
+// 16 kHz mono s16le, as written by convert_to_wav.
+pub(super) const WAV_BYTES_PER_SECOND: f64 = 32_000.0;
+
+// whisper.txt/.srt/.json, summary and note together stay well below this.
+pub(super) const TRANSCRIPT_BYTES_PER_SECOND: f64 = 2_000.0;
+
+// Free bytes on the volume holding `path` (or its nearest existing ancestor).
+pub(super) fn available_disk_bytes(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|dir| dir.exists())?;
+    fs4::available_space(existing).ok()
+}
+
+pub(super) fn format_disk_bytes(bytes: u64) -> String {
+    if bytes >= 1_000_000_000 {
+        format!("{:.1} GB", bytes as f64 / 1e9)
+    } else {
+        format!("{} MB", bytes.div_ceil(1_000_000))
+    }
+}
+
+// Fails before a download or job starts writing instead of half-way through with "No space
+// left on device". Passes when free space can't be determined.
+pub(super) fn ensure_disk_space(dir: &Path, needed: u64, action: &str) -> Result<(), String> {
+    let Some(available) = available_disk_bytes(dir) else {
+        return Ok(());
+    };
+    if available >= needed.saturating_add(DISK_SPACE_HEADROOM_BYTES) {
+        return Ok(());
+    }
+    Err(format!(
+        "Not enough disk space to {action}: about {} is needed but only {} is free. Free up space and try again.",
+        format_disk_bytes(needed),
+        format_disk_bytes(available)
+    ))
+}
+
+// Space a job still needs: the converted WAV (plus one per channel when stereo is split)
+// unless it already exists, and the transcript artifacts.
+pub(super) fn estimated_job_bytes(
+    duration_seconds: f64,
+    trim: Option<(f64, Option<f64>)>,
+    split_stereo: bool,
+    wav_pending: bool,
+) -> u64 {
+    let seconds = match trim {
+        Some((start, end)) => end.unwrap_or(duration_seconds).min(duration_seconds) - start,
+        None => duration_seconds,
+    }
+    .max(0.0);
+    let wav_files = match (wav_pending, split_stereo) {
+        (false, false) => 0.0,
+        (false, true) => 2.0,
+        (true, false) => 1.0,
+        (true, true) => 3.0,
+    };
+    (seconds * (wav_files * WAV_BYTES_PER_SECOND + TRANSCRIPT_BYTES_PER_SECOND)) as u64
+}
+
+// CPU architectures a Mach-O (thin or universal), ELF or PE binary was built for, in Rust's
+// std::env::consts::ARCH naming.
+pub(super) fn binary_architectures(header: &[u8]) -> Vec<&'static str> {
+    let arch_name = |cputype: u32| match cputype {
+        0x0100_0007 => Some("x86_64"),
+        0x0100_000C => Some("aarch64"),
+        _ => None,
+    };
+    let word = |at: usize, big_endian: bool| -> Option<u32> {
+        let bytes: [u8; 4] = header.get(at..at + 4)?.try_into().ok()?;
+        Some(if big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) })
+    };
+    if header.starts_with(b"\x7fELF") {
+        let machine = header.get(18..20).map(|b| u16::from_le_bytes([b[0], b[1]]));
+        return match machine {
+            Some(0x3E) => vec!["x86_64"],
+            Some(0xB7) => vec!["aarch64"],
+            _ => Vec::new(),
+        };
+    }
+    if header.starts_with(b"MZ") {
+        // The PE header offset sits at 0x3C; the machine field follows the "PE\0\0" signature.
+        let pe = word(0x3C, false).unwrap_or(0) as usize;
+        if header.get(pe..pe + 4) != Some(b"PE\0\0".as_slice()) {
+            return Vec::new();
+        }
+        return match header.get(pe + 4..pe + 6).map(|b| u16::from_le_bytes([b[0], b[1]])) {
+            Some(0x8664) => vec!["x86_64"],
+            Some(0xAA64) => vec!["aarch64"],
+            _ => Vec::new(),
+        };
+    }
+    match word(0, true) {
+        // Universal binary: big-endian header followed by 20-byte fat_arch records.
+        Some(0xCAFE_BABE) => {
+            let count = word(4, true).unwrap_or(0).min(16) as usize;
+            (0..count)
+                .filter_map(|i| word(8 + i * 20, true).and_then(arch_name))
+                .collect()
+        }
+        Some(0xCFFA_EDFE | 0xCEFA_EDFE) => word(4, false).and_then(arch_name).into_iter().collect(),
+        Some(0xFEED_FACF | 0xFEED_FACE) => word(4, true).and_then(arch_name).into_iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+pub(super) fn check_ffmpeg(app: &Core) -> (DiagnosticCheck, DiagnosticCheck) {
+    match resolve_ffmpeg_path(app) {
+        Ok(ffmpeg) => {
+            let ffprobe = match resolve_ffprobe_path(&ffmpeg) {
+                Ok(path) => DiagnosticCheck::new("ffprobe", "ok", "ffprobe found.").at(&path),
+                Err(err) => DiagnosticCheck::new("ffprobe", "error", err).fix("download_ffmpeg"),
+            };
+            (DiagnosticCheck::new("ffmpeg", "ok", "LGPL FFmpeg found.").at(&ffmpeg), ffprobe)
+        }
+        Err(err) => (
+            DiagnosticCheck::new("ffmpeg", "error", err).fix("download_ffmpeg"),
+            DiagnosticCheck::new("ffprobe", "skipped", "Needs FFmpeg first."),
+        ),
+    }
+}
+
+pub(super) fn check_whisper_binary(app: &Core, model_size: &str) -> DiagnosticCheck {
+    let env_bin = std::env::var("VOICENOTE_WHISPER_PATH").ok().map(PathBuf::from);
+    let (candidates, _) = whisper_candidates(app, model_size);
+    let Some(path) = env_bin.into_iter().chain(candidates).find(|path| path.exists()) else {
+        return DiagnosticCheck::new("whisper", "error", "whisper.cpp binary not found.").fix("download_whisper");
+    };
+    if !is_executable(&path) {
+        return DiagnosticCheck::new("whisper", "error", "whisper.cpp binary is not executable.")
+            .at(&path)
+            .fix("download_whisper");
+    }
+    let mut header = Vec::new();
+    let _ = File::open(&path).and_then(|file| file.take(4096).read_to_end(&mut header));
+    let archs = binary_architectures(&header);
+    let host = std::env::consts::ARCH;
+    let check = if archs.is_empty() {
+        DiagnosticCheck::new("whisper", "error", "whisper.cpp binary is not a recognised executable.")
+            .fix("download_whisper")
+    } else if archs.contains(&host) {
+        DiagnosticCheck::new("whisper", "ok", format!("whisper.cpp binary found ({host})."))
+    } else if cfg!(target_os = "macos") && host == "aarch64" && archs.contains(&"x86_64") {
+        DiagnosticCheck::new(
+            "whisper",
+            "warning",
+            "whisper.cpp is an Intel build and runs under Rosetta, several times slower. Download the arm64 build.",
+        )
+        .fix("download_whisper")
+    } else {
+        DiagnosticCheck::new(
+            "whisper",
+            "error",
+            format!("whisper.cpp was built for {}, but this machine is {host}.", archs.join("/")),
+        )
+        .fix("download_whisper")
+    };
+    check.at(&path)
+}
+
+pub(super) fn check_whisper_model(app: &Core, model_size: &str) -> DiagnosticCheck {
+    let env_model = std::env::var("VOICENOTE_WHISPER_MODEL").ok().map(PathBuf::from);
+    let (_, candidates) = whisper_candidates(app, model_size);
+    let Some(path) = env_model.into_iter().chain(candidates).find(|path| path.exists()) else {
+        return DiagnosticCheck::new("model", "error", format!("The {model_size} model is not downloaded."))
+            .fix("download_model");
+    };
+    let verification = verify_model_file(&path, model_size);
+    if verification.ok {
+        DiagnosticCheck::new("model", "ok", format!("The {model_size} model is installed.")).at(&path)
+    } else {
+        DiagnosticCheck::new("model", "error", verification.message.unwrap_or_default())
+            .at(&path)
+            .fix("download_model")
+    }
+}
+
+pub(super) fn check_llm(app: &Core, config: &AppConfig) -> DiagnosticCheck {
+    if !config.enable_summarization {
+        return DiagnosticCheck::new("llm", "skipped", "Summarization is disabled.");
+    }
+    let status = llm_connection_status(&LlmSettings::resolve(app, config));
+    match status.error {
+        None => DiagnosticCheck::new("llm", "ok", format!("{} is reachable at {}.", status.model, status.base_url)),
+        // Summaries fall back to the extractive TL;DR, so an unreachable LLM isn't fatal.
+        Some(err) => DiagnosticCheck::new("llm", "warning", err).fix("open_llm_settings"),
+    }
+}
+
+pub(super) fn check_vault(config: &AppConfig) -> DiagnosticCheck {
+    let vault = config.vault_path.trim();
+    if vault.is_empty() {
+        return DiagnosticCheck::new("vault", "warning", "No Obsidian vault is selected.").fix("choose_vault");
+    }
+    let path = Path::new(vault);
+    if !path.is_dir() {
+        return DiagnosticCheck::new("vault", "error", "The vault folder does not exist.")
+            .at(path)
+            .fix("choose_vault");
+    }
+    let probe = path.join(".voicenote-write-test");
+    match fs::write(&probe, b"") {
+        Ok(()) => {
+            let _ = fs::remove_file(&probe);
+            DiagnosticCheck::new("vault", "ok", "The vault is writable.").at(path)
+        }
+        Err(err) => DiagnosticCheck::new("vault", "error", format!("Cannot write to the vault: {err}"))
+            .at(path)
+            .fix("choose_vault"),
+    }
+}
+
+pub(super) fn check_disk_space(app: &Core) -> DiagnosticCheck {
+    let Ok(dir) = app_data_root(app) else {
+        return DiagnosticCheck::new("disk", "skipped", "App data folder unavailable.");
+    };
+    let Some(available) = available_disk_bytes(&dir) else {
+        return DiagnosticCheck::new("disk", "skipped", "Could not read free disk space.").at(&dir);
+    };
+    let gb = available as f64 / 1e9;
+    let check = if available < DISK_SPACE_ERROR_BYTES {
+        DiagnosticCheck::new("disk", "error", format!("Only {gb:.1} GB free.")).fix("free_disk_space")
+    } else if available < DISK_SPACE_WARNING_BYTES {
+        DiagnosticCheck::new("disk", "warning", format!("{gb:.1} GB free; large models and long recordings may not fit."))
+            .fix("free_disk_space")
+    } else {
+        DiagnosticCheck::new("disk", "ok", format!("{gb:.1} GB free."))
+    };
+    check.at(&dir)
+}
+
+pub(super) fn diagnostics_report(app: &Core) -> Result<DiagnosticsReport, String> {
+    let config = app
+        .state::<ConfigState>()
+        .config
+        .lock()
+        .map_err(|_| "config mutex poisoned".to_string())?
+        .clone();
+    let (ffmpeg, ffprobe) = check_ffmpeg(app);
+    let checks = vec![
+        ffmpeg,
+        ffprobe,
+        check_whisper_binary(app, &config.model_size),
+        check_whisper_model(app, &config.model_size),
+        check_llm(app, &config),
+        check_vault(&config),
+        check_disk_space(app),
+    ];
+    Ok(DiagnosticsReport {
+        ok: checks.iter().all(|check| check.status != "error"),
+        checks,
+        checked_at: now_ts(),
+    })
+}
+
+// Structured log: JSON lines in <app data>/logs, one file per day, the last LOG_FILES_KEPT
+// kept. VOICENOTE_LOG_LEVEL (error/warn/info/debug/trace) overrides the default of info.
+pub(super) const LOG_DIR: &str = "logs";
+
+pub(super) const LOG_FILE_PREFIX: &str = "voicenote";
+
+pub(super) const LOG_FILE_SUFFIX: &str = "log";
+
+pub(super) const LOG_FILES_KEPT: usize = 7;
+
+pub(super) const APP_LOGS_DEFAULT_LIMIT: usize = 200;
+
+pub(super) const APP_LOGS_MAX_LIMIT: usize = 5000;
+
+pub(super) fn log_dir(app: &Core) -> Result<PathBuf, String> {
+    Ok(app_data_root(app)?.join(LOG_DIR))
+}
+
+// Warnings and errors also go to stderr as before; everything at the configured level goes to
+// the log files. Job log lines are not (see push_log).
+pub fn init_logging(app: &Core) -> Result<(), String> {
+    use tracing_subscriber::{filter::LevelFilter, prelude::*};
+    let dir = log_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create log dir: {err}"))?;
+    let files = tracing_appender::rolling::RollingFileAppender::builder()
+        .rotation(tracing_appender::rolling::Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(LOG_FILES_KEPT)
+        .build(&dir)
+        .map_err(|err| format!("failed to open log files: {err}"))?;
+    let level = std::env::var("VOICENOTE_LOG_LEVEL")
+        .ok()
+        .and_then(|level| level.parse::<LevelFilter>().ok())
+        .unwrap_or(LevelFilter::INFO);
+    use tracing_subscriber::fmt::writer::MakeWriterExt;
+    let files = files.with_filter(|_: &tracing::Metadata<'_>| !app_data_moving());
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_ansi(false)
+                .with_writer(files)
+                .with_filter(level),
+        )
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_filter(LevelFilter::WARN),
+        )
+        .try_init()
+        .map_err(|err| format!("failed to start logging: {err}"))
+}
+
+// Log files, oldest first; the date in the name sorts them.
+pub(super) fn log_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name().and_then(|name| name.to_str()).is_some_and(|name| {
+                name.starts_with(&format!("{LOG_FILE_PREFIX}.")) && name.ends_with(&format!(".{LOG_FILE_SUFFIX}"))
+            })
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AppLogEntry {
+    pub timestamp: String,
+    // "ERROR", "WARN", "INFO", "DEBUG" or "TRACE".
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    pub job_id: Option<String>,
+}
+
+pub(super) fn log_level_rank(level: &str) -> u8 {
+    match level.to_ascii_uppercase().as_str() {
+        "ERROR" => 4,
+        "WARN" => 3,
+        "INFO" => 2,
+        "DEBUG" => 1,
+        _ => 0,
+    }
+}
+
+// One line as tracing-subscriber's JSON formatter writes it; anything else is skipped.
+pub(super) fn parse_log_line(line: &str) -> Option<AppLogEntry> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    let text = |value: Option<&serde_json::Value>| value.and_then(|value| value.as_str()).map(str::to_string);
+    let fields = value.get("fields");
+    Some(AppLogEntry {
+        timestamp: text(value.get("timestamp")).unwrap_or_default(),
+        level: text(value.get("level"))?,
+        target: text(value.get("target")).unwrap_or_default(),
+        message: text(fields.and_then(|fields| fields.get("message"))).unwrap_or_default(),
+        job_id: text(fields.and_then(|fields| fields.get("job_id"))),
+    })
+}
+
+// The newest `limit` entries at `level` or above, oldest first.
+pub(super) fn read_app_logs(files: &[PathBuf], level: &str, limit: usize) -> Vec<AppLogEntry> {
+    let min_rank = log_level_rank(level);
+    let mut entries = Vec::new();
+    for file in files.iter().rev() {
+        let Ok(contents) = fs::read_to_string(file) else {
+            continue;
+        };
+        let matching = contents
+            .lines()
+            .rev()
+            .filter_map(parse_log_line)
+            .filter(|entry| log_level_rank(&entry.level) >= min_rank);
+        entries.extend(matching.take(limit - entries.len()));
+        if entries.len() >= limit {
+            break;
+        }
+    }
+    entries.reverse();
+    entries
+}
+
+// Keys whose values never go into a support bundle, whatever they hold.
+pub(super) const REDACTED_CONFIG_KEYS: &[&str] = &["calendar_username", "joplin_notebook_id"];
+
+// Strips what could identify the user or let someone in: credentials and query strings in URLs,
+// and the home folder in paths. Config keeps API keys out by design (they live in the keychain
+// or secret files), so none are present to begin with.
+pub(super) fn redact_config_value(value: &mut serde_json::Value, home: Option<&str>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let lower = key.to_lowercase();
+                let secret = ["password", "token", "secret", "api_key"]
+                    .iter()
+                    .any(|word| lower.contains(word));
+                if (secret || REDACTED_CONFIG_KEYS.contains(&key.as_str()))
+                    && value.as_str().is_some_and(|text| !text.is_empty())
+                {
+                    *value = serde_json::Value::String("[redacted]".to_string());
+                } else {
+                    redact_config_value(value, home);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                redact_config_value(item, home);
+            }
+        }
+        serde_json::Value::String(text) => *text = redact_text(text, home),
+        _ => {}
+    }
+}
+
+pub(super) fn redact_text(text: &str, home: Option<&str>) -> String {
+    let mut text = text.to_string();
+    if let Some(scheme_end) = text.find("://") {
+        let authority_start = scheme_end + 3;
+        let authority_end = text[authority_start..]
+            .find(['/', '?', '#'])
+            .map_or(text.len(), |end| authority_start + end);
+        if let Some(at) = text[authority_start..authority_end].rfind('@') {
+            text.replace_range(authority_start..authority_start + at, "[redacted]");
+        }
+        if let Some(query) = text.find('?') {
+            text.replace_range(query.., "?[redacted]");
+        }
+    }
+    match home.filter(|home| !home.is_empty()) {
+        Some(home) => text.replace(home, "~"),
+        None => text,
+    }
+}
+
+// Log files hold one JSON object per line; redacting the fields rather than the raw line keeps
+// a query string from swallowing the rest of the object.
+pub(super) fn redact_log_line(line: &str, home: Option<&str>) -> String {
+    match serde_json::from_str::<serde_json::Value>(line) {
+        Ok(mut value) => {
+            redact_config_value(&mut value, home);
+            value.to_string()
+        }
+        Err(_) => redact_text(line, home),
+    }
+}
+
+// Per-job state for a bug report: no file names, transcripts or summaries.
+pub(super) fn support_job_manifest(jobs: &[Job]) -> serde_json::Value {
+    let jobs: Vec<serde_json::Value> = jobs
+        .iter()
+        .map(|job| {
+            serde_json::json!({
+                "id": job.id,
+                "status": job.status,
+                "stage": job.stage,
+                "progress": job.progress,
+                "created_at": job.created_at,
+                "summary_status": job.summary_status,
+                "trashed": job.trashed_at.is_some(),
+                "model_size": job.options.model_size,
+                "codec": job.media_info.as_ref().and_then(|info| info.codec.clone()),
+                "duration_seconds": job.media_info.as_ref().and_then(|info| info.duration_seconds),
+            })
+        })
+        .collect();
+    serde_json::Value::Array(jobs)
+}
+
+pub(super) fn create_support_bundle_blocking(app: &Core, dest_dir: Option<String>) -> Result<String, String> {
+    let config_state = app.state::<ConfigState>();
+    let index_state = app.state::<JobIndexState>();
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")).ok();
+    let mut config = serde_json::to_value(
+        &*config_state
+            .config
+            .lock()
+            .map_err(|_| "config mutex poisoned".to_string())?,
+    )
+    .map_err(|err| err.to_string())?;
+    redact_config_value(&mut config, home.as_deref());
+    let manifest = support_job_manifest(
+        &index_state
+            .index
+            .lock()
+            .map_err(|_| "job index mutex poisoned".to_string())?
+            .jobs,
+    );
+    let system = serde_json::json!({
+        "version": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "created_at": now_ts(),
+        "queue": queue_status(app).ok(),
+    });
+
+    let dir = match dest_dir.filter(|dir| !dir.trim().is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => app_data_root(app)?.join("support"),
+    };
+    fs::create_dir_all(&dir).map_err(|err| format!("Failed to create {}: {err}", dir.display()))?;
+    let path = dir.join(format!("voicenote-support-{}.zip", now_ts()));
+    let file = File::create(&path).map_err(|err| format!("Failed to create {}: {err}", path.display()))?;
+    let mut writer = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default();
+    let documents = [("config.json", config), ("jobs.json", manifest), ("system.json", system)];
+    for (name, value) in documents {
+        let json = serde_json::to_string_pretty(&value).map_err(|err| err.to_string())?;
+        writer
+            .start_file(name, options)
+            .map_err(|err| format!("Failed to write {name}: {err}"))?;
+        writer
+            .write_all(json.as_bytes())
+            .map_err(|err| format!("Failed to write {name}: {err}"))?;
+    }
+    for log in log_files(&log_dir(app)?) {
+        let Some(name) = log.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        // Messages can still carry URLs or paths (errors from downloads, ffmpeg, the vault), so
+        // each line goes through the same redaction as the config.
+        let contents = fs::read_to_string(&log).map_err(|err| format!("Failed to read {}: {err}", log.display()))?;
+        let contents: String = contents
+            .lines()
+            .map(|line| redact_log_line(line, home.as_deref()) + "\n")
+            .collect();
+        writer
+            .start_file(format!("logs/{name}"), options)
+            .map_err(|err| format!("Failed to write {name}: {err}"))?;
+        writer
+            .write_all(contents.as_bytes())
+            .map_err(|err| format!("Failed to write {name}: {err}"))?;
+    }
+    writer.finish().map_err(|err| format!("Failed to finish the support bundle: {err}"))?;
+    tracing::info!(path = %path.display(), "support bundle created");
+    Ok(path.to_string_lossy().to_string())
+}
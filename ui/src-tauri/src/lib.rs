@@ -0,0 +1,238 @@
+//! The Rust core. The pipeline, the job store, summaries and exports live in the submodules of
+//! `commands`, which know nothing of Tauri; `commands` itself only adds the Tauri commands on
+//! top of them, plus the tray and the window glue. The window (`run`) and the command line
+//! (`run_cli`, also the `voicenote` binary) share it.
+
+pub mod commands;
+
+use tauri::Manager;
+
+fn context() -> tauri::Context {
+    tauri::generate_context!()
+}
+
+// `transcribe` and `mcp` run on a headless core: no Tauri runtime, window or display. None when
+// the arguments are not a CLI command.
+pub fn run_cli(args: &[String]) -> Option<i32> {
+    match commands::parse_cli_args(args) {
+        Ok(Some(command)) => Some(commands::run_cli(&context(), command)),
+        Ok(None) => None,
+        Err(err) => {
+            eprintln!("{err}");
+            Some(2)
+        }
+    }
+}
+
+pub fn run() {
+    tauri::Builder::default()
+        // Registered first so a second launch exits before it touches the store.
+        .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            commands::open_second_instance(app, args, &cwd);
+        }))
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_notification::init())
+        // The updater plugin and the check/download/install commands stay unregistered until
+        // releases are signed: tauri.conf.json has no plugins.updater.pubkey and
+        // bundle.createUpdaterArtifacts is off, so every update would fail verification.
+        .manage(commands::OpenedFilesState::new())
+        .setup(|app| {
+            let handle = app.handle();
+            let core = &commands::Core::from(handle);
+            if let Err(err) = commands::init_logging(core) {
+                eprintln!("{err}");
+            }
+            let config_state = commands::ConfigState::load(core).map_err(std::io::Error::other)?;
+            app.manage(config_state);
+            let job_index_state = commands::JobIndexState::load(core).map_err(std::io::Error::other)?;
+            app.manage(job_index_state);
+            let alone = commands::lock_job_store(core).map_err(std::io::Error::other)?;
+            let model_state = commands::ModelDownloadState::load(core).map_err(std::io::Error::other)?;
+            app.manage(model_state);
+            let summary_queue = commands::spawn_summary_workers(core);
+            app.manage(summary_queue);
+            let queue_state = commands::spawn_worker(core);
+            app.manage(queue_state);
+            app.manage(commands::RecordingState::new());
+            app.manage(commands::ResummarizeState::new());
+            app.manage(commands::SummaryCancelState::new());
+            let watcher_state = commands::spawn_inbox_watcher(core);
+            app.manage(watcher_state);
+            commands::spawn_config_watcher(core);
+            commands::recover_interrupted_imports(core);
+            if alone {
+                commands::resume_interrupted_jobs(core);
+            } else {
+                // `voicenote transcribe` is running and may be working on them.
+                tracing::warn!("another process is running jobs; leaving interrupted jobs to it");
+            }
+            commands::purge_expired_trash(core);
+            app.manage(commands::AppUpdateState::new(handle));
+            commands::check_after_update(handle);
+            if let Err(err) = commands::spawn_tray(handle) {
+                tracing::warn!("{err}");
+            }
+            // macOS reads voicenote:// from the bundle; elsewhere the app registers it itself.
+            #[cfg(any(windows, target_os = "linux"))]
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+                if let Err(err) = app.deep_link().register_all() {
+                    tracing::warn!("failed to register voicenote:// links: {err}");
+                }
+            }
+            commands::open_launch_files(handle);
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                if window.label() == "main" && commands::close_to_tray(window.app_handle()) {
+                    api.prevent_close();
+                    let _ = window.hide();
+                }
+            }
+        })
+        .invoke_handler(tauri::generate_handler![
+            commands::get_health,
+            commands::get_config,
+            commands::get_changes_since,
+            commands::update_config,
+            commands::validate_config,
+            commands::move_app_data,
+            commands::export_settings,
+            commands::import_settings,
+            commands::reset_to_defaults,
+            commands::list_profiles,
+            commands::switch_profile,
+            commands::delete_profile,
+            commands::initialize_config,
+            commands::get_config_initialized,
+            commands::list_jobs,
+            commands::query_jobs,
+            commands::get_job_logs,
+            commands::set_job_tags,
+            commands::toggle_favorite,
+            commands::rename_job,
+            commands::set_job_notes,
+            commands::archive_job,
+            commands::unarchive_job,
+            commands::reconcile_storage,
+            commands::list_tags,
+            commands::get_job,
+            commands::add_files,
+            commands::create_job_from_path,
+            commands::start_recording,
+            commands::stop_recording,
+            commands::get_recording_status,
+            commands::trim_job_audio,
+            commands::split_job,
+            commands::cancel_job,
+            commands::delete_job,
+            commands::export_to_obsidian,
+            commands::update_obsidian_export,
+            commands::preview_export_path,
+            commands::get_segments,
+            commands::export_subtitles,
+            commands::export_job,
+            commands::export_all,
+            commands::export_job_bundle,
+            commands::import_job_bundle,
+            commands::export_to_target,
+            commands::set_export_target_token,
+            commands::set_calendar_password,
+            commands::match_calendar_event,
+            commands::copy_transcript_to_clipboard,
+            commands::copy_summary_to_clipboard,
+            commands::export_bilingual,
+            commands::get_clip_path,
+            commands::export_clip,
+            commands::get_storage_usage,
+            commands::get_stats,
+            commands::get_watcher_status,
+            commands::get_queue_status,
+            commands::set_queue_paused,
+            commands::get_system_status,
+            commands::get_app_logs,
+            commands::create_support_bundle,
+            commands::get_app_update_status,
+            commands::import_voice_memos,
+            commands::apply_job_encryption,
+            commands::restore_job,
+            commands::empty_trash,
+            commands::clear_clip_cache,
+            commands::get_do_not_disturb_status,
+            commands::find_duplicates,
+            commands::dismiss_duplicate,
+            commands::get_run_manifest,
+            commands::resummarize_jobs,
+            commands::cancel_resummarize,
+            commands::set_llm_api_key,
+            commands::has_llm_api_key,
+            commands::set_secret,
+            commands::delete_secret,
+            commands::has_secret,
+            commands::get_waveform,
+            commands::get_chapters,
+            commands::reveal_job_in_finder,
+            commands::get_summary,
+            commands::summarize_job,
+            commands::cancel_summary,
+            commands::regenerate_summary,
+            commands::list_summary_versions,
+            commands::ask_transcript,
+            commands::index_embeddings,
+            commands::semantic_search,
+            commands::list_ollama_models,
+            commands::check_llm_connection,
+            commands::get_summaries,
+            commands::get_action_items,
+            commands::extract_action_items,
+            commands::get_model_size,
+            commands::get_model_download_status,
+            commands::get_model_installed,
+            commands::verify_model,
+            commands::start_model_download,
+            commands::cancel_model_download,
+            commands::cancel_whisper_download,
+            commands::cancel_ffmpeg_download,
+            commands::list_installed_models,
+            commands::delete_model,
+            commands::remove_partial_model_downloads,
+            commands::run_diagnostics,
+            commands::bootstrap_dependencies,
+            commands::get_whisper_version,
+            commands::check_for_whisper_update,
+            commands::upgrade_whisper_binary,
+            commands::list_custom_models,
+            commands::import_model_from_path,
+            commands::get_whisper_download_status,
+            commands::get_whisper_installed,
+            commands::start_whisper_download,
+            commands::get_latest_whisper_release_url,
+            commands::get_ffmpeg_download_status,
+            commands::get_ffmpeg_installed,
+            commands::detect_system_binaries,
+            commands::start_ffmpeg_download,
+        ])
+        .build(context())
+        .expect("error while building tauri application")
+        .run(|_app, _event| {
+            // Dock drops and "Open With" arrive as file URLs, also when they launched the app;
+            // voicenote:// links come through the same event.
+            #[cfg(target_os = "macos")]
+            match _event {
+                tauri::RunEvent::Opened { urls } => {
+                    let (links, files): (Vec<_>, Vec<_>) = urls.iter().partition(|url| url.scheme() != "file");
+                    for url in links {
+                        commands::handle_deep_link(_app, url);
+                    }
+                    commands::open_files(_app, files.iter().filter_map(|url| url.to_file_path().ok()).collect());
+                }
+                // Dock icon clicked while the window is hidden in the menu bar.
+                tauri::RunEvent::Reopen { has_visible_windows: false, .. } => commands::show_main_window(_app),
+                _ => {}
+            }
+        });
+}
@@ -89,7 +89,15 @@ export default function App() {
     if (!isTauri) return;
     let unlistenUpdated: (() => void) | null = null;
     let unlistenLog: (() => void) | null = null;
+    let unlistenConfig: (() => void) | null = null;
+    let unlistenDeepLink: (() => void) | null = null;
     const setup = async () => {
+      // voicenote://job/<id> from Shortcuts, Raycast or Alfred.
+      unlistenDeepLink = await listen<{ id: string }>("deeplink:job", (event) => {
+        setSelectedJobId(event.payload.id);
+        setPage("details");
+      });
+      unlistenConfig = await listen<AppConfig>("config:updated", (event) => setConfig(event.payload));
       unlistenUpdated = await listen<Job>("job:updated", (event) => {
         const job = event.payload;
         setJobs((prev) => {
@@ -123,6 +131,8 @@ export default function App() {
     return () => {
       if (unlistenUpdated) unlistenUpdated();
       if (unlistenLog) unlistenLog();
+      if (unlistenConfig) unlistenConfig();
+      if (unlistenDeepLink) unlistenDeepLink();
     };
   }, [isTauri]);
 
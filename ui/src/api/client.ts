@@ -1,4 +1,4 @@
-import type { AppConfig, Job, Segment, ModelDownloadStatus, SummaryResponse } from "./types";
+import type { AppConfig, Job, JobOptions, Segment, ModelDownloadStatus, ModelVerification, SummaryResponse, Waveform, JobArtifact, RecordingStatus, StorageUsage, Chapter, DuplicateMatch, RunManifest, JobFilter, ActionItem, OllamaModel, LlmConnectionStatus, SummaryVersion, TranscriptAnswer, SemanticMatch, SubtitleExportOptions, ExportPathPreview, ExportAllSummary, CalendarEvent, InstalledModel, DiagnosticsReport, WhisperVersion, WhisperUpdateCheck, CustomModel, ConfigFieldError, ConfigProfile, SettingsImport, ResetScope, JobQuery, JobPage, JobLogPage, TagCount, StorageReconciliation, VoiceNoteStats, Revisioned, StateChanges, WatcherStatus, QueueStatus, SystemStatus, AppUpdateStatus, SystemBinaries, AppLogEntry } from "./types";
 import { invoke, convertFileSrc } from "@tauri-apps/api/core";
 
 const IS_TAURI_RUNTIME = typeof window !== "undefined" && "__TAURI__" in window;
@@ -14,14 +14,54 @@ export async function getHealth(): Promise<boolean> {
   return invokeCommand<boolean>("get_health");
 }
 
-export async function getConfig(): Promise<AppConfig> {
-  return invokeCommand<AppConfig>("get_config");
+export async function getConfig(): Promise<Revisioned<AppConfig>> {
+  return invokeCommand<Revisioned<AppConfig>>("get_config");
+}
+
+// Jobs and config changed after `revision`, for windows that missed events or just opened.
+export async function getChangesSince(revision: number): Promise<StateChanges> {
+  return invokeCommand<StateChanges>("get_changes_since", { revision });
 }
 
 export async function updateConfig(cfg: AppConfig): Promise<AppConfig> {
   return invokeCommand<AppConfig>("update_config", { cfg });
 }
 
+export async function validateConfig(cfg: AppConfig): Promise<ConfigFieldError[]> {
+  return invokeCommand<ConfigFieldError[]>("validate_config", { cfg });
+}
+
+// Moves the voicenote data folder into `newLocation` and restarts the app; progress arrives as
+// "app_data:move" events.
+export async function moveAppData(newLocation: string): Promise<string> {
+  return invokeCommand<string>("move_app_data", { newLocation });
+}
+
+// Writes the config (without keychain secrets or proxy credentials) to `path`.
+export async function exportSettings(path: string): Promise<string> {
+  return invokeCommand<string>("export_settings", { path });
+}
+
+export async function importSettings(path: string): Promise<SettingsImport> {
+  return invokeCommand<SettingsImport>("import_settings", { path });
+}
+
+export async function resetToDefaults(scope: ResetScope): Promise<AppConfig> {
+  return invokeCommand<AppConfig>("reset_to_defaults", { scope });
+}
+
+export async function listProfiles(): Promise<ConfigProfile[]> {
+  return invokeCommand<ConfigProfile[]>("list_profiles");
+}
+
+export async function switchProfile(name: string): Promise<AppConfig> {
+  return invokeCommand<AppConfig>("switch_profile", { name });
+}
+
+export async function deleteProfile(name: string): Promise<AppConfig> {
+  return invokeCommand<AppConfig>("delete_profile", { name });
+}
+
 export async function initializeConfig(cfg: AppConfig): Promise<AppConfig> {
   return invokeCommand<AppConfig>("initialize_config", { cfg });
 }
@@ -34,6 +74,64 @@ export async function getJobs(): Promise<Job[]> {
   return invokeCommand<Job[]>("list_jobs");
 }
 
+// Lightweight, filtered page of the job list; logs are fetched separately with getJobLogs.
+export async function queryJobs(filter?: JobQuery): Promise<JobPage> {
+  return invokeCommand<JobPage>("query_jobs", { filter });
+}
+
+export async function getJobLogs(id: string, offset?: number, limit?: number): Promise<JobLogPage> {
+  return invokeCommand<JobLogPage>("get_job_logs", { id, offset, limit });
+}
+
+// Returns the normalized tags (lowercase, no '#', duplicates dropped).
+export async function setJobTags(id: string, tags: string[]): Promise<string[]> {
+  return invokeCommand<string[]>("set_job_tags", { id, tags });
+}
+
+// Empty title restores the automatic one; resolves to the title now in effect.
+export async function renameJob(id: string, title: string): Promise<string> {
+  return invokeCommand<string>("rename_job", { id, title });
+}
+
+export async function setJobNotes(id: string, text: string): Promise<void> {
+  return invokeCommand<void>("set_job_notes", { id, text });
+}
+
+// Packs the job's audio and raw artifacts into archive.zip (transcript and summaries stay
+// readable); dropWav also deletes the converted WAV. Resolves to the bytes freed.
+export async function archiveJob(id: string, dropWav?: boolean): Promise<number> {
+  return invokeCommand<number>("archive_job", { id, dropWav });
+}
+
+export async function unarchiveJob(id: string): Promise<void> {
+  return invokeCommand<void>("unarchive_job", { id });
+}
+
+export async function restoreJob(id: string): Promise<Job> {
+  return invokeCommand<Job>("restore_job", { id });
+}
+
+// Deletes trashed jobs for good; without a day count the whole trash goes.
+export async function emptyTrash(olderThanDays?: number): Promise<number> {
+  return invokeCommand<number>("empty_trash", { olderThanDays });
+}
+
+// "report" lists orphaned folders and jobs with missing files; "adopt" re-indexes orphaned
+// folders that still hold audio; "delete" removes them and drops jobs whose folder is gone.
+export async function reconcileStorage(
+  action: "report" | "adopt" | "delete" = "report"
+): Promise<StorageReconciliation> {
+  return invokeCommand<StorageReconciliation>("reconcile_storage", { action });
+}
+
+export async function toggleFavorite(id: string): Promise<boolean> {
+  return invokeCommand<boolean>("toggle_favorite", { id });
+}
+
+export async function listTags(): Promise<TagCount[]> {
+  return invokeCommand<TagCount[]>("list_tags");
+}
+
 export async function getJob(id: string): Promise<Job> {
   return invokeCommand<Job>("get_job", { id });
 }
@@ -46,8 +144,28 @@ export async function createJob(file: File): Promise<Job> {
   return invokeCommand<Job>("create_job_from_path", { path });
 }
 
-export async function createJobFromPath(path: string): Promise<Job> {
-  return invokeCommand<Job>("create_job_from_path", { path });
+export async function createJobFromPath(path: string, options?: JobOptions): Promise<Job> {
+  return invokeCommand<Job>("create_job_from_path", { path, options });
+}
+
+export async function startRecording(): Promise<RecordingStatus> {
+  return invokeCommand<RecordingStatus>("start_recording");
+}
+
+export async function stopRecording(): Promise<Job> {
+  return invokeCommand<Job>("stop_recording");
+}
+
+export async function getRecordingStatus(): Promise<RecordingStatus> {
+  return invokeCommand<RecordingStatus>("get_recording_status");
+}
+
+export async function trimJobAudio(id: string, start: number, end: number): Promise<Job> {
+  return invokeCommand<Job>("trim_job_audio", { id, start, end });
+}
+
+export async function splitJob(id: string, boundaries: number[]): Promise<Job[]> {
+  return invokeCommand<Job[]>("split_job", { id, boundaries });
 }
 
 export async function cancelJob(id: string): Promise<boolean> {
@@ -66,12 +184,298 @@ export async function getSegments(id: string): Promise<Segment[]> {
   return invokeCommand<Segment[]>("get_segments", { id });
 }
 
-export async function getSummary(id: string): Promise<SummaryResponse> {
-  return invokeCommand<SummaryResponse>("get_summary", { id });
+export async function exportSubtitles(
+  id: string,
+  format: "srt" | "vtt",
+  options: SubtitleExportOptions
+): Promise<string> {
+  return invokeCommand<string>("export_subtitles", { id, format, options });
 }
 
-export async function summarizeJob(id: string): Promise<SummaryResponse> {
-  return invokeCommand<SummaryResponse>("summarize_job", { id });
+// "mp3" needs an ffmpeg with LAME; the bundled build rejects it, so prefer "m4a".
+export async function exportClip(
+  id: string,
+  start: number,
+  end: number,
+  format: "m4a" | "mp3" | "wav",
+  dest: string
+): Promise<string> {
+  return invokeCommand<string>("export_clip", { id, start, end, format, dest });
+}
+
+export async function getStorageUsage(): Promise<StorageUsage> {
+  return invokeCommand<StorageUsage>("get_storage_usage");
+}
+
+// Dashboard totals and monthly series; `year` limits them to recordings from that year.
+export async function getStats(year?: number): Promise<VoiceNoteStats> {
+  return invokeCommand<VoiceNoteStats>("get_stats", { year });
+}
+
+// Inbox watcher mode, folders and what it imported since launch.
+export async function getWatcherStatus(): Promise<WatcherStatus> {
+  return invokeCommand<WatcherStatus>("get_watcher_status");
+}
+
+export async function getQueueStatus(): Promise<QueueStatus> {
+  return invokeCommand<QueueStatus>("get_queue_status");
+}
+
+// Power state, whether power saving is holding back the queue, and the queue itself.
+export async function getSystemStatus(): Promise<SystemStatus> {
+  return invokeCommand<SystemStatus>("get_system_status");
+}
+
+export async function getAppUpdateStatus(): Promise<AppUpdateStatus> {
+  return invokeCommand<AppUpdateStatus>("get_app_update_status");
+}
+
+export async function checkForAppUpdate(): Promise<AppUpdateStatus> {
+  return invokeCommand<AppUpdateStatus>("check_for_app_update");
+}
+
+// Progress arrives as "update:status" events while this runs.
+export async function downloadAppUpdate(): Promise<AppUpdateStatus> {
+  return invokeCommand<AppUpdateStatus>("download_app_update");
+}
+
+// With pauseQueue only the running jobs are waited for; queued ones resume after the restart.
+export async function installAppUpdate(pauseQueue: boolean): Promise<AppUpdateStatus> {
+  return invokeCommand<AppUpdateStatus>("install_app_update", { pauseQueue });
+}
+
+// Stops waiting for the jobs; the ones cut short are requeued after the restart.
+export async function installAppUpdateNow(): Promise<void> {
+  return invokeCommand<void>("install_app_update_now");
+}
+
+// Also toggled from the menu bar; "queue:updated" carries the new status either way.
+export async function setQueuePaused(paused: boolean): Promise<QueueStatus> {
+  return invokeCommand<QueueStatus>("set_queue_paused", { paused });
+}
+
+// Imports new Voice Memos recordings now. The first import only remembers what is already in the
+// library unless `includeExisting` is set.
+export async function importVoiceMemos(includeExisting?: boolean): Promise<Job[]> {
+  return invokeCommand<Job[]>("import_voice_memos", { includeExisting });
+}
+
+// Seals or decrypts existing job folders to match the saved settings; returns files changed.
+export async function applyJobEncryption(): Promise<number> {
+  return invokeCommand<number>("apply_job_encryption");
+}
+
+export async function clearClipCache(id?: string): Promise<number> {
+  return invokeCommand<number>("clear_clip_cache", { id: id ?? null });
+}
+
+export async function getDoNotDisturbStatus(): Promise<boolean> {
+  return invokeCommand<boolean>("get_do_not_disturb_status");
+}
+
+export async function getChapters(id: string): Promise<Chapter[]> {
+  return invokeCommand<Chapter[]>("get_chapters", { id });
+}
+
+export async function findDuplicates(id: string): Promise<DuplicateMatch[]> {
+  return invokeCommand<DuplicateMatch[]>("find_duplicates", { id });
+}
+
+export async function dismissDuplicate(id: string): Promise<boolean> {
+  return invokeCommand<boolean>("dismiss_duplicate", { id });
+}
+
+export async function getRunManifest(id: string): Promise<RunManifest | null> {
+  return invokeCommand<RunManifest | null>("get_run_manifest", { id });
+}
+
+export async function resummarizeJobs(filter: JobFilter): Promise<string[]> {
+  return invokeCommand<string[]>("resummarize_jobs", { filter });
+}
+
+export async function cancelResummarize(): Promise<boolean> {
+  return invokeCommand<boolean>("cancel_resummarize");
+}
+
+export async function exportBilingual(
+  id: string,
+  dest: string,
+  targetLanguage: string | null,
+  includeClips: boolean
+): Promise<string> {
+  return invokeCommand<string>("export_bilingual", { id, dest, targetLanguage, includeClips });
+}
+
+export async function setLlmApiKey(key: string): Promise<boolean> {
+  return invokeCommand<boolean>("set_llm_api_key", { key });
+}
+
+export async function hasLlmApiKey(): Promise<boolean> {
+  return invokeCommand<boolean>("has_llm_api_key");
+}
+
+export async function setSecret(name: string, value: string): Promise<boolean> {
+  return invokeCommand<boolean>("set_secret", { name, value });
+}
+
+export async function deleteSecret(name: string): Promise<void> {
+  return invokeCommand<void>("delete_secret", { name });
+}
+
+export async function hasSecret(name: string): Promise<boolean> {
+  return invokeCommand<boolean>("has_secret", { name });
+}
+
+export async function getActionItems(id: string): Promise<ActionItem[]> {
+  return invokeCommand<ActionItem[]>("get_action_items", { id });
+}
+
+export async function extractActionItems(id: string): Promise<boolean> {
+  return invokeCommand<boolean>("extract_action_items", { id });
+}
+
+export async function getWaveform(id: string, samplesPerSecond: number): Promise<Waveform> {
+  return invokeCommand<Waveform>("get_waveform", { id, samplesPerSecond });
+}
+
+export async function revealJobInFinder(id: string, artifact?: JobArtifact): Promise<boolean> {
+  return invokeCommand<boolean>("reveal_job_in_finder", { id, artifact });
+}
+
+export async function getSummary(id: string, template?: string): Promise<SummaryResponse> {
+  return invokeCommand<SummaryResponse>("get_summary", { id, template: template ?? null });
+}
+
+export async function getSummaries(id: string): Promise<Record<string, SummaryResponse>> {
+  return invokeCommand<Record<string, SummaryResponse>>("get_summaries", { id });
+}
+
+export async function summarizeJob(id: string, template?: string): Promise<SummaryResponse> {
+  return invokeCommand<SummaryResponse>("summarize_job", { id, template: template ?? null });
+}
+
+export async function cancelSummary(id: string): Promise<boolean> {
+  return invokeCommand<boolean>("cancel_summary", { id });
+}
+
+export async function listOllamaModels(baseUrl?: string): Promise<OllamaModel[]> {
+  return invokeCommand<OllamaModel[]>("list_ollama_models", { baseUrl });
+}
+
+export async function checkLlmConnection(): Promise<LlmConnectionStatus> {
+  return invokeCommand<LlmConnectionStatus>("check_llm_connection");
+}
+
+export async function regenerateSummary(id: string, extraInstructions: string): Promise<SummaryResponse> {
+  return invokeCommand<SummaryResponse>("regenerate_summary", { id, extraInstructions });
+}
+
+export async function listSummaryVersions(id: string): Promise<SummaryVersion[]> {
+  return invokeCommand<SummaryVersion[]>("list_summary_versions", { id });
+}
+
+export async function askTranscript(id: string, question: string): Promise<TranscriptAnswer> {
+  return invokeCommand<TranscriptAnswer>("ask_transcript", { id, question });
+}
+
+export async function indexEmbeddings(ids?: string[]): Promise<number> {
+  return invokeCommand<number>("index_embeddings", { ids });
+}
+
+export async function semanticSearch(query: string, limit?: number): Promise<SemanticMatch[]> {
+  return invokeCommand<SemanticMatch[]>("semantic_search", { query, limit });
+}
+
+export async function updateObsidianExport(id: string): Promise<"exported" | "updated" | "unchanged"> {
+  return invokeCommand<"exported" | "updated" | "unchanged">("update_obsidian_export", { id });
+}
+
+export async function exportJob(id: string, format: "html" | "docx" | "pdf", dest: string): Promise<string> {
+  return invokeCommand<string>("export_job", { id, format, dest });
+}
+
+export async function copyTranscriptToClipboard(id: string, withTimestamps: boolean): Promise<void> {
+  return invokeCommand<void>("copy_transcript_to_clipboard", { id, withTimestamps });
+}
+
+export async function copySummaryToClipboard(id: string): Promise<void> {
+  return invokeCommand<void>("copy_summary_to_clipboard", { id });
+}
+
+export async function previewExportPath(id: string): Promise<ExportPathPreview> {
+  return invokeCommand<ExportPathPreview>("preview_export_path", { id });
+}
+
+export async function exportToTarget(id: string, targetId: string): Promise<string> {
+  return invokeCommand<string>("export_to_target", { id, targetId });
+}
+
+export async function setExportTargetToken(targetId: string, token: string): Promise<boolean> {
+  return invokeCommand<boolean>("set_export_target_token", { targetId, token });
+}
+
+// Progress arrives as "export:batch" events (ExportBatchProgress).
+export async function exportAll(filter: JobFilter): Promise<ExportAllSummary> {
+  return invokeCommand<ExportAllSummary>("export_all", { filter });
+}
+
+export async function exportJobBundle(id: string, dest: string): Promise<string> {
+  return invokeCommand<string>("export_job_bundle", { id, dest });
+}
+
+export async function importJobBundle(path: string): Promise<Job> {
+  return invokeCommand<Job>("import_job_bundle", { path });
+}
+
+export async function setCalendarPassword(password: string): Promise<boolean> {
+  return invokeCommand<boolean>("set_calendar_password", { password });
+}
+
+export async function matchCalendarEvent(id: string): Promise<CalendarEvent | null> {
+  return invokeCommand<CalendarEvent | null>("match_calendar_event", { id });
+}
+
+// Checks ffmpeg/ffprobe, whisper, the selected model, the LLM, the vault and free disk space.
+// The newest `limit` app log lines at `level` ("error", "warn", "info", "debug") or above.
+export async function getAppLogs(level?: string, limit?: number): Promise<AppLogEntry[]> {
+  return invokeCommand<AppLogEntry[]>("get_app_logs", { level, limit });
+}
+
+// Zips logs, the redacted config and a job manifest; returns the zip's path.
+export async function createSupportBundle(destDir?: string): Promise<string> {
+  return invokeCommand<string>("create_support_bundle", { destDir });
+}
+
+export async function runDiagnostics(): Promise<DiagnosticsReport> {
+  return invokeCommand<DiagnosticsReport>("run_diagnostics");
+}
+
+// Installs whatever is missing (whisper, FFmpeg, the configured model) in one run and
+// returns the planned steps; progress arrives as "bootstrap:progress" events.
+export async function bootstrapDependencies(): Promise<string[]> {
+  return invokeCommand<string[]>("bootstrap_dependencies");
+}
+
+export async function getWhisperVersion(): Promise<WhisperVersion> {
+  return invokeCommand<WhisperVersion>("get_whisper_version");
+}
+
+export async function checkForWhisperUpdate(): Promise<WhisperUpdateCheck> {
+  return invokeCommand<WhisperUpdateCheck>("check_for_whisper_update");
+}
+
+// Replaces the installed binary only after the new one passes a smoke test; progress is
+// reported like startWhisperDownload. `url` defaults to the latest release.
+export async function upgradeWhisperBinary(url?: string): Promise<ModelDownloadStatus> {
+  return invokeCommand<ModelDownloadStatus>("upgrade_whisper_binary", { url: url ?? null });
+}
+
+export async function listCustomModels(): Promise<CustomModel[]> {
+  return invokeCommand<CustomModel[]>("list_custom_models");
+}
+
+export async function importModelFromPath(path: string, name?: string): Promise<CustomModel> {
+  return invokeCommand<CustomModel>("import_model_from_path", { path, name: name ?? null });
 }
 
 export async function getModelSize(modelSize: string): Promise<number> {
@@ -82,8 +486,40 @@ export async function getModelDownloadStatus(modelSize: string): Promise<ModelDo
   return invokeCommand<ModelDownloadStatus>("get_model_download_status", { modelSize });
 }
 
-export async function startModelDownload(modelSize: string): Promise<ModelDownloadStatus> {
-  return invokeCommand<ModelDownloadStatus>("start_model_download", { modelSize });
+export async function verifyModel(modelSize: string): Promise<ModelVerification> {
+  return invokeCommand<ModelVerification>("verify_model", { modelSize });
+}
+
+// With `url`, downloads a custom ggml/gguf model registered as `name`; the returned status's
+// model_size is its new id.
+export async function startModelDownload(
+  modelSize: string,
+  url?: string,
+  name?: string
+): Promise<ModelDownloadStatus> {
+  return invokeCommand<ModelDownloadStatus>("start_model_download", {
+    modelSize,
+    url: url ?? null,
+    name: name ?? null,
+  });
+}
+
+// Stops the download in flight for a model size; false when none is running.
+export async function cancelModelDownload(key: string): Promise<boolean> {
+  return invokeCommand<boolean>("cancel_model_download", { key });
+}
+
+export async function listInstalledModels(): Promise<InstalledModel[]> {
+  return invokeCommand<InstalledModel[]>("list_installed_models");
+}
+
+// Returns the bytes freed.
+export async function deleteModel(modelSize: string): Promise<number> {
+  return invokeCommand<number>("delete_model", { modelSize });
+}
+
+export async function removePartialModelDownloads(): Promise<number> {
+  return invokeCommand<number>("remove_partial_model_downloads");
 }
 
 export async function getModelInstalled(modelSize: string): Promise<boolean> {
@@ -94,6 +530,10 @@ export async function startWhisperDownload(url: string): Promise<ModelDownloadSt
   return invokeCommand<ModelDownloadStatus>("start_whisper_download", { url });
 }
 
+export async function cancelWhisperDownload(): Promise<boolean> {
+  return invokeCommand<boolean>("cancel_whisper_download");
+}
+
 export async function getLatestWhisperReleaseUrl(): Promise<string> {
   return invokeCommand<string>("get_latest_whisper_release_url");
 }
@@ -114,10 +554,20 @@ export async function getFfmpegInstalled(): Promise<boolean> {
   return invokeCommand<boolean>("get_ffmpeg_installed");
 }
 
+// ffmpeg, ffprobe and whisper-cli already installed on this machine, with versions and
+// ffmpeg's licence flags.
+export async function detectSystemBinaries(): Promise<SystemBinaries> {
+  return invokeCommand<SystemBinaries>("detect_system_binaries");
+}
+
 export async function startFfmpegDownload(url: string): Promise<ModelDownloadStatus> {
   return invokeCommand<ModelDownloadStatus>("start_ffmpeg_download", { url });
 }
 
+export async function cancelFfmpegDownload(): Promise<boolean> {
+  return invokeCommand<boolean>("cancel_ffmpeg_download");
+}
+
 export async function getClipUrl(id: string, start: number, end: number): Promise<string> {
   const path = await invokeCommand<string>("get_clip_path", { id, start, end });
   if (!path) {
@@ -1,6 +1,21 @@
 // Shared types for the Tauri Rust core API.
 
-export type JobStatus = "queued" | "running" | "done" | "error" | "cancelled";
+// "stalled": was running when the app quit or crashed; it is queued again on the next launch.
+export type JobStatus = "queued" | "running" | "done" | "error" | "cancelled" | "stalled";
+
+export type JobTransition = {
+  from: JobStatus;
+  to: JobStatus;
+  // Unix seconds.
+  at: number;
+};
+
+// Carried by "job:summary_chunk" while a summary is written; `reset` drops the text so far.
+export type SummaryChunkEvent = {
+  id: string;
+  delta: string;
+  reset: boolean;
+};
 
 export type Job = {
   id: string;
@@ -16,21 +31,203 @@ export type Job = {
   transcript_srt_path: string;
   md_preview?: string;
   // Summary fields are returned by the core so UI can show status and content.
-  summary_status?: "not_started" | "running" | "done" | "skipped" | "error";
+  summary_status?: "not_started" | "queued" | "running" | "done" | "skipped" | "error" | "cancelled";
   summary_model?: string;
   summary_error?: string;
   summary_md?: string;
   exported_to_obsidian: boolean;
+  media_info?: MediaInfo;
+  source_path?: string;
+  tags?: string[];
+  options?: JobOptions;
+  audio_sha256?: string;
+  // Set at import when the same audio already exists; the job is skipped by the worker.
+  duplicate_of?: string;
+  // Exact whisper invocations behind the current transcript.
+  run_manifest?: RunManifest | null;
+  // Title/date/location recovered from Apple Voice Memos; preferred over the filename.
+  source_metadata?: SourceMetadata | null;
+  // Per-template run status keyed by template id ("summary" uses the summary_* fields).
+  template_summaries?: Record<string, TemplateSummary>;
+  action_items_status?: "queued" | "running" | "done" | "error" | "cancelled" | null;
+  // Generated title used for the exported note name and frontmatter.
+  title?: string | null;
+  // Language the summary was written in when it differs from the transcript.
+  summary_language?: string | null;
+  // Note written by the last Obsidian export; re-exports overwrite it unless the user edited it.
+  exported_path?: string | null;
+  exported_hash?: string | null;
+  // Profile active when the job was created; auto-export uses its vault.
+  profile?: string | null;
+  // Export target id -> written file or Joplin note id.
+  target_exports?: Record<string, string>;
+  export_status?: "exported" | "error" | null;
+  export_error?: string | null;
+  // Calendar event overlapping the recording; its title names the job.
+  calendar_event?: CalendarEvent | null;
+  favorite?: boolean;
+  // Set with renameJob; wins over the generated title.
+  display_title?: string | null;
+  // Free-form user notes; exported with the note and matched by search.
+  notes?: string;
+  // Audio and raw artifacts are packed into archive.zip; hidden from getJobs and default queries.
+  archived?: boolean;
+  // Unix seconds when deleteJob moved it to the trash; restoreJob clears it.
+  trashed_at?: number | null;
+  // Every status change, oldest first.
+  transitions?: JobTransition[];
+};
+
+// queryJobs parameters: the JobFilter fields plus sorting and paging; a zero limit returns
+// every match.
+export type JobQuery = JobFilter & {
+  sort?: "newest" | "oldest" | "filename" | "title";
+  offset?: number;
+  limit?: number;
+};
+
+// A job list row without logs or summary text.
+export type JobSummary = {
+  id: string;
+  filename: string;
+  title: string;
+  status: JobStatus;
+  stage: string;
+  progress: number;
+  created_at: string;
+  summary_status?: Job["summary_status"] | null;
+  tags: string[];
+  favorite: boolean;
+  archived: boolean;
+  trashed_at?: number | null;
+  duration_seconds?: number | null;
+  exported_to_obsidian: boolean;
+  export_status?: "exported" | "error" | null;
+  duplicate_of?: string | null;
+  log_count: number;
+};
+
+export type JobPage = {
+  jobs: JobSummary[];
+  // Matches before pagination.
+  total: number;
+  // Revision the page was read at; pass it to getChangesSince to stay current.
+  revision: number;
+};
+
+// job:updated / config:updated payloads and getConfig carry the revision they were current at.
+export type Revisioned<T> = T & { revision: number };
+
+// What changed after a revision; `revision` is the one to ask from next time.
+export type StateChanges = {
+  revision: number;
+  jobs: Job[];
+  removed_job_ids: string[];
+  // Only set when the config changed too.
+  config: AppConfig | null;
+};
+
+// A folder under jobs/ that no job points at; without audio it can only be deleted.
+export type OrphanedJobFolder = {
+  id: string;
+  path: string;
+  bytes: number;
+  audio: string | null;
+  has_transcript: boolean;
+};
+
+// A job whose folder or files were deleted outside the app.
+export type BrokenJobEntry = {
+  id: string;
+  title: string;
+  folder_missing: boolean;
+  missing: ("audio" | "transcript" | "srt" | "json")[];
+};
+
+export type StorageReconciliation = {
+  orphaned: OrphanedJobFolder[];
+  broken: BrokenJobEntry[];
+  adopted: string[];
+  deleted: string[];
+  errors: string[];
+};
+
+export type TagCount = {
+  tag: string;
+  count: number;
+};
+
+export type JobLogPage = {
+  lines: string[];
+  offset: number;
+  total: number;
 };
 
+export type CalendarEvent = {
+  title: string;
+  // ISO 8601, UTC.
+  start: string;
+  end: string;
+  attendees: string[];
+};
+
+// Import-time options; start/end (seconds) limit transcription to a range.
+export type JobOptions = {
+  start?: number;
+  end?: number;
+  // Watch-folder preset overrides of the configured model and language.
+  model_size?: string | null;
+  language?: string | null;
+  // Export target id the job goes to once transcribed.
+  export_target?: string | null;
+  // Run at full speed regardless of power_saving_mode.
+  ignore_power_state?: boolean;
+};
+
+// Probed with ffprobe at import time; every field is optional because containers vary.
+export type MediaInfo = {
+  duration_seconds?: number;
+  sample_rate?: number;
+  channels?: number;
+  codec?: string;
+  bit_rate?: number;
+  recorded_at?: string;
+};
+
+// Artifacts that can be revealed in the system file manager.
+export type JobArtifact = "dir" | "audio" | "wav" | "transcript" | "json" | "srt" | "summary";
+
 export type Segment = {
   start: number;
   end: number;
   text: string;
+  // Present when a stereo recording was transcribed per channel.
+  channel?: "left" | "right";
+  speaker?: string;
+};
+
+export type RecordingStatus = {
+  recording: boolean;
+  job_id?: string;
+  audio_path?: string;
+};
+
+// Payload of the `recording:level` event emitted while capturing.
+export type RecordingLevelEvent = {
+  job_id: string;
+  level_db: number;
+};
+
+// Normalized 0..1 peak per bucket, cached as waveform.json in the job folder.
+export type Waveform = {
+  samples_per_second: number;
+  duration_seconds: number;
+  peaks: number[];
 };
 
 export type ModelDownloadStatus = {
-  state: "idle" | "downloading" | "done" | "error";
+  // "checksum_mismatch": the file did not match the SHA-256 published by the host and was deleted.
+  state: "idle" | "downloading" | "done" | "error" | "checksum_mismatch" | "cancelled";
   model_size: string;
   repo_id: string;
   total_bytes: number;
@@ -40,6 +237,96 @@ export type ModelDownloadStatus = {
   finished_at?: number;
 };
 
+// One line of run_diagnostics; `fix` is the action onboarding should offer.
+export type DiagnosticCheck = {
+  id: "ffmpeg" | "ffprobe" | "whisper" | "model" | "llm" | "vault" | "disk";
+  status: "ok" | "warning" | "error" | "skipped";
+  message: string;
+  path?: string | null;
+  fix?:
+    | "download_ffmpeg"
+    | "download_whisper"
+    | "download_model"
+    | "open_llm_settings"
+    | "choose_vault"
+    | "free_disk_space"
+    | null;
+};
+
+export type DiagnosticsReport = {
+  // False when any check is an error; warnings don't block transcription.
+  ok: boolean;
+  checks: DiagnosticCheck[];
+  checked_at: number;
+};
+
+// Emitted as "bootstrap:progress" while bootstrap_dependencies runs.
+export type BootstrapProgress = {
+  steps: ("whisper" | "ffmpeg" | "model")[];
+  current_step?: "whisper" | "ffmpeg" | "model" | null;
+  // The failed step's state ends the run: "error", "cancelled" or "checksum_mismatch".
+  state: "running" | "done" | "error" | "cancelled" | "checksum_mismatch";
+  downloaded_bytes: number;
+  // Sum of the advertised download sizes; 0 when unknown.
+  total_bytes: number;
+  message?: string | null;
+  // Files this run installed and removed again because a step failed.
+  rolled_back: string[];
+};
+
+// A model in the app's models folder (list_installed_models).
+export type InstalledModel = {
+  // null for files that don't follow the ggml-<size>.bin naming.
+  model_size: string | null;
+  name: string;
+  path: string;
+  bytes: number;
+  // Unix seconds of the last transcription that used it.
+  last_used: number | null;
+  // Catalog name of a custom model.
+  display_name: string | null;
+  // False for files in a shared models folder that the app didn't download.
+  deletable: boolean;
+};
+
+// A model added from a file or URL; `id` ("custom-<slug>") is used like a model size.
+export type CustomModel = {
+  id: string;
+  name: string;
+  // Download URL or the imported file's path.
+  source: string;
+  added_at: number;
+};
+
+// Installed whisper.cpp version (get_whisper_version).
+export type WhisperVersion = {
+  path: string | null;
+  version: string | null;
+  // "binary": reported by the binary itself; "release": from the tag it was downloaded from.
+  source: "binary" | "release" | "unknown";
+  release_tag: string | null;
+  // "owner/repo" the release_tag belongs to.
+  release_repo: string | null;
+  installed_at: number | null;
+};
+
+export type WhisperUpdateCheck = {
+  installed: string | null;
+  latest: string | null;
+  latest_url: string;
+  update_available: boolean;
+};
+
+// Result of checking a ggml model for truncation or corruption.
+export type ModelVerification = {
+  ok: boolean;
+  model_size: string;
+  path: string;
+  size_bytes: number;
+  expected_min_bytes: number;
+  message?: string;
+};
+
 // Summary payload returned by core commands.
 export type SummaryResponse = {
   summary_status: string;
@@ -48,26 +335,529 @@ export type SummaryResponse = {
   summary_md: string;
 };
 
+// A watched inbox folder and the preset its jobs get; unset fields use the global settings.
+export type WatchFolder = {
+  path: string;
+  model_size?: string | null;
+  language?: string | null;
+  export_target?: string | null;
+  // Delete the file once imported instead of applying inbox_after_import.
+  delete_source: boolean;
+};
+
+export type ExportRoute = {
+  tag?: string;
+  profile?: string;
+  watch_folder?: string;
+  subfolder: string;
+};
+
+// "markdown": plain folder; "logseq": journal pages under <path>/journals; "joplin": Web
+// Clipper API (token stored in the keychain via setExportTargetToken).
+export type ExportTarget = {
+  id: string;
+  name: string;
+  kind: "markdown" | "logseq" | "joplin";
+  path?: string;
+  // markdown only; same placeholders as obsidian_path_template. Empty = "{title}.md".
+  path_template?: string;
+  // joplin only; empty = http://127.0.0.1:41184.
+  joplin_url?: string;
+  joplin_notebook_id?: string;
+};
+
+// "app_data:move" event payload while moveAppData runs.
+// "model:import" event while importModelFromPath copies the file into the models folder.
+export type ModelImportProgress = {
+  model_id: string;
+  copied_bytes: number;
+  total_bytes: number;
+};
+
+export type AppDataMoveProgress = {
+  stage: "copying" | "rewriting" | "cleaning_up" | "done";
+  copied_bytes: number;
+  total_bytes: number;
+};
+
+// Result of importSettings: the saved config and the folders that need fixing on this machine.
+// Settings with any other invalid field are rejected.
+export type SettingsImport = {
+  config: AppConfig;
+  errors: ConfigFieldError[];
+};
+
+export type ResetScope = "transcription" | "summarization" | "export" | "all";
+
+// Per-profile settings; the active profile's values live in the top-level AppConfig fields.
+export type ConfigProfile = {
+  name: string;
+  vault_path: string;
+  output_subfolder: string;
+  export_routes: ExportRoute[];
+  summary_prompt: string;
+  summary_templates: SummaryTemplate[];
+  export_targets: ExportTarget[];
+};
+
+export type WatcherStatus = {
+  enabled: boolean;
+  // "events" when the OS reports folder changes, "polling" when only the periodic scan runs.
+  mode: "events" | "polling" | "off";
+  folders: string[];
+  last_scan_at: number | null;
+  // Jobs created since launch.
+  imported: number;
+  last_job_id: string | null;
+  last_error: string | null;
+  // Files seen but not imported yet: still changing, in use, or downloading from iCloud.
+  waiting: string[];
+};
+
+// Jobs waiting for and in the transcription worker; a paused queue finishes the running job.
+export type QueueStatus = {
+  paused: boolean;
+  running: number;
+  queued: number;
+};
+
+// Reported by pmset on macOS; always false elsewhere.
+export type PowerState = {
+  on_battery: boolean;
+  battery_percent: number | null;
+  low_power_mode: boolean;
+  thermal_pressure: boolean;
+};
+
+export type SystemStatus = {
+  power: PowerState;
+  power_saving_mode: string;
+  // power_saving_mode applies right now.
+  throttled: boolean;
+  queue: QueueStatus;
+};
+
+// One tool found by detect_system_binaries; `usable` is false for GPL/nonfree ffmpeg builds.
+export type SystemBinary = {
+  name: "ffmpeg" | "ffprobe" | "whisper";
+  path: string | null;
+  version: string | null;
+  license_flags: string[];
+  usable: boolean;
+  message: string | null;
+};
+
+export type SystemBinaries = {
+  enabled: boolean;
+  binaries: SystemBinary[];
+};
+
+// One line of the app log files (get_app_logs); job_id is set for lines from a job's log.
+export type AppLogEntry = {
+  timestamp: string;
+  level: "ERROR" | "WARN" | "INFO" | "DEBUG" | "TRACE";
+  target: string;
+  message: string;
+  job_id: string | null;
+};
+
+// Carried by "update:status". Installing waits until no job is running (or queued, unless the
+// queue is paused) and then restarts the app.
+export type AppUpdateStatus = {
+  state: "idle" | "available" | "downloading" | "ready" | "waiting_for_jobs" | "installing" | "error";
+  // False for builds without the release signing key; the updater is hidden then.
+  configured: boolean;
+  current_version: string;
+  version: string | null;
+  notes: string | null;
+  downloaded_bytes: number;
+  total_bytes: number | null;
+  blocking_jobs: number;
+  error: string | null;
+  // ffmpeg and whisper checks from the first launch after an update.
+  verification: DiagnosticCheck[];
+};
+
+// One invalid AppConfig field, as returned by validate_config; `field` is the AppConfig key.
+// Warnings (a vault folder that isn't there right now) don't block saving.
+export type ConfigFieldError = {
+  field: string;
+  message: string;
+  warning: boolean;
+};
+
 export type AppConfig = {
   initialized: boolean;
+  // switchProfile swaps the profile fields; inactive profiles are kept in `profiles`.
+  active_profile?: string;
+  profiles?: ConfigProfile[];
   vault_path: string;
   output_subfolder: string;
-  model_size: "tiny" | "base" | "small" | "medium" | "large-v3";
+  // First matching route wins; subfolder supports YYYY/MM/DD placeholders.
+  export_routes?: ExportRoute[];
+  // Built-in sizes or a custom model id (see CustomModel).
+  model_size: "tiny" | "base" | "small" | "medium" | "large-v3" | `custom-${string}`;
   preload_model?: boolean;
   language?: string;
-  // Summarization settings (Ollama).
+  // Summarization settings. "openai" targets any OpenAI-compatible chat endpoint
+  // (LM Studio, llama.cpp server, vLLM, hosted APIs); its API key is stored via set_llm_api_key.
   enable_summarization?: boolean;
   auto_summarize_after_transcription?: boolean;
+  // Export to Obsidian automatically; the summarization flag refreshes the note once the
+  // summary is ready. Failures show up as Job.export_status "error".
+  auto_export_after_transcription?: boolean;
+  auto_export_after_summarization?: boolean;
+  // "auto" summarizes in the transcript's language; otherwise a code ("en") or name.
+  summary_language?: string;
+  llm_provider?: "ollama" | "openai";
   ollama_base_url?: string;
   ollama_model?: string;
+  openai_base_url?: string;
+  openai_model?: string;
+  // Name of a secret stored with setSecret; empty = the key from setLlmApiKey.
+  openai_api_key_secret?: string;
+  // Model context window; longer transcripts are summarized in chunks, then combined.
+  llm_context_tokens?: number;
+  llm_timeout_seconds?: number;
+  // Retries for timeouts/connection errors/5xx before a summary is marked as failed.
+  llm_max_retries?: number;
+  // Parallel summarizations (applied on restart); 1 keeps a local Ollama responsive.
+  summary_concurrency?: number;
+  // Write a TextRank TL;DR (summary_model "extractive") when no LLM is reachable or summarization is off.
+  extractive_fallback?: boolean;
+  semantic_index?: boolean;
+  embedding_model?: string;
+  // Placeholders: {text}, {title}, {date}, {duration}, {language}, {filename},
+  // {segments_with_timestamps}. Unknown ones are rejected when the config is saved.
   summary_prompt?: string;
+  // Extra named prompts (action items, decisions, ...); output goes to <id>.md.
+  summary_templates?: SummaryTemplate[];
+  // Run structured action-item extraction after each summary (stored as actions.json).
+  extract_action_items?: boolean;
+  // Generate a note title and topic tags after transcription (keywords if summarization is off).
+  auto_title_tags?: boolean;
   enable_summarization?: boolean;
   auto_summarize_after_transcription?: boolean;
   ollama_base_url?: string;
   ollama_model?: string;
   include_timestamps: boolean;
+  // Exported note layout. Placeholders: {frontmatter}, {title}, {summary}, {action_items},
+  // {templates}, {notes}, {audio}, {transcript}, {date}, {duration}, {language}, {filename}, {tags},
+  // {model}. Empty uses the built-in layout.
+  obsidian_note_template?: string;
+  obsidian_audio_mode?: "none" | "copy" | "link";
+  // Vault-relative folder for copied audio; empty = "attachments" next to the note.
+  obsidian_attachments_folder?: string;
+  // Transcript lines become "#t=" links that seek the audio (Media Extended plugin).
+  obsidian_timestamp_links?: boolean;
+  // Vault-relative note path. Placeholders: {folder} (routed subfolder), {year}, {month},
+  // {day}, {date}, {title}, {filename}, {id}. Empty = "<subfolder>/<title>.md".
+  obsidian_path_template?: string;
+  // What goes into the recording date's daily note (daily_note_path, same placeholders,
+  // default "{date}.md"): "link" adds "- HH:MM [[note|title]] — gist" next to the job's own
+  // note, "full" puts the whole job there instead of a separate note.
+  daily_note_mode?: "off" | "link" | "full";
+  daily_note_path?: string;
+  // Non-Obsidian destinations used by export_to_target.
+  export_targets?: ExportTarget[];
+  // .ics file path, .ics/webcal URL or CalDAV collection URL; the CalDAV password is set via
+  // set_calendar_password. Matched events name the job and feed the summary prompt.
+  calendar_enabled?: boolean;
+  calendar_source?: string;
+  calendar_username?: string;
+  // Bias whisper toward note names found in the vault and fix their spelling afterwards.
+  vault_vocabulary_enabled?: boolean;
+  // Caption layout used by export_subtitles.
+  subtitle_max_line_chars?: number;
+  subtitle_max_lines?: number;
+  subtitle_speaker_prefix?: boolean;
   watch_inbox_enabled: boolean;
   inbox_poll_seconds: number;
+  // Folders the inbox watcher imports from, each with a preset; empty means inbox/ in the app
+  // data folder with the global settings.
+  watch_folders?: WatchFolder[];
+  // What happens to a watched file once its job exists.
+  inbox_after_import?: "keep" | "move" | "rename";
+  // A watched file is imported once its size held for this many scans and it is this old.
+  inbox_stable_polls?: number;
+  inbox_min_age_seconds?: number;
+  // Import new Apple Voice Memos recordings on every scan; empty path = the usual library folder.
+  voice_memos_import_enabled?: boolean;
+  voice_memos_path?: string;
+  // Closing the window hides it; the app keeps working from the menu bar.
+  close_to_tray?: boolean;
+  // On battery, in Low Power Mode or when hot: pause the queue, limit whisper threads or defer
+  // medium/large-model jobs.
+  power_saving_mode?: "off" | "pause" | "reduce_threads" | "defer_large";
+  power_saving_threads?: number;
+  // Optional ffmpeg pre-processing applied to the converted WAV only.
+  normalize_loudness?: boolean;
+  reduce_noise?: boolean;
+  // What to keep of whisper's raw JSON once segments are extracted.
+  whisper_json_retention?: "full" | "segments" | "discard";
+  // Transcribe stereo channels separately and label them (e.g. caller/callee).
+  split_stereo_channels?: boolean;
+  left_channel_label?: string;
+  right_channel_label?: string;
+  // LRU cap for cached playback clips across all jobs; 0 disables eviction.
+  clip_cache_max_mb?: number;
+  // Completion alerts for finished/failed jobs; suppressed while a macOS Focus mode is on.
+  notify_on_success?: boolean;
+  notify_on_failure?: boolean;
+  notify_on_summary?: boolean;
+  sound_on_success?: boolean;
+  sound_on_failure?: boolean;
+  respect_do_not_disturb?: boolean;
+  // Developer mode: simulate the pipeline with stub artifacts (also VOICENOTE_MOCK_PIPELINE=1).
+  mock_pipeline?: boolean;
+  // What to keep after a successful transcription to save disk space.
+  audio_retention?: "keep_all" | "converted_only" | "opus_only";
+  // Default language for bilingual (language-learning) exports.
+  translation_target_language?: string;
   whisper_binary_url?: string;
   ffmpeg_binary_url?: string;
+  // Fall back to ffmpeg and whisper-cli on PATH or in Homebrew's folders before downloading.
+  use_system_binaries?: boolean;
+  // Models folder, e.g. "~/whisper-models" shared with other tools; empty = app data folder.
+  models_dir?: string;
+  // Used for model, whisper and ffmpeg downloads. Proxy: http(s):// or socks5(h):// URL
+  // (empty = HTTP(S)_PROXY/ALL_PROXY); no_proxy: comma-separated hosts.
+  download_proxy?: string;
+  download_no_proxy?: string;
+  // Replace https://huggingface.co / https://github.com, e.g. "https://hf-mirror.com".
+  huggingface_mirror?: string;
+  github_mirror?: string;
+  // Download speed cap in KB/s, 0 = unlimited; saving applies it to running downloads.
+  download_speed_limit_kbps?: number;
+  // Trashed jobs older than this are purged at startup; 0 keeps them until emptyTrash.
+  trash_retention_days?: number;
+  // Encrypt transcripts and summaries in job folders; the audio flag stops playback.
+  encrypt_job_files?: boolean;
+  encrypt_job_audio?: boolean;
+  // Keep secrets in a 0600 file when the keychain refuses them instead of failing the save.
+  allow_plaintext_secrets?: boolean;
+};
+
+export type JobStorageUsage = {
+  id: string;
+  filename: string;
+  total_bytes: number;
+  clip_bytes: number;
+  clip_count: number;
+};
+
+export type StorageUsage = {
+  total_bytes: number;
+  clip_cache_bytes: number;
+  clip_cache_max_bytes: number;
+  jobs: JobStorageUsage[];
+};
+
+// Audio seconds transcribed per second of whisper processing.
+export type ModelSpeedStats = {
+  model: string;
+  runs: number;
+  audio_seconds: number;
+  processing_seconds: number;
+  speed: number;
+};
+
+export type StorageByCategory = {
+  audio: number;
+  transcripts: number;
+  summaries: number;
+  clips: number;
+  archives: number;
+  other: number;
+  models: number;
+};
+
+export type MonthlyStats = {
+  // "YYYY-MM" of the recording date.
+  month: string;
+  notes: number;
+  hours: number;
+  words: number;
+};
+
+export type VoiceNoteStats = {
+  jobs: number;
+  // Jobs with a finished transcript.
+  notes: number;
+  hours_transcribed: number;
+  words_transcribed: number;
+  summaries_done: number;
+  summaries_failed: number;
+  summary_success_rate: number | null;
+  models: ModelSpeedStats[];
+  storage: StorageByCategory;
+  // Oldest month first; months without notes are omitted.
+  monthly: MonthlyStats[];
+};
+
+export type Chapter = {
+  start: number;
+  end: number;
+  title: string;
+};
+
+export type DuplicateMatch = {
+  id: string;
+  filename: string;
+  similarity: number;
+  method: "checksum" | "fingerprint";
+};
+
+export type WhisperRun = {
+  binary_path: string;
+  binary_version?: string | null;
+  binary_sha256?: string | null;
+  model_path: string;
+  model_sha256?: string | null;
+  args: string[];
+  started_at: string;
+  duration_seconds: number;
+  exit_code?: number | null;
+};
+
+export type RunManifest = {
+  app_version: string;
+  runs: WhisperRun[];
+};
+
+// Selects jobs for queryJobs and bulk operations; empty fields match everything.
+export type JobFilter = {
+  ids?: string[];
+  statuses?: JobStatus[];
+  stages?: string[];
+  // Jobs carrying any of these tags.
+  tags?: string[];
+  favorite?: boolean;
+  // Unset matches both; query_jobs then hides archived jobs unless `search` is set.
+  archived?: boolean;
+  // true selects only the trash; otherwise trashed jobs are left out.
+  trashed?: boolean;
+  // Unix seconds, compared against the job's created_at.
+  created_after?: number;
+  created_before?: number;
+  // "not_started" matches jobs that were never summarized.
+  summary_statuses?: string[];
+  // Case-insensitive match against the filename, title and notes.
+  search?: string;
+};
+
+// Payload of the "summary:batch" event emitted while resummarize_jobs runs.
+export type ResummarizeProgress = {
+  total: number;
+  completed: number;
+  failed: number;
+  current_id?: string | null;
+  finished: boolean;
+};
+
+// Payload of the "export:batch" event emitted while export_all runs.
+export type ExportBatchProgress = {
+  total: number;
+  processed: number;
+  current_id?: string | null;
+  finished: boolean;
+};
+
+export type ExportAllSummary = {
+  created: string[];
+  // Already exported into the current vault.
+  skipped: string[];
+  failed: { id: string; error: string }[];
+};
+
+export type SourceMetadata = {
+  title?: string | null;
+  recorded_at?: string | null;
+  location?: string | null;
+};
+
+export type SummaryTemplate = {
+  id: string;
+  name: string;
+  prompt: string;
+  // "minutes": speaker-attributed minutes (needs speaker labels); empty for a plain prompt.
+  mode?: "" | "minutes";
+};
+
+export type TemplateSummary = {
+  status: "not_started" | "queued" | "running" | "done" | "error" | "cancelled";
+  model?: string | null;
+  error?: string | null;
+  language?: string | null;
+};
+
+export type ActionItem = {
+  text: string;
+  owner?: string | null;
+  due?: string | null;
+};
+
+export type OllamaModel = {
+  name: string;
+  size: number;
+  modified_at?: string | null;
+};
+
+export type LlmConnectionStatus = {
+  provider: string;
+  base_url: string;
+  model: string;
+  reachable: boolean;
+  model_available: boolean;
+  available_models: string[];
+  error?: string | null;
+};
+
+export type SummaryVersion = {
+  version: number;
+  path: string;
+  modified_at?: number | null;
+  summary_md: string;
+};
+
+export type AnswerCitation = {
+  index: number;
+  start: number;
+  end: number;
+  text: string;
+};
+
+export type TranscriptAnswer = {
+  answer: string;
+  model: string;
+  citations: AnswerCitation[];
+};
+
+export type SemanticMatch = {
+  job_id: string;
+  title: string;
+  start: number;
+  end: number;
+  text: string;
+  score: number;
+};
+
+// Unset fields fall back to the subtitle_* config values.
+export type SubtitleExportOptions = {
+  dest: string;
+  max_line_chars?: number;
+  max_lines?: number;
+  // SRT: "Name: " prefix; WebVTT: <v Name> voice tags.
+  speaker_labels?: boolean;
+  // Merge same-speaker segments separated by at most this many seconds; 0 keeps whisper's cuts.
+  merge_gap_seconds?: number;
+  // WebVTT only: interpolated per-word timestamps (karaoke-style cues).
+  word_timing?: boolean;
+};
+
+export type ExportPathPreview = {
+  path: string;
+  relative_path: string;
+  exists: boolean;
+  daily_note: boolean;
 };
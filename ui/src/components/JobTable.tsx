@@ -16,6 +16,7 @@ const STATUS_LABELS: Record<JobStatus, string> = {
   done: "jobs.status.done",
   error: "jobs.status.error",
   cancelled: "jobs.status.cancelled",
+  stalled: "jobs.status.stalled",
 };
 
 export default function JobTable({ jobs, onView, onExport, onCancel }: Props) {
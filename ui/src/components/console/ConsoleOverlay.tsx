@@ -11,6 +11,7 @@ const STATUS_LABELS: Record<Job["status"], string> = {
   done: "jobs.status.done",
   error: "jobs.status.error",
   cancelled: "jobs.status.cancelled",
+  stalled: "jobs.status.stalled",
 };
 
 const STATUS_TONE: Record<Job["status"], "neutral" | "success" | "warning" | "error"> = {
@@ -19,6 +20,7 @@ const STATUS_TONE: Record<Job["status"], "neutral" | "success" | "warning" | "er
   done: "success",
   error: "error",
   cancelled: "neutral",
+  stalled: "warning",
 };
 
 type Props = {
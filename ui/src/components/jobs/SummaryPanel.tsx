@@ -1,6 +1,7 @@
 import React, { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
 import { getSummary, summarizeJob } from "../../api/client";
-import type { SummaryResponse } from "../../api/types";
+import type { SummaryChunkEvent, SummaryResponse } from "../../api/types";
 import Button from "../ui/Button";
 import MarkdownPreview from "../MarkdownPreview";
 import { useI18n } from "../../i18n/I18nProvider";
@@ -15,6 +16,8 @@ export default function SummaryPanel({ jobId }: Props) {
   const [status, setStatus] = useState<string | null>(null);
   const [error, setError] = useState<string | null>(null);
   const [copied, setCopied] = useState(false);
+  // The summary as it streams in; polling only sees what the core saved last.
+  const [liveText, setLiveText] = useState<string | null>(null);
 
   const load = async () => {
     try {
@@ -32,20 +35,41 @@ export default function SummaryPanel({ jobId }: Props) {
   }, [jobId]);
 
   useEffect(() => {
-    if (!summary || summary.summary_status !== "running") return;
+    if (!summary || (summary.summary_status !== "running" && summary.summary_status !== "queued")) return;
     const timer = setInterval(() => {
       getSummary(jobId)
         .then((data) => setSummary(data))
         .catch(() => {
-          // Keep polling while running.
+          // Keep polling while queued or running.
         });
     }, 1000);
     return () => clearInterval(timer);
   }, [jobId, summary?.summary_status]);
 
+  useEffect(() => {
+    setLiveText(null);
+    let unlisten: (() => void) | null = null;
+    listen<SummaryChunkEvent>("job:summary_chunk", (event) => {
+      const { id, delta, reset } = event.payload;
+      if (id !== jobId) return;
+      setLiveText((prev) => (reset ? "" : prev ?? "") + delta);
+    }).then((fn) => {
+      unlisten = fn;
+    });
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, [jobId]);
+
+  useEffect(() => {
+    if (summary?.summary_status !== "running") setLiveText(null);
+  }, [summary?.summary_status]);
+
   const regenerate = async () => {
     setStatus(null);
     setError(null);
+    // summarizeJob resolves only when the summary is done; show it streaming in meanwhile.
+    setSummary((prev) => (prev ? { ...prev, summary_status: "running" } : prev));
     try {
       const data = await summarizeJob(jobId);
       setSummary(data);
@@ -102,6 +126,8 @@ export default function SummaryPanel({ jobId }: Props) {
             )}
           </div>
         </div>
+      ) : summary?.summary_status === "running" && liveText ? (
+        <MarkdownPreview markdown={liveText} />
       ) : summary?.summary_md ? (
         <MarkdownPreview markdown={summary.summary_md} />
       ) : (
@@ -20,6 +20,7 @@ export function getJobStatusLabel(job: Job, t: (key: string) => string): string
   if (job.status === "done") return t("jobs.status.done");
   if (job.status === "error") return t("jobs.status.error");
   if (job.status === "cancelled") return t("jobs.status.cancelled");
+  if (job.status === "stalled") return t("jobs.status.stalled");
 
   return job.status;
 }
@@ -30,5 +31,6 @@ export function getJobStatusTone(job: Job): "neutral" | "success" | "warning" |
   if (job.status === "running") return "warning";
   if (job.status === "queued") return "info";
   if (job.status === "cancelled") return "neutral";
+  if (job.status === "stalled") return "warning";
   return "neutral";
 }
@@ -41,6 +41,7 @@ export const STRINGS: Record<Locale, Strings> = {
     "jobs.status.done": "Done",
     "jobs.status.error": "Error",
     "jobs.status.cancelled": "Canceled",
+    "jobs.status.stalled": "Interrupted",
     "jobs.summary_done": "Summary ✓",
     "jobs.summary_none": "Summary —",
     "jobs.model": "Model",
@@ -65,6 +66,9 @@ export const STRINGS: Record<Locale, Strings> = {
     "details.tabs.transcript": "Transcript",
     "details.tabs.summary": "Summary",
     "details.tabs.console": "Console",
+    "details.tabs.notes": "Notes",
+    "details.notes_placeholder": "Your notes about this recording. They are exported with the note and found by search.",
+    "details.rename": "Double-click to rename",
     "details.title": "Details",
     "details.loading": "Loading job…",
     "details.status.running": "Processing",
@@ -115,11 +119,102 @@ export const STRINGS: Record<Locale, Strings> = {
     "settings.general.output_folder": "Output subfolder",
     "settings.general.browse": "Browse",
     "settings.general.browse_help": "Choose a folder on disk",
+    "settings.general.profile": "Profile",
+    "settings.general.profile_active": "Active profile",
+    "settings.general.profile_new": "New profile",
+    "settings.general.profile_create": "Create",
+    "settings.general.profile_delete": "Delete",
+    "settings.general.profile_help": "Each profile has its own vault, folder, prompts and export targets.",
+    "settings.general.profile_unsaved": "Save or discard changes before switching profiles.",
+    "settings.general.backup": "Backup and reset",
+    "settings.general.settings_export": "Export settings…",
+    "settings.general.settings_import": "Import settings…",
+    "settings.general.settings_exported": "Settings exported to",
+    "settings.general.trash_retention": "Keep deleted recordings in the trash, days (0 — until emptied)",
+    "settings.general.encryption": "Encryption",
+    "settings.general.encrypt_files": "Encrypt transcripts and summaries",
+    "settings.general.encrypt_audio": "Encrypt audio too (disables playback)",
+    "settings.general.plaintext_secrets": "Store API keys and passwords in a file if the Keychain is unavailable",
+    "settings.general.encryption_apply": "Apply to existing recordings",
+    "settings.general.encryption_applied": "Files updated:",
+    "settings.general.encryption_help": "The key is kept in the Keychain. Without it encrypted recordings cannot be read; vault exports stay unencrypted.",
+    "settings.general.reset_scope": "Reset section",
+    "settings.general.reset_export": "Export",
+    "settings.general.reset_all": "Everything (keeps vault and profiles)",
+    "settings.general.reset": "Reset to defaults",
+    "settings.general.backup_help": "The file has no API keys or passwords — set them again on the new machine.",
+    "settings.general.app_data_move": "Move app data…",
+    "settings.general.app_data_move_help": "Moves jobs, models and settings to another folder or drive; the app restarts afterwards.",
+    "settings.general.app_data_moved": "App data moved. Restarting…",
     "settings.general.ui_language": "Interface language",
     "settings.general.ui_language_help": "Applies to the app interface.",
     "settings.general.inbox": "Inbox",
     "settings.general.inbox_watch": "Watch inbox folder (inbox/)",
     "settings.general.inbox_interval": "Polling interval (sec.)",
+    "settings.general.inbox_folders": "Watched folders, each with its own model, language and export (none — inbox/ in the app data folder)",
+    "settings.general.inbox_add_folder": "Add folder…",
+    "settings.general.inbox_default_model": "Default model",
+    "settings.general.inbox_language": "Language",
+    "settings.general.inbox_no_export": "No extra export",
+    "settings.general.inbox_delete_source": "Delete the file after import",
+    "settings.general.inbox_stable_polls": "Import once the size is unchanged for scans",
+    "settings.general.inbox_min_age": "and the file is older than (sec.)",
+    "settings.general.inbox_waiting": "waiting for files to finish copying:",
+    "settings.general.voice_memos": "Import new Voice Memos recordings",
+    "settings.general.voice_memos_path": "Voice Memos library (empty — find automatically)",
+    "settings.general.voice_memos_import": "Import all not yet imported",
+    "settings.general.notifications": "Notifications",
+    "settings.general.notify_success": "Transcription finished",
+    "settings.general.notify_summary": "Summary ready",
+    "settings.general.notify_failure": "Errors",
+    "settings.general.sound_success": "Sound when done",
+    "settings.general.sound_failure": "Sound on errors",
+    "settings.general.respect_focus": "Stay quiet in Focus mode",
+    "settings.general.notifications_help": "Clicking a notification opens its job.",
+    "settings.general.power": "Battery and heat",
+    "settings.general.power_mode": "On battery, in Low Power Mode or when hot",
+    "settings.general.power_off": "Work as usual",
+    "settings.general.power_pause": "Pause the queue",
+    "settings.general.power_threads": "Use fewer whisper threads",
+    "settings.general.power_defer": "Put medium and large model jobs last",
+    "settings.general.power_thread_count": "Threads",
+    "settings.general.power_now": "Now:",
+    "settings.general.power_battery": "on battery",
+    "settings.general.power_ac": "on power adapter",
+    "settings.general.power_low": "Low Power Mode",
+    "settings.general.power_hot": "thermal pressure",
+    "settings.general.tray": "Menu bar",
+    "settings.general.close_to_tray": "Keep running in the menu bar when the window is closed",
+    "settings.general.updates": "Updates",
+    "settings.general.update_version": "Version",
+    "settings.general.update_check": "Check for updates",
+    "settings.general.update_none": "You have the latest version.",
+    "settings.general.update_available": "Available:",
+    "settings.general.update_download": "Download",
+    "settings.general.update_downloading": "Downloading…",
+    "settings.general.update_install": "Install and restart",
+    "settings.general.update_pause_queue": "Pause the queue instead of waiting for it to finish",
+    "settings.general.update_waiting": "Installs when these jobs finish:",
+    "settings.general.update_installing": "Installing…",
+    "settings.general.update_install_now": "Install now",
+    "settings.general.update_not_configured": "Updates are not available in this build.",
+    "settings.general.logs": "Logs",
+    "settings.general.logs_level_error": "Errors",
+    "settings.general.logs_level_warn": "Warnings",
+    "settings.general.logs_level_info": "Info",
+    "settings.general.logs_level_debug": "Debug",
+    "settings.general.logs_show": "Show logs",
+    "settings.general.logs_empty": "No log entries at this level.",
+    "settings.general.support_bundle": "Create support bundle…",
+    "settings.general.support_bundle_hint": "Zips the logs, settings with credentials removed and a list of jobs without file names or transcripts.",
+    "settings.general.support_bundle_saved": "Support bundle saved:",
+    "settings.general.update_check_failed": "After the update:",
+    "settings.general.voice_memos_imported": "Voice Memos imported:",
+    "settings.general.inbox_after_import": "After import",
+    "settings.general.inbox_keep": "Leave the file in place",
+    "settings.general.inbox_move": "Move it to processed/",
+    "settings.general.inbox_rename": "Add .imported to its name",
+    "settings.general.inbox_status": "Watcher mode and files imported since launch:",
     "settings.transcription.model": "Whisper model",
     "settings.transcription.language": "Recognition language",
     "settings.transcription.timestamps": "Show timestamps",
@@ -128,6 +223,25 @@ export const STRINGS: Record<Locale, Strings> = {
     "settings.transcription.size": "Size",
     "settings.transcription.download_done": "Model downloaded.",
     "settings.transcription.download_error": "Download error",
+    "settings.transcription.checksum_mismatch": "Checksum mismatch — the download was corrupted",
+    "settings.transcription.network": "Download network",
+    "settings.transcription.network_help": "Proxy and mirrors used for model, whisper and FFmpeg downloads. Save settings before downloading.",
+    "settings.transcription.download_proxy": "Proxy (http, https, socks5)",
+    "settings.transcription.download_no_proxy": "Bypass proxy for",
+    "settings.transcription.huggingface_mirror": "Hugging Face mirror",
+    "settings.transcription.github_mirror": "GitHub mirror",
+    "settings.transcription.download_speed_limit": "Speed limit, KB/s (0 — unlimited, applies to running downloads)",
+    "settings.transcription.installed_models": "Installed models",
+    "settings.transcription.models_dir": "Models folder",
+    "settings.transcription.models_dir_help": "empty — app data; a shared folder avoids duplicate downloads",
+    "settings.transcription.models_dir_choose": "Choose…",
+    "settings.transcription.custom_model_name": "Custom model name",
+    "settings.transcription.custom_model_url": "ggml/gguf model URL",
+    "settings.transcription.import_model": "Import file…",
+    "settings.transcription.model_last_used": "last used {date}",
+    "settings.transcription.model_never_used": "never used",
+    "settings.transcription.delete_model": "Delete",
+    "settings.transcription.remove_partial": "Remove unfinished downloads",
     "settings.transcription.download_button": "Download model",
     "settings.transcription.binary_url": "Whisper binary URL",
     "settings.transcription.binary_url_help": "Use a macOS arm64 zip (bizenlabs) or official release.",
@@ -144,6 +258,10 @@ export const STRINGS: Record<Locale, Strings> = {
     "settings.transcription.ffmpeg_download_button": "Download FFmpeg",
     "settings.transcription.ffmpeg_download_error": "FFmpeg download error",
     "settings.transcription.ffmpeg_installed": "FFmpeg installed",
+    "settings.transcription.system_binaries": "Installed tools",
+    "settings.transcription.use_system_binaries": "Use ffmpeg and whisper-cli installed on this computer (PATH, Homebrew) when nothing is downloaded",
+    "settings.transcription.detect_system_binaries": "Look for installed tools",
+    "settings.transcription.system_binary_unused": "not used",
     "settings.transcription.ffmpeg_missing": "FFmpeg missing",
     "settings.transcription.model_installed": "Model installed",
     "settings.transcription.model_missing": "Model missing",
@@ -158,6 +276,7 @@ export const STRINGS: Record<Locale, Strings> = {
     "language.en": "English",
     "settings.status.saved": "Saved",
     "settings.status.failed": "Failed to save",
+    "settings.status.invalid": "Fix the highlighted settings",
     "wizard.title": "Welcome to VoiceNote",
     "wizard.step1": "Step 1: Choose Obsidian vault folder",
     "wizard.step2": "Step 2: Output subfolder",
@@ -928,6 +1047,7 @@ export const STRINGS: Record<Locale, Strings> = {
     "jobs.status.done": "Готово",
     "jobs.status.error": "Ошибка",
     "jobs.status.cancelled": "Отмена",
+    "jobs.status.stalled": "Прервано",
     "jobs.summary_done": "Саммари ✓",
     "jobs.summary_none": "Саммари —",
     "jobs.model": "Модель",
@@ -952,6 +1072,9 @@ export const STRINGS: Record<Locale, Strings> = {
     "details.tabs.transcript": "Расшифровка",
     "details.tabs.summary": "Сводка",
     "details.tabs.console": "Консоль",
+    "details.tabs.notes": "Заметки",
+    "details.notes_placeholder": "Ваши заметки о записи. Они экспортируются вместе с заметкой и находятся поиском.",
+    "details.rename": "Дважды щёлкните, чтобы переименовать",
     "details.title": "Детали",
     "details.loading": "Загрузка задания…",
     "details.status.running": "Обработка",
@@ -1002,11 +1125,102 @@ export const STRINGS: Record<Locale, Strings> = {
     "settings.general.output_folder": "Подпапка вывода",
     "settings.general.browse": "Обзор",
     "settings.general.browse_help": "Выбрать папку на диске",
+    "settings.general.profile": "Профиль",
+    "settings.general.profile_active": "Активный профиль",
+    "settings.general.profile_new": "Новый профиль",
+    "settings.general.profile_create": "Создать",
+    "settings.general.profile_delete": "Удалить",
+    "settings.general.profile_help": "У каждого профиля свои vault, папка, промпты и цели экспорта.",
+    "settings.general.profile_unsaved": "Сохраните или отмените изменения перед сменой профиля.",
+    "settings.general.backup": "Резервная копия и сброс",
+    "settings.general.settings_export": "Экспорт настроек…",
+    "settings.general.settings_import": "Импорт настроек…",
+    "settings.general.settings_exported": "Настройки сохранены в",
+    "settings.general.trash_retention": "Хранить удалённые записи в корзине, дней (0 — до очистки)",
+    "settings.general.encryption": "Шифрование",
+    "settings.general.encrypt_files": "Шифровать расшифровки и саммари",
+    "settings.general.encrypt_audio": "Шифровать и аудио (отключает воспроизведение)",
+    "settings.general.plaintext_secrets": "Хранить API-ключи и пароли в файле, если Связка ключей недоступна",
+    "settings.general.encryption_apply": "Применить к существующим записям",
+    "settings.general.encryption_applied": "Изменено файлов:",
+    "settings.general.encryption_help": "Ключ хранится в Связке ключей. Без него зашифрованные записи не прочитать; экспорт в vault остаётся незашифрованным.",
+    "settings.general.reset_scope": "Сбросить раздел",
+    "settings.general.reset_export": "Экспорт",
+    "settings.general.reset_all": "Всё (кроме vault и профилей)",
+    "settings.general.reset": "Сбросить",
+    "settings.general.backup_help": "В файле нет API-ключей и паролей — задайте их заново на новой машине.",
+    "settings.general.app_data_move": "Перенести данные…",
+    "settings.general.app_data_move_help": "Переносит записи, модели и настройки в другую папку или на другой диск; после этого приложение перезапустится.",
+    "settings.general.app_data_moved": "Данные перенесены. Перезапуск…",
     "settings.general.ui_language": "Язык интерфейса",
     "settings.general.ui_language_help": "Применяется к интерфейсу приложения.",
     "settings.general.inbox": "Inbox",
     "settings.general.inbox_watch": "Отслеживать папку inbox (inbox/)",
     "settings.general.inbox_interval": "Интервал опроса (сек.)",
+    "settings.general.inbox_folders": "Отслеживаемые папки, у каждой своя модель, язык и экспорт (нет ни одной — inbox/ в папке данных)",
+    "settings.general.inbox_add_folder": "Добавить папку…",
+    "settings.general.inbox_default_model": "Модель по умолчанию",
+    "settings.general.inbox_language": "Язык",
+    "settings.general.inbox_no_export": "Без доп. экспорта",
+    "settings.general.inbox_delete_source": "Удалять файл после импорта",
+    "settings.general.inbox_stable_polls": "Импортировать, когда размер не меняется сканирований подряд",
+    "settings.general.inbox_min_age": "и файл старше (сек.)",
+    "settings.general.inbox_waiting": "ждут окончания копирования:",
+    "settings.general.voice_memos": "Импортировать новые записи из Диктофона",
+    "settings.general.voice_memos_path": "Медиатека Диктофона (пусто — найти автоматически)",
+    "settings.general.voice_memos_import": "Импортировать все ещё не импортированные",
+    "settings.general.notifications": "Уведомления",
+    "settings.general.notify_success": "Транскрипция готова",
+    "settings.general.notify_summary": "Саммари готово",
+    "settings.general.notify_failure": "Ошибки",
+    "settings.general.sound_success": "Звук по готовности",
+    "settings.general.sound_failure": "Звук при ошибке",
+    "settings.general.respect_focus": "Молчать в режиме фокусирования",
+    "settings.general.notifications_help": "Клик по уведомлению открывает его job.",
+    "settings.general.power": "Батарея и нагрев",
+    "settings.general.power_mode": "От батареи, в режиме энергосбережения или при нагреве",
+    "settings.general.power_off": "Работать как обычно",
+    "settings.general.power_pause": "Ставить очередь на паузу",
+    "settings.general.power_threads": "Меньше потоков whisper",
+    "settings.general.power_defer": "Откладывать jobs с моделями medium и large",
+    "settings.general.power_thread_count": "Потоков",
+    "settings.general.power_now": "Сейчас:",
+    "settings.general.power_battery": "от батареи",
+    "settings.general.power_ac": "от сети",
+    "settings.general.power_low": "режим энергосбережения",
+    "settings.general.power_hot": "перегрев",
+    "settings.general.tray": "Строка меню",
+    "settings.general.close_to_tray": "При закрытии окна продолжать работу в строке меню",
+    "settings.general.updates": "Обновления",
+    "settings.general.update_version": "Версия",
+    "settings.general.update_check": "Проверить обновления",
+    "settings.general.update_none": "Установлена последняя версия.",
+    "settings.general.update_available": "Доступна:",
+    "settings.general.update_download": "Скачать",
+    "settings.general.update_downloading": "Скачивание…",
+    "settings.general.update_install": "Установить и перезапустить",
+    "settings.general.update_pause_queue": "Поставить очередь на паузу, а не ждать её завершения",
+    "settings.general.update_waiting": "Установка после завершения jobs:",
+    "settings.general.update_installing": "Установка…",
+    "settings.general.update_install_now": "Установить сейчас",
+    "settings.general.update_not_configured": "В этой сборке обновления недоступны.",
+    "settings.general.logs": "Журналы",
+    "settings.general.logs_level_error": "Ошибки",
+    "settings.general.logs_level_warn": "Предупреждения",
+    "settings.general.logs_level_info": "Информация",
+    "settings.general.logs_level_debug": "Отладка",
+    "settings.general.logs_show": "Показать журнал",
+    "settings.general.logs_empty": "Нет записей этого уровня.",
+    "settings.general.support_bundle": "Создать архив для поддержки…",
+    "settings.general.support_bundle_hint": "Архив с журналами, настройками без учётных данных и списком задач без имён файлов и расшифровок.",
+    "settings.general.support_bundle_saved": "Архив для поддержки сохранён:",
+    "settings.general.update_check_failed": "После обновления:",
+    "settings.general.voice_memos_imported": "Импортировано из Диктофона:",
+    "settings.general.inbox_after_import": "После импорта",
+    "settings.general.inbox_keep": "Оставить файл на месте",
+    "settings.general.inbox_move": "Перенести в processed/",
+    "settings.general.inbox_rename": "Добавить .imported к имени",
+    "settings.general.inbox_status": "Режим наблюдения и файлов импортировано с запуска:",
     "settings.transcription.model": "Whisper модель",
     "settings.transcription.language": "Язык распознавания",
     "settings.transcription.timestamps": "Показывать метки времени",
@@ -1015,6 +1229,25 @@ export const STRINGS: Record<Locale, Strings> = {
     "settings.transcription.size": "Размер",
     "settings.transcription.download_done": "Модель скачана.",
     "settings.transcription.download_error": "Ошибка скачивания",
+    "settings.transcription.checksum_mismatch": "Контрольная сумма не совпала — файл повреждён",
+    "settings.transcription.network": "Сеть для загрузок",
+    "settings.transcription.network_help": "Прокси и зеркала для скачивания моделей, whisper и FFmpeg. Сохраните настройки перед загрузкой.",
+    "settings.transcription.download_proxy": "Прокси (http, https, socks5)",
+    "settings.transcription.download_no_proxy": "Без прокси для",
+    "settings.transcription.huggingface_mirror": "Зеркало Hugging Face",
+    "settings.transcription.github_mirror": "Зеркало GitHub",
+    "settings.transcription.download_speed_limit": "Ограничение скорости, КБ/с (0 — без ограничения, действует и на текущие загрузки)",
+    "settings.transcription.installed_models": "Установленные модели",
+    "settings.transcription.models_dir": "Папка моделей",
+    "settings.transcription.models_dir_help": "пусто — app data; общая папка избавляет от дубликатов",
+    "settings.transcription.models_dir_choose": "Выбрать…",
+    "settings.transcription.custom_model_name": "Название своей модели",
+    "settings.transcription.custom_model_url": "URL модели ggml/gguf",
+    "settings.transcription.import_model": "Импорт файла…",
+    "settings.transcription.model_last_used": "использовалась {date}",
+    "settings.transcription.model_never_used": "не использовалась",
+    "settings.transcription.delete_model": "Удалить",
+    "settings.transcription.remove_partial": "Удалить недокачанные файлы",
     "settings.transcription.download_button": "Скачать модель",
     "settings.transcription.binary_url": "Whisper binary URL",
     "settings.transcription.binary_url_help": "Ссылка на macOS arm64 zip (bizenlabs) или официальный релиз.",
@@ -1029,6 +1262,10 @@ export const STRINGS: Record<Locale, Strings> = {
     "settings.transcription.ffmpeg_download": "FFmpeg download",
     "settings.transcription.ffmpeg_download_button": "Скачать FFmpeg",
     "settings.transcription.ffmpeg_download_error": "Ошибка скачивания FFmpeg",
+    "settings.transcription.system_binaries": "Установленные инструменты",
+    "settings.transcription.use_system_binaries": "Использовать ffmpeg и whisper-cli, установленные в системе (PATH, Homebrew), если ничего не скачано",
+    "settings.transcription.detect_system_binaries": "Найти установленные",
+    "settings.transcription.system_binary_unused": "не используется",
     "settings.transcription.ffmpeg_installed": "FFmpeg установлен",
     "settings.transcription.ffmpeg_missing": "FFmpeg отсутствует",
     "settings.transcription.model_installed": "Модель установлена",
@@ -1044,6 +1281,7 @@ export const STRINGS: Record<Locale, Strings> = {
     "language.en": "English",
     "settings.status.saved": "Сохранено",
     "settings.status.failed": "Не удалось сохранить",
+    "settings.status.invalid": "Исправьте отмеченные настройки",
     "wizard.title": "Добро пожаловать в VoiceNote",
     "wizard.step1": "Шаг 1: Выберите папку хранилища Obsidian",
     "wizard.step2": "Шаг 2: Подпапка вывода",
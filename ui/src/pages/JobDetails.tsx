@@ -8,6 +8,7 @@ import TranscriptPanel from "../components/jobs/TranscriptPanel";
 import SummaryPanel from "../components/jobs/SummaryPanel";
 import ConsolePanel from "../components/console/ConsolePanel";
 import { getJobStatusLabel, getJobStatusTone } from "../components/jobs/statusLabels";
+import { renameJob, setJobNotes } from "../api/client";
 import { useI18n } from "../i18n/I18nProvider";
 
 type Props = {
@@ -40,9 +41,20 @@ export default function JobDetails({
   const [activeTab, setActiveTab] = useState("transcript");
   const [menuOpen, setMenuOpen] = useState(false);
   const [exportStatus, setExportStatus] = useState<string | null>(null);
+  const [titleDraft, setTitleDraft] = useState<string | null>(null);
   const menuRef = useRef<HTMLDivElement | null>(null);
 
-  const title = useMemo(() => (job ? humanizeFilename(job.filename) : ""), [job]);
+  const title = useMemo(
+    () => (job ? job.display_title || job.title || humanizeFilename(job.filename) : ""),
+    [job]
+  );
+
+  const commitTitle = async () => {
+    if (!job || titleDraft === null) return;
+    setTitleDraft(null);
+    // The job:updated event refreshes the header; an empty title restores the automatic one.
+    await renameJob(job.id, titleDraft).catch(() => undefined);
+  };
 
   useEffect(() => {
     if (!menuOpen) return;
@@ -92,7 +104,28 @@ export default function JobDetails({
           ← {t("sidebar.jobs")}
         </Button>
         <div>
-          <div className="list-row-title">{title}</div>
+          {titleDraft === null ? (
+            <div className="list-row-title" title={t("details.rename")} onDoubleClick={() => setTitleDraft(title)}>
+              {title}
+            </div>
+          ) : (
+            <input
+              className="input"
+              autoFocus
+              value={titleDraft}
+              placeholder={humanizeFilename(job.filename)}
+              onChange={(e) => setTitleDraft(e.target.value)}
+              onBlur={commitTitle}
+              onKeyDown={(e) => {
+                if (e.key === "Enter") void commitTitle();
+                if (e.key === "Escape") {
+                  // Keep Esc from also closing the details view.
+                  e.stopPropagation();
+                  setTitleDraft(null);
+                }
+              }}
+            />
+          )}
           <div className="details-meta">
             {job.status === "running" && (
               <div style={{ minWidth: 160 }}>
@@ -172,6 +205,7 @@ export default function JobDetails({
           tabs={[
             { id: "transcript", label: t("details.tabs.transcript") },
             { id: "summary", label: t("details.tabs.summary") },
+            { id: "notes", label: t("details.tabs.notes") },
             { id: "console", label: t("details.tabs.console") },
           ]}
           activeId={activeTab}
@@ -187,6 +221,18 @@ export default function JobDetails({
           <TranscriptPanel jobId={jobId} jobStatus={job.status} />
         )}
         {activeTab === "summary" && <SummaryPanel jobId={jobId} />}
+        {activeTab === "notes" && (
+          <textarea
+            key={jobId}
+            className="textarea"
+            rows={10}
+            defaultValue={job.notes ?? ""}
+            placeholder={t("details.notes_placeholder")}
+            onBlur={(e) => {
+              if (e.target.value !== (job.notes ?? "")) void setJobNotes(job.id, e.target.value);
+            }}
+          />
+        )}
         {activeTab === "console" && <ConsolePanel job={job} logs={job.logs || []} />}
       </div>
     </div>
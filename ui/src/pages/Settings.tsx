@@ -1,8 +1,16 @@
 import React, { forwardRef, useEffect, useImperativeHandle, useMemo, useState } from "react";
 import { open } from "@tauri-apps/plugin-dialog";
+import { listen } from "@tauri-apps/api/event";
 import {
   getConfig,
   updateConfig,
+  validateConfig,
+  switchProfile,
+  deleteProfile,
+  exportSettings,
+  importSettings,
+  moveAppData,
+  resetToDefaults,
   getModelSize,
   getModelDownloadStatus,
   startModelDownload,
@@ -12,9 +20,44 @@ import {
   getModelInstalled,
   getFfmpegDownloadStatus,
   getFfmpegInstalled,
+  detectSystemBinaries,
   startFfmpegDownload,
+  cancelModelDownload,
+  cancelWhisperDownload,
+  cancelFfmpegDownload,
+  listInstalledModels,
+  deleteModel,
+  removePartialModelDownloads,
+  listCustomModels,
+  importModelFromPath,
+  applyJobEncryption,
+  getWatcherStatus,
+  importVoiceMemos,
+  getSystemStatus,
+  getAppUpdateStatus,
+  checkForAppUpdate,
+  getAppLogs,
+  createSupportBundle,
+  downloadAppUpdate,
+  installAppUpdate,
+  installAppUpdateNow,
 } from "../api/client";
-import type { AppConfig, ModelDownloadStatus } from "../api/types";
+import type {
+  AppUpdateStatus,
+  AppLogEntry,
+  AppConfig,
+  AppDataMoveProgress,
+  ModelImportProgress,
+  ConfigFieldError,
+  CustomModel,
+  InstalledModel,
+  ModelDownloadStatus,
+  ResetScope,
+  SystemBinaries,
+  SystemStatus,
+  WatchFolder,
+  WatcherStatus,
+} from "../api/types";
 import Card from "../components/ui/Card";
 import Button from "../components/ui/Button";
 import ProgressBar from "../components/ui/ProgressBar";
@@ -51,6 +94,17 @@ const DEFAULT_WHISPER_URL =
   "https://github.com/bizenlabs/whisper-cpp-macos-bin/releases/latest";
 const DEFAULT_FFMPEG_URL =
   "https://github.com/ravaru/voicenoteapp/releases/latest/download/ffmpeg-macos-arm64-lgpl.zip";
+// Fields whose validation errors render next to their input; the rest are listed at the top.
+const INLINE_ERROR_FIELDS = [
+  "vault_path",
+  "ffmpeg_binary_url",
+  "whisper_binary_url",
+  "model_size",
+  "download_proxy",
+  "huggingface_mirror",
+  "github_mirror",
+  "ollama_base_url",
+];
 const getDefaultLanguage = (): "ru" | "en" => {
   const system = navigator.language?.toLowerCase() ?? "en";
   return system.startsWith("ru") ? "ru" : "en";
@@ -74,6 +128,7 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
   const [cfg, setCfg] = useState<AppConfig | null>(null);
   const [initialCfg, setInitialCfg] = useState<AppConfig | null>(null);
   const [status, setStatus] = useState<string | null>(null);
+  const [fieldErrors, setFieldErrors] = useState<ConfigFieldError[]>([]);
   const [activeTab, setActiveTab] = useState("general");
   const [modelSizeBytes, setModelSizeBytes] = useState<number | null>(null);
   const [downloadStatus, setDownloadStatus] = useState<ModelDownloadStatus | null>(null);
@@ -85,26 +140,54 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
   const [ffmpegStatus, setFfmpegStatus] = useState<ModelDownloadStatus | null>(null);
   const [ffmpegError, setFfmpegError] = useState<string | null>(null);
   const [ffmpegInstalled, setFfmpegInstalled] = useState(false);
+  const [installedModels, setInstalledModels] = useState<InstalledModel[]>([]);
+  const [customModels, setCustomModels] = useState<CustomModel[]>([]);
+  const [customName, setCustomName] = useState("");
+  const [customUrl, setCustomUrl] = useState("");
+  const [newProfile, setNewProfile] = useState("");
+  const [resetScope, setResetScope] = useState<ResetScope>("transcription");
+  const [moveProgress, setMoveProgress] = useState<AppDataMoveProgress | null>(null);
+  const [importProgress, setImportProgress] = useState<ModelImportProgress | null>(null);
+  const [watcherStatus, setWatcherStatus] = useState<WatcherStatus | null>(null);
+  const [systemStatus, setSystemStatus] = useState<SystemStatus | null>(null);
+  const [appUpdate, setAppUpdate] = useState<AppUpdateStatus | null>(null);
+  const [systemBinaries, setSystemBinaries] = useState<SystemBinaries | null>(null);
+  const [updateChecked, setUpdateChecked] = useState(false);
+  const [appLogs, setAppLogs] = useState<AppLogEntry[] | null>(null);
+  const [appLogLevel, setAppLogLevel] = useState("warn");
+  const [pauseQueueForUpdate, setPauseQueueForUpdate] = useState(false);
   const modelSizeRef = React.useRef<string | null>(null);
 
+  const loadConfig = (data: AppConfig) => {
+    const supported = data.language === "ru" || data.language === "en";
+    const normalized = supported ? data.language : getDefaultLanguage();
+    const storedPrompt = localStorage.getItem(PROMPT_STORAGE_KEY) || "";
+    const next = {
+      ...data,
+      language: normalized,
+      summary_prompt: data.summary_prompt || storedPrompt || DEFAULT_SUMMARY_PROMPT,
+      whisper_binary_url: data.whisper_binary_url || DEFAULT_WHISPER_URL,
+      ffmpeg_binary_url: data.ffmpeg_binary_url || DEFAULT_FFMPEG_URL,
+    };
+    if (next.summary_prompt) {
+      localStorage.setItem(PROMPT_STORAGE_KEY, next.summary_prompt);
+    }
+    setCfg(next);
+    setInitialCfg(next);
+  };
+
   useEffect(() => {
-    getConfig().then((data) => {
-      const supported = data.language === "ru" || data.language === "en";
-      const normalized = supported ? data.language : getDefaultLanguage();
-      const storedPrompt = localStorage.getItem(PROMPT_STORAGE_KEY) || "";
-      const next = {
-        ...data,
-        language: normalized,
-        summary_prompt: data.summary_prompt || storedPrompt || DEFAULT_SUMMARY_PROMPT,
-        whisper_binary_url: data.whisper_binary_url || DEFAULT_WHISPER_URL,
-        ffmpeg_binary_url: data.ffmpeg_binary_url || DEFAULT_FFMPEG_URL,
-      };
-      if (next.summary_prompt) {
-        localStorage.setItem(PROMPT_STORAGE_KEY, next.summary_prompt);
-      }
-      setCfg(next);
-      setInitialCfg(next);
+    getConfig().then(loadConfig);
+    getWatcherStatus().then(setWatcherStatus).catch(() => setWatcherStatus(null));
+    getSystemStatus().then(setSystemStatus).catch(() => setSystemStatus(null));
+    getAppUpdateStatus().then(setAppUpdate).catch(() => setAppUpdate(null));
+    let unlisten: (() => void) | null = null;
+    listen<AppUpdateStatus>("update:status", (event) => setAppUpdate(event.payload)).then((fn) => {
+      unlisten = fn;
     });
+    return () => {
+      if (unlisten) unlisten();
+    };
   }, []);
 
   useEffect(() => {
@@ -139,6 +222,31 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
       .catch(() => setModelInstalled(false));
   }, [cfg?.model_size]);
 
+  const refreshInstalledModels = () => {
+    listInstalledModels()
+      .then(setInstalledModels)
+      .catch(() => setInstalledModels([]));
+    listCustomModels()
+      .then(setCustomModels)
+      .catch(() => setCustomModels([]));
+  };
+
+  useEffect(() => {
+    if (!downloadStatus || downloadStatus.state === "downloading") return;
+    refreshInstalledModels();
+  }, [downloadStatus?.state]);
+
+  const removeModel = async (modelSize: string) => {
+    setDownloadError(null);
+    try {
+      await deleteModel(modelSize);
+      if (cfg && modelSize === cfg.model_size) setModelInstalled(false);
+    } catch (e) {
+      setDownloadError(typeof e === "string" ? e : e instanceof Error ? e.message : JSON.stringify(e));
+    }
+    refreshInstalledModels();
+  };
+
   useEffect(() => {
     if (!cfg || downloadStatus?.state !== "downloading") return;
     const timer = setInterval(() => {
@@ -202,6 +310,19 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
     onDirtyChange?.(dirty);
   }, [dirty, onDirtyChange]);
 
+  // Changes saved elsewhere (another window, an edit to config.json) replace the form unless
+  // it has unsaved edits of its own.
+  useEffect(() => {
+    if (dirty) return;
+    let unlisten: (() => void) | null = null;
+    listen<AppConfig>("config:updated", (event) => loadConfig(event.payload)).then((fn) => {
+      unlisten = fn;
+    });
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, [dirty]);
+
   useEffect(() => {
     if (!status) return;
     const timer = setTimeout(() => {
@@ -220,6 +341,12 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
           ? cfg.summary_prompt
           : storedPrompt || DEFAULT_SUMMARY_PROMPT;
       const payload = { ...cfg, summary_prompt: promptValue };
+      const errors = await validateConfig(payload);
+      setFieldErrors(errors);
+      if (errors.some((error) => !error.warning)) {
+        setStatus(t("settings.status.invalid"));
+        return;
+      }
       const updated = await updateConfig(payload);
       const normalized = {
         ...updated,
@@ -249,10 +376,123 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
     [dirty]
   );
 
+  const fieldError = (field: string) => {
+    const error = fieldErrors.find((item) => item.field === field);
+    return error ? (
+      <div className={error.warning ? "field-warning" : "field-error"}>{error.message}</div>
+    ) : null;
+  };
+
   if (!cfg) {
     return <div className="text-muted">{t("app.loading")}</div>;
   }
 
+  const pickModelsDir = async () => {
+    const selected = await open({ directory: true, multiple: false });
+    if (typeof selected === "string") {
+      setCfg({ ...cfg, models_dir: selected });
+    }
+  };
+
+  const runAppUpdate = async (action: () => Promise<AppUpdateStatus>) => {
+    setStatus(null);
+    try {
+      setAppUpdate(await action());
+    } catch (err) {
+      setStatus(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  // Profiles switch on the backend, so unsaved edits must be saved or discarded first.
+  const applyProfile = async (action: Promise<AppConfig>) => {
+    setStatus(null);
+    try {
+      const next = await action;
+      setCfg(next);
+      setInitialCfg(next);
+      setFieldErrors([]);
+      onSaved?.(next);
+    } catch (err) {
+      setStatus(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const loadAppLogs = async (level: string) => {
+    setAppLogLevel(level);
+    try {
+      setAppLogs(await getAppLogs(level, 200));
+    } catch (err) {
+      setStatus(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const saveSupportBundle = async () => {
+    const selected = await open({ directory: true, multiple: false });
+    if (typeof selected !== "string") return;
+    setStatus(null);
+    try {
+      const path = await createSupportBundle(selected);
+      setStatus(`${t("settings.general.support_bundle_saved")} ${path}`);
+    } catch (err) {
+      setStatus(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const exportSettingsToFolder = async () => {
+    const selected = await open({ directory: true, multiple: false });
+    if (typeof selected !== "string") return;
+    try {
+      const path = await exportSettings(`${selected}/voicenote-settings.json`);
+      setStatus(`${t("settings.general.settings_exported")} ${path}`);
+    } catch (err) {
+      setStatus(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const importSettingsFromFile = async () => {
+    const selected = await open({ multiple: false, filters: [{ name: "JSON", extensions: ["json"] }] });
+    if (typeof selected !== "string") return;
+    setStatus(null);
+    try {
+      const result = await importSettings(selected);
+      setCfg(result.config);
+      setInitialCfg(result.config);
+      setFieldErrors(result.errors);
+      onSaved?.(result.config);
+      setStatus(t(result.errors.length > 0 ? "settings.status.invalid" : "settings.status.saved"));
+    } catch (err) {
+      setStatus(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const applyEncryptionToJobs = async () => {
+    setStatus(null);
+    try {
+      const changed = await applyJobEncryption();
+      setStatus(`${t("settings.general.encryption_applied")} ${changed}`);
+    } catch (err) {
+      setStatus(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const moveAppDataToFolder = async () => {
+    const selected = await open({ directory: true, multiple: false });
+    if (typeof selected !== "string") return;
+    setStatus(null);
+    const unlisten = await listen<AppDataMoveProgress>("app_data:move", (event) =>
+      setMoveProgress(event.payload)
+    );
+    try {
+      await moveAppData(selected);
+      setStatus(t("settings.general.app_data_moved"));
+    } catch (err) {
+      setMoveProgress(null);
+      setStatus(err instanceof Error ? err.message : String(err));
+    } finally {
+      unlisten();
+    }
+  };
+
   const pickVault = async () => {
     const selected = await open({ directory: true, multiple: false });
     if (typeof selected === "string") {
@@ -260,6 +500,65 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
     }
   };
 
+  const updateWatchFolder = (index: number, patch: Partial<WatchFolder>) => {
+    setCfg({
+      ...cfg,
+      watch_folders: (cfg.watch_folders ?? []).map((folder, i) => (i === index ? { ...folder, ...patch } : folder)),
+    });
+  };
+
+  const addWatchFolder = async () => {
+    const selected = await open({ directory: true, multiple: false });
+    if (typeof selected === "string") {
+      setCfg({ ...cfg, watch_folders: [...(cfg.watch_folders ?? []), { path: selected, delete_source: false }] });
+    }
+  };
+
+  const importAllVoiceMemos = async () => {
+    try {
+      const jobs = await importVoiceMemos(true);
+      setStatus(`${t("settings.general.voice_memos_imported")} ${jobs.length}`);
+    } catch (err) {
+      setStatus(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const startCustomDownload = async () => {
+    setDownloadError(null);
+    try {
+      const next = await startModelDownload(cfg.model_size, customUrl.trim(), customName.trim() || undefined);
+      setDownloadStatus(next);
+      setCfg({ ...cfg, model_size: next.model_size as AppConfig["model_size"] });
+      setCustomUrl("");
+      setCustomName("");
+    } catch (e) {
+      setDownloadError(typeof e === "string" ? e : e instanceof Error ? e.message : JSON.stringify(e));
+    }
+  };
+
+  const importCustomModel = async () => {
+    const selected = await open({
+      multiple: false,
+      filters: [{ name: "ggml / gguf", extensions: ["bin", "gguf"] }],
+    });
+    if (typeof selected !== "string") return;
+    setDownloadError(null);
+    const unlisten = await listen<ModelImportProgress>("model:import", (event) =>
+      setImportProgress(event.payload)
+    );
+    try {
+      const model = await importModelFromPath(selected, customName.trim() || undefined);
+      setCfg({ ...cfg, model_size: model.id as AppConfig["model_size"] });
+      setCustomName("");
+      refreshInstalledModels();
+    } catch (e) {
+      setDownloadError(typeof e === "string" ? e : e instanceof Error ? e.message : JSON.stringify(e));
+    } finally {
+      unlisten();
+      setImportProgress(null);
+    }
+  };
+
   const startDownload = async () => {
     setDownloadError(null);
     try {
@@ -352,11 +651,13 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
     downloading: t("summary.status.running"),
     done: t("summary.status.done"),
     error: t("summary.status.error"),
+    checksum_mismatch: t("summary.status.error"),
+    cancelled: t("jobs.status.cancelled"),
   }[downloadState] ?? downloadState;
   const downloadStateColor =
     downloadState === "done"
       ? "#4ade80"
-      : downloadState === "error"
+      : downloadState === "error" || downloadState === "checksum_mismatch"
       ? "#f87171"
       : downloadState === "downloading"
       ? "#facc15"
@@ -377,8 +678,76 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
           />
           {status && <div className="text-muted">{status}</div>}
         </div>
+        {fieldErrors
+          .filter((error) => !INLINE_ERROR_FIELDS.includes(error.field))
+          .map((error) => (
+            <div
+              key={`${error.field}:${error.message}`}
+              className={error.warning ? "field-warning" : "field-error"}
+            >
+              {error.message}
+            </div>
+          ))}
         {activeTab === "general" && (
           <>
+            <Card>
+              <div className="section-title">{t("settings.general.profile")}</div>
+              <div className="form-row" style={{ display: "flex", gap: 12, alignItems: "flex-end" }}>
+                <label style={{ flex: 1 }}>
+                  {t("settings.general.profile_active")}
+                  <select
+                    className="select"
+                    value={cfg.active_profile ?? "default"}
+                    disabled={dirty}
+                    onChange={(e) => void applyProfile(switchProfile(e.target.value))}
+                  >
+                    {[cfg.active_profile ?? "default", ...(cfg.profiles ?? []).map((p) => p.name)].map(
+                      (name) => (
+                        <option key={name} value={name}>
+                          {name}
+                        </option>
+                      )
+                    )}
+                  </select>
+                </label>
+                <label style={{ flex: 1 }}>
+                  {t("settings.general.profile_new")}
+                  <input
+                    className="input"
+                    value={newProfile}
+                    onChange={(e) => setNewProfile(e.target.value)}
+                    placeholder="work"
+                  />
+                </label>
+                <Button
+                  variant="secondary"
+                  disabled={dirty || !newProfile.trim()}
+                  onClick={() => {
+                    void applyProfile(switchProfile(newProfile.trim()));
+                    setNewProfile("");
+                  }}
+                >
+                  {t("settings.general.profile_create")}
+                </Button>
+              </div>
+              {(cfg.profiles ?? []).length > 0 && (
+                <div className="form-row" style={{ display: "flex", gap: 8, flexWrap: "wrap" }}>
+                  {(cfg.profiles ?? []).map((profile) => (
+                    <Button
+                      key={profile.name}
+                      variant="secondary"
+                      disabled={dirty}
+                      onClick={() => void applyProfile(deleteProfile(profile.name))}
+                    >
+                      {t("settings.general.profile_delete")} {profile.name}
+                    </Button>
+                  ))}
+                </div>
+              )}
+              <div className="text-muted">
+                {dirty ? t("settings.general.profile_unsaved") : t("settings.general.profile_help")}
+              </div>
+            </Card>
             <Card>
               <div className="section-title">{t("settings.general.obsidian")}</div>
               <div className="form-row">
@@ -394,6 +763,7 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
                       {t("settings.general.browse")}
                     </Button>
                   </div>
+                  {fieldError("vault_path")}
                 </label>
               </div>
               <div className="form-row">
@@ -430,7 +800,477 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
                 {t("settings.general.ui_language_help")}
               </div>
             </Card>
-
+            <Card>
+              <div className="section-title">{t("settings.general.notifications")}</div>
+              <div className="form-row" style={{ display: "flex", gap: 16, flexWrap: "wrap" }}>
+                {(
+                  [
+                    ["notify_on_success", "settings.general.notify_success"],
+                    ["notify_on_summary", "settings.general.notify_summary"],
+                    ["notify_on_failure", "settings.general.notify_failure"],
+                    ["sound_on_success", "settings.general.sound_success"],
+                    ["sound_on_failure", "settings.general.sound_failure"],
+                    ["respect_do_not_disturb", "settings.general.respect_focus"],
+                  ] as const
+                ).map(([key, label]) => (
+                  <label key={key}>
+                    <input
+                      type="checkbox"
+                      checked={cfg[key] ?? false}
+                      onChange={(e) => setCfg({ ...cfg, [key]: e.target.checked })}
+                    />{" "}
+                    {t(label)}
+                  </label>
+                ))}
+              </div>
+              <div className="text-muted">{t("settings.general.notifications_help")}</div>
+            </Card>
+            <Card>
+              <div className="section-title">{t("settings.general.power")}</div>
+              <div className="form-row" style={{ display: "flex", gap: 16, flexWrap: "wrap" }}>
+                <label>
+                  {t("settings.general.power_mode")}
+                  <select
+                    className="select"
+                    value={cfg.power_saving_mode ?? "off"}
+                    onChange={(e) =>
+                      setCfg({ ...cfg, power_saving_mode: e.target.value as AppConfig["power_saving_mode"] })
+                    }
+                  >
+                    <option value="off">{t("settings.general.power_off")}</option>
+                    <option value="pause">{t("settings.general.power_pause")}</option>
+                    <option value="reduce_threads">{t("settings.general.power_threads")}</option>
+                    <option value="defer_large">{t("settings.general.power_defer")}</option>
+                  </select>
+                </label>
+                {cfg.power_saving_mode === "reduce_threads" && (
+                  <label>
+                    {t("settings.general.power_thread_count")}
+                    <input
+                      className="input"
+                      type="number"
+                      min={1}
+                      value={cfg.power_saving_threads ?? 2}
+                      onChange={(e) => setCfg({ ...cfg, power_saving_threads: Number(e.target.value) })}
+                    />
+                  </label>
+                )}
+              </div>
+              {fieldError("power_saving_mode")}
+              {fieldError("power_saving_threads")}
+              {systemStatus && (
+                <div className="text-muted">
+                  {t("settings.general.power_now")}{" "}
+                  {[
+                    systemStatus.power.on_battery
+                      ? `${t("settings.general.power_battery")}${
+                          systemStatus.power.battery_percent != null ? ` ${systemStatus.power.battery_percent}%` : ""
+                        }`
+                      : t("settings.general.power_ac"),
+                    systemStatus.power.low_power_mode ? t("settings.general.power_low") : null,
+                    systemStatus.power.thermal_pressure ? t("settings.general.power_hot") : null,
+                  ]
+                    .filter(Boolean)
+                    .join(", ")}
+                </div>
+              )}
+            </Card>
+            <Card>
+              <div className="section-title">{t("settings.general.tray")}</div>
+              <div className="form-row">
+                <label>
+                  <input
+                    type="checkbox"
+                    checked={cfg.close_to_tray ?? false}
+                    onChange={(e) => setCfg({ ...cfg, close_to_tray: e.target.checked })}
+                  />{" "}
+                  {t("settings.general.close_to_tray")}
+                </label>
+              </div>
+            </Card>
+            {appUpdate && (
+              <Card>
+                <div className="section-title">{t("settings.general.updates")}</div>
+                <div className="text-muted">
+                  {t("settings.general.update_version")} {appUpdate.current_version}
+                </div>
+                {appUpdate.verification
+                  .filter((check) => check.status === "error")
+                  .map((check) => (
+                    <div key={check.id} className="field-error">
+                      {t("settings.general.update_check_failed")} {check.message}
+                    </div>
+                  ))}
+                {!appUpdate.configured && (
+                  <div className="text-muted">{t("settings.general.update_not_configured")}</div>
+                )}
+                {appUpdate.configured && (appUpdate.state === "idle" || appUpdate.state === "error") && (
+                  <div className="form-row">
+                    <Button
+                      variant="secondary"
+                      onClick={() => runAppUpdate(checkForAppUpdate).then(() => setUpdateChecked(true))}
+                    >
+                      {t("settings.general.update_check")}
+                    </Button>
+                    {updateChecked && appUpdate.state === "idle" && (
+                      <span className="text-muted"> {t("settings.general.update_none")}</span>
+                    )}
+                    {appUpdate.error && <div className="field-error">{appUpdate.error}</div>}
+                  </div>
+                )}
+                {appUpdate.version && appUpdate.state !== "idle" && appUpdate.state !== "error" && (
+                  <div className="form-row">
+                    <div>
+                      {t("settings.general.update_available")} {appUpdate.version}
+                    </div>
+                    {appUpdate.notes && <div className="text-muted">{appUpdate.notes}</div>}
+                  </div>
+                )}
+                {appUpdate.state === "available" && (
+                  <Button variant="secondary" onClick={() => runAppUpdate(downloadAppUpdate)}>
+                    {t("settings.general.update_download")}
+                  </Button>
+                )}
+                {appUpdate.state === "downloading" && (
+                  <div className="text-muted">
+                    {t("settings.general.update_downloading")}{" "}
+                    {appUpdate.total_bytes
+                      ? `${Math.round((appUpdate.downloaded_bytes / appUpdate.total_bytes) * 100)}%`
+                      : `${Math.round(appUpdate.downloaded_bytes / 1024 / 1024)} MB`}
+                  </div>
+                )}
+                {appUpdate.state === "ready" && (
+                  <div className="form-row">
+                    <label>
+                      <input
+                        type="checkbox"
+                        checked={pauseQueueForUpdate}
+                        onChange={(e) => setPauseQueueForUpdate(e.target.checked)}
+                      />{" "}
+                      {t("settings.general.update_pause_queue")}
+                    </label>
+                    <Button variant="primary" onClick={() => runAppUpdate(() => installAppUpdate(pauseQueueForUpdate))}>
+                      {t("settings.general.update_install")}
+                    </Button>
+                  </div>
+                )}
+                {appUpdate.state === "waiting_for_jobs" && (
+                  <div className="text-muted">
+                    {t("settings.general.update_waiting")} {appUpdate.blocking_jobs}{" "}
+                    <Button variant="secondary" onClick={() =>
+                        installAppUpdateNow().catch((err) => setStatus(err instanceof Error ? err.message : String(err)))
+                      }>
+                      {t("settings.general.update_install_now")}
+                    </Button>
+                  </div>
+                )}
+                {appUpdate.state === "installing" && (
+                  <div className="text-muted">{t("settings.general.update_installing")}</div>
+                )}
+              </Card>
+            )}
+            <Card>
+              <div className="section-title">{t("settings.general.logs")}</div>
+              <div className="form-row" style={{ display: "flex", gap: 8 }}>
+                <select value={appLogLevel} onChange={(e) => loadAppLogs(e.target.value)}>
+                  <option value="error">{t("settings.general.logs_level_error")}</option>
+                  <option value="warn">{t("settings.general.logs_level_warn")}</option>
+                  <option value="info">{t("settings.general.logs_level_info")}</option>
+                  <option value="debug">{t("settings.general.logs_level_debug")}</option>
+                </select>
+                <Button variant="secondary" onClick={() => loadAppLogs(appLogLevel)}>
+                  {t("settings.general.logs_show")}
+                </Button>
+                <Button variant="secondary" onClick={saveSupportBundle}>
+                  {t("settings.general.support_bundle")}
+                </Button>
+              </div>
+              <div className="text-muted">{t("settings.general.support_bundle_hint")}</div>
+              {appLogs && appLogs.length === 0 && <div className="text-muted">{t("settings.general.logs_empty")}</div>}
+              {appLogs && appLogs.length > 0 && (
+                <div className="console-body">
+                  {appLogs.map((entry, idx) => (
+                    <div key={`${idx}-${entry.timestamp}`} className="console-line">
+                      {entry.timestamp} {entry.level} {entry.job_id ? `[${entry.job_id}] ` : ""}
+                      {entry.message}
+                    </div>
+                  ))}
+                </div>
+              )}
+            </Card>
+            <Card>
+              <div className="section-title">{t("settings.general.backup")}</div>
+              <div className="form-row" style={{ display: "flex", gap: 8 }}>
+                <Button variant="secondary" onClick={exportSettingsToFolder}>
+                  {t("settings.general.settings_export")}
+                </Button>
+                <Button variant="secondary" onClick={importSettingsFromFile} disabled={dirty}>
+                  {t("settings.general.settings_import")}
+                </Button>
+              </div>
+              <div className="form-row" style={{ display: "flex", gap: 12, alignItems: "flex-end" }}>
+                <label style={{ flex: 1 }}>
+                  {t("settings.general.reset_scope")}
+                  <select
+                    className="select"
+                    value={resetScope}
+                    onChange={(e) => setResetScope(e.target.value as ResetScope)}
+                  >
+                    <option value="transcription">{t("settings.tabs.transcription")}</option>
+                    <option value="summarization">{t("settings.tabs.summary")}</option>
+                    <option value="export">{t("settings.general.reset_export")}</option>
+                    <option value="all">{t("settings.general.reset_all")}</option>
+                  </select>
+                </label>
+                <Button
+                  variant="secondary"
+                  disabled={dirty}
+                  onClick={() => void applyProfile(resetToDefaults(resetScope))}
+                >
+                  {t("settings.general.reset")}
+                </Button>
+              </div>
+              <div className="text-muted">{t("settings.general.backup_help")}</div>
+              <div className="form-row" style={{ display: "flex", gap: 8, alignItems: "center" }}>
+                <Button
+                  variant="secondary"
+                  onClick={moveAppDataToFolder}
+                  disabled={dirty || (moveProgress !== null && moveProgress.stage !== "done")}
+                >
+                  {t("settings.general.app_data_move")}
+                </Button>
+                {moveProgress && (
+                  <div style={{ flex: 1 }}>
+                    <ProgressBar
+                      value={
+                        moveProgress.total_bytes > 0
+                          ? (moveProgress.copied_bytes / moveProgress.total_bytes) * 100
+                          : 0
+                      }
+                    />
+                  </div>
+                )}
+              </div>
+              <div className="text-muted">{t("settings.general.app_data_move_help")}</div>
+              <div className="form-row">
+                <label>
+                  {t("settings.general.trash_retention")}
+                  <input
+                    className="input"
+                    type="number"
+                    min={0}
+                    value={cfg.trash_retention_days ?? 30}
+                    onChange={(e) =>
+                      setCfg({ ...cfg, trash_retention_days: Math.max(0, Number(e.target.value) || 0) })
+                    }
+                  />
+                </label>
+              </div>
+            </Card>
+            <Card>
+              <div className="section-title">{t("settings.general.inbox")}</div>
+              <div className="form-row" style={{ display: "flex", gap: 16, flexWrap: "wrap", alignItems: "center" }}>
+                <label>
+                  <input
+                    type="checkbox"
+                    checked={cfg.watch_inbox_enabled}
+                    onChange={(e) => setCfg({ ...cfg, watch_inbox_enabled: e.target.checked })}
+                  />{" "}
+                  {t("settings.general.inbox_watch")}
+                </label>
+                <label>
+                  {t("settings.general.inbox_interval")}
+                  <input
+                    className="input"
+                    type="number"
+                    min={1}
+                    value={cfg.inbox_poll_seconds}
+                    onChange={(e) =>
+                      setCfg({ ...cfg, inbox_poll_seconds: Math.max(1, Number(e.target.value) || 1) })
+                    }
+                  />
+                </label>
+                {fieldError("inbox_poll_seconds")}
+              </div>
+              <div className="form-row" style={{ display: "flex", gap: 16, flexWrap: "wrap" }}>
+                <label>
+                  {t("settings.general.inbox_stable_polls")}
+                  <input
+                    className="input"
+                    type="number"
+                    min={0}
+                    value={cfg.inbox_stable_polls ?? 2}
+                    onChange={(e) =>
+                      setCfg({ ...cfg, inbox_stable_polls: Math.max(0, Number(e.target.value) || 0) })
+                    }
+                  />
+                </label>
+                <label>
+                  {t("settings.general.inbox_min_age")}
+                  <input
+                    className="input"
+                    type="number"
+                    min={0}
+                    value={cfg.inbox_min_age_seconds ?? 5}
+                    onChange={(e) =>
+                      setCfg({ ...cfg, inbox_min_age_seconds: Math.max(0, Number(e.target.value) || 0) })
+                    }
+                  />
+                </label>
+              </div>
+              <div className="text-muted">{t("settings.general.inbox_folders")}</div>
+              {(cfg.watch_folders ?? []).map((folder, index) => (
+                <div
+                  key={index}
+                  className="form-row"
+                  style={{ display: "flex", gap: 8, flexWrap: "wrap", alignItems: "center" }}
+                >
+                  <input
+                    className="input"
+                    style={{ flex: 1, minWidth: 200 }}
+                    value={folder.path}
+                    onChange={(e) => updateWatchFolder(index, { path: e.target.value })}
+                  />
+                  <select
+                    className="select"
+                    value={folder.model_size ?? ""}
+                    onChange={(e) => updateWatchFolder(index, { model_size: e.target.value || null })}
+                  >
+                    <option value="">{t("settings.general.inbox_default_model")}</option>
+                    {["tiny", "base", "small", "medium", "large-v3", ...customModels.map((model) => model.id)].map(
+                      (size) => (
+                        <option key={size} value={size}>
+                          {size}
+                        </option>
+                      )
+                    )}
+                  </select>
+                  <input
+                    className="input"
+                    style={{ width: 80 }}
+                    placeholder={t("settings.general.inbox_language")}
+                    value={folder.language ?? ""}
+                    onChange={(e) => updateWatchFolder(index, { language: e.target.value || null })}
+                  />
+                  <select
+                    className="select"
+                    value={folder.export_target ?? ""}
+                    onChange={(e) => updateWatchFolder(index, { export_target: e.target.value || null })}
+                  >
+                    <option value="">{t("settings.general.inbox_no_export")}</option>
+                    {(cfg.export_targets ?? []).map((target) => (
+                      <option key={target.id} value={target.id}>
+                        {target.name || target.id}
+                      </option>
+                    ))}
+                  </select>
+                  <label>
+                    <input
+                      type="checkbox"
+                      checked={folder.delete_source}
+                      onChange={(e) => updateWatchFolder(index, { delete_source: e.target.checked })}
+                    />{" "}
+                    {t("settings.general.inbox_delete_source")}
+                  </label>
+                  <Button
+                    variant="secondary"
+                    onClick={() =>
+                      setCfg({ ...cfg, watch_folders: (cfg.watch_folders ?? []).filter((_, i) => i !== index) })
+                    }
+                  >
+                    {t("settings.general.profile_delete")}
+                  </Button>
+                </div>
+              ))}
+              <div className="form-row">
+                <Button variant="secondary" onClick={addWatchFolder}>
+                  {t("settings.general.inbox_add_folder")}
+                </Button>
+                {fieldError("watch_folders")}
+              </div>
+              <div className="form-row">
+                <label>
+                  {t("settings.general.inbox_after_import")}
+                  <select
+                    className="select"
+                    value={cfg.inbox_after_import ?? "keep"}
+                    onChange={(e) =>
+                      setCfg({ ...cfg, inbox_after_import: e.target.value as AppConfig["inbox_after_import"] })
+                    }
+                  >
+                    <option value="keep">{t("settings.general.inbox_keep")}</option>
+                    <option value="move">{t("settings.general.inbox_move")}</option>
+                    <option value="rename">{t("settings.general.inbox_rename")}</option>
+                  </select>
+                </label>
+                {fieldError("inbox_after_import")}
+              </div>
+              <div className="form-row" style={{ display: "flex", gap: 8, flexWrap: "wrap", alignItems: "center" }}>
+                <label>
+                  <input
+                    type="checkbox"
+                    checked={cfg.voice_memos_import_enabled ?? false}
+                    onChange={(e) => setCfg({ ...cfg, voice_memos_import_enabled: e.target.checked })}
+                  />{" "}
+                  {t("settings.general.voice_memos")}
+                </label>
+                <input
+                  className="input"
+                  style={{ flex: 1, minWidth: 200 }}
+                  placeholder={t("settings.general.voice_memos_path")}
+                  value={cfg.voice_memos_path ?? ""}
+                  onChange={(e) => setCfg({ ...cfg, voice_memos_path: e.target.value })}
+                />
+                <Button variant="secondary" onClick={importAllVoiceMemos} disabled={dirty}>
+                  {t("settings.general.voice_memos_import")}
+                </Button>
+              </div>
+              {watcherStatus?.enabled && (
+                <div className="text-muted">
+                  {t("settings.general.inbox_status")} {watcherStatus.mode}, {watcherStatus.imported}
+                  {watcherStatus.waiting.length > 0
+                    ? ` (${t("settings.general.inbox_waiting")} ${watcherStatus.waiting.length})`
+                    : ""}
+                  {watcherStatus.last_error ? ` — ${watcherStatus.last_error}` : ""}
+                </div>
+              )}
+            </Card>
+            <Card>
+              <div className="section-title">{t("settings.general.encryption")}</div>
+              <div className="form-row" style={{ display: "flex", gap: 16, flexWrap: "wrap" }}>
+                <label>
+                  <input
+                    type="checkbox"
+                    checked={cfg.encrypt_job_files ?? false}
+                    onChange={(e) => setCfg({ ...cfg, encrypt_job_files: e.target.checked })}
+                  />{" "}
+                  {t("settings.general.encrypt_files")}
+                </label>
+                <label>
+                  <input
+                    type="checkbox"
+                    checked={cfg.encrypt_job_audio ?? false}
+                    disabled={!cfg.encrypt_job_files}
+                    onChange={(e) => setCfg({ ...cfg, encrypt_job_audio: e.target.checked })}
+                  />{" "}
+                  {t("settings.general.encrypt_audio")}
+                </label>
+                <label>
+                  <input
+                    type="checkbox"
+                    checked={cfg.allow_plaintext_secrets ?? false}
+                    onChange={(e) => setCfg({ ...cfg, allow_plaintext_secrets: e.target.checked })}
+                  />{" "}
+                  {t("settings.general.plaintext_secrets")}
+                </label>
+              </div>
+              <div className="form-row">
+                <Button variant="secondary" onClick={applyEncryptionToJobs} disabled={dirty}>
+                  {t("settings.general.encryption_apply")}
+                </Button>
+              </div>
+              <div className="text-muted">{t("settings.general.encryption_help")}</div>
+            </Card>
           </>
         )}
 
@@ -477,7 +1317,13 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
                     >
                       {t("settings.transcription.ffmpeg_download_button")}
                     </Button>
+                    {ffmpegStatus?.state === "downloading" && (
+                      <Button variant="secondary" onClick={() => cancelFfmpegDownload().catch(() => {})}>
+                        {t("jobs.actions.cancel")}
+                      </Button>
+                    )}
                   </div>
+                  {fieldError("ffmpeg_binary_url")}
                 </label>
                 <div className="text-muted" style={{ marginTop: 6 }} />
               </div>
@@ -494,15 +1340,116 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
                   </div>
                 </div>
               ) : null}
-              {ffmpegStatus?.state === "error" && (
+              {(ffmpegStatus?.state === "error" || ffmpegStatus?.state === "checksum_mismatch") && (
                 <div className="text-muted">
-                  {t("settings.transcription.ffmpeg_download_error")}:{" "}
+                  {t(
+                    ffmpegStatus.state === "checksum_mismatch"
+                      ? "settings.transcription.checksum_mismatch"
+                      : "settings.transcription.ffmpeg_download_error"
+                  )}:{" "}
                   {ffmpegStatus.message || "—"}
                 </div>
               )}
               {ffmpegError && <div className="text-muted">{ffmpegError}</div>}
               <div className="text-muted" style={{ marginTop: 6 }} />
             </div>
+            <div className="panel" style={{ marginTop: 12 }}>
+              <div className="section-title">{t("settings.transcription.system_binaries")}</div>
+              <div className="form-row">
+                <label>
+                  <input
+                    type="checkbox"
+                    checked={cfg.use_system_binaries ?? true}
+                    onChange={(e) => setCfg({ ...cfg, use_system_binaries: e.target.checked })}
+                  />{" "}
+                  {t("settings.transcription.use_system_binaries")}
+                </label>
+              </div>
+              <div className="form-row">
+                <Button
+                  variant="secondary"
+                  onClick={() =>
+                    detectSystemBinaries()
+                      .then(setSystemBinaries)
+                      .catch((err) => setStatus(err instanceof Error ? err.message : String(err)))
+                  }
+                >
+                  {t("settings.transcription.detect_system_binaries")}
+                </Button>
+              </div>
+              {systemBinaries?.binaries.map((binary) => (
+                <div key={binary.name} className="text-muted">
+                  {binary.name}: {binary.path ?? "—"}
+                  {binary.version ? ` (${binary.version})` : ""}
+                  {binary.license_flags.length > 0 ? ` ${binary.license_flags.join(" ")}` : ""}
+                  {binary.path && !binary.usable ? `, ${t("settings.transcription.system_binary_unused")}` : ""}
+                  {binary.message ? ` — ${binary.message}` : ""}
+                </div>
+              ))}
+            </div>
+            <div className="panel" style={{ marginTop: 12 }}>
+              <div className="section-title">{t("settings.transcription.network")}</div>
+              <div className="text-muted" style={{ marginBottom: 8 }}>
+                {t("settings.transcription.network_help")}
+              </div>
+              <div className="form-row">
+                <label>
+                  {t("settings.transcription.download_proxy")}
+                  <input
+                    className="input"
+                    value={cfg.download_proxy ?? ""}
+                    onChange={(e) => setCfg({ ...cfg, download_proxy: e.target.value })}
+                    placeholder="socks5h://127.0.0.1:1080"
+                  />
+                  {fieldError("download_proxy")}
+                </label>
+                <label>
+                  {t("settings.transcription.download_no_proxy")}
+                  <input
+                    className="input"
+                    value={cfg.download_no_proxy ?? ""}
+                    onChange={(e) => setCfg({ ...cfg, download_no_proxy: e.target.value })}
+                    placeholder="localhost,.corp.example"
+                  />
+                </label>
+              </div>
+              <div className="form-row">
+                <label>
+                  {t("settings.transcription.huggingface_mirror")}
+                  <input
+                    className="input"
+                    value={cfg.huggingface_mirror ?? ""}
+                    onChange={(e) => setCfg({ ...cfg, huggingface_mirror: e.target.value })}
+                    placeholder="https://hf-mirror.com"
+                  />
+                  {fieldError("huggingface_mirror")}
+                </label>
+                <label>
+                  {t("settings.transcription.github_mirror")}
+                  <input
+                    className="input"
+                    value={cfg.github_mirror ?? ""}
+                    onChange={(e) => setCfg({ ...cfg, github_mirror: e.target.value })}
+                    placeholder="https://github.com"
+                  />
+                  {fieldError("github_mirror")}
+                </label>
+              </div>
+              <div className="form-row">
+                <label>
+                  {t("settings.transcription.download_speed_limit")}
+                  <input
+                    className="input"
+                    type="number"
+                    min={0}
+                    value={cfg.download_speed_limit_kbps ?? 0}
+                    onChange={(e) =>
+                      setCfg({ ...cfg, download_speed_limit_kbps: Math.max(0, Number(e.target.value) || 0) })
+                    }
+                  />
+                </label>
+              </div>
+            </div>
             <div className="panel" style={{ marginTop: 12 }}>
               <div
                 className="section-title"
@@ -543,7 +1490,13 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
                     >
                       {t("settings.transcription.binary_download_button")}
                     </Button>
+                    {whisperStatus?.state === "downloading" && (
+                      <Button variant="secondary" onClick={() => cancelWhisperDownload().catch(() => {})}>
+                        {t("jobs.actions.cancel")}
+                      </Button>
+                    )}
                   </div>
+                  {fieldError("whisper_binary_url")}
                 </label>
                 <div className="text-muted" style={{ marginTop: 6 }} />
               </div>
@@ -560,9 +1513,13 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
                   </div>
                 </div>
               ) : null}
-              {whisperStatus?.state === "error" && (
+              {(whisperStatus?.state === "error" || whisperStatus?.state === "checksum_mismatch") && (
                 <div className="text-muted">
-                  {t("settings.transcription.binary_download_error")}:{" "}
+                  {t(
+                    whisperStatus.state === "checksum_mismatch"
+                      ? "settings.transcription.checksum_mismatch"
+                      : "settings.transcription.binary_download_error"
+                  )}:{" "}
                   {whisperStatus.message || "—"}
                 </div>
               )}
@@ -597,12 +1554,7 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
                     onChange={(e) =>
                       setCfg({
                         ...cfg,
-                        model_size: e.target.value as
-                          | "tiny"
-                          | "base"
-                          | "small"
-                          | "medium"
-                          | "large-v3",
+                        model_size: e.target.value as AppConfig["model_size"],
                       })
                     }
                   >
@@ -611,7 +1563,13 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
                     <option value="small">small</option>
                     <option value="medium">medium</option>
                     <option value="large-v3">large-v3</option>
+                    {customModels.map((model) => (
+                      <option key={model.id} value={model.id}>
+                        {model.name}
+                      </option>
+                    ))}
                   </select>
+                  {fieldError("model_size")}
                 </label>
                 <label style={{ flex: 1 }}>
                   {t("settings.transcription.language")}
@@ -631,6 +1589,14 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
                 >
                   {t("settings.transcription.download_button")}
                 </Button>
+                {downloadStatus?.state === "downloading" && (
+                  <Button
+                    variant="secondary"
+                    onClick={() => cancelModelDownload(downloadStatus.model_size).catch(() => {})}
+                  >
+                    {t("jobs.actions.cancel")}
+                  </Button>
+                )}
               </div>
               <div className="form-row" style={{ display: "flex", gap: 16, flexWrap: "wrap" }}>
                 <label>
@@ -662,7 +1628,9 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
                 <span>
                   {t("settings.transcription.size")}: {formatBytes(sizeBytes)}
                 </span>
-                {downloadState === "downloading" || downloadState === "error" ? (
+                {downloadState === "downloading" ||
+                downloadState === "error" ||
+                downloadState === "checksum_mismatch" ? (
                   <span
                     style={{
                       display: "inline-flex",
@@ -689,12 +1657,105 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
                   <ProgressBar value={downloadProgress} />
                 </div>
               ) : null}
-              {downloadStatus?.state === "error" && (
+              {(downloadStatus?.state === "error" || downloadStatus?.state === "checksum_mismatch") && (
                 <div className="text-muted">
-                  {t("settings.transcription.download_error")}: {downloadStatus.message || "—"}
+                  {t(
+                    downloadStatus.state === "checksum_mismatch"
+                      ? "settings.transcription.checksum_mismatch"
+                      : "settings.transcription.download_error"
+                  )}
+                  : {downloadStatus.message || "—"}
                 </div>
               )}
               {downloadError && <div className="text-muted">{downloadError}</div>}
+              <div className="form-row" style={{ display: "flex", gap: 12, alignItems: "flex-end" }}>
+                <label style={{ flex: 1 }}>
+                  {t("settings.transcription.models_dir")}{" "}
+                  <span className="text-muted">({t("settings.transcription.models_dir_help")})</span>
+                  <input
+                    className="input"
+                    value={cfg.models_dir ?? ""}
+                    onChange={(e) => setCfg({ ...cfg, models_dir: e.target.value })}
+                    placeholder="~/whisper-models"
+                  />
+                </label>
+                <Button variant="secondary" onClick={pickModelsDir}>
+                  {t("settings.transcription.models_dir_choose")}
+                </Button>
+              </div>
+              <div className="form-row" style={{ display: "flex", gap: 12, alignItems: "flex-end" }}>
+                <label style={{ flex: 1 }}>
+                  {t("settings.transcription.custom_model_name")}
+                  <input
+                    className="input"
+                    value={customName}
+                    onChange={(e) => setCustomName(e.target.value)}
+                    placeholder="distil-large-v3"
+                  />
+                </label>
+                <label style={{ flex: 2 }}>
+                  {t("settings.transcription.custom_model_url")}
+                  <input
+                    className="input"
+                    value={customUrl}
+                    onChange={(e) => setCustomUrl(e.target.value)}
+                    placeholder="https://huggingface.co/.../ggml-model.bin"
+                  />
+                </label>
+                <Button
+                  variant="secondary"
+                  onClick={startCustomDownload}
+                  disabled={!customUrl.trim() || downloadStatus?.state === "downloading"}
+                >
+                  {t("settings.transcription.download_button")}
+                </Button>
+                <Button variant="secondary" onClick={importCustomModel} disabled={importProgress !== null}>
+                  {t("settings.transcription.import_model")}
+                </Button>
+              </div>
+              {importProgress && (
+                <ProgressBar
+                  value={
+                    importProgress.total_bytes > 0
+                      ? (importProgress.copied_bytes / importProgress.total_bytes) * 100
+                      : 0
+                  }
+                />
+              )}
+              {installedModels.length > 0 && (
+                <div style={{ marginTop: 12 }}>
+                  <div className="text-muted" style={{ marginBottom: 6 }}>
+                    {t("settings.transcription.installed_models")}
+                  </div>
+                  {installedModels.map((model) => (
+                    <div
+                      key={model.name}
+                      style={{ display: "flex", alignItems: "center", gap: 12, marginBottom: 4 }}
+                    >
+                      <span style={{ flex: 1 }}>{model.display_name ?? model.name}</span>
+                      <span className="table-muted">{formatBytes(model.bytes)}</span>
+                      <span className="table-muted">
+                        {model.last_used
+                          ? t("settings.transcription.model_last_used", {
+                              date: new Date(model.last_used * 1000).toLocaleDateString(),
+                            })
+                          : t("settings.transcription.model_never_used")}
+                      </span>
+                      {model.model_size && model.deletable && (
+                        <Button variant="secondary" onClick={() => removeModel(model.model_size!)}>
+                          {t("settings.transcription.delete_model")}
+                        </Button>
+                      )}
+                    </div>
+                  ))}
+                  <Button
+                    variant="secondary"
+                    onClick={() => removePartialModelDownloads().catch(() => 0)}
+                  >
+                    {t("settings.transcription.remove_partial")}
+                  </Button>
+                </div>
+              )}
               <div className="text-muted" style={{ marginTop: 6 }} />
               {downloadStatus?.state !== "done" ? null : null}
             </div>
@@ -749,6 +1810,7 @@ const Settings = forwardRef<SettingsHandle, Props>(function Settings(
                   value={cfg.ollama_base_url ?? "http://127.0.0.1:11434"}
                   onChange={(e) => setCfg({ ...cfg, ollama_base_url: e.target.value })}
                 />
+                {fieldError("ollama_base_url")}
               </label>
             </details>
             <div className="form-row">